@@ -0,0 +1,294 @@
+use crate::divisor_functions::{divisor_sum, sigma_sieve};
+
+/// Finds every number up to a limit whose aliquot sum is `n`.
+///
+/// The aliquot sum of $m$ is the sum of its proper divisors, $s(m) = \sigma(m) - m$.
+/// Every $\sigma(m)$ up to the limit is filled in with a single divisor-sum sieve.
+///
+/// # Arguments
+///
+/// * `n` - The aliquot sum to find preimages of.
+/// * `search_limit` - The largest `m` to check (inclusive).
+///
+/// # Returns
+///
+/// Vector of all `m <= search_limit` with $s(m) = n$, in increasing order.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::aliquot::aliquot_preimages;
+/// assert_eq!(aliquot_preimages(6, 100), vec![6, 25]); // 1 + 2 + 3 and 1 + 5
+/// assert_eq!(aliquot_preimages(1, 10), vec![2, 3, 5, 7]);
+/// ```
+pub fn aliquot_preimages(n: u64, search_limit: u64) -> Vec<u64> {
+    let sums: Vec<u64> = sigma_sieve(search_limit as usize);
+    let mut preimages: Vec<u64> = vec![];
+    let mut m: usize = 1;
+    while m < sums.len() {
+        if sums[m] - m as u64 == n {
+            preimages.push(m as u64);
+        }
+        m += 1;
+    }
+    preimages
+}
+
+/// Generates the untouchable numbers below a given limit.
+///
+/// `n` is untouchable if it is not the aliquot sum $s(m) = \sigma(m) - m$ of any number $m$.
+///
+/// A composite $m$ has a divisor $d \ge \sqrt{m}$ other than itself, so $s(m) \ge 1 + \sqrt{m}$,
+/// and primes all have $s(m) = 1$. So for $n \ge 2$, only $m \le (n - 1)^2$ can have $s(m) = n$,
+/// and a sieve up to $(\text{limit} - 2)^2$ is enough to decide every `n` below the limit.
+/// This makes the memory used grow quadratically with the limit.
+///
+/// [A005114](https://oeis.org/A005114)
+///
+/// # Arguments
+///
+/// * `limit` - The (exclusive) upper bound of the numbers to check.
+///
+/// # Returns
+///
+/// Vector of all untouchable numbers less than `limit`.
+///
+/// # Panics
+///
+/// Panics if $(\text{limit} - 2)^2$ does not fit in a `usize` (so for a limit above $2^{32} + 1$ on 64-bit
+/// targets), long before which the sieve runs out of memory anyway.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::aliquot::untouchable_numbers_below;
+/// assert_eq!(untouchable_numbers_below(100), vec![2, 5, 52, 88, 96]);
+/// ```
+pub fn untouchable_numbers_below(limit: u64) -> Vec<u64> {
+    if limit <= 2 {
+        return vec![];
+    }
+    let bound: usize = match (limit - 2)
+        .checked_mul(limit - 2)
+        .and_then(|bound| usize::try_from(bound).ok())
+    {
+        Some(bound) => bound,
+        None => panic!("the sieve bound (limit - 2)^2 does not fit in a usize"),
+    };
+    let sums: Vec<u64> = sigma_sieve(bound);
+    let mut touched: Vec<bool> = vec![false; limit as usize];
+    let mut m: usize = 1;
+    while m < sums.len() {
+        let s: u64 = sums[m] - m as u64;
+        if s < limit {
+            touched[s as usize] = true;
+        }
+        m += 1;
+    }
+    let mut untouchable: Vec<u64> = vec![];
+    let mut n: usize = 2;
+    while n < touched.len() {
+        if !touched[n] {
+            untouchable.push(n as u64);
+        }
+        n += 1;
+    }
+    untouchable
+}
+
+/// Finds the sociable cycles (aliquot cycles of length at least 3) whose smallest member is below a limit.
+///
+/// Repeatedly taking aliquot sums $n \to s(n) \to s(s(n)) \to \cdots$ either ends at $0$, grows,
+/// or falls into a cycle. Cycles of length 1 are the perfect numbers and cycles of length 2 the amicable pairs,
+/// neither of which is included here.
+///
+/// Each starting value `n` is followed for at most `max_period` steps, and abandoned as soon as the trajectory
+/// drops below `n` (so `n` is not the smallest member) or climbs above $\text{limit}^2$, the working bound.
+/// Aliquot sums below $16 \cdot \text{limit}$ come from a single divisor-sum sieve,
+/// larger ones are computed individually. A cycle with a member above the working bound would be missed,
+/// but every known one stays far below it.
+///
+/// # Arguments
+///
+/// * `limit` - The (exclusive) upper bound of the smallest member of each cycle.
+/// * `max_period` - The longest cycle to look for.
+///
+/// # Returns
+///
+/// Vector of the cycles found, each starting at its smallest member and ordered by it.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::aliquot::sociable_cycles_below;
+/// assert_eq!(sociable_cycles_below(12500, 5), vec![vec![12496, 14288, 15472, 14536, 14264]]);
+/// ```
+pub fn sociable_cycles_below(limit: u64, max_period: u32) -> Vec<Vec<u64>> {
+    let mut cycles: Vec<Vec<u64>> = vec![];
+    if limit <= 1 {
+        return cycles;
+    }
+    let working_bound: u64 = limit.saturating_mul(limit);
+    let sums: Vec<u64> = sigma_sieve(limit.saturating_mul(16) as usize);
+    let aliquot_sum = |m: u64| -> u64 {
+        match sums.get(m as usize) {
+            Some(sigma) => sigma - m,
+            None => divisor_sum(m) - m,
+        }
+    };
+
+    let mut n: u64 = 2;
+    while n < limit {
+        let mut trajectory: Vec<u64> = vec![n];
+        let mut m: u64 = aliquot_sum(n);
+        while m > n && m <= working_bound && (trajectory.len() as u32) < max_period {
+            trajectory.push(m);
+            m = aliquot_sum(m);
+        }
+        if m == n && trajectory.len() >= 3 {
+            cycles.push(trajectory);
+        }
+        n += 1;
+    }
+    cycles
+}
+
+/// Calculates the aliquot sum $s(n) = \sigma(n) - n$ for every $n$ up to a limit.
+///
+/// The divisor sums come from [`sigma_sieve`], so this takes $O(n)$ time, and the range functions below all read
+/// from it instead of summing divisors for each number.
+///
+/// [A001065](https://oeis.org/A001065)
+///
+/// # Arguments
+///
+/// * `limit` - The (inclusive) upper bound of the numbers.
+///
+/// # Returns
+///
+/// Vector of length `limit + 1` where index `n` holds $s(n)$ (and index `0` holds `0`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::aliquot::aliquot_sieve;
+/// assert_eq!(aliquot_sieve(12), vec![0, 0, 1, 1, 3, 1, 6, 1, 7, 4, 8, 1, 16]);
+/// ```
+pub fn aliquot_sieve(limit: usize) -> Vec<u64> {
+    let mut sums: Vec<u64> = sigma_sieve(limit);
+    let mut n: usize = 1;
+    while n < sums.len() {
+        sums[n] -= n as u64;
+        n += 1;
+    }
+    sums
+}
+
+/// Finds the numbers below a limit whose aliquot sum compares to them in a given way.
+///
+/// # Arguments
+///
+/// * `limit` - The (exclusive) upper bound of the numbers.
+/// * `ordering` - How $s(n)$ has to compare to $n$.
+///
+/// # Returns
+///
+/// Vector of the $1 \leq n < \text{limit}$ with $s(n)$ ordered that way against $n$, in increasing order.
+fn numbers_by_aliquot_sum(limit: u64, ordering: std::cmp::Ordering) -> Vec<u64> {
+    if limit <= 1 {
+        return vec![];
+    }
+    let sums: Vec<u64> = aliquot_sieve(limit as usize - 1);
+    (1..limit)
+        .filter(|n| sums[*n as usize].cmp(n) == ordering)
+        .collect()
+}
+
+/// Generates the abundant numbers below a limit, the numbers whose proper divisors add up to more than
+/// themselves.
+///
+/// [A005101](https://oeis.org/A005101)
+///
+/// # Arguments
+///
+/// * `limit` - The (exclusive) upper bound of the numbers.
+///
+/// # Returns
+///
+/// Vector of the $n < \text{limit}$ with $s(n) > n$, in increasing order.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::aliquot::abundant_numbers_below;
+/// assert_eq!(abundant_numbers_below(50), vec![12, 18, 20, 24, 30, 36, 40, 42, 48]);
+/// ```
+pub fn abundant_numbers_below(limit: u64) -> Vec<u64> {
+    numbers_by_aliquot_sum(limit, std::cmp::Ordering::Greater)
+}
+
+/// Generates the deficient numbers below a limit, the numbers whose proper divisors add up to less than
+/// themselves.
+///
+/// [A005100](https://oeis.org/A005100)
+///
+/// # Arguments
+///
+/// * `limit` - The (exclusive) upper bound of the numbers.
+///
+/// # Returns
+///
+/// Vector of the $n < \text{limit}$ with $s(n) < n$, in increasing order (starting with $1$, whose aliquot sum
+/// is $0$).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::aliquot::deficient_numbers_below;
+/// assert_eq!(deficient_numbers_below(12), vec![1, 2, 3, 4, 5, 7, 8, 9, 10, 11]); // 6 is perfect
+/// ```
+pub fn deficient_numbers_below(limit: u64) -> Vec<u64> {
+    numbers_by_aliquot_sum(limit, std::cmp::Ordering::Less)
+}
+
+/// Generates the numbers below a limit that are not the sum of two (not necessarily different) abundant numbers.
+///
+/// The sums of every pair of abundant numbers below the limit are marked in a bitset, which takes
+/// $O(A^2)$ time for the $A$ abundant numbers. Every number above $20161$ is such a sum (the classical bound
+/// is $28123$), so the list never grows past it.
+///
+/// [A048242](https://oeis.org/A048242)
+///
+/// # Arguments
+///
+/// * `limit` - The (exclusive) upper bound of the numbers.
+///
+/// # Returns
+///
+/// Vector of the $1 \leq n < \text{limit}$ that are not a sum of two abundant numbers, in increasing order.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::aliquot::non_sum_of_two_abundants_below;
+/// let numbers: Vec<u64> = non_sum_of_two_abundants_below(30_000);
+/// assert_eq!(numbers.iter().sum::<u64>(), 4_179_871);
+/// assert_eq!(numbers.last(), Some(&20161));
+/// ```
+pub fn non_sum_of_two_abundants_below(limit: u64) -> Vec<u64> {
+    let abundant: Vec<u64> = abundant_numbers_below(limit);
+    let mut sums: Vec<u64> = vec![0; (limit as usize).div_ceil(64)];
+    let mut i: usize = 0;
+    while i < abundant.len() {
+        let mut j: usize = i;
+        while j < abundant.len() && abundant[i] + abundant[j] < limit {
+            let sum: u64 = abundant[i] + abundant[j];
+            sums[(sum / 64) as usize] |= 1 << (sum % 64);
+            j += 1;
+        }
+        i += 1;
+    }
+    (1..limit)
+        .filter(|n| sums[(n / 64) as usize] >> (n % 64) & 1 == 0)
+        .collect()
+}
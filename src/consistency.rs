@@ -0,0 +1,179 @@
+use crate::factorization::least_prime_factor;
+use crate::primes::{
+    is_prime_u64, is_strong_lucas_prp, prove_prime_n_minus_1, sieve_segment, sieving_primes,
+    strong_prp_bases,
+};
+use std::ops::Range;
+
+/// The first number on which the primality implementations do not all agree, found by [`check_agreement`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disagreement {
+    /// The number the implementations disagree on.
+    pub n: u64,
+    /// The name of every implementation that checked `n`, with whether it said `n` is prime, starting with the
+    /// sieve.
+    pub votes: Vec<(&'static str, bool)>,
+}
+
+impl std::fmt::Display for Disagreement {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "the primality checks disagree on {}:", self.n)?;
+        for (i, (name, prime)) in self.votes.iter().enumerate() {
+            let separator: &str = if i == 0 { "" } else { "," };
+            let verdict: &str = if *prime { "prime" } else { "composite" };
+            write!(f, "{separator} {name} says {verdict}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Disagreement {}
+
+/// A primality check with the bound below which it is exact.
+type Implementation = (&'static str, fn(u64) -> bool, u64);
+
+/// The primality checks of the crate that are compared with the sieve.
+const IMPLEMENTATIONS: [Implementation; 4] = [
+    ("trial division", trial_division_vote, u64::MAX),
+    ("miller-rabin", is_prime_u64, u64::MAX),
+    ("baillie-psw", baillie_psw_vote, u64::MAX),
+    ("lucas n - 1", lucas_vote, 1 << 40),
+];
+
+/// Checks a number with [`least_prime_factor`], which trial divides until it finds a factor.
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// `true` if `n` is its own least prime factor.
+fn trial_division_vote(n: u64) -> bool {
+    least_prime_factor(n) == Some(n)
+}
+
+/// Checks a number with the Baillie–PSW test, a strong probable prime test to the base $2$ followed by
+/// [`is_strong_lucas_prp`].
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// `true` if `n` passes both halves of the test.
+fn baillie_psw_vote(n: u64) -> bool {
+    if n < 3 || n.is_multiple_of(2) {
+        return n == 2;
+    }
+    !strong_prp_bases(n, [2]).is_empty() && is_strong_lucas_prp(n)
+}
+
+/// Checks a number with the Lucas (Pocklington–Lehmer) $n - 1$ test of [`prove_prime_n_minus_1`].
+///
+/// # Arguments
+///
+/// * `n` - The number to check, below $2^{40}$ so that every prime has a certificate.
+///
+/// # Returns
+///
+/// `true` if `n` is $2$ or has a certificate.
+fn lucas_vote(n: u64) -> bool {
+    n == 2 || prove_prime_n_minus_1(n).is_some()
+}
+
+/// Checks that every primality implementation of the crate gives the same answer on a range of numbers.
+///
+/// The range is sieved in blocks of $2^{16}$, and every number is also checked by trial division, Miller–Rabin,
+/// Baillie–PSW and (below $2^{40}$, where it always finds a certificate for a prime) the Lucas $n - 1$ test. The
+/// sieve needs the primes up to $\sqrt{\text{end}}$, so the range should end well below $2^{64}$.
+///
+/// # Arguments
+///
+/// * `range` - The numbers to check.
+///
+/// # Returns
+///
+/// `Ok(())` if the implementations agree on every number, otherwise `Err` with the first [`Disagreement`].
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::consistency::check_agreement;
+/// assert_eq!(check_agreement(0..100_000), Ok(()));
+/// ```
+pub fn check_agreement(range: Range<u64>) -> Result<(), Disagreement> {
+    check_agreement_with(range, &[])
+}
+
+/// Checks that the primality implementations of the crate and some extra ones give the same answer on a range of
+/// numbers.
+///
+/// This is [`check_agreement`] with more implementations taking part, which makes it a cheap way to test a new
+/// primality check against the ones in the crate.
+///
+/// # Arguments
+///
+/// * `range` - The numbers to check.
+/// * `extra` - `(name, check)` pairs for the extra implementations, which vote on every number after the built-in
+///   ones.
+///
+/// # Returns
+///
+/// `Ok(())` if the implementations agree on every number, otherwise `Err` with the first [`Disagreement`].
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::consistency::check_agreement_with;
+///
+/// // an odd-number check is right about 3, 5 and 7 but wrong about 9
+/// let odd = |n: u64| n == 2 || n % 2 == 1 && n > 1;
+/// let disagreement = check_agreement_with(0..100, &[("odd", &odd)]).unwrap_err();
+/// assert_eq!(disagreement.n, 9);
+/// assert!(disagreement.votes.contains(&("sieve", false)));
+/// assert!(disagreement.votes.contains(&("odd", true)));
+/// ```
+pub fn check_agreement_with(
+    range: Range<u64>,
+    extra: &[(&'static str, &dyn Fn(u64) -> bool)],
+) -> Result<(), Disagreement> {
+    const SEGMENT: u64 = 1 << 16;
+    if range.is_empty() {
+        return Ok(());
+    }
+    let base: Vec<u64> = sieving_primes(range.end);
+    let mut segment: Vec<bool> = vec![false; SEGMENT as usize];
+    let mut low: u64 = range.start;
+    while low < range.end {
+        let high: u64 = low.saturating_add(SEGMENT).min(range.end);
+        // the sieve only covers numbers from 2 on
+        let sieve_low: u64 = low.max(2);
+        if sieve_low < high {
+            sieve_segment(sieve_low, high, &base, &mut segment);
+        }
+        let mut n: u64 = low;
+        while n < high {
+            let sieved: bool = n >= 2 && segment[(n - sieve_low) as usize];
+            let agrees = |(_, check, bound): &Implementation| n >= *bound || check(n) == sieved;
+            if !IMPLEMENTATIONS.iter().all(agrees)
+                || extra.iter().any(|(_, check)| check(n) != sieved)
+            {
+                let mut votes: Vec<(&'static str, bool)> = vec![("sieve", sieved)];
+                for (name, check, bound) in IMPLEMENTATIONS {
+                    if n < bound {
+                        votes.push((name, check(n)));
+                    }
+                }
+                for (name, check) in extra {
+                    votes.push((name, check(n)));
+                }
+                return Err(Disagreement { n, votes });
+            }
+            n += 1;
+        }
+        low = high;
+    }
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Something one of the algorithms of the crate reports about its internals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// A Miller–Rabin round found that `n` is not a strong probable prime to `base`, which proves it composite.
+    WitnessFailed {
+        /// The number being tested.
+        n: u128,
+        /// The base that caught it.
+        base: u128,
+    },
+    /// A prime factor was split off while factoring a number.
+    FactorFound {
+        /// The number being factored.
+        n: u64,
+        /// The prime factor.
+        factor: u64,
+        /// The exponent of `factor` in `n`.
+        exponent: u32,
+        /// How the factor was found.
+        method: FactorMethod,
+    },
+    /// A block of a segmented sieve was finished.
+    SieveSegmentDone {
+        /// The first number of the block.
+        lo: u64,
+        /// The exclusive end of the block.
+        hi: u64,
+        /// The number of primes in the block.
+        count: u64,
+    },
+}
+
+/// The ways a prime factor reported by [`Event::FactorFound`] can be found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FactorMethod {
+    /// The factor divided the number during trial division.
+    TrialDivision,
+    /// The factor is what was left after trial division passed its square root, so it had to be prime.
+    Cofactor,
+}
+
+/// A receiver for the [`Event`]s emitted by the algorithms of the crate.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::diagnostics::{set_sink, DiagnosticsSink, Event};
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// struct Collector(RefCell<Vec<Event>>);
+///
+/// impl DiagnosticsSink for Collector {
+///     fn event(&self, ev: Event) {
+///         self.0.borrow_mut().push(ev);
+///     }
+/// }
+///
+/// let collector: Rc<Collector> = Rc::new(Collector(RefCell::new(vec![])));
+/// set_sink(collector.clone());
+/// // 151 * 751 * 28351, a strong pseudoprime to the bases 2, 3, 5 and 7
+/// assert!(!bens_number_theory::primes::check_prime(3_215_031_751));
+/// assert_eq!(
+///     collector.0.borrow()[..],
+///     [Event::WitnessFailed { n: 3_215_031_751, base: 11 }]
+/// );
+/// ```
+pub trait DiagnosticsSink {
+    /// Receives one event.
+    ///
+    /// # Arguments
+    ///
+    /// * `ev` - The event.
+    fn event(&self, ev: Event);
+}
+
+thread_local! {
+    /// The sink registered on this thread.
+    static SINK: RefCell<Option<Rc<dyn DiagnosticsSink>>> = const { RefCell::new(None) };
+}
+
+/// Registers the sink that receives the events of the current thread.
+///
+/// Each thread has its own sink, so computations on other threads (such as other tests) do not show up in it.
+///
+/// # Arguments
+///
+/// * `sink` - The sink.
+///
+/// # Returns
+///
+/// The sink registered before, if there was one.
+pub fn set_sink(sink: Rc<dyn DiagnosticsSink>) -> Option<Rc<dyn DiagnosticsSink>> {
+    SINK.with(|current| current.borrow_mut().replace(sink))
+}
+
+/// Removes the sink of the current thread, so events are no longer reported.
+///
+/// # Returns
+///
+/// The sink that was registered, if there was one.
+pub fn clear_sink() -> Option<Rc<dyn DiagnosticsSink>> {
+    SINK.with(|current| current.borrow_mut().take())
+}
+
+/// Sends an event to the sink of the current thread.
+///
+/// The event is only built if a sink is registered, so events that take work to describe cost nothing otherwise.
+///
+/// # Arguments
+///
+/// * `event` - Builds the event.
+pub(crate) fn emit(event: impl FnOnce() -> Event) {
+    // clone the Rc and release the borrow before calling the sink, so that a sink which calls back into the crate
+    // (and so into emit, set_sink or clear_sink) does not hit a RefCell that is still borrowed
+    let sink: Option<Rc<dyn DiagnosticsSink>> = SINK.with(|current| current.borrow().clone());
+    if let Some(sink) = sink {
+        sink.event(event());
+    }
+}
@@ -0,0 +1,1869 @@
+use crate::factorization::factorize;
+use crate::modular::{gcd, multiplicative_order};
+use crate::primes::{check_prime, is_prime_u64, is_probable_prime, primes_in_range};
+use num::BigInt;
+use std::collections::HashMap;
+
+/// Splits a number into its digits in a given base.
+///
+/// # Arguments
+///
+/// * `n` - The number to split into digits.
+/// * `base` - The base to represent `n` in.
+///
+/// # Returns
+///
+/// Vector of digits of `n`, most significant digit first (`0` has the single digit `0`).
+///
+/// # Panics
+///
+/// Panics if `base` is less than 2.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::digits;
+/// assert_eq!(digits(1234, 10), vec![1, 2, 3, 4]);
+/// assert_eq!(digits(6, 2), vec![1, 1, 0]);
+/// assert_eq!(digits(0, 10), vec![0]);
+/// ```
+pub fn digits(n: u64, base: u32) -> Vec<u32> {
+    if base < 2 {
+        panic!("base must be at least 2");
+    }
+    if n == 0 {
+        return vec![0];
+    }
+    let mut d: Vec<u32> = vec![];
+    let mut m: u64 = n;
+    while m > 0 {
+        d.push((m % base as u64) as u32);
+        m /= base as u64;
+    }
+    d.reverse();
+    d
+}
+
+/// Calculates the sum of the digits of a number in a given base.
+///
+/// # Arguments
+///
+/// * `n` - The number whose digits are summed.
+/// * `base` - The base to represent `n` in.
+///
+/// # Returns
+///
+/// The sum of the digits of `n` in base `base`.
+///
+/// # Panics
+///
+/// Panics if `base` is less than 2.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::digit_sum;
+/// assert_eq!(digit_sum(1234, 10), 10);
+/// assert_eq!(digit_sum(7, 2), 3); // 111
+/// ```
+pub fn digit_sum(n: u64, base: u32) -> u64 {
+    if base < 2 {
+        panic!("base must be at least 2");
+    }
+    let mut sum: u64 = 0;
+    let mut m: u64 = n;
+    while m > 0 {
+        sum += m % base as u64;
+        m /= base as u64;
+    }
+    sum
+}
+
+/// Determines whether or not a number is a Harshad (or Niven) number in a given base.
+///
+/// `n` is a Harshad number if it is divisible by the sum of its digits.
+///
+/// [A005349](https://oeis.org/A005349)
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+/// * `base` - The base the digits of `n` are taken in.
+///
+/// # Returns
+///
+/// Boolean representing whether `n` is a Harshad number (`0` is not, as its digit sum is `0`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::is_harshad;
+/// assert_eq!(is_harshad(18, 10), true); // 18 / 9 = 2
+/// assert_eq!(is_harshad(11, 10), false);
+/// assert_eq!(is_harshad(10, 2), true); // 1010 has digit sum 2
+/// ```
+pub fn is_harshad(n: u64, base: u32) -> bool {
+    let sum: u64 = digit_sum(n, base);
+    sum != 0 && n.is_multiple_of(sum)
+}
+
+/// Base 10 version of [`is_harshad`].
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::is_harshad_base_10;
+/// assert_eq!(is_harshad_base_10(21), true);
+/// assert_eq!(is_harshad_base_10(22), false);
+/// ```
+pub fn is_harshad_base_10(n: u64) -> bool {
+    is_harshad(n, 10)
+}
+
+/// Counts how many consecutive integers starting at `n` are Harshad numbers in a given base.
+///
+/// # Arguments
+///
+/// * `n` - The first number of the streak.
+/// * `base` - The base the digits are taken in.
+///
+/// # Returns
+///
+/// The length of the run of Harshad numbers `n, n + 1, n + 2, ...` (`0` if `n` itself is not Harshad).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::harshad_streak;
+/// assert_eq!(harshad_streak(1, 10), 10); // 1 through 10, but not 11
+/// assert_eq!(harshad_streak(11, 10), 0);
+/// ```
+pub fn harshad_streak(n: u64, base: u32) -> u32 {
+    let mut count: u32 = 0;
+    let mut i: u64 = n;
+    while is_harshad(i, base) {
+        count += 1;
+        i = match i.checked_add(1) {
+            Some(next) => next,
+            None => break,
+        };
+    }
+    count
+}
+
+/// Base 10 version of [`harshad_streak`].
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::harshad_streak_base_10;
+/// assert_eq!(harshad_streak_base_10(110), 3); // 110, 111, 112
+/// ```
+pub fn harshad_streak_base_10(n: u64) -> u32 {
+    harshad_streak(n, 10)
+}
+
+/// Determines whether or not a number is a strong Harshad (or Moran) number.
+///
+/// `n` is a strong Harshad number if dividing it by the sum of its (base 10) digits results in a prime.
+///
+/// [A001101](https://oeis.org/A001101)
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// Boolean representing whether `n` is a strong Harshad number.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::is_strong_harshad;
+/// assert_eq!(is_strong_harshad(201), true); // 201 / 3 = 67
+/// assert_eq!(is_strong_harshad(12), false); // 12 / 3 = 4
+/// ```
+pub fn is_strong_harshad(n: u64) -> bool {
+    if !is_harshad(n, 10) {
+        return false;
+    }
+    is_prime_u64(n / digit_sum(n, 10))
+}
+
+/// Calculates the sum of the squares of the digits of a number in a given base.
+///
+/// This is the map iterated when classifying happy numbers.
+///
+/// # Arguments
+///
+/// * `n` - The number whose digits are squared and summed.
+/// * `base` - The base to represent `n` in.
+///
+/// # Returns
+///
+/// The sum of the squares of the digits of `n` in base `base`.
+///
+/// # Example
+///
+/// ```
+/// fn digit_square_sum(n: u64, base: u32) -> u64 {
+///     let mut sum: u64 = 0;
+///     let mut m: u64 = n;
+///     while m > 0 {
+///         let d: u64 = m % base as u64;
+///         sum += d * d;
+///         m /= base as u64;
+///     }
+///     sum
+/// }
+///
+/// assert_eq!(digit_square_sum(19, 10), 82); // 1 + 81
+/// ```
+fn digit_square_sum(n: u64, base: u32) -> u64 {
+    let mut sum: u64 = 0;
+    let mut m: u64 = n;
+    while m > 0 {
+        let d: u64 = m % base as u64;
+        sum += d * d;
+        m /= base as u64;
+    }
+    sum
+}
+
+/// Determines whether or not a number is happy in a given base.
+///
+/// A number is happy if repeatedly replacing it with the sum of the squares of its digits eventually reaches $1$.
+/// Every other number ends up stuck in a cycle, which is detected with Floyd's tortoise and hare algorithm.
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+/// * `base` - The base the digits of `n` are taken in.
+///
+/// # Returns
+///
+/// Boolean representing whether `n` is happy in base `base` (`0` is a fixed point, so it is not happy).
+///
+/// # Panics
+///
+/// Panics if `base` is less than 2.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::is_happy_in_base;
+/// assert_eq!(is_happy_in_base(3, 3), true); // 10 -> 1
+/// assert_eq!(is_happy_in_base(2, 3), false);
+/// assert_eq!(is_happy_in_base(12345, 2), true); // every number is happy in base 2
+/// ```
+pub fn is_happy_in_base(n: u64, base: u32) -> bool {
+    if base < 2 {
+        panic!("base must be at least 2");
+    }
+    let mut slow: u64 = n;
+    let mut fast: u64 = digit_square_sum(n, base);
+    while slow != fast {
+        slow = digit_square_sum(slow, base);
+        fast = digit_square_sum(digit_square_sum(fast, base), base);
+    }
+    slow == 1
+}
+
+/// Determines whether or not a number is a (base 10) happy number.
+///
+/// [A007770](https://oeis.org/A007770)
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// Boolean representing whether `n` is happy.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::is_happy;
+/// assert_eq!(is_happy(19), true); // 82 -> 68 -> 100 -> 1
+/// assert_eq!(is_happy(4), false); // 16 -> 37 -> 58 -> 89 -> 145 -> 42 -> 20 -> 4
+/// ```
+pub fn is_happy(n: u64) -> bool {
+    is_happy_in_base(n, 10)
+}
+
+/// Generates a list of the (base 10) happy numbers below a given limit.
+///
+/// # Arguments
+///
+/// * `limit` - The (exclusive) upper bound of the numbers to check.
+///
+/// # Returns
+///
+/// Vector of all happy numbers less than `limit`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::happy_numbers_below;
+/// assert_eq!(happy_numbers_below(30), vec![1, 7, 10, 13, 19, 23, 28]);
+/// ```
+pub fn happy_numbers_below(limit: u64) -> Vec<u64> {
+    let mut happy: Vec<u64> = vec![];
+    let mut i: u64 = 1;
+    while i < limit {
+        if is_happy(i) {
+            happy.push(i);
+        }
+        i += 1;
+    }
+    happy
+}
+
+/// Determines whether or not a number is a happy prime (both happy and prime).
+///
+/// [A035497](https://oeis.org/A035497)
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// Boolean representing whether `n` is a happy prime.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::is_happy_prime;
+/// assert_eq!(is_happy_prime(7), true);
+/// assert_eq!(is_happy_prime(10), false); // happy, but not prime
+/// assert_eq!(is_happy_prime(11), false); // prime, but not happy
+/// ```
+pub fn is_happy_prime(n: u64) -> bool {
+    is_happy(n) && is_prime_u64(n)
+}
+
+/// The orbit of a number under repeatedly taking the sum of a power of its digits, found by
+/// [`digit_power_sum_trajectory`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trajectory {
+    /// The distinct values reached, starting with the number itself.
+    pub path: Vec<u64>,
+    /// The index in `path` where the eventual cycle starts, so the digit power sum of the last value is
+    /// `path[cycle_start]`.
+    pub cycle_start: usize,
+}
+
+impl Trajectory {
+    /// The values the trajectory eventually cycles through.
+    ///
+    /// # Returns
+    ///
+    /// The end of `path` from `cycle_start` on.
+    pub fn cycle(&self) -> &[u64] {
+        &self.path[self.cycle_start..]
+    }
+}
+
+/// The sum of a power of the digits of a number.
+///
+/// # Arguments
+///
+/// * `n` - The number whose digits are summed.
+/// * `power` - The power the digits are raised to.
+/// * `base` - The base the digits are taken in.
+///
+/// # Returns
+///
+/// The sum of `d^power` over the digits `d` of `n` in base `base`.
+///
+/// # Panics
+///
+/// Panics if the sum does not fit in a `u64`.
+fn digit_power_sum(n: u64, power: u32, base: u32) -> u64 {
+    let mut sum: u64 = 0;
+    let mut m: u64 = n;
+    while m > 0 {
+        let term: Option<u64> = (m % base as u64).checked_pow(power);
+        sum = match term.and_then(|term| sum.checked_add(term)) {
+            Some(sum) => sum,
+            None => panic!("the digit power sum of {n} does not fit in a u64"),
+        };
+        m /= base as u64;
+    }
+    sum
+}
+
+/// Follows a number under repeatedly replacing it with the sum of a power of its digits, until it repeats.
+///
+/// This generalizes the happy numbers, which use the squares of the digits, and the numbers equal to the sum of a
+/// power of their digits are the fixed points. A number with $d$ digits has a digit power sum of at most
+/// $d(b - 1)^p$, which falls behind $b^{d - 1}$ as $d$ grows, so every trajectory ends in a cycle.
+///
+/// # Arguments
+///
+/// * `n` - The starting number.
+/// * `power` - The power the digits are raised to.
+/// * `base` - The base the digits are taken in.
+///
+/// # Returns
+///
+/// The [`Trajectory`] of `n`, with every value up to the first repeat.
+///
+/// # Panics
+///
+/// Panics if `base` is less than 2 or a digit power sum does not fit in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::digit_power_sum_trajectory;
+/// let trajectory = digit_power_sum_trajectory(19, 2, 10);
+/// assert_eq!(trajectory.path, vec![19, 82, 68, 100, 1]);
+/// assert_eq!(trajectory.cycle(), [1]); // 19 is happy
+///
+/// let trajectory = digit_power_sum_trajectory(55, 3, 10);
+/// assert_eq!(trajectory.path, vec![55, 250, 133]); // 1 + 27 + 27 = 55
+/// assert_eq!(trajectory.cycle_start, 0);
+/// ```
+pub fn digit_power_sum_trajectory(n: u64, power: u32, base: u32) -> Trajectory {
+    if base < 2 {
+        panic!("base must be at least 2");
+    }
+    let mut seen: HashMap<u64, usize> = HashMap::new();
+    let mut path: Vec<u64> = vec![];
+    let mut m: u64 = n;
+    while !seen.contains_key(&m) {
+        seen.insert(m, path.len());
+        path.push(m);
+        m = digit_power_sum(m, power, base);
+    }
+    Trajectory {
+        path,
+        cycle_start: seen[&m],
+    }
+}
+
+/// Finds every cycle of the map taking a number to the sum of a power of its digits, fixed points included.
+///
+/// Let $D$ be the smallest number of digits with $b^{D - 1} > D(b - 1)^p$. From $D$ digits on the digit power sum
+/// is always smaller than the number, so every cycle has a value below $b^{D - 1}$ whose digit power sum is the
+/// largest value of the cycle, and every cycle lies in $[0, (D - 1)(b - 1)^p]$. The map sends this interval into
+/// itself, and each number in it is followed until it reaches a value seen before. This takes time and memory
+/// linear in the bound, which grows exponentially in `power`.
+///
+/// [A023052](https://oeis.org/A023052)
+///
+/// # Arguments
+///
+/// * `power` - The power the digits are raised to.
+/// * `base` - The base the digits are taken in.
+///
+/// # Returns
+///
+/// Vector of the cycles, each starting at its smallest value and listed in order, sorted by their smallest values.
+/// This includes the trivial fixed points $0$ and (for a positive `power`) $1$.
+///
+/// # Panics
+///
+/// Panics if `base` is less than 2 or the bound does not fit in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::fixed_points_and_cycles;
+/// let cycles: Vec<Vec<u64>> = fixed_points_and_cycles(2, 10);
+/// assert_eq!(cycles, vec![vec![0], vec![1], vec![4, 16, 37, 58, 89, 145, 42, 20]]);
+///
+/// let fixed_points: Vec<u64> = fixed_points_and_cycles(4, 10)
+///     .into_iter()
+///     .filter(|cycle| cycle.len() == 1)
+///     .map(|cycle| cycle[0])
+///     .collect();
+/// assert_eq!(fixed_points, vec![0, 1, 1634, 8208, 9474]);
+/// ```
+pub fn fixed_points_and_cycles(power: u32, base: u32) -> Vec<Vec<u64>> {
+    if base < 2 {
+        panic!("base must be at least 2");
+    }
+    let largest_term: u128 = ((base - 1) as u128).pow(power);
+    let mut digit_count: u32 = 1;
+    while (base as u128).pow(digit_count - 1) <= digit_count as u128 * largest_term {
+        digit_count += 1;
+    }
+    let bound: u64 = match ((digit_count - 1) as u128 * largest_term).try_into() {
+        Ok(bound) => bound,
+        Err(_) => panic!("the cycle bound does not fit in a u64"),
+    };
+    // 0 for unvisited, 1 for on the current path, 2 for finished
+    let mut state: Vec<u8> = vec![0; bound as usize + 1];
+    let mut cycles: Vec<Vec<u64>> = vec![];
+    let mut path: Vec<u64> = vec![];
+    let mut n: u64 = 0;
+    while n <= bound {
+        let mut m: u64 = n;
+        while state[m as usize] == 0 {
+            state[m as usize] = 1;
+            path.push(m);
+            m = digit_power_sum(m, power, base);
+        }
+        if state[m as usize] == 1 {
+            let start: usize = path.iter().position(|value| *value == m).unwrap();
+            let mut cycle: Vec<u64> = path[start..].to_vec();
+            let smallest: usize = (0..cycle.len()).min_by_key(|i| cycle[*i]).unwrap();
+            cycle.rotate_left(smallest);
+            cycles.push(cycle);
+        }
+        for value in path.drain(..) {
+            state[value as usize] = 2;
+        }
+        n += 1;
+    }
+    cycles.sort();
+    cycles
+}
+
+/// Determines whether or not a number is a Kaprekar number.
+///
+/// `n` is a Kaprekar number if $n^2$ can be split into two parts that add up to `n`,
+/// where the right part is nonzero (e.g. $45^2 = 2025$ and $20 + 25 = 45$).
+///
+/// [A006886](https://oeis.org/A006886)
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// Boolean representing whether `n` is a Kaprekar number.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::is_kaprekar;
+/// assert_eq!(is_kaprekar(45), true);
+/// assert_eq!(is_kaprekar(297), true); // 88209 -> 88 + 209
+/// assert_eq!(is_kaprekar(100), false); // 10000 -> 100 + 00, but the right part is zero
+/// ```
+pub fn is_kaprekar(n: u64) -> bool {
+    if n == 0 {
+        return false;
+    }
+    let square: u128 = n as u128 * n as u128;
+    let mut power: u128 = 10;
+    loop {
+        let left: u128 = square / power;
+        let right: u128 = square % power;
+        if right != 0 && left + right == n as u128 {
+            return true;
+        }
+        if left == 0 {
+            return false;
+        }
+        // past 10^38 no split is left that could add up to n
+        power = match power.checked_mul(10) {
+            Some(power) => power,
+            None => return false,
+        };
+    }
+}
+
+/// Performs a single step of Kaprekar's routine on a 4 digit number.
+///
+/// The digits (including leading zeros) are arranged in descending and ascending order,
+/// and the smaller number is subtracted from the larger one.
+///
+/// # Arguments
+///
+/// * `n` - The number to perform the step on (at most 4 digits).
+///
+/// # Returns
+///
+/// The next number in Kaprekar's routine.
+///
+/// # Example
+///
+/// ```
+/// fn kaprekar_step(n: u32) -> u32 {
+///     let mut d: [u32; 4] = [n / 1000, (n / 100) % 10, (n / 10) % 10, n % 10];
+///     d.sort();
+///     let ascending: u32 = d[0] * 1000 + d[1] * 100 + d[2] * 10 + d[3];
+///     let descending: u32 = d[3] * 1000 + d[2] * 100 + d[1] * 10 + d[0];
+///     descending - ascending
+/// }
+///
+/// assert_eq!(kaprekar_step(3524), 3087); // 5432 - 2345
+/// ```
+fn kaprekar_step(n: u32) -> u32 {
+    let mut d: [u32; 4] = [n / 1000, (n / 100) % 10, (n / 10) % 10, n % 10];
+    d.sort();
+    let ascending: u32 = d[0] * 1000 + d[1] * 100 + d[2] * 10 + d[3];
+    let descending: u32 = d[3] * 1000 + d[2] * 100 + d[1] * 10 + d[0];
+    descending - ascending
+}
+
+/// Iterates Kaprekar's routine on a 4 digit number until it reaches a fixed point.
+///
+/// Every 4 digit number with at least two distinct digits (leading zeros count as digits)
+/// reaches *Kaprekar's constant* $6174$ within 7 steps.
+/// Repdigits like $1111$ instead collapse to $0$ after a single step.
+///
+/// # Arguments
+///
+/// * `n` - The starting number (at most 4 digits).
+///
+/// # Returns
+///
+/// Vector containing the trajectory, starting with `n` and ending with the fixed point (`6174` or `0`).
+///
+/// # Panics
+///
+/// Panics if `n` has more than 4 digits.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::kaprekar_routine;
+/// assert_eq!(kaprekar_routine(3524), vec![3524, 3087, 8352, 6174]);
+/// assert_eq!(kaprekar_routine(6174), vec![6174]);
+/// assert_eq!(kaprekar_routine(7777), vec![7777, 0]);
+/// ```
+pub fn kaprekar_routine(n: u32) -> Vec<u32> {
+    if n > 9999 {
+        panic!("Kaprekar's routine is only defined here for 4 digit numbers");
+    }
+    let d: [u32; 4] = [n / 1000, (n / 100) % 10, (n / 10) % 10, n % 10];
+    if d.iter().all(|digit| *digit == d[0]) {
+        // repdigits have no distinct digits to rearrange, so they go straight to 0
+        return if n == 0 { vec![0] } else { vec![n, 0] };
+    }
+    let mut trajectory: Vec<u32> = vec![n];
+    loop {
+        let next: u32 = kaprekar_step(*trajectory.last().unwrap());
+        if trajectory.contains(&next) {
+            return trajectory;
+        }
+        trajectory.push(next);
+    }
+}
+
+/// Determines whether or not a number is narcissistic (or an Armstrong number) in a given base.
+///
+/// A $d$ digit number is narcissistic if it is equal to the sum of its digits each raised to the power $d$
+/// (e.g. $153 = 1^3 + 5^3 + 3^3$).
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+/// * `base` - The base the digits of `n` are taken in.
+///
+/// # Returns
+///
+/// Boolean representing whether `n` is narcissistic in base `base`.
+///
+/// # Panics
+///
+/// Panics if `base` is less than 2.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::is_narcissistic;
+/// assert_eq!(is_narcissistic(153, 10), true);
+/// assert_eq!(is_narcissistic(154, 10), false);
+/// assert_eq!(is_narcissistic(5, 3), true); // 12 -> 1^2 + 2^2
+/// ```
+pub fn is_narcissistic(n: u64, base: u32) -> bool {
+    let d: Vec<u32> = digits(n, base);
+    let power: u32 = d.len() as u32;
+    let mut sum: u128 = 0;
+    for digit in d {
+        sum += (digit as u128).pow(power);
+    }
+    sum == n as u128
+}
+
+/// Base 10 version of [`is_narcissistic`].
+///
+/// [A005188](https://oeis.org/A005188)
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::is_narcissistic_base_10;
+/// assert_eq!(is_narcissistic_base_10(9474), true); // 9^4 + 4^4 + 7^4 + 4^4
+/// ```
+pub fn is_narcissistic_base_10(n: u64) -> bool {
+    is_narcissistic(n, 10)
+}
+
+/// Enumerates every narcissistic number in a given base that fits in a `u64`.
+///
+/// There are only finitely many narcissistic numbers in any base, since a $d$ digit number is at least $b^{d-1}$
+/// while its digit power sum is at most $d(b-1)^d$, which eventually falls behind.
+/// Rather than scanning every number, this goes through each multiset of $d$ digits,
+/// since the digit power sum does not depend on the order of the digits,
+/// and checks whether the sum is made up of exactly those digits.
+///
+/// Note: The list is capped to the `u64` range, so in base 10 it ends at $4929273885928088826$
+/// rather than at the largest narcissistic number $115132219018763992565095597973971522401$.
+///
+/// # Arguments
+///
+/// * `base` - The base the digits are taken in.
+///
+/// # Returns
+///
+/// Sorted vector of all positive narcissistic numbers in base `base` which fit in a `u64`.
+///
+/// # Panics
+///
+/// Panics if `base` is less than 2.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::narcissistic_numbers;
+/// assert_eq!(narcissistic_numbers(3), vec![1, 2, 5, 8, 17]);
+/// ```
+pub fn narcissistic_numbers(base: u32) -> Vec<u64> {
+    if base < 2 {
+        panic!("base must be at least 2");
+    }
+    let mut found: Vec<u64> = vec![];
+    let mut d: u32 = 1;
+    loop {
+        // the smallest d digit number
+        let lower: u128 = (base as u128).pow(d - 1);
+        if lower > u64::MAX as u128 || (d as u128) * ((base - 1) as u128).pow(d) < lower {
+            break;
+        }
+        let upper: u128 = ((base as u128).pow(d) - 1).min(u64::MAX as u128);
+        let mut search: NarcissisticSearch = NarcissisticSearch {
+            base,
+            powers: (0..base).map(|digit| (digit as u128).pow(d)).collect(),
+            counts: vec![0; base as usize],
+            sum_counts: vec![0; base as usize],
+            bounds: (lower, upper),
+            found: vec![],
+        };
+        search.search(base - 1, d, 0);
+        found.append(&mut search.found);
+        d += 1;
+    }
+    found.sort();
+    found
+}
+
+/// Base 10 version of [`narcissistic_numbers`].
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::narcissistic_numbers_base_10;
+/// assert_eq!(narcissistic_numbers_base_10()[9..14], [153, 370, 371, 407, 1634]);
+/// ```
+pub fn narcissistic_numbers_base_10() -> Vec<u64> {
+    narcissistic_numbers(10)
+}
+
+/// State shared across the recursive multiset search done by `narcissistic_numbers`.
+struct NarcissisticSearch {
+    /// The base the digits are taken in.
+    base: u32,
+    /// Each digit raised to the digit count.
+    powers: Vec<u128>,
+    /// How many times each digit has been chosen so far.
+    counts: Vec<u32>,
+    /// Scratch space for counting the digits of a candidate sum.
+    sum_counts: Vec<u32>,
+    /// The smallest and largest values a number with this many digits can have.
+    bounds: (u128, u128),
+    /// The narcissistic numbers found so far.
+    found: Vec<u64>,
+}
+
+impl NarcissisticSearch {
+    /// Recursively chooses a multiset of digits, from largest to smallest.
+    ///
+    /// # Arguments
+    ///
+    /// * `digit` - The largest digit that can still be chosen.
+    /// * `remaining` - How many digits are still left to choose.
+    /// * `sum` - The digit power sum of the digits chosen so far.
+    fn search(&mut self, digit: u32, remaining: u32, sum: u128) {
+        // the digits left to choose can add at most `remaining * digit^d`
+        if sum > self.bounds.1
+            || sum + remaining as u128 * self.powers[digit as usize] < self.bounds.0
+        {
+            return;
+        }
+        if remaining == 0 {
+            self.check(sum as u64);
+            return;
+        }
+        if !self.prefix_possible(digit, remaining, sum) {
+            return;
+        }
+        if digit == 0 {
+            self.counts[0] = remaining;
+            self.check(sum as u64);
+            self.counts[0] = 0;
+            return;
+        }
+        let mut taken: u32 = 0;
+        let mut partial: u128 = sum;
+        while taken <= remaining && partial <= self.bounds.1 {
+            self.counts[digit as usize] = taken;
+            self.search(digit - 1, remaining - taken, partial);
+            taken += 1;
+            partial += self.powers[digit as usize];
+        }
+        self.counts[digit as usize] = 0;
+    }
+
+    /// Checks whether the leading digits shared by every sum still reachable can be made from the digits left.
+    ///
+    /// Whatever digits are chosen next, the final sum lies between `sum` and `sum + remaining * digit^d`,
+    /// so the leading digits those two bounds have in common are already decided.
+    fn prefix_possible(&self, digit: u32, remaining: u32, sum: u128) -> bool {
+        let base: u64 = self.base as u64;
+        let mut low: u64 = sum as u64;
+        let mut high: u64 = match (sum + remaining as u128 * self.powers[digit as usize]).try_into()
+        {
+            Ok(high) => high,
+            Err(_) => return true,
+        };
+        while low != high {
+            low /= base;
+            high /= base;
+        }
+        let mut used: Vec<u32> = vec![0; self.base as usize];
+        let mut unchosen: u32 = 0;
+        while low > 0 {
+            let d: usize = (low % base) as usize;
+            if d as u32 > digit {
+                used[d] += 1;
+                if used[d] > self.counts[d] {
+                    return false;
+                }
+            } else {
+                unchosen += 1;
+                if unchosen > remaining {
+                    return false;
+                }
+            }
+            low /= base;
+        }
+        true
+    }
+
+    /// Checks whether `sum` is made up of exactly the chosen multiset of digits.
+    fn check(&mut self, sum: u64) {
+        let base: u64 = self.base as u64;
+        let mut m: u64 = sum;
+        while m > 0 {
+            self.sum_counts[(m % base) as usize] += 1;
+            m /= base;
+        }
+        if self.sum_counts == self.counts {
+            self.found.push(sum);
+        }
+        let mut m: u64 = sum;
+        while m > 0 {
+            self.sum_counts[(m % base) as usize] = 0;
+            m /= base;
+        }
+    }
+}
+
+/// Finds the right-truncatable primes in a given base, the primes that stay prime as their last digit is removed
+/// over and over.
+///
+/// The primes are built up a digit at a time, starting from the prime digits and appending every digit to each
+/// prime of the previous length, which keeps only the extensions that are prime (checked with [`check_prime`]).
+/// This ends once no prime of some length extends, so the list is finite. In base 2 it is empty, since neither
+/// binary digit is prime.
+///
+/// Note: Extensions that do not fit in a `u128` are dropped, which only cuts the list short in base 34.
+///
+/// [A024770](https://oeis.org/A024770)
+///
+/// # Arguments
+///
+/// * `base` - The base the digits are taken in, from 2 to 36.
+///
+/// # Returns
+///
+/// Sorted vector of the right-truncatable primes in base `base` which fit in a `u128`.
+///
+/// # Panics
+///
+/// Panics if `base` is not between 2 and 36.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::right_truncatable_primes_in_base;
+/// let primes: Vec<u128> = right_truncatable_primes_in_base(10);
+/// assert_eq!(primes.len(), 83);
+/// assert_eq!(primes[..8], [2, 3, 5, 7, 23, 29, 31, 37]);
+/// assert_eq!(right_truncatable_primes_in_base(3), vec![2, 7, 23, 71]); // 2, 21, 212, 2122
+/// ```
+pub fn right_truncatable_primes_in_base(base: u32) -> Vec<u128> {
+    truncatable_primes(base, |p, digit, _| {
+        p.checked_mul(base as u128)?.checked_add(digit as u128)
+    })
+}
+
+/// Finds the left-truncatable primes in a given base, the primes that stay prime as their first digit is removed
+/// over and over.
+///
+/// The primes are built up a digit at a time, starting from the prime digits and putting every nonzero digit in
+/// front of each prime of the previous length, which keeps only the extensions that are prime (checked with
+/// [`check_prime`]). Zero digits are left out as usual, since removing the digit in front of a zero would leave a
+/// leading zero. This ends once no prime of some length extends, so the list is finite, though it grows quickly
+/// with the base ($4260$ primes in base 10, but $170053$ in base 12). In base 2 it is empty, since neither binary
+/// digit is prime.
+///
+/// Note: Extensions that do not fit in a `u128` are dropped, so in large bases only the primes below $2^{128}$ are
+/// found.
+///
+/// [A024785](https://oeis.org/A024785)
+///
+/// # Arguments
+///
+/// * `base` - The base the digits are taken in, from 2 to 36.
+///
+/// # Returns
+///
+/// Sorted vector of the left-truncatable primes in base `base` which fit in a `u128`.
+///
+/// # Panics
+///
+/// Panics if `base` is not between 2 and 36.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::left_truncatable_primes_in_base;
+/// assert_eq!(left_truncatable_primes_in_base(3), vec![2, 5, 23]); // 2, 12, 212
+/// assert_eq!(left_truncatable_primes_in_base(7).len(), 22);
+/// ```
+pub fn left_truncatable_primes_in_base(base: u32) -> Vec<u128> {
+    truncatable_primes(base, |p, digit, power| {
+        if digit == 0 {
+            return None;
+        }
+        (digit as u128).checked_mul(power?)?.checked_add(p)
+    })
+}
+
+/// Builds the truncatable primes in a base by extending the primes of each length by one digit.
+///
+/// # Arguments
+///
+/// * `base` - The base the digits are taken in, from 2 to 36.
+/// * `extend` - Called with a prime, a digit and the power of the base equal to the place the prime's digits
+///   end at (`None` once it does not fit in a `u128`), giving the extension by that digit or `None` if there
+///   is none.
+///
+/// # Returns
+///
+/// Sorted vector of the primes reached from the prime digits by prime extensions.
+///
+/// # Panics
+///
+/// Panics if `base` is not between 2 and 36.
+fn truncatable_primes(
+    base: u32,
+    extend: impl Fn(u128, u32, Option<u128>) -> Option<u128>,
+) -> Vec<u128> {
+    if !(2..=36).contains(&base) {
+        panic!("base must be between 2 and 36");
+    }
+    let mut layer: Vec<u128> = (2..base as u128).filter(|d| check_prime(*d)).collect();
+    let mut found: Vec<u128> = layer.clone();
+    // the smallest number with one more digit than the primes in the current layer
+    let mut power: Option<u128> = Some(base as u128);
+    while !layer.is_empty() {
+        let mut next: Vec<u128> = vec![];
+        for p in &layer {
+            let mut digit: u32 = 0;
+            while digit < base {
+                if let Some(candidate) = extend(*p, digit, power) {
+                    if check_prime(candidate) {
+                        next.push(candidate);
+                    }
+                }
+                digit += 1;
+            }
+        }
+        found.extend_from_slice(&next);
+        layer = next;
+        power = power.and_then(|power| power.checked_mul(base as u128));
+    }
+    found.sort();
+    found
+}
+
+/// Checks if the digits of one number appear, in order but not necessarily next to each other, among the digits
+/// of another.
+///
+/// # Arguments
+///
+/// * `small` - The number whose digits are looked for.
+/// * `big` - The number whose digits are searched.
+/// * `base` - The base the digits are taken in.
+///
+/// # Returns
+///
+/// `true` if the digits of `small` are a subsequence of the digits of `big`.
+///
+/// # Panics
+///
+/// Panics if `base` is less than 2.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::is_digit_subsequence;
+/// assert!(is_digit_subsequence(49, 60000049, 10));
+/// assert!(is_digit_subsequence(19, 1009, 10));
+/// assert!(!is_digit_subsequence(91, 1009, 10)); // the order matters
+/// assert!(is_digit_subsequence(3, 7, 2)); // 11 in 111
+/// ```
+pub fn is_digit_subsequence(small: u64, big: u64, base: u32) -> bool {
+    is_subsequence(&digits(small, base), &digits(big, base))
+}
+
+/// Checks if one digit sequence is a (not necessarily contiguous) subsequence of another.
+///
+/// # Arguments
+///
+/// * `small` - The digits looked for.
+/// * `big` - The digits searched.
+///
+/// # Returns
+///
+/// `true` if the digits of `small` appear in `big` in the same order.
+fn is_subsequence(small: &[u32], big: &[u32]) -> bool {
+    let mut i: usize = 0;
+    for digit in big {
+        if i < small.len() && small[i] == *digit {
+            i += 1;
+        }
+    }
+    i == small.len()
+}
+
+/// Finds the minimal primes in a given base, the primes that have no shorter prime as a (not necessarily
+/// contiguous) subsequence of their digits.
+///
+/// By Higman's lemma there are only finitely many in any base, and every prime has one of them as a subsequence.
+/// The search builds up digit strings a digit at a time, keeping only the composite strings (and $1$) that have no
+/// minimal prime found so far as a subsequence, since any string with a minimal prime inside it can only lead to
+/// non-minimal primes. If the strings run out, the set is complete.
+/// However in most bases the strings never run out (in base 10 any string of even digits can be extended forever),
+/// and the largest minimal prime can be far too big to reach, so the search also stops at `max_digits` digits.
+/// Base 10 needs 8 digits for the full set of 26 found by Shallit, which ends with $66600049$.
+///
+/// Note: Strings whose value does not fit in a `u64` are dropped.
+///
+/// [A071062](https://oeis.org/A071062)
+///
+/// # Arguments
+///
+/// * `base` - The base the digits are taken in.
+/// * `max_digits` - The largest number of digits to search.
+///
+/// # Returns
+///
+/// Sorted vector of the minimal primes in base `base` with at most `max_digits` digits.
+///
+/// # Panics
+///
+/// Panics if `base` is less than 2.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::minimal_primes;
+/// let primes: Vec<u64> = minimal_primes(10, 8);
+/// assert_eq!(primes.len(), 26);
+/// assert_eq!(primes[..10], [2, 3, 5, 7, 11, 19, 41, 61, 89, 409]);
+/// assert_eq!(minimal_primes(10, 4).len(), 20);
+/// assert_eq!(minimal_primes(2, 100), vec![2, 3]); // 10 and 11
+/// ```
+pub fn minimal_primes(base: u32, max_digits: u32) -> Vec<u64> {
+    if base < 2 {
+        panic!("base must be at least 2");
+    }
+    let mut found: Vec<(u64, Vec<u32>)> = vec![];
+    // the strings with no minimal prime inside, with their values
+    let mut layer: Vec<(u64, Vec<u32>)> = vec![(0, vec![])];
+    let mut length: u32 = 0;
+    while length < max_digits && !layer.is_empty() {
+        let mut next: Vec<(u64, Vec<u32>)> = vec![];
+        let mut new_primes: Vec<(u64, Vec<u32>)> = vec![];
+        for (value, string) in &layer {
+            let mut digit: u32 = if string.is_empty() { 1 } else { 0 };
+            while digit < base {
+                let extended: u64 = match value
+                    .checked_mul(base as u64)
+                    .and_then(|v| v.checked_add(digit as u64))
+                {
+                    Some(extended) => extended,
+                    None => break,
+                };
+                // the string had no minimal prime inside, so one could only end with the new digit
+                let contains_minimal: bool = found.iter().any(|(_, prime)| {
+                    prime.last() == Some(&digit)
+                        && is_subsequence(&prime[..prime.len() - 1], string)
+                });
+                if !contains_minimal {
+                    let mut extended_string: Vec<u32> = string.clone();
+                    extended_string.push(digit);
+                    if is_prime_u64(extended) {
+                        new_primes.push((extended, extended_string));
+                    } else {
+                        next.push((extended, extended_string));
+                    }
+                }
+                digit += 1;
+            }
+        }
+        // primes of the same length cannot contain each other
+        found.append(&mut new_primes);
+        layer = next;
+        length += 1;
+    }
+    let mut primes: Vec<u64> = found.into_iter().map(|(p, _)| p).collect();
+    primes.sort();
+    primes
+}
+
+/// Calculates the repunit of length `k` in a given base.
+///
+/// A repunit is a number whose digits are all $1$, which is
+/// $$R_k^{(b)} = \frac{b^k - 1}{b - 1} = 1 + b + b^2 + \dots + b^{k-1}.$$
+///
+/// # Arguments
+///
+/// * `k` - The number of digits.
+/// * `base` - The base the digits are taken in.
+///
+/// # Returns
+///
+/// `Some` repunit, or `None` if it does not fit in a `u128`.
+///
+/// # Panics
+///
+/// Panics if `base` is less than 2.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::repunit;
+/// assert_eq!(repunit(4, 10), Some(1111));
+/// assert_eq!(repunit(5, 2), Some(31));
+/// assert_eq!(repunit(0, 10), Some(0));
+/// assert_eq!(repunit(40, 10), None);
+/// ```
+pub fn repunit(k: u32, base: u32) -> Option<u128> {
+    if base < 2 {
+        panic!("base must be at least 2");
+    }
+    let mut r: u128 = 0;
+    let mut i: u32 = 0;
+    while i < k {
+        r = r.checked_mul(base as u128)?.checked_add(1)?;
+        i += 1;
+    }
+    Some(r)
+}
+
+/// Determines whether or not a number is a repunit (all of its digits are $1$) in a given base.
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+/// * `base` - The base the digits of `n` are taken in.
+///
+/// # Returns
+///
+/// Boolean representing whether `n` is a repunit (`0`, the empty repunit, is not counted).
+///
+/// # Panics
+///
+/// Panics if `base` is less than 2.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::is_repunit;
+/// assert_eq!(is_repunit(11111, 10), true);
+/// assert_eq!(is_repunit(13, 3), true); // 111
+/// assert_eq!(is_repunit(12, 10), false);
+/// ```
+pub fn is_repunit(n: u128, base: u32) -> bool {
+    if base < 2 {
+        panic!("base must be at least 2");
+    }
+    if n == 0 {
+        return false;
+    }
+    let mut m: u128 = n;
+    while m > 0 {
+        if m % base as u128 != 1 {
+            return false;
+        }
+        m /= base as u128;
+    }
+    true
+}
+
+/// Finds the lengths `k` for which the repunit $R_k^{(b)}$ is a (probable) prime.
+///
+/// Since $R_{ab}$ is divisible by $R_a$, a repunit can only be prime when its length is prime,
+/// so composite lengths are skipped without testing. The remaining repunits are checked with
+/// [`is_probable_prime`](crate::primes::is_probable_prime), so lengths whose repunit does not fit in a `u128` still work.
+///
+/// [A004023](https://oeis.org/A004023)
+///
+/// # Arguments
+///
+/// * `base` - The base of the repunits.
+/// * `k_max` - The largest length to check (inclusive).
+///
+/// # Returns
+///
+/// Vector of the lengths `k <= k_max` whose repunit is (probably) prime.
+///
+/// # Panics
+///
+/// Panics if `base` is less than 2.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::repunit_prime_exponents;
+/// assert_eq!(repunit_prime_exponents(10, 30), vec![2, 19, 23]);
+/// assert_eq!(repunit_prime_exponents(2, 20), vec![2, 3, 5, 7, 13, 17, 19]); // Mersenne primes
+/// ```
+pub fn repunit_prime_exponents(base: u32, k_max: u32) -> Vec<u32> {
+    if base < 2 {
+        panic!("base must be at least 2");
+    }
+    let mut exponents: Vec<u32> = vec![];
+    let mut r: BigInt = BigInt::from(0);
+    let mut k: u32 = 1;
+    while k <= k_max {
+        r = r * base + 1;
+        if is_prime_u64(k as u64) && is_probable_prime(&r) {
+            exponents.push(k);
+        }
+        k += 1;
+    }
+    exponents
+}
+
+/// Calculates the length of the repeating cycle in the expansion of $1/n$ in a given base.
+///
+/// Removing every factor shared with the base from $n$ leaves $n'$, and the period is the multiplicative
+/// order of the base modulo $n'$.
+///
+/// # Arguments
+///
+/// * `n` - The denominator.
+/// * `base` - The base to expand $1/n$ in.
+///
+/// # Returns
+///
+/// The period of $1/n$, or `0` if the expansion terminates.
+///
+/// # Panics
+///
+/// Panics if `n` is `0` or `base` is less than 2.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::period_in_base;
+/// assert_eq!(period_in_base(3, 2), 2); // 0.010101...
+/// assert_eq!(period_in_base(9, 3), 0); // 0.01
+/// ```
+pub fn period_in_base(n: u64, base: u32) -> u64 {
+    if n == 0 {
+        panic!("n must be positive");
+    }
+    if base < 2 {
+        panic!("base must be at least 2");
+    }
+    let mut m: u64 = n;
+    let mut g: u64 = gcd(m, base as u64);
+    while g > 1 {
+        while m.is_multiple_of(g) {
+            m /= g;
+        }
+        g = gcd(m, base as u64);
+    }
+    if m == 1 {
+        return 0;
+    }
+    multiplicative_order(base as u64, m).unwrap()
+}
+
+/// Calculates the length of the repeating cycle in the decimal expansion of $1/n$.
+///
+/// [A051626](https://oeis.org/A051626)
+///
+/// # Arguments
+///
+/// * `n` - The denominator.
+///
+/// # Returns
+///
+/// The period of $1/n$, or `0` if the only prime factors of `n` are $2$ and $5$.
+///
+/// # Panics
+///
+/// Panics if `n` is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::decimal_period;
+/// assert_eq!(decimal_period(7), 6); // 0.142857...
+/// assert_eq!(decimal_period(12), 1); // 0.08333...
+/// assert_eq!(decimal_period(40), 0); // 0.025
+/// ```
+pub fn decimal_period(n: u64) -> u64 {
+    period_in_base(n, 10)
+}
+
+/// Calculates the decimal expansion of a fraction, split into the part before the repeating cycle and the cycle.
+///
+/// The digits are found by long division. The number of digits before the cycle is the larger of the
+/// exponents of $2$ and $5$ in the reduced denominator, and the cycle has length [`decimal_period`].
+///
+/// # Arguments
+///
+/// * `num` - The numerator.
+/// * `den` - The denominator.
+///
+/// # Returns
+///
+/// A tuple `(prefix, repetend)`, where `prefix` is the integer part, followed by a decimal point and the
+/// non-repeating digits when the fraction is not an integer, and `repetend` is the repeating digits
+/// (empty if the expansion terminates).
+///
+/// # Panics
+///
+/// Panics if `den` is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::repeating_decimal;
+/// assert_eq!(repeating_decimal(1, 7), ("0.".to_string(), "142857".to_string()));
+/// assert_eq!(repeating_decimal(1, 6), ("0.1".to_string(), "6".to_string()));
+/// assert_eq!(repeating_decimal(7, 4), ("1.75".to_string(), "".to_string()));
+/// assert_eq!(repeating_decimal(6, 3), ("2".to_string(), "".to_string()));
+/// ```
+pub fn repeating_decimal(num: u64, den: u64) -> (String, String) {
+    if den == 0 {
+        panic!("the denominator must be positive");
+    }
+    let g: u64 = gcd(num, den);
+    let den: u64 = den / g;
+    let mut prefix: String = (num / g / den).to_string();
+    let mut remainder: u128 = ((num / g) % den) as u128;
+    if remainder == 0 {
+        return (prefix, String::new());
+    }
+    prefix.push('.');
+
+    let mut twos: u32 = 0;
+    let mut fives: u32 = 0;
+    let mut m: u64 = den;
+    while m.is_multiple_of(2) {
+        m /= 2;
+        twos += 1;
+    }
+    while m.is_multiple_of(5) {
+        m /= 5;
+        fives += 1;
+    }
+
+    let mut next_digit = || {
+        remainder *= 10;
+        let digit: u128 = remainder / den as u128;
+        remainder %= den as u128;
+        char::from_digit(digit as u32, 10).unwrap()
+    };
+    for _ in 0..twos.max(fives) {
+        prefix.push(next_digit());
+    }
+    let mut repetend: String = String::new();
+    for _ in 0..decimal_period(m) {
+        repetend.push(next_digit());
+    }
+    (prefix, repetend)
+}
+
+/// Checks if a number is a Smith number.
+///
+/// A Smith number is a composite number whose base 10 digit sum equals the sum of the digit sums of its
+/// prime factors, counted with multiplicity ([A006753](https://oeis.org/A006753)).
+/// For example, $22 = 2 \times 11$ has digit sum $4 = 2 + (1 + 1)$.
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// `true` if `n` is a Smith number (primes never are).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::is_smith;
+/// assert!(is_smith(666)); // 2 * 3 * 3 * 37
+/// assert!(!is_smith(7));
+/// assert!(!is_smith(10));
+/// ```
+pub fn is_smith(n: u64) -> bool {
+    let factors: Vec<(u64, u32)> = factorize(n);
+    if factors.is_empty() || factors == [(n, 1)] {
+        return false;
+    }
+    let mut factor_digit_sum: u64 = 0;
+    for (p, e) in factors {
+        factor_digit_sum += e as u64 * digit_sum(p, 10);
+    }
+    factor_digit_sum == digit_sum(n, 10)
+}
+
+/// Lists the Smith numbers below a limit (see [`is_smith`]).
+///
+/// # Arguments
+///
+/// * `limit` - The exclusive upper bound.
+///
+/// # Returns
+///
+/// Vector of the Smith numbers below `limit`, in increasing order.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::smith_numbers_below;
+/// assert_eq!(smith_numbers_below(100), vec![4, 22, 27, 58, 85, 94]);
+/// ```
+pub fn smith_numbers_below(limit: u64) -> Vec<u64> {
+    (4..limit).filter(|n| is_smith(*n)).collect()
+}
+
+/// Groups the primes with a given number of decimal digits into families of digit permutations of each other.
+///
+/// The primes are found with one sieve of $[10^{d - 1}, 10^d)$ and grouped in a `HashMap` keyed by their digits
+/// in decreasing order read as a number, which is the same for two primes exactly when they are permutations of
+/// each other (the primes all have $d$ digits, so no digit is lost as a leading zero).
+///
+/// # Arguments
+///
+/// * `digits` - The number of decimal digits $d$ (from $1$ to $19$).
+///
+/// # Returns
+///
+/// Vector of the families (including the primes that are alone in theirs), each in increasing order, ordered by
+/// their smallest member.
+///
+/// # Panics
+///
+/// Panics if `digits` is `0` or more than `19`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::prime_permutation_families;
+///
+/// let families: Vec<Vec<u64>> = prime_permutation_families(2);
+/// assert_eq!(families[..4], [vec![11], vec![13, 31], vec![17, 71], vec![19]]);
+/// assert!(families.contains(&vec![37, 73]));
+/// ```
+pub fn prime_permutation_families(digits: u32) -> Vec<Vec<u64>> {
+    if digits == 0 || digits > 19 {
+        panic!("the number of digits must be from 1 to 19");
+    }
+    let mut families: HashMap<u64, Vec<u64>> = HashMap::new();
+    for p in primes_in_range(10_u64.pow(digits - 1), 10_u64.pow(digits)) {
+        families.entry(digit_signature(p)).or_default().push(p);
+    }
+    let mut families: Vec<Vec<u64>> = families.into_values().collect();
+    families.sort_unstable_by_key(|family| family[0]);
+    families
+}
+
+/// Calculates the digits of a number in decreasing order, read as a number.
+///
+/// # Arguments
+///
+/// * `n` - The number.
+///
+/// # Returns
+///
+/// The same value for any two numbers with the same number of digits that are permutations of each other.
+fn digit_signature(n: u64) -> u64 {
+    let mut sorted: Vec<u32> = digits(n, 10);
+    sorted.sort_unstable_by(|a, b| b.cmp(a));
+    sorted
+        .iter()
+        .fold(0, |signature, d| signature * 10 + *d as u64)
+}
+
+/// Finds the triples of primes with a given number of decimal digits that are digit permutations of each other
+/// and in arithmetic progression, such as $1487$, $4817$, $8147$ (Project Euler problem 49).
+///
+/// # Arguments
+///
+/// * `digits` - The number of decimal digits (from $1$ to $19$).
+///
+/// # Returns
+///
+/// Vector of the triples `[a, b, c]` with $a < b < c$ and $b - a = c - b$, in increasing order.
+///
+/// # Panics
+///
+/// Panics if `digits` is `0` or more than `19`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::arithmetic_prime_permutation_triples;
+/// assert_eq!(
+///     arithmetic_prime_permutation_triples(4),
+///     vec![[1487, 4817, 8147], [2969, 6299, 9629]]
+/// );
+/// ```
+pub fn arithmetic_prime_permutation_triples(digits: u32) -> Vec<[u64; 3]> {
+    let mut triples: Vec<[u64; 3]> = vec![];
+    for family in prime_permutation_families(digits) {
+        let mut i: usize = 0;
+        while i < family.len() {
+            let mut j: usize = i + 1;
+            while j < family.len() {
+                let c: Option<u64> = family[j].checked_add(family[j] - family[i]);
+                if let Some(c) = c.filter(|c| family[j + 1..].binary_search(c).is_ok()) {
+                    triples.push([family[i], family[j], c]);
+                }
+                j += 1;
+            }
+            i += 1;
+        }
+    }
+    triples.sort_unstable();
+    triples
+}
+
+/// Finds the primes made by replacing some digits of a number with the same digit, for each digit $0$ to $9$.
+///
+/// For example, replacing the first digit of $13$ gives $13, 23, \ldots, 93$, of which $13, 23, 43, 53, 73$ and
+/// $83$ are prime. A replacement that would start with a `0` is skipped, so every member has as many digits as
+/// the number.
+///
+/// # Arguments
+///
+/// * `p` - The number whose digits are replaced (it does not have to be prime itself).
+/// * `positions` - The positions of the digits to replace, counted from `0` at the most significant digit.
+///
+/// # Returns
+///
+/// Vector of the primes in the family, in increasing order.
+///
+/// # Panics
+///
+/// Panics if `positions` is empty or a position is not a digit of `p`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::digit_replacement_family;
+///
+/// assert_eq!(digit_replacement_family(13, &[0]), vec![13, 23, 43, 53, 73, 83]);
+/// // 56**3 gives seven primes
+/// assert_eq!(
+///     digit_replacement_family(56003, &[2, 3]),
+///     vec![56003, 56113, 56333, 56443, 56663, 56773, 56993]
+/// );
+/// ```
+pub fn digit_replacement_family(p: u64, positions: &[u8]) -> Vec<u64> {
+    let digits: Vec<u32> = digits(p, 10);
+    if positions.is_empty() {
+        panic!("at least one position must be replaced");
+    }
+    if positions
+        .iter()
+        .any(|position| *position as usize >= digits.len())
+    {
+        panic!("the positions must be digits of the number");
+    }
+    let mut mask: u32 = 0;
+    for position in positions {
+        mask |= 1 << position;
+    }
+    replacement_family(&digits, mask)
+        .filter(|candidate| is_prime_u64(*candidate))
+        .collect()
+}
+
+/// Generates the numbers made by replacing some digits of a number with the same digit.
+///
+/// # Arguments
+///
+/// * `digits` - The digits of the number, most significant digit first.
+/// * `mask` - The positions to replace, with bit `i` set to replace digit `i`.
+///
+/// # Returns
+///
+/// Iterator over the numbers for the digits $0$ to $9$ in increasing order, skipping those with a leading `0`.
+fn replacement_family(digits: &[u32], mask: u32) -> impl Iterator<Item = u64> + '_ {
+    let start: u32 = if mask & 1 == 1 { 1 } else { 0 };
+    (start..10).map(move |common: u32| {
+        let mut value: u64 = 0;
+        for (i, digit) in digits.iter().enumerate() {
+            let digit: u32 = if mask >> i & 1 == 1 { common } else { *digit };
+            value = value * 10 + digit as u64;
+        }
+        value
+    })
+}
+
+/// Finds the smallest prime that is part of a family of exactly `target` primes made by replacing some of its
+/// equal digits with the same digit (Project Euler problem 51).
+///
+/// The primes of each number of digits come from one sieve, and for every prime each nonempty set of positions
+/// holding the same digit is tried (so the prime is always in its own family), with family members looked up in
+/// the sieved primes.
+///
+/// # Arguments
+///
+/// * `target` - The size of the family, from `1` to `10`.
+///
+/// # Returns
+///
+/// The smallest such prime. The search does not end if there is none, which may be the case for `9` and `10`.
+///
+/// # Panics
+///
+/// Panics if `target` is `0` or more than `10`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::smallest_prime_with_family_size;
+/// assert_eq!(smallest_prime_with_family_size(6), 13); // *3
+/// assert_eq!(smallest_prime_with_family_size(7), 56003); // 56**3
+/// ```
+pub fn smallest_prime_with_family_size(target: usize) -> u64 {
+    if target == 0 || target > 10 {
+        panic!("a family has from 1 to 10 members");
+    }
+    let mut length: u32 = 1;
+    while length <= 19 {
+        let primes: Vec<u64> = primes_in_range(10_u64.pow(length - 1), 10_u64.pow(length));
+        for p in &primes {
+            let digits: Vec<u32> = digits(*p, 10);
+            let mut common: u32 = 0;
+            while common < 10 {
+                let mut same: u32 = 0;
+                for (i, digit) in digits.iter().enumerate() {
+                    if *digit == common {
+                        same |= 1 << i;
+                    }
+                }
+                // every nonempty subset of the positions holding this digit
+                let mut mask: u32 = same;
+                while mask > 0 {
+                    let size: usize = replacement_family(&digits, mask)
+                        .filter(|candidate| primes.binary_search(candidate).is_ok())
+                        .count();
+                    if size == target {
+                        return *p;
+                    }
+                    mask = (mask - 1) & same;
+                }
+                common += 1;
+            }
+        }
+        length += 1;
+    }
+    panic!("no prime with at most 19 digits has a family of the target size")
+}
+
+/// Checks if the concatenation of the decimal digits of two numbers is prime, so `12` and `345` give `12345`.
+///
+/// # Arguments
+///
+/// * `a` - The number whose digits come first.
+/// * `b` - The number whose digits come last.
+///
+/// # Returns
+///
+/// `true` if $a \cdot 10^k + b$ is prime, where $b$ has $k$ digits. Values that fit in a `u64` are checked
+/// deterministically and larger ones with [`is_probable_prime`].
+fn concatenation_is_prime(a: u64, b: u64) -> bool {
+    let mut shift: u128 = 10;
+    while shift <= b as u128 {
+        shift *= 10;
+    }
+    let n: Option<u128> = (a as u128)
+        .checked_mul(shift)
+        .and_then(|n| n.checked_add(b as u128));
+    match n {
+        Some(n) if n <= u64::MAX as u128 => is_prime_u64(n as u64),
+        Some(n) => is_probable_prime(&BigInt::from(n)),
+        None => is_probable_prime(&(BigInt::from(a) * shift + b)),
+    }
+}
+
+/// Checks if two primes concatenate to primes in both orders, like $3$ and $7$ do as $37$ and $73$.
+///
+/// # Arguments
+///
+/// * `a` - The first number.
+/// * `b` - The second number.
+///
+/// # Returns
+///
+/// `true` if the concatenations of `a` then `b` and of `b` then `a` are both prime. The concatenation is done
+/// arithmetically; numbers past `u64::MAX` use [`is_probable_prime`].
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::prime_pair_concat_compatible;
+/// assert!(prime_pair_concat_compatible(3, 7)); // 37 and 73
+/// assert!(prime_pair_concat_compatible(7, 109)); // 7109 and 1097
+/// assert!(!prime_pair_concat_compatible(3, 13)); // 313 is prime but 133 = 7 * 19 is not
+/// ```
+pub fn prime_pair_concat_compatible(a: u64, b: u64) -> bool {
+    concatenation_is_prime(a, b) && concatenation_is_prime(b, a)
+}
+
+/// Finds a set of primes below a limit that are pairwise [`prime_pair_concat_compatible`] with the smallest sum
+/// (Project Euler problem 60).
+///
+/// The compatibility graph of the primes below the limit is built first, and then cliques are grown from the
+/// smallest primes up. A partial clique is dropped as soon as its sum plus the smallest primes it could still add
+/// reaches the best sum found, since the candidates are in increasing order.
+///
+/// # Arguments
+///
+/// * `size` - The number of primes in the set.
+/// * `prime_limit` - The exclusive upper bound of the primes.
+///
+/// # Returns
+///
+/// `Some` with the primes in increasing order, or `None` if no set of `size` primes below the limit works.
+/// Among sets with the same sum, the one with the smallest primes first is chosen.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::digits::smallest_concat_prime_clique;
+/// assert_eq!(smallest_concat_prime_clique(2, 100), Some(vec![3, 7]));
+/// assert_eq!(smallest_concat_prime_clique(4, 1000), Some(vec![3, 7, 109, 673]));
+/// assert_eq!(smallest_concat_prime_clique(3, 10), None);
+/// ```
+pub fn smallest_concat_prime_clique(size: usize, prime_limit: u64) -> Option<Vec<u64>> {
+    let primes: Vec<u64> = primes_in_range(0, prime_limit);
+    let mut compatible: Vec<Vec<bool>> = vec![vec![false; primes.len()]; primes.len()];
+    let mut i: usize = 0;
+    while i < primes.len() {
+        let mut j: usize = i + 1;
+        while j < primes.len() {
+            if prime_pair_concat_compatible(primes[i], primes[j]) {
+                compatible[i][j] = true;
+                compatible[j][i] = true;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    let candidates: Vec<usize> = (0..primes.len()).collect();
+    let mut best: Option<(u64, Vec<u64>)> = None;
+    extend_clique(
+        &primes,
+        &compatible,
+        size,
+        &mut vec![],
+        0,
+        &candidates,
+        &mut best,
+    );
+    best.map(|(_, clique)| clique)
+}
+
+/// Grows a clique of compatible primes in every possible way, keeping the one with the smallest sum.
+///
+/// # Arguments
+///
+/// * `primes` - The primes in increasing order.
+/// * `compatible` - The compatibility graph as an adjacency matrix over the indices of `primes`.
+/// * `size` - The number of primes the clique needs.
+/// * `clique` - The indices of the primes in the clique so far.
+/// * `sum` - The sum of the primes in the clique so far.
+/// * `candidates` - The indices of the primes compatible with the whole clique, in increasing order.
+/// * `best` - The smallest sum found so far with its clique, updated when a smaller one is found.
+fn extend_clique(
+    primes: &[u64],
+    compatible: &[Vec<bool>],
+    size: usize,
+    clique: &mut Vec<usize>,
+    sum: u64,
+    candidates: &[usize],
+    best: &mut Option<(u64, Vec<u64>)>,
+) {
+    if clique.len() == size {
+        if best.as_ref().is_none_or(|(best_sum, _)| sum < *best_sum) {
+            *best = Some((sum, clique.iter().map(|i| primes[*i]).collect()));
+        }
+        return;
+    }
+    let missing: usize = size - clique.len();
+    let mut k: usize = 0;
+    while k + missing <= candidates.len() {
+        let c: usize = candidates[k];
+        // the cheapest way to finish uses this prime and the next ones, which are all larger
+        let lowest: u64 = sum + primes[c] * missing as u64;
+        if best
+            .as_ref()
+            .is_some_and(|(best_sum, _)| lowest >= *best_sum)
+        {
+            break;
+        }
+        let next: Vec<usize> = candidates[k + 1..]
+            .iter()
+            .copied()
+            .filter(|other| compatible[c][*other])
+            .collect();
+        clique.push(c);
+        extend_clique(
+            primes,
+            compatible,
+            size,
+            clique,
+            sum + primes[c],
+            &next,
+            best,
+        );
+        clique.pop();
+        k += 1;
+    }
+}
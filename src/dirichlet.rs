@@ -0,0 +1,290 @@
+use crate::modular::totient_sieve;
+use crate::summatory::mobius_sieve;
+use num::{One, Zero};
+
+/// Calculates the Dirichlet convolution $(f * g)(n) = \sum_{d \mid n} f(d) g(n/d)$ of two arithmetic functions.
+///
+/// The functions are given as arrays where index `n` holds the value at $n$. Arithmetic functions start at
+/// $n = 1$, so index `0` must hold `0`, and it holds `0` in the result too. Every pair $(d, m)$ with
+/// $dm < \text{len}$ is visited once, which takes $O(\text{len} \log \text{len})$ time.
+///
+/// # Arguments
+///
+/// * `f` - The values of the first function.
+/// * `g` - The values of the second function.
+///
+/// # Returns
+///
+/// Vector whose length is the shorter of the two lengths, where index `n` holds $(f * g)(n)$.
+///
+/// # Panics
+///
+/// Panics if index `0` of either array is not `0`, or if a value overflows an `i64`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::dirichlet::{dirichlet_convolve, mobius_array, one, unit};
+///
+/// // the Möbius function is the inverse of the constant function 1
+/// assert_eq!(dirichlet_convolve(&mobius_array(100), &one(100)), unit(100));
+/// // 1 * 1 counts the divisors
+/// assert_eq!(dirichlet_convolve(&one(6), &one(6)), vec![0, 1, 2, 2, 3, 2, 4]);
+/// ```
+pub fn dirichlet_convolve(f: &[i64], g: &[i64]) -> Vec<i64> {
+    let len: usize = f.len().min(g.len());
+    if f.first().is_some_and(|x| *x != 0) || g.first().is_some_and(|x| *x != 0) {
+        panic!("index 0 of an arithmetic function must be 0");
+    }
+    let mut h: Vec<i64> = vec![0; len];
+    let mut d: usize = 1;
+    while d < len {
+        if f[d] != 0 {
+            let mut m: usize = 1;
+            while d * m < len {
+                let term: Option<i64> = f[d].checked_mul(g[m]);
+                h[d * m] = match term.and_then(|term| h[d * m].checked_add(term)) {
+                    Some(value) => value,
+                    None => panic!("the Dirichlet convolution overflowed"),
+                };
+                m += 1;
+            }
+        }
+        d += 1;
+    }
+    h
+}
+
+/// Calculates a multiplicative function for every $n$ up to a limit from its values on prime powers.
+///
+/// A linear sieve reaches every composite $n$ exactly once as $n = ip$ with $p$ the smallest prime factor of
+/// $n$. It tracks the largest power $p^e$ of that prime dividing $n$, so $f(n) = f(p^e) f(n / p^e)$ unless $n$
+/// is itself the prime power, which is the only time `on_prime_power` is called. This takes $O(n)$ time.
+///
+/// # Arguments
+///
+/// * `limit` - The (inclusive) upper bound of the numbers.
+/// * `on_prime_power` - The value $f(p^k)$, called as `on_prime_power(p, k)` exactly once for every prime
+///   power $p^k \leq \text{limit}$.
+///
+/// # Returns
+///
+/// Vector of length `limit + 1` where index `n` holds $f(n)$ (so index `1` holds `1`, and index `0` holds `0`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::dirichlet::multiplicative_sieve;
+///
+/// // the number of divisors
+/// assert_eq!(multiplicative_sieve(6, |_, k| k as i64 + 1), vec![0, 1, 2, 2, 3, 2, 4]);
+/// // the Liouville function
+/// assert_eq!(
+///     multiplicative_sieve(8, |_, k| if k.is_multiple_of(2) { 1 } else { -1 }),
+///     vec![0, 1, -1, -1, 1, -1, 1, -1, -1]
+/// );
+/// ```
+pub fn multiplicative_sieve(limit: usize, on_prime_power: impl Fn(u64, u32) -> i64) -> Vec<i64> {
+    multiplicative_sieve_with(limit, on_prime_power)
+}
+
+/// Calculates a multiplicative function for every $n$ up to a limit from its values on prime powers, with
+/// values of any numeric type.
+///
+/// This is [`multiplicative_sieve`] for the crate's own sieves, which store their values as `u64` or `i8`.
+///
+/// # Arguments
+///
+/// * `limit` - The (inclusive) upper bound of the numbers.
+/// * `on_prime_power` - The value $f(p^k)$, called once for every prime power $p^k \leq \text{limit}$.
+///
+/// # Returns
+///
+/// Vector of length `limit + 1` where index `n` holds $f(n)$ (and index `0` holds `0`).
+pub(crate) fn multiplicative_sieve_with<T: Copy + Zero + One>(
+    limit: usize,
+    mut on_prime_power: impl FnMut(u64, u32) -> T,
+) -> Vec<T> {
+    let mut values: Vec<T> = vec![T::zero(); limit + 1];
+    if limit == 0 {
+        return values;
+    }
+    values[1] = T::one();
+    // power[n] is the largest power of the smallest prime factor of n dividing n, or 0 until n is reached
+    let mut power: Vec<usize> = vec![0; limit + 1];
+    let mut exponent: Vec<u8> = vec![0; limit + 1];
+    let mut primes: Vec<usize> = vec![];
+    let mut i: usize = 2;
+    while i <= limit {
+        if power[i] == 0 {
+            power[i] = i;
+            exponent[i] = 1;
+            values[i] = on_prime_power(i as u64, 1);
+            primes.push(i);
+        }
+        let mut j: usize = 0;
+        while j < primes.len() {
+            let p: usize = primes[j];
+            let m: usize = match i.checked_mul(p) {
+                Some(m) if m <= limit => m,
+                _ => break,
+            };
+            if i.is_multiple_of(p) {
+                power[m] = power[i] * p;
+                exponent[m] = exponent[i] + 1;
+                values[m] = if power[m] == m {
+                    on_prime_power(p as u64, exponent[m] as u32)
+                } else {
+                    values[m / power[m]] * values[power[m]]
+                };
+                break;
+            }
+            power[m] = p;
+            exponent[m] = 1;
+            values[m] = values[i] * values[p];
+            j += 1;
+        }
+        i += 1;
+    }
+    values
+}
+
+/// Calculates the multiplicative identity $\varepsilon$ of Dirichlet convolution, which is $1$ at $n = 1$ and $0$
+/// elsewhere.
+///
+/// # Arguments
+///
+/// * `limit` - The (inclusive) upper bound of the numbers.
+///
+/// # Returns
+///
+/// Vector of length `limit + 1` where index `n` holds $\varepsilon(n)$ (and index `0` holds `0`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::dirichlet::unit;
+/// assert_eq!(unit(4), vec![0, 1, 0, 0, 0]);
+/// ```
+pub fn unit(limit: usize) -> Vec<i64> {
+    let mut values: Vec<i64> = vec![0; limit + 1];
+    if limit >= 1 {
+        values[1] = 1;
+    }
+    values
+}
+
+/// Calculates the constant function $1$.
+///
+/// # Arguments
+///
+/// * `limit` - The (inclusive) upper bound of the numbers.
+///
+/// # Returns
+///
+/// Vector of length `limit + 1` where index `n` holds $1$ (and index `0` holds `0`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::dirichlet::one;
+/// assert_eq!(one(4), vec![0, 1, 1, 1, 1]);
+/// ```
+pub fn one(limit: usize) -> Vec<i64> {
+    let mut values: Vec<i64> = vec![1; limit + 1];
+    values[0] = 0;
+    values
+}
+
+/// Calculates the identity function $\text{id}(n) = n$.
+///
+/// # Arguments
+///
+/// * `limit` - The (inclusive) upper bound of the numbers.
+///
+/// # Returns
+///
+/// Vector of length `limit + 1` where index `n` holds $n$.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::dirichlet::identity;
+/// assert_eq!(identity(4), vec![0, 1, 2, 3, 4]);
+/// ```
+pub fn identity(limit: usize) -> Vec<i64> {
+    (0..=limit as i64).collect()
+}
+
+/// Calculates the Möbius function $\mu(n)$ for every $n$ up to a limit.
+///
+/// [A008683](https://oeis.org/A008683)
+///
+/// # Arguments
+///
+/// * `limit` - The (inclusive) upper bound of the numbers.
+///
+/// # Returns
+///
+/// Vector of length `limit + 1` where index `n` holds $\mu(n)$ (and index `0` holds `0`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::dirichlet::mobius_array;
+/// assert_eq!(mobius_array(10), vec![0, 1, -1, -1, 0, -1, 1, -1, 0, 0, 1]);
+/// ```
+pub fn mobius_array(limit: usize) -> Vec<i64> {
+    mobius_sieve(limit).into_iter().map(i64::from).collect()
+}
+
+/// Calculates the Liouville function $\lambda(n) = (-1)^{\Omega(n)}$ for every $n$ up to a limit, where
+/// $\Omega(n)$ is the number of prime factors of $n$ counted with multiplicity.
+///
+/// [A008836](https://oeis.org/A008836)
+///
+/// # Arguments
+///
+/// * `limit` - The (inclusive) upper bound of the numbers.
+///
+/// # Returns
+///
+/// Vector of length `limit + 1` where index `n` holds $\lambda(n)$ (and index `0` holds `0`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::dirichlet::liouville_array;
+/// assert_eq!(liouville_array(10), vec![0, 1, -1, -1, 1, -1, 1, -1, -1, 1, 1]);
+/// ```
+pub fn liouville_array(limit: usize) -> Vec<i64> {
+    multiplicative_sieve(limit, |_, k| if k.is_multiple_of(2) { 1 } else { -1 })
+}
+
+/// Calculates Euler's totient $\varphi(n)$ for every $n$ up to a limit.
+///
+/// [A000010](https://oeis.org/A000010)
+///
+/// # Arguments
+///
+/// * `limit` - The (inclusive) upper bound of the numbers.
+///
+/// # Returns
+///
+/// Vector of length `limit + 1` where index `n` holds $\varphi(n)$ (and index `0` holds `0`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::dirichlet::{dirichlet_convolve, identity, one, totient_array};
+///
+/// assert_eq!(totient_array(6), vec![0, 1, 1, 2, 2, 4, 2]);
+/// // Gauss's identity: the totients of the divisors of n sum to n
+/// assert_eq!(dirichlet_convolve(&totient_array(100), &one(100)), identity(100));
+/// ```
+pub fn totient_array(limit: usize) -> Vec<i64> {
+    totient_sieve(limit + 1)
+        .into_iter()
+        .map(|phi| phi as i64)
+        .collect()
+}
@@ -0,0 +1,802 @@
+use crate::factorization::factorize;
+use crate::primes::generate_primes;
+use std::collections::HashMap;
+
+/// Calculates the number of divisors of a number, $\tau(n)$ (also written $d(n)$ or $\sigma_0(n)$).
+///
+/// If $n = \prod p_i^{e_i}$ then $\tau(n) = \prod (e_i + 1)$.
+///
+/// [A000005](https://oeis.org/A000005)
+///
+/// # Arguments
+///
+/// * `n` - The number whose divisors are counted.
+///
+/// # Returns
+///
+/// The number of positive divisors of `n` (`0` for `0`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::divisor_functions::divisor_count;
+/// assert_eq!(divisor_count(12), 6); // 1, 2, 3, 4, 6, 12
+/// assert_eq!(divisor_count(1), 1);
+/// ```
+pub fn divisor_count(n: u64) -> u64 {
+    if n == 0 {
+        return 0;
+    }
+    let mut count: u64 = 1;
+    for (_, e) in factorize(n) {
+        count *= e as u64 + 1;
+    }
+    count
+}
+
+/// Calculates the sum of the divisors of a number, $\sigma(n)$.
+///
+/// If $n = \prod p_i^{e_i}$ then $\sigma(n) = \prod \frac{p_i^{e_i + 1} - 1}{p_i - 1}$.
+///
+/// [A000203](https://oeis.org/A000203)
+///
+/// # Arguments
+///
+/// * `n` - The number whose divisors are summed.
+///
+/// # Returns
+///
+/// The sum of the positive divisors of `n` (`0` for `0`).
+///
+/// # Panics
+///
+/// Panics if the sum does not fit in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::divisor_functions::divisor_sum;
+/// assert_eq!(divisor_sum(12), 28); // 1 + 2 + 3 + 4 + 6 + 12
+/// assert_eq!(divisor_sum(7), 8);
+/// ```
+pub fn divisor_sum(n: u64) -> u64 {
+    if n == 0 {
+        return 0;
+    }
+    let mut sum: u128 = 1;
+    for (p, e) in factorize(n) {
+        // 1 + p + p^2 + ... + p^e
+        let mut term: u128 = 1;
+        let mut power: u128 = 1;
+        for _ in 0..e {
+            power *= p as u128;
+            term += power;
+        }
+        sum *= term;
+    }
+    sum.try_into().expect("divisor sum does not fit in a u64")
+}
+
+/// Calculates the divisors of a number paired with their complementary divisors.
+///
+/// The divisors are built from the prime factorization, so this takes time proportional to $\tau(n)$ once `n`
+/// is factored rather than $\sqrt{n}$.
+///
+/// # Arguments
+///
+/// * `n` - The number whose divisors are paired.
+///
+/// # Returns
+///
+/// Vector of every pair `(d, n / d)` with $d \leq n / d$, in increasing order of `d` (empty for `0`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::divisor_functions::divisor_pairs;
+/// assert_eq!(
+///     divisor_pairs(36),
+///     vec![(1, 36), (2, 18), (3, 12), (4, 9), (6, 6)]
+/// );
+/// assert_eq!(divisor_pairs(13), vec![(1, 13)]);
+/// ```
+pub fn divisor_pairs(n: u64) -> Vec<(u64, u64)> {
+    if n == 0 {
+        return vec![];
+    }
+    sorted_divisors(n)
+        .iter()
+        .take_while(|d| **d <= n / **d)
+        .map(|d| (*d, n / d))
+        .collect()
+}
+
+/// Lists the divisors of a number from its prime factorization.
+///
+/// # Arguments
+///
+/// * `n` - A positive number.
+///
+/// # Returns
+///
+/// Vector of the divisors of `n` in increasing order.
+fn sorted_divisors(n: u64) -> Vec<u64> {
+    let mut divisors: Vec<u64> = vec![1];
+    for (p, e) in factorize(n) {
+        let count: usize = divisors.len();
+        let mut power: u64 = 1;
+        for _ in 0..e {
+            power *= p;
+            for i in 0..count {
+                divisors.push(divisors[i] * power);
+            }
+        }
+    }
+    divisors.sort();
+    divisors
+}
+
+/// Calculates the unitary divisors of a number, the divisors $d$ with $\gcd(d, n/d) = 1$.
+///
+/// A unitary divisor takes each prime power $p^e$ exactly dividing $n$ either completely or not at all, so there
+/// are $2^{\omega(n)}$ of them.
+///
+/// [A077610](https://oeis.org/A077610)
+///
+/// # Arguments
+///
+/// * `n` - The number whose unitary divisors are found.
+///
+/// # Returns
+///
+/// Vector of the unitary divisors of `n` in increasing order (empty for `0`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::divisor_functions::unitary_divisors;
+/// assert_eq!(unitary_divisors(72), vec![1, 8, 9, 72]);
+/// assert_eq!(unitary_divisors(12), vec![1, 3, 4, 12]);
+/// ```
+pub fn unitary_divisors(n: u64) -> Vec<u64> {
+    if n == 0 {
+        return vec![];
+    }
+    let mut divisors: Vec<u64> = vec![1];
+    for (p, e) in factorize(n) {
+        let power: u64 = p.pow(e);
+        let count: usize = divisors.len();
+        for i in 0..count {
+            divisors.push(divisors[i] * power);
+        }
+    }
+    divisors.sort();
+    divisors
+}
+
+/// Calculates the sum of the unitary divisors of a number, $\sigma^*(n)$.
+///
+/// $\sigma^*$ is multiplicative with $\sigma^*(p^k) = p^k + 1$, so if $n = \prod p_i^{e_i}$ then
+/// $\sigma^*(n) = \prod (p_i^{e_i} + 1)$.
+///
+/// [A034448](https://oeis.org/A034448)
+///
+/// # Arguments
+///
+/// * `n` - The number whose unitary divisors are summed.
+///
+/// # Returns
+///
+/// The sum of the unitary divisors of `n` (`0` for `0`).
+///
+/// # Panics
+///
+/// Panics if the sum does not fit in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::divisor_functions::unitary_divisor_sum;
+/// assert_eq!(unitary_divisor_sum(72), 90); // 1 + 8 + 9 + 72
+/// assert_eq!(unitary_divisor_sum(7), 8);
+/// ```
+pub fn unitary_divisor_sum(n: u64) -> u64 {
+    if n == 0 {
+        return 0;
+    }
+    let mut sum: u128 = 1;
+    for (p, e) in factorize(n) {
+        sum *= p.pow(e) as u128 + 1;
+    }
+    sum.try_into()
+        .expect("unitary divisor sum does not fit in a u64")
+}
+
+/// Generates the highly composite numbers below a given limit.
+///
+/// A highly composite number has more divisors than every smaller positive number.
+///
+/// Every highly composite number has the form $2^{a_1} 3^{a_2} 5^{a_3} \cdots$ with $a_1 \ge a_2 \ge a_3 \ge \dots$,
+/// since moving a larger exponent onto a smaller prime gives a smaller number with the same number of divisors.
+/// So instead of checking every number, only numbers of that form are generated (recursively choosing the exponent
+/// of each successive prime), and the records among them are kept.
+///
+/// [A002182](https://oeis.org/A002182)
+///
+/// # Arguments
+///
+/// * `limit` - The (exclusive) upper bound of the numbers to generate.
+///
+/// # Returns
+///
+/// Vector of all highly composite numbers less than `limit`, in increasing order.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::divisor_functions::highly_composite_numbers_below;
+/// assert_eq!(highly_composite_numbers_below(100), vec![1, 2, 4, 6, 12, 24, 36, 48, 60]);
+/// ```
+pub fn highly_composite_numbers_below(limit: u64) -> Vec<u64> {
+    if limit <= 1 {
+        return vec![];
+    }
+    // the primorial 2 * 3 * 5 * ... grows so quickly that 16 primes cover every u64
+    let primes: Vec<u64> = generate_primes(54_u64);
+    let mut candidates: Vec<(u64, u64)> = vec![];
+    nonincreasing_exponent_numbers(&primes, 0, u32::MAX, 1, 1, limit, &mut candidates);
+    candidates.sort();
+
+    let mut records: Vec<u64> = vec![];
+    let mut best: u64 = 0;
+    for (n, count) in candidates {
+        if count > best {
+            best = count;
+            records.push(n);
+        }
+    }
+    records
+}
+
+/// Recursively generates the numbers $2^{a_1} 3^{a_2} 5^{a_3} \cdots$ with $a_1 \ge a_2 \ge \dots$ below a limit,
+/// along with their number of divisors $\prod (a_i + 1)$.
+///
+/// # Arguments
+///
+/// * `primes` - The primes in increasing order.
+/// * `index` - The index of the prime whose exponent is chosen next.
+/// * `max_exponent` - The exponent of the previous prime, which the next exponent cannot exceed.
+/// * `n` - The number built so far.
+/// * `count` - The number of divisors of `n`.
+/// * `limit` - The (exclusive) upper bound of the numbers to generate.
+/// * `found` - The `(number, divisor count)` pairs generated so far.
+fn nonincreasing_exponent_numbers(
+    primes: &[u64],
+    index: usize,
+    max_exponent: u32,
+    n: u64,
+    count: u64,
+    limit: u64,
+    found: &mut Vec<(u64, u64)>,
+) {
+    found.push((n, count));
+    if index == primes.len() {
+        return;
+    }
+    let p: u64 = primes[index];
+    let mut m: u64 = n;
+    let mut e: u32 = 1;
+    while e <= max_exponent {
+        m = match m.checked_mul(p) {
+            Some(m) if m < limit => m,
+            _ => break,
+        };
+        nonincreasing_exponent_numbers(
+            primes,
+            index + 1,
+            e,
+            m,
+            count * (e as u64 + 1),
+            limit,
+            found,
+        );
+        e += 1;
+    }
+}
+
+/// Determines whether or not a number is highly composite (has more divisors than every smaller positive number).
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// Boolean representing whether `n` is highly composite.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::divisor_functions::is_highly_composite;
+/// assert_eq!(is_highly_composite(720720), true);
+/// assert_eq!(is_highly_composite(100), false);
+/// ```
+pub fn is_highly_composite(n: u64) -> bool {
+    match n.checked_add(1) {
+        Some(limit) => highly_composite_numbers_below(limit).last() == Some(&n),
+        // u64::MAX is odd, and every highly composite number above 1 is even
+        None => false,
+    }
+}
+
+/// Generates the highly abundant numbers below a given limit.
+///
+/// A highly abundant number has a larger divisor sum than every smaller positive number.
+///
+/// Unlike highly composite numbers, these do not need to have nonincreasing exponents
+/// ($10 = 2 \cdot 5$ is highly abundant), so the exponent pattern trick does not apply.
+/// Instead every divisor sum below the limit is filled in at once with a sieve,
+/// adding each $d$ to all of its multiples.
+///
+/// [A002093](https://oeis.org/A002093)
+///
+/// # Arguments
+///
+/// * `limit` - The (exclusive) upper bound of the numbers to generate.
+///
+/// # Returns
+///
+/// Vector of all highly abundant numbers less than `limit`, in increasing order.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::divisor_functions::highly_abundant_numbers_below;
+/// assert_eq!(highly_abundant_numbers_below(25), vec![1, 2, 3, 4, 6, 8, 10, 12, 16, 18, 20, 24]);
+/// ```
+pub fn highly_abundant_numbers_below(limit: u64) -> Vec<u64> {
+    let sums: Vec<u64> = sigma_sieve(limit.saturating_sub(1) as usize);
+    let mut records: Vec<u64> = vec![];
+    let mut best: u64 = 0;
+    let mut n: usize = 1;
+    while n < sums.len() {
+        if sums[n] > best {
+            best = sums[n];
+            records.push(n as u64);
+        }
+        n += 1;
+    }
+    records
+}
+
+/// Calculates $\sigma(n)$ for every $n$ up to a limit by adding each $d$ to all of its multiples.
+///
+/// This takes $O(n \log n)$ time, so range searches over divisor sums should use it instead of calling
+/// [`divisor_sum`] for every number. The output is the only allocation, one `u64` per number, which matters for
+/// the range searches that sieve far past their limit. (The linear
+/// [`multiplicative_sieve`](crate::dirichlet::multiplicative_sieve) is faster but needs several extra arrays.)
+///
+/// # Arguments
+///
+/// * `limit` - The (inclusive) upper bound of the numbers.
+///
+/// # Returns
+///
+/// Vector of length `limit + 1` where index `n` holds $\sigma(n)$, and index `0` holds the sentinel `0` (matching
+/// [`divisor_sum`]).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::divisor_functions::sigma_sieve;
+/// assert_eq!(sigma_sieve(6), vec![0, 1, 3, 4, 7, 6, 12]);
+/// assert_eq!(sigma_sieve(0), vec![0]);
+/// ```
+pub fn sigma_sieve(limit: usize) -> Vec<u64> {
+    let mut sums: Vec<u64> = vec![0; limit + 1];
+    let mut d: usize = 1;
+    while d <= limit {
+        let mut multiple: usize = d;
+        while multiple <= limit {
+            sums[multiple] += d as u64;
+            multiple += d;
+        }
+        d += 1;
+    }
+    sums
+}
+
+/// Calculates $\tau(n)$ for every $n$ up to a limit by counting each $d$ at all of its multiples.
+///
+/// This takes $O(n \log n)$ time, so range searches over divisor counts should use it instead of calling
+/// [`divisor_count`] for every number. The output is the only allocation, one `u32` per number.
+///
+/// # Arguments
+///
+/// * `limit` - The (inclusive) upper bound of the numbers.
+///
+/// # Returns
+///
+/// Vector of length `limit + 1` where index `n` holds $\tau(n)$, and index `0` holds the sentinel `0` (matching
+/// [`divisor_count`]).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::divisor_functions::tau_sieve;
+/// assert_eq!(tau_sieve(6), vec![0, 1, 2, 2, 3, 2, 4]);
+/// assert_eq!(tau_sieve(0), vec![0]);
+/// ```
+pub fn tau_sieve(limit: usize) -> Vec<u32> {
+    let mut counts: Vec<u32> = vec![0; limit + 1];
+    let mut d: usize = 1;
+    while d <= limit {
+        let mut multiple: usize = d;
+        while multiple <= limit {
+            counts[multiple] += 1;
+            multiple += d;
+        }
+        d += 1;
+    }
+    counts
+}
+
+/// Determines whether or not a number is practical.
+///
+/// `n` is practical if every number from $1$ to $n$ can be written as a sum of distinct divisors of `n`
+/// (e.g. $12$: $5 = 1 + 4$, $7 = 1 + 6$, $11 = 1 + 4 + 6$, ...).
+///
+/// Rather than searching subset sums, this uses the characterization by Stewart and Sierpiński:
+/// writing $n = p_1^{a_1} p_2^{a_2} \cdots p_k^{a_k}$ with $p_1 < p_2 < \dots < p_k$,
+/// `n` is practical exactly when $p_1 = 2$ and for every $i \ge 2$
+/// $$p_i \le 1 + \sigma(p_1^{a_1} \cdots p_{i-1}^{a_{i-1}}).$$
+///
+/// [A005153](https://oeis.org/A005153)
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// Boolean representing whether `n` is practical (`1` is, `0` is not).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::divisor_functions::is_practical;
+/// assert_eq!(is_practical(12), true);
+/// assert_eq!(is_practical(10), false); // 4 is not a sum of distinct divisors of 10
+/// ```
+pub fn is_practical(n: u64) -> bool {
+    if n == 0 {
+        return false;
+    }
+    if n == 1 {
+        return true;
+    }
+    if !n.is_multiple_of(2) {
+        return false;
+    }
+    // sigma of the part of n made from the primes checked so far
+    let mut sigma: u128 = 1;
+    for (p, e) in factorize(n) {
+        if p as u128 > sigma + 1 {
+            return false;
+        }
+        let mut term: u128 = 1;
+        let mut power: u128 = 1;
+        for _ in 0..e {
+            power *= p as u128;
+            term += power;
+        }
+        sigma *= term;
+    }
+    true
+}
+
+/// Generates the practical numbers below a given limit.
+///
+/// # Arguments
+///
+/// * `limit` - The (exclusive) upper bound of the numbers to check.
+///
+/// # Returns
+///
+/// Vector of all practical numbers less than `limit`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::divisor_functions::practical_numbers_below;
+/// assert_eq!(practical_numbers_below(25), vec![1, 2, 4, 6, 8, 12, 16, 18, 20, 24]);
+/// ```
+pub fn practical_numbers_below(limit: u64) -> Vec<u64> {
+    let mut practical: Vec<u64> = vec![];
+    let mut n: u64 = 1;
+    while n < limit {
+        if is_practical(n) {
+            practical.push(n);
+        }
+        n += 1;
+    }
+    practical
+}
+
+/// Calculates the smallest number with exactly `k` divisors.
+///
+/// A number with prime signature $[e_1, \dots, e_r]$ has $\prod (e_i + 1)$ divisors, so the search runs over
+/// the ways of writing `k` as a product of factors $e_i + 1$ in nonincreasing order, giving the largest
+/// exponents to the smallest primes and abandoning any branch that is already larger than the best number
+/// found.
+///
+/// [A005179](https://oeis.org/A005179)
+///
+/// # Arguments
+///
+/// * `k` - The number of divisors.
+///
+/// # Returns
+///
+/// The smallest $n$ with $\tau(n) = k$.
+///
+/// # Panics
+///
+/// Panics if `k` is `0`, or if the answer does not fit in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::divisor_functions::smallest_with_divisor_count;
+/// assert_eq!(smallest_with_divisor_count(6), 12);
+/// assert_eq!(smallest_with_divisor_count(48), 2520);
+/// assert_eq!(smallest_with_divisor_count(7), 64); // 2^6
+/// ```
+pub fn smallest_with_divisor_count(k: u64) -> u64 {
+    if k == 0 {
+        panic!("every positive number has at least one divisor");
+    }
+    match smallest_with_n_divisors(k).and_then(|n| u64::try_from(n).ok()) {
+        Some(n) => n,
+        None => panic!("the smallest number with this many divisors does not fit in a u64"),
+    }
+}
+
+/// Calculates the smallest number with exactly `n` divisors, or `None` if it does not fit in a `u128`.
+///
+/// This is the search behind [`smallest_with_divisor_count`] without its `u64` limit. Each exponent $e$ is
+/// chosen so that $e + 1$ divides the divisor count still to be produced, and only the divisors of `n` (from its
+/// factorization) are tried, so a large prime `n` costs a single branch, whose product saturates at
+/// `u128::MAX` after about $128$ steps.
+///
+/// [A005179](https://oeis.org/A005179)
+///
+/// # Arguments
+///
+/// * `n` - The number of divisors.
+///
+/// # Returns
+///
+/// `Some(m)` for the smallest $m$ with $\tau(m) = n$, or `None` if that is at least `u128::MAX` (or `n` is
+/// `0`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::divisor_functions::smallest_with_n_divisors;
+/// assert_eq!(smallest_with_n_divisors(16), Some(120));
+/// assert_eq!(smallest_with_n_divisors(127), Some(1 << 126));
+/// assert_eq!(smallest_with_n_divisors(131), None); // 2^130
+/// ```
+pub fn smallest_with_n_divisors(n: u64) -> Option<u128> {
+    if n == 0 {
+        return None;
+    }
+    // n has at most 64 factors above 1, so the first 64 primes are enough
+    let primes: Vec<u64> = generate_primes(320_u64);
+    let mut factors: Vec<u64> = vec![1];
+    for (p, e) in factorize(n) {
+        let count: usize = factors.len();
+        let mut power: u64 = 1;
+        for _ in 0..e {
+            power *= p;
+            for i in 0..count {
+                factors.push(factors[i] * power);
+            }
+        }
+    }
+    factors.sort_unstable();
+    let mut best: u128 = u128::MAX;
+    smallest_with_divisor_count_search(n, n, 0, 1, &primes, &factors, &mut best);
+    // u128::MAX is odd, and only n = 1 has an odd answer, so it can only mean nothing was found
+    if best == u128::MAX {
+        None
+    } else {
+        Some(best)
+    }
+}
+
+/// Searches for the smallest number with a given number of divisors (see [`smallest_with_n_divisors`]).
+///
+/// # Arguments
+///
+/// * `remaining` - The part of the divisor count still to be produced.
+/// * `max_factor` - The largest allowed next factor $e + 1$, which keeps the exponents nonincreasing.
+/// * `index` - The index of the next prime to use.
+/// * `product` - The number built from the primes used so far.
+/// * `primes` - The primes in increasing order.
+/// * `factors` - The divisors of the whole divisor count in increasing order, which include every divisor of
+///   `remaining`.
+/// * `best` - The smallest number found so far, updated in place.
+fn smallest_with_divisor_count_search(
+    remaining: u64,
+    max_factor: u64,
+    index: usize,
+    product: u128,
+    primes: &[u64],
+    factors: &[u64],
+    best: &mut u128,
+) {
+    if remaining == 1 {
+        *best = (*best).min(product);
+        return;
+    }
+    let p: u128 = primes[index] as u128;
+    for &factor in &factors[1..] {
+        if factor > max_factor.min(remaining) {
+            break;
+        }
+        if remaining.is_multiple_of(factor) {
+            let mut value: u128 = product;
+            let mut e: u64 = 1;
+            while e < factor && value < *best {
+                value = value.saturating_mul(p);
+                e += 1;
+            }
+            if value < *best {
+                smallest_with_divisor_count_search(
+                    remaining / factor,
+                    factor,
+                    index + 1,
+                    value,
+                    primes,
+                    factors,
+                    best,
+                );
+            }
+        }
+    }
+}
+
+/// Finds every number up to a limit whose divisor sum is `m`.
+///
+/// Since $\sigma(n) > n$ for $n > 1$, every solution is at most $m$, so the divisor sum sieve only runs up to
+/// the smaller of `limit` and `m`.
+///
+/// # Arguments
+///
+/// * `m` - The divisor sum to invert.
+/// * `limit` - The (inclusive) upper bound of the search.
+///
+/// # Returns
+///
+/// Vector of every $n \leq \text{limit}$ with $\sigma(n) = m$, in increasing order.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::divisor_functions::sigma_inverse;
+/// assert_eq!(sigma_inverse(12, 100), vec![6, 11]);
+/// assert_eq!(sigma_inverse(2, 100), vec![]);
+/// ```
+pub fn sigma_inverse(m: u64, limit: u64) -> Vec<u64> {
+    let bound: u64 = limit.min(m);
+    let sums: Vec<u64> = sigma_sieve(bound as usize);
+    let mut preimages: Vec<u64> = vec![];
+    for (n, sigma) in sums.iter().enumerate() {
+        if *sigma == m && n > 0 {
+            preimages.push(n as u64);
+        }
+    }
+    preimages
+}
+
+/// Counts the ordered factorizations of a number, the ways to write it as a product of factors above $1$ where
+/// the order of the factors matters.
+///
+/// With $H(1) = 1$ (the empty product), $H(n) = \sum_{d \mid n, d < n} H(d)$, since the last factor is $n/d$ for
+/// some proper divisor $d$. The values are filled in for every divisor of `n` in increasing order, kept in a map
+/// keyed by the divisor, so this takes $O(\tau(n)^2)$ steps. For a prime power $p^k$ the count is the number of
+/// compositions of $k$, which is $2^{k - 1}$.
+///
+/// [A074206](https://oeis.org/A074206)
+///
+/// # Arguments
+///
+/// * `n` - The number to factor.
+///
+/// # Returns
+///
+/// The number of ordered factorizations of `n`.
+///
+/// # Panics
+///
+/// Panics if `n` is `0` or the count does not fit in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::divisor_functions::ordered_factorizations;
+/// assert_eq!(ordered_factorizations(12), 8); // 12, 2*6, 6*2, 3*4, 4*3, 2*2*3, 2*3*2, 3*2*2
+/// assert_eq!(ordered_factorizations(1), 1);
+/// assert_eq!(ordered_factorizations(1 << 10), 512);
+/// ```
+pub fn ordered_factorizations(n: u64) -> u64 {
+    if n == 0 {
+        panic!("n must be positive");
+    }
+    let divisors: Vec<u64> = sorted_divisors(n);
+    let mut counts: HashMap<u64, u64> = HashMap::with_capacity(divisors.len());
+    counts.insert(1, 1);
+    for x in &divisors[1..] {
+        let mut count: u64 = 0;
+        for d in divisors.iter().take_while(|d| **d < *x) {
+            if x.is_multiple_of(*d) {
+                count = match count.checked_add(counts[d]) {
+                    Some(count) => count,
+                    None => panic!("the number of ordered factorizations does not fit in a u64"),
+                };
+            }
+        }
+        counts.insert(*x, count);
+    }
+    counts[&n]
+}
+
+/// Counts the unordered factorizations of a number (multiplicative partitions), the ways to write it as a
+/// product of factors above $1$ where the order of the factors does not matter.
+///
+/// The factors are added one value at a time, in increasing order, like counting coin change: after the divisor
+/// $d$ has been added, the count for each divisor $x$ of `n` is the number of ways to write $x$ with factors up to
+/// $d$, and adding $d$ increases it by the count for $x/d$, which already includes factorizations using $d$
+/// again. For a prime power $p^k$ the count is the number of partitions of $k$.
+///
+/// [A001055](https://oeis.org/A001055)
+///
+/// # Arguments
+///
+/// * `n` - The number to factor.
+///
+/// # Returns
+///
+/// The number of unordered factorizations of `n`.
+///
+/// # Panics
+///
+/// Panics if `n` is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::divisor_functions::unordered_factorizations;
+/// assert_eq!(unordered_factorizations(12), 4); // 12, 2*6, 3*4, 2*2*3
+/// assert_eq!(unordered_factorizations(1), 1);
+/// assert_eq!(unordered_factorizations(1 << 10), 42);
+/// ```
+pub fn unordered_factorizations(n: u64) -> u64 {
+    if n == 0 {
+        panic!("n must be positive");
+    }
+    let divisors: Vec<u64> = sorted_divisors(n);
+    let mut counts: HashMap<u64, u64> = divisors.iter().map(|d| (*d, 0)).collect();
+    counts.insert(1, 1);
+    for d in &divisors[1..] {
+        for x in &divisors {
+            if *x >= *d && x.is_multiple_of(*d) {
+                let extra: u64 = counts[&(x / d)];
+                *counts.get_mut(x).unwrap() += extra;
+            }
+        }
+    }
+    counts[&n]
+}
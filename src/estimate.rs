@@ -0,0 +1,71 @@
+/// The ways a sieve can store which numbers up to its limit are prime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SieveRepr {
+    /// One `bool` (a byte) per number, as in a `Vec<bool>` of length `limit + 1`.
+    Bytes,
+    /// One bit per number, packed into `u64` words as in [`prime_bitset`](crate::primes::prime_bitset).
+    Bitset,
+    /// One bit per odd number, packed into `u64` words, since no even number above $2$ is prime.
+    OddBitset,
+    /// The primes themselves as a list of `u64`, like the output of
+    /// [`generate_primes`](crate::primes::generate_primes), sized by [`prime_count_upper_bound`].
+    PrimeList,
+}
+
+/// Estimates how many bytes a sieve of the numbers up to a limit needs.
+///
+/// # Arguments
+///
+/// * `limit` - The largest number the sieve covers.
+/// * `representation` - How the sieve stores its result.
+///
+/// # Returns
+///
+/// The size of the main buffer in bytes, saturating at `u64::MAX`. Small fixed costs (like the `Vec` header) are
+/// not included.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::estimate::{sieve_memory_bytes, SieveRepr};
+/// assert_eq!(sieve_memory_bytes(1_000_000_000, SieveRepr::Bytes), 1_000_000_001);
+/// assert_eq!(sieve_memory_bytes(1_000_000_000, SieveRepr::Bitset), 125_000_008);
+/// assert_eq!(sieve_memory_bytes(1_000_000_000, SieveRepr::OddBitset), 62_500_000);
+/// ```
+pub fn sieve_memory_bytes(limit: u64, representation: SieveRepr) -> u64 {
+    let numbers: u64 = limit.saturating_add(1);
+    match representation {
+        SieveRepr::Bytes => numbers,
+        SieveRepr::Bitset => numbers.div_ceil(64).saturating_mul(8),
+        SieveRepr::OddBitset => (numbers / 2).div_ceil(64).saturating_mul(8),
+        SieveRepr::PrimeList => prime_count_upper_bound(limit).saturating_mul(8),
+    }
+}
+
+/// Bounds the number of primes up to a limit from above.
+///
+/// Rosser and Schoenfeld showed that $\pi(x) < 1.25506 \frac{x}{\ln x}$ for every $x > 1$, so this is enough room for
+/// every prime up to `limit`.
+///
+/// # Arguments
+///
+/// * `limit` - The (inclusive) upper bound.
+///
+/// # Returns
+///
+/// A number at least $\pi(\text{limit})$ (`0` below `2`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::estimate::prime_count_upper_bound;
+/// assert_eq!(prime_count_upper_bound(100), 28); // pi(100) = 25
+/// assert_eq!(prime_count_upper_bound(1_000_000_000), 60_562_849); // pi(10^9) = 50847534
+/// ```
+pub fn prime_count_upper_bound(limit: u64) -> u64 {
+    if limit < 2 {
+        return 0;
+    }
+    let x: f64 = limit as f64;
+    (1.25506 * x / x.ln()).ceil() as u64
+}
@@ -0,0 +1,1173 @@
+use crate::primes::{generate_primes, is_prime_u64, is_probable_prime};
+use num::BigInt;
+use std::collections::HashSet;
+
+/// Calculates the prime factorization of a number.
+///
+/// The factors are found by trial division by $2$ and then by every odd number up to $\sqrt{n}$.
+///
+/// # Arguments
+///
+/// * `n` - The number to factorize.
+///
+/// # Returns
+///
+/// Vector of `(prime, exponent)` pairs in increasing order of the primes, so that
+/// $n = \prod p_i^{e_i}$ (empty for both `0` and `1`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::factorization::factorize;
+/// assert_eq!(factorize(360), vec![(2, 3), (3, 2), (5, 1)]);
+/// assert_eq!(factorize(97), vec![(97, 1)]);
+/// assert_eq!(factorize(1), vec![]);
+/// ```
+pub fn factorize(n: u64) -> Vec<(u64, u32)> {
+    let TrialDivision {
+        mut factors,
+        cofactor,
+    } = trial_divide(n, u64::MAX);
+    if cofactor > 1 {
+        factors.push((cofactor, 1));
+    }
+    factors
+}
+
+/// The result of [`trial_divide`]: the prime factors found and the part of the number left unfactored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrialDivision {
+    /// The `(prime, exponent)` pairs found, in increasing order of the primes.
+    pub factors: Vec<(u64, u32)>,
+    /// The number divided by every prime power in `factors`, which is `1` when the number was fully factored.
+    pub cofactor: u64,
+}
+
+/// Divides out the prime factors of a number up to a limit, leaving the rest of the number unfactored.
+///
+/// The trial divisors are $2$ and then the odd numbers up to the limit (or up to the square root of what is left,
+/// if that comes first). If the limit is at least the square root of what is left, that is a prime and is moved
+/// into the factors, so the cofactor is always `1` for a limit of at least $\sqrt{n}$. This is the
+/// first stage of factoring methods that only need the small factors, such as checking $p - 1$ for smoothness.
+///
+/// # Arguments
+///
+/// * `n` - The number to factorize.
+/// * `limit` - The largest trial divisor.
+///
+/// # Returns
+///
+/// The factors found and the cofactor, so that $n = \text{cofactor} \cdot \prod p_i^{e_i}$ (with no factors and
+/// `n` as the cofactor when `n` is at most `1` or `limit` is below `2`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::factorization::{trial_divide, TrialDivision};
+///
+/// let partial: TrialDivision = trial_divide(16 * 3 * 101, 10);
+/// assert_eq!(partial.factors, vec![(2, 4), (3, 1)]);
+/// assert_eq!(partial.cofactor, 101);
+/// // 101 < 11^2, so a limit of 11 is enough to finish
+/// assert_eq!(trial_divide(16 * 3 * 101, 11).cofactor, 1);
+/// ```
+pub fn trial_divide(n: u64, limit: u64) -> TrialDivision {
+    let mut factors: Vec<(u64, u32)> = vec![];
+    if n <= 1 {
+        return TrialDivision {
+            factors,
+            cofactor: n,
+        };
+    }
+    let mut m: u64 = n;
+    let mut p: u64 = 2;
+    while p <= limit && p <= m / p {
+        if m.is_multiple_of(p) {
+            let mut e: u32 = 0;
+            while m.is_multiple_of(p) {
+                m /= p;
+                e += 1;
+            }
+            factors.push((p, e));
+            #[cfg(feature = "diagnostics")]
+            crate::diagnostics::emit(|| crate::diagnostics::Event::FactorFound {
+                n,
+                factor: p,
+                exponent: e,
+                method: crate::diagnostics::FactorMethod::TrialDivision,
+            });
+        }
+        p += if p == 2 { 1 } else { 2 };
+    }
+    // every prime up to the limit is gone, so m is prime if the limit is at least sqrt(m)
+    if m > 1 && limit as u128 * limit as u128 >= m as u128 {
+        factors.push((m, 1));
+        #[cfg(feature = "diagnostics")]
+        crate::diagnostics::emit(|| crate::diagnostics::Event::FactorFound {
+            n,
+            factor: m,
+            exponent: 1,
+            method: crate::diagnostics::FactorMethod::Cofactor,
+        });
+        m = 1;
+    }
+    TrialDivision {
+        factors,
+        cofactor: m,
+    }
+}
+
+/// Writes a number as a perfect power $n = b^k$ with the largest possible exponent $k \geq 2$.
+///
+/// Every exponent from $63$ down to $2$ is tried, with the $k$-th root estimated in floating point and then
+/// corrected by checking neighbouring integers.
+///
+/// # Arguments
+///
+/// * `n` - The number to write as a perfect power.
+///
+/// # Returns
+///
+/// `Some((b, k))` with $n = b^k$ and $k$ as large as possible, or `None` if `n` is not a perfect power
+/// (including `0` and `1`, which are powers with every exponent).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::factorization::as_perfect_power;
+/// assert_eq!(as_perfect_power(64), Some((2, 6)));
+/// assert_eq!(as_perfect_power(36), Some((6, 2)));
+/// assert_eq!(as_perfect_power(72), None);
+/// ```
+pub fn as_perfect_power(n: u64) -> Option<(u64, u32)> {
+    if n < 4 {
+        return None;
+    }
+    let mut k: u32 = 63;
+    while k >= 2 {
+        let estimate: u64 = (n as f64).powf(1.0 / k as f64).round() as u64;
+        for b in estimate.saturating_sub(1).max(2)..=estimate + 1 {
+            if b.checked_pow(k) == Some(n) {
+                return Some((b, k));
+            }
+        }
+        k -= 1;
+    }
+    None
+}
+
+/// Checks if a number is a semiprime, a product of exactly two (not necessarily distinct) primes.
+///
+/// Only trial division up to $\sqrt[3]{n}$ is needed: if no prime factor is found by then, every prime
+/// factor of $n$ is larger than $\sqrt[3]{n}$, so $n$ has at most two of them and is a semiprime exactly
+/// when it is not prime.
+///
+/// [A001358](https://oeis.org/A001358)
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// `true` if `n` is a semiprime.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::factorization::is_semiprime;
+/// assert!(is_semiprime(4));
+/// assert!(is_semiprime(35));
+/// assert!(!is_semiprime(45)); // 3 * 3 * 5
+/// assert!(!is_semiprime(13));
+/// ```
+pub fn is_semiprime(n: u64) -> bool {
+    if n < 4 {
+        return false;
+    }
+    let mut q: u64 = 2;
+    while q <= n / q / q {
+        if n.is_multiple_of(q) {
+            return is_probable_prime(&BigInt::from(n / q));
+        }
+        q += if q == 2 { 1 } else { 2 };
+    }
+    !is_probable_prime(&BigInt::from(n))
+}
+
+/// Calculates the arithmetic derivative of a number.
+///
+/// The arithmetic derivative is defined by $p' = 1$ for every prime $p$ and the Leibniz rule
+/// $(ab)' = a'b + ab'$, with $0' = 1' = 0$. For $n = \prod p_i^{e_i}$ this gives
+/// $$n' = n \sum \frac{e_i}{p_i} = \sum e_i \frac{n}{p_i},$$
+/// which is calculated exactly from the factorization.
+///
+/// [A003415](https://oeis.org/A003415)
+///
+/// # Arguments
+///
+/// * `n` - The number to differentiate.
+///
+/// # Returns
+///
+/// The arithmetic derivative $n'$.
+///
+/// # Panics
+///
+/// Panics if $n'$ does not fit in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::factorization::arithmetic_derivative;
+/// assert_eq!(arithmetic_derivative(6), 5); // 2' * 3 + 2 * 3'
+/// assert_eq!(arithmetic_derivative(27), 27); // 3 * 3^2
+/// assert_eq!(arithmetic_derivative(7), 1);
+/// ```
+pub fn arithmetic_derivative(n: u64) -> u64 {
+    match checked_arithmetic_derivative(n) {
+        Some(derivative) => derivative,
+        None => panic!("the arithmetic derivative does not fit in a u64"),
+    }
+}
+
+/// Calculates the arithmetic derivative of a number, or `None` if it does not fit in a `u64`.
+///
+/// # Arguments
+///
+/// * `n` - The number to differentiate.
+///
+/// # Returns
+///
+/// `Some(n')`, or `None` on overflow.
+///
+/// # Examples
+///
+/// ```
+/// // the function is private, so it's re-defined here
+/// fn checked_arithmetic_derivative(n: u64) -> Option<u64> {
+///     let mut derivative: u128 = 0;
+///     for (p, e) in bens_number_theory::factorization::factorize(n) {
+///         derivative += e as u128 * (n / p) as u128;
+///     }
+///     u64::try_from(derivative).ok()
+/// }
+///
+/// assert_eq!(checked_arithmetic_derivative(1 << 60), None); // 60 * 2^59
+/// ```
+fn checked_arithmetic_derivative(n: u64) -> Option<u64> {
+    let mut derivative: u128 = 0;
+    for (p, e) in factorize(n) {
+        derivative += e as u128 * (n / p) as u128;
+    }
+    u64::try_from(derivative).ok()
+}
+
+/// Calculates the arithmetic derivative of a signed number.
+///
+/// The derivative is extended to negative numbers by $(-n)' = -(n')$, which is what the Leibniz rule forces
+/// from $(-1)' = 0$.
+///
+/// # Arguments
+///
+/// * `n` - The number to differentiate.
+///
+/// # Returns
+///
+/// The arithmetic derivative $n'$.
+///
+/// # Panics
+///
+/// Panics if $n'$ does not fit in an `i64`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::factorization::arithmetic_derivative_signed;
+/// assert_eq!(arithmetic_derivative_signed(-6), -5);
+/// assert_eq!(arithmetic_derivative_signed(0), 0);
+/// assert_eq!(arithmetic_derivative_signed(-1), 0);
+/// ```
+pub fn arithmetic_derivative_signed(n: i64) -> i64 {
+    let derivative: Option<i64> = checked_arithmetic_derivative(n.unsigned_abs())
+        .and_then(|derivative| i64::try_from(derivative).ok());
+    match derivative {
+        Some(derivative) if n < 0 => -derivative,
+        Some(derivative) => derivative,
+        None => panic!("the arithmetic derivative does not fit in an i64"),
+    }
+}
+
+/// Iterator over the repeated arithmetic derivatives $n, n', n'', \dots$ of a number.
+///
+/// The iterator stops once it reaches a number it has already visited (such as $0$, or a fixed point
+/// $p^p$), or when the next derivative does not fit in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::factorization::arithmetic_derivative_sequence;
+///
+/// let mut sequence = arithmetic_derivative_sequence(10);
+/// assert_eq!(sequence.by_ref().collect::<Vec<u64>>(), vec![10, 7, 1, 0]);
+/// assert!(sequence.cycled());
+/// ```
+pub struct ArithmeticDerivativeSequence {
+    next: Option<u64>,
+    seen: HashSet<u64>,
+    cycled: bool,
+    overflowed: bool,
+}
+
+impl ArithmeticDerivativeSequence {
+    /// Returns whether the iterator stopped because the next derivative had already been visited.
+    pub fn cycled(&self) -> bool {
+        self.cycled
+    }
+
+    /// Returns whether the iterator stopped because the next derivative did not fit in a `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bens_number_theory::factorization::arithmetic_derivative_sequence;
+    ///
+    /// let mut sequence = arithmetic_derivative_sequence(1 << 60);
+    /// assert_eq!(sequence.next(), Some(1 << 60));
+    /// assert_eq!(sequence.next(), None); // 60 * 2^59 does not fit
+    /// assert!(sequence.overflowed());
+    /// ```
+    pub fn overflowed(&self) -> bool {
+        self.overflowed
+    }
+}
+
+impl Iterator for ArithmeticDerivativeSequence {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let current: u64 = self.next?;
+        self.seen.insert(current);
+        self.next = match checked_arithmetic_derivative(current) {
+            Some(derivative) if self.seen.contains(&derivative) => {
+                self.cycled = true;
+                None
+            }
+            Some(derivative) => Some(derivative),
+            None => {
+                self.overflowed = true;
+                None
+            }
+        };
+        Some(current)
+    }
+}
+
+/// Generates the repeated arithmetic derivatives of a number (see [`arithmetic_derivative`]).
+///
+/// # Arguments
+///
+/// * `n` - The number to start from.
+///
+/// # Returns
+///
+/// An [`ArithmeticDerivativeSequence`] starting at `n`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::factorization::arithmetic_derivative_sequence;
+///
+/// assert_eq!(arithmetic_derivative_sequence(27).collect::<Vec<u64>>(), vec![27]);
+/// assert_eq!(
+///     arithmetic_derivative_sequence(15).take(5).collect::<Vec<u64>>(),
+///     vec![15, 8, 12, 16, 32]
+/// );
+/// ```
+pub fn arithmetic_derivative_sequence(n: u64) -> ArithmeticDerivativeSequence {
+    ArithmeticDerivativeSequence {
+        next: Some(n),
+        seen: HashSet::new(),
+        cycled: false,
+        overflowed: false,
+    }
+}
+
+/// Calculates the smallest prime factor of every number up to a limit with a sieve.
+///
+/// # Arguments
+///
+/// * `limit` - The largest number to sieve.
+///
+/// # Returns
+///
+/// Vector of length `limit + 1` where index `n` holds the smallest prime factor of `n` (`0` for `0` and `1`).
+pub(crate) fn smallest_prime_factor_sieve(limit: usize) -> Vec<u64> {
+    let mut spf: Vec<u64> = vec![0; limit + 1];
+    let mut p: usize = 2;
+    while p <= limit {
+        if spf[p] == 0 {
+            let mut multiple: usize = p;
+            while multiple <= limit {
+                if spf[multiple] == 0 {
+                    spf[multiple] = p as u64;
+                }
+                multiple += p;
+            }
+        }
+        p += 1;
+    }
+    spf
+}
+
+/// Calculates the sum of the prime factors of a number using a smallest prime factor sieve.
+///
+/// # Arguments
+///
+/// * `n` - The number to sum the prime factors of, at most the sieve limit.
+/// * `spf` - The smallest prime factors from [`smallest_prime_factor_sieve`].
+/// * `distinct` - Whether to count each prime once instead of with its multiplicity.
+///
+/// # Returns
+///
+/// The sum of the prime factors of `n`.
+fn prime_factor_sum(n: u64, spf: &[u64], distinct: bool) -> u64 {
+    let mut m: u64 = n;
+    let mut sum: u64 = 0;
+    let mut previous: u64 = 0;
+    while m > 1 {
+        let p: u64 = spf[m as usize];
+        if !distinct || p != previous {
+            sum += p;
+        }
+        previous = p;
+        m /= p;
+    }
+    sum
+}
+
+/// Checks if a number starts a Ruth–Aaron pair, counting prime factors with multiplicity.
+///
+/// Two consecutive numbers $n$ and $n + 1$ form a Ruth–Aaron pair when the sums of their prime factors are equal,
+/// like $714 = 2 \times 3 \times 7 \times 17$ and $715 = 5 \times 11 \times 13$
+/// ([A039752](https://oeis.org/A039752)).
+///
+/// # Arguments
+///
+/// * `n` - The smaller number of the pair.
+///
+/// # Returns
+///
+/// `true` if $n$ and $n + 1$ (which must fit in a `u64`) form a Ruth–Aaron pair (`0` and `1` never do).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::factorization::is_ruth_aaron_pair;
+/// assert!(is_ruth_aaron_pair(714));
+/// assert!(is_ruth_aaron_pair(8)); // 2 + 2 + 2 = 3 + 3
+/// assert!(!is_ruth_aaron_pair(24));
+/// ```
+pub fn is_ruth_aaron_pair(n: u64) -> bool {
+    n >= 2 && ruth_aaron_sum(n, false) == ruth_aaron_sum(n + 1, false)
+}
+
+/// Checks if a number starts a Ruth–Aaron pair, counting each distinct prime factor once
+/// ([A006145](https://oeis.org/A006145), see [`is_ruth_aaron_pair`]).
+///
+/// # Arguments
+///
+/// * `n` - The smaller number of the pair.
+///
+/// # Returns
+///
+/// `true` if $n$ and $n + 1$ (which must fit in a `u64`) have equal sums of distinct prime factors (`0` and
+/// `1` never do).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::factorization::is_ruth_aaron_pair_distinct;
+/// assert!(is_ruth_aaron_pair_distinct(714));
+/// assert!(is_ruth_aaron_pair_distinct(24)); // 2 + 3 = 5
+/// assert!(!is_ruth_aaron_pair_distinct(8));
+/// ```
+pub fn is_ruth_aaron_pair_distinct(n: u64) -> bool {
+    n >= 2 && ruth_aaron_sum(n, true) == ruth_aaron_sum(n + 1, true)
+}
+
+/// Calculates the sum of the prime factors of a number from its factorization.
+///
+/// # Arguments
+///
+/// * `n` - The number to sum the prime factors of.
+/// * `distinct` - Whether to count each prime once instead of with its multiplicity.
+///
+/// # Returns
+///
+/// The sum of the prime factors of `n` (`0` for `0` and `1`).
+fn ruth_aaron_sum(n: u64, distinct: bool) -> u128 {
+    let mut sum: u128 = 0;
+    for (p, e) in factorize(n) {
+        sum += p as u128 * if distinct { 1 } else { e as u128 };
+    }
+    sum
+}
+
+/// Lists the numbers below a limit that start a Ruth–Aaron pair, counting prime factors with multiplicity
+/// (see [`is_ruth_aaron_pair`]).
+///
+/// # Arguments
+///
+/// * `limit` - The exclusive upper bound on the smaller number of each pair.
+///
+/// # Returns
+///
+/// Vector of the smaller numbers of the pairs, in increasing order.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::factorization::ruth_aaron_pairs_below;
+/// assert_eq!(ruth_aaron_pairs_below(200), vec![5, 8, 15, 77, 125]);
+/// ```
+pub fn ruth_aaron_pairs_below(limit: u64) -> Vec<u64> {
+    ruth_aaron_search(limit, false)
+}
+
+/// Lists the numbers below a limit that start a Ruth–Aaron pair, counting each distinct prime factor once
+/// (see [`is_ruth_aaron_pair_distinct`]).
+///
+/// # Arguments
+///
+/// * `limit` - The exclusive upper bound on the smaller number of each pair.
+///
+/// # Returns
+///
+/// Vector of the smaller numbers of the pairs, in increasing order.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::factorization::ruth_aaron_pairs_below_distinct;
+/// assert_eq!(ruth_aaron_pairs_below_distinct(200), vec![5, 24, 49, 77, 104, 153]);
+/// ```
+pub fn ruth_aaron_pairs_below_distinct(limit: u64) -> Vec<u64> {
+    ruth_aaron_search(limit, true)
+}
+
+/// Finds the Ruth–Aaron pairs below a limit from a single smallest prime factor sieve.
+///
+/// # Arguments
+///
+/// * `limit` - The exclusive upper bound on the smaller number of each pair.
+/// * `distinct` - Whether to count each prime once instead of with its multiplicity.
+///
+/// # Returns
+///
+/// Vector of the smaller numbers of the pairs, in increasing order.
+fn ruth_aaron_search(limit: u64, distinct: bool) -> Vec<u64> {
+    let mut pairs: Vec<u64> = vec![];
+    if limit < 2 {
+        return pairs;
+    }
+    let spf: Vec<u64> = smallest_prime_factor_sieve(limit as usize);
+    let mut previous: u64 = prime_factor_sum(2, &spf, distinct);
+    for n in 2..limit {
+        let next: u64 = prime_factor_sum(n + 1, &spf, distinct);
+        if previous == next {
+            pairs.push(n);
+        }
+        previous = next;
+    }
+    pairs
+}
+
+/// The outcome of iterating towards a home prime with [`home_prime`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HomePrimeResult {
+    /// The iteration reached the prime `value` after `steps` steps.
+    Prime(u64, u32),
+    /// The iteration took the largest number of steps allowed without reaching a prime.
+    ExceededSteps,
+    /// The next term did not fit in a `u64`, after the given number of steps.
+    ExceededWidth(u32),
+}
+
+/// Calculates the home prime of a number.
+///
+/// Each step factorizes the current number and concatenates the decimal digits of its prime factors in
+/// nondecreasing order, repeated with multiplicity, until the result is prime
+/// ([A037274](https://oeis.org/A037274)). For example $10 = 2 \times 5 \to 25 = 5 \times 5 \to 55 \to 511 \to 773$.
+///
+/// The terms are kept to `u64` because every term but the last has to be factorized, and [`factorize`] works on
+/// `u64` by trial division. Wider terms could be tested with [`check_prime`](crate::primes::check_prime), but
+/// not broken into their factors, so a term past `u64::MAX` ends the iteration with
+/// [`HomePrimeResult::ExceededWidth`] instead.
+///
+/// # Arguments
+///
+/// * `n` - The number to start from.
+/// * `max_steps` - The largest number of steps to take.
+///
+/// # Returns
+///
+/// [`HomePrimeResult::Prime`] with the home prime and the number of steps (`0` if `n` is already prime),
+/// [`HomePrimeResult::ExceededSteps`] if no prime was reached within `max_steps` steps, or
+/// [`HomePrimeResult::ExceededWidth`] with the number of steps taken if the next term does not fit in a `u64`.
+///
+/// # Panics
+///
+/// Panics if `n` is less than 2.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::factorization::{home_prime, HomePrimeResult};
+/// assert_eq!(home_prime(10, 100), HomePrimeResult::Prime(773, 4));
+/// assert_eq!(home_prime(10, 3), HomePrimeResult::ExceededSteps);
+/// // 2^63 concatenates to 63 twos
+/// assert_eq!(home_prime(1 << 63, 100), HomePrimeResult::ExceededWidth(0));
+/// assert_eq!(home_prime(13, 0), HomePrimeResult::Prime(13, 0));
+/// ```
+pub fn home_prime(n: u64, max_steps: u32) -> HomePrimeResult {
+    if n < 2 {
+        panic!("n must be at least 2");
+    }
+    let mut current: u64 = n;
+    let mut steps: u32 = 0;
+    loop {
+        if is_prime_u64(current) {
+            return HomePrimeResult::Prime(current, steps);
+        }
+        if steps == max_steps {
+            return HomePrimeResult::ExceededSteps;
+        }
+        let factors: Vec<(u64, u32)> = factorize(current);
+        let mut next: u128 = 0;
+        for (p, e) in factors {
+            let width: u128 = 10_u128.pow(p.ilog10() + 1);
+            for _ in 0..e {
+                next = next * width + p as u128;
+                if next > u64::MAX as u128 {
+                    return HomePrimeResult::ExceededWidth(steps);
+                }
+            }
+        }
+        current = next as u64;
+        steps += 1;
+    }
+}
+
+/// Calculates the prime signature of a number, the exponents of its factorization in decreasing order.
+///
+/// Numbers with the same prime signature have the same divisor structure, for example the same number of
+/// divisors.
+///
+/// # Arguments
+///
+/// * `n` - The number to find the prime signature of.
+///
+/// # Returns
+///
+/// Vector of the exponents in the factorization of `n`, largest first (empty for `0` and `1`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::factorization::prime_signature;
+/// assert_eq!(prime_signature(360), vec![3, 2, 1]); // 2^3 * 3^2 * 5
+/// assert_eq!(prime_signature(1), vec![]);
+/// ```
+pub fn prime_signature(n: u64) -> Vec<u32> {
+    let mut signature: Vec<u32> = factorize(n).into_iter().map(|(_, e)| e).collect();
+    signature.sort_unstable_by(|a, b| b.cmp(a));
+    signature
+}
+
+/// Checks if two numbers have the same prime signature (see [`prime_signature`]).
+///
+/// # Arguments
+///
+/// * `a` - The first number.
+/// * `b` - The second number.
+///
+/// # Returns
+///
+/// `true` if the factorizations of `a` and `b` have the same exponents.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::factorization::same_signature;
+/// assert!(same_signature(12, 75)); // 2^2 * 3 and 3 * 5^2
+/// assert!(!same_signature(12, 30));
+/// ```
+pub fn same_signature(a: u64, b: u64) -> bool {
+    prime_signature(a) == prime_signature(b)
+}
+
+/// Calculates the smallest number with a given prime signature.
+///
+/// The smallest number is found by giving the largest exponents to the smallest primes, so
+/// $[e_1, e_2, \dots]$ sorted decreasingly gives $2^{e_1} 3^{e_2} \cdots$.
+///
+/// # Arguments
+///
+/// * `signature` - The exponents, in any order (zeros are ignored).
+///
+/// # Returns
+///
+/// The smallest number whose prime signature is `signature`.
+///
+/// # Panics
+///
+/// Panics if the number does not fit in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::factorization::smallest_with_signature;
+/// assert_eq!(smallest_with_signature(&[1, 2, 3]), 360);
+/// assert_eq!(smallest_with_signature(&[]), 1);
+/// ```
+pub fn smallest_with_signature(signature: &[u32]) -> u64 {
+    let mut exponents: Vec<u32> = signature.iter().copied().filter(|e| *e > 0).collect();
+    exponents.sort_unstable_by(|a, b| b.cmp(a));
+    let primes: Vec<u64> = generate_primes(320_u64);
+    if exponents.len() > primes.len() {
+        panic!("the smallest number with this signature does not fit in a u64");
+    }
+    let mut n: u64 = 1;
+    for (p, e) in primes.iter().zip(exponents) {
+        n = match p.checked_pow(e).and_then(|power| n.checked_mul(power)) {
+            Some(n) => n,
+            None => panic!("the smallest number with this signature does not fit in a u64"),
+        };
+    }
+    n
+}
+
+/// The prime factorization of a positive number, kept in factored form.
+///
+/// Multiplying factorizations merges their exponents, so products, divisor counts and divisor sums can be
+/// worked with long after the value itself stops fitting in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::factorization::Factorization;
+///
+/// let f: Factorization = Factorization::of(360);
+/// assert_eq!(f.to_string(), "2^3 · 3^2 · 5");
+/// assert_eq!(f.to_latex(), "2^{3} \\cdot 3^{2} \\cdot 5");
+/// assert_eq!((f.clone() * f).checked_value(), Some(129600));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Factorization {
+    factors: Vec<(u64, u32)>,
+}
+
+/// The ways a [`Factorization`] can be written out by [`Factorization::format`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FactorizationStyle {
+    /// Carets for exponents and middle dots between factors, `2^3 · 3^2 · 5` (used by `Display`).
+    Caret,
+    /// Only ASCII characters, `2^3 * 3^2 * 5`.
+    Ascii,
+    /// Unicode superscripts and middle dots, `2³ · 3² · 5`.
+    Unicode,
+}
+
+impl Factorization {
+    /// Calculates the factorization of a positive number with [`factorize`].
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number to factorize.
+    ///
+    /// # Returns
+    ///
+    /// The factorization of `n` (with no factors for `1`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is `0`.
+    pub fn of(n: u64) -> Factorization {
+        if n == 0 {
+            panic!("0 has no prime factorization");
+        }
+        Factorization {
+            factors: factorize(n),
+        }
+    }
+
+    /// Returns the `(prime, exponent)` pairs in increasing order of the primes.
+    pub fn factors(&self) -> &[(u64, u32)] {
+        &self.factors
+    }
+
+    /// Calculates the value of the factorization.
+    ///
+    /// # Returns
+    ///
+    /// `Some(n)` with the product of the prime powers, or `None` if it does not fit in a `u64`.
+    pub fn checked_value(&self) -> Option<u64> {
+        let mut n: u64 = 1;
+        for (p, e) in &self.factors {
+            n = n.checked_mul(p.checked_pow(*e)?)?;
+        }
+        Some(n)
+    }
+
+    /// Calculates the number of divisors, $\prod (e_i + 1)$.
+    ///
+    /// # Returns
+    ///
+    /// The number of positive divisors of the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bens_number_theory::factorization::Factorization;
+    /// use num::BigInt;
+    ///
+    /// assert_eq!(Factorization::of(360).divisor_count(), BigInt::from(24));
+    /// ```
+    pub fn divisor_count(&self) -> BigInt {
+        let mut count: BigInt = BigInt::from(1);
+        for (_, e) in &self.factors {
+            count *= *e as u64 + 1;
+        }
+        count
+    }
+
+    /// Calculates the sum of the divisors, $\prod \frac{p_i^{e_i + 1} - 1}{p_i - 1}$.
+    ///
+    /// # Returns
+    ///
+    /// The sum of the positive divisors of the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bens_number_theory::factorization::Factorization;
+    /// use num::BigInt;
+    ///
+    /// assert_eq!(Factorization::of(12).divisor_sum(), BigInt::from(28));
+    /// ```
+    pub fn divisor_sum(&self) -> BigInt {
+        let mut sum: BigInt = BigInt::from(1);
+        for (p, e) in &self.factors {
+            let p: BigInt = BigInt::from(*p);
+            sum *= (num::pow(p.clone(), *e as usize + 1) - 1) / (p - 1);
+        }
+        sum
+    }
+
+    /// Writes out the factorization in a given style.
+    ///
+    /// # Arguments
+    ///
+    /// * `style` - How to write exponents and the multiplication between factors.
+    ///
+    /// # Returns
+    ///
+    /// The factors in increasing order, with exponents of `1` left out (`"1"` for no factors).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bens_number_theory::factorization::{Factorization, FactorizationStyle};
+    ///
+    /// let f: Factorization = Factorization::of(360);
+    /// assert_eq!(f.format(FactorizationStyle::Ascii), "2^3 * 3^2 * 5");
+    /// assert_eq!(f.format(FactorizationStyle::Unicode), "2³ · 3² · 5");
+    /// ```
+    pub fn format(&self, style: FactorizationStyle) -> String {
+        let separator: &str = match style {
+            FactorizationStyle::Ascii => " * ",
+            _ => " · ",
+        };
+        let mut parts: Vec<String> = vec![];
+        for (p, e) in &self.factors {
+            if *e == 1 {
+                parts.push(p.to_string());
+            } else if style == FactorizationStyle::Unicode {
+                parts.push(format!("{}{}", p, superscript(*e)));
+            } else {
+                parts.push(format!("{}^{}", p, e));
+            }
+        }
+        if parts.is_empty() {
+            return "1".to_string();
+        }
+        parts.join(separator)
+    }
+
+    /// Writes out the factorization as LaTeX, like `2^{3} \cdot 3^{2} \cdot 5`.
+    ///
+    /// # Returns
+    ///
+    /// The LaTeX source for the factorization (`"1"` for no factors).
+    pub fn to_latex(&self) -> String {
+        let mut parts: Vec<String> = vec![];
+        for (p, e) in &self.factors {
+            if *e == 1 {
+                parts.push(p.to_string());
+            } else {
+                parts.push(format!("{}^{{{}}}", p, e));
+            }
+        }
+        if parts.is_empty() {
+            return "1".to_string();
+        }
+        parts.join(" \\cdot ")
+    }
+}
+
+/// Writes a number with Unicode superscript digits.
+///
+/// # Arguments
+///
+/// * `n` - The number to write.
+///
+/// # Returns
+///
+/// The digits of `n` as superscripts.
+fn superscript(n: u32) -> String {
+    const DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+    n.to_string()
+        .chars()
+        .map(|c| DIGITS[c.to_digit(10).unwrap() as usize])
+        .collect()
+}
+
+impl From<Vec<(u64, u32)>> for Factorization {
+    /// Builds a factorization from `(prime, exponent)` pairs, such as the output of [`factorize`].
+    ///
+    /// The pairs are sorted, repeated primes have their exponents added and zero exponents are dropped.
+    /// The primes are not checked for primality.
+    fn from(pairs: Vec<(u64, u32)>) -> Factorization {
+        let mut pairs: Vec<(u64, u32)> = pairs;
+        pairs.sort_unstable();
+        let mut factors: Vec<(u64, u32)> = vec![];
+        for (p, e) in pairs {
+            match factors.last_mut() {
+                Some((q, f)) if *q == p => *f += e,
+                _ => factors.push((p, e)),
+            }
+        }
+        factors.retain(|(_, e)| *e > 0);
+        Factorization { factors }
+    }
+}
+
+impl std::fmt::Display for Factorization {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.format(FactorizationStyle::Caret))
+    }
+}
+
+impl std::ops::Mul for Factorization {
+    type Output = Factorization;
+
+    /// Multiplies two factorizations by merging their exponents.
+    fn mul(self, other: Factorization) -> Factorization {
+        let mut pairs: Vec<(u64, u32)> = self.factors;
+        pairs.extend(other.factors);
+        Factorization::from(pairs)
+    }
+}
+
+/// Checks if a number is a Blum integer, a product $pq$ of two distinct primes with $p \equiv q \equiv 3 \pmod{4}$.
+///
+/// [A016105](https://oeis.org/A016105)
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// `true` if `n` is a Blum integer.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::factorization::is_blum_integer;
+/// assert!(is_blum_integer(21)); // 3 * 7
+/// assert!(!is_blum_integer(15)); // 5 is 1 mod 4
+/// assert!(!is_blum_integer(9)); // the primes must be distinct
+/// ```
+pub fn is_blum_integer(n: u64) -> bool {
+    match factorize(n)[..] {
+        [(p, 1), (q, 1)] => p % 4 == 3 && q % 4 == 3,
+        _ => false,
+    }
+}
+
+/// Finds the least prime factor of a number without factorizing it completely.
+///
+/// Trial division stops at the first divisor found. Numbers with no factor below $2^{10}$ are checked with the
+/// deterministic Miller–Rabin test first, so a large prime is answered at once instead of after $\sqrt{n}$
+/// divisions.
+///
+/// # Arguments
+///
+/// * `n` - The number to find the least prime factor of.
+///
+/// # Returns
+///
+/// `Some(p)` for the smallest prime $p \mid n$, or `None` for `0` and `1`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::factorization::least_prime_factor;
+/// assert_eq!(least_prime_factor(91), Some(7));
+/// assert_eq!(least_prime_factor(2_305_843_009_213_693_951), Some(2_305_843_009_213_693_951)); // 2^61 - 1
+/// assert_eq!(least_prime_factor(1), None);
+/// ```
+pub fn least_prime_factor(n: u64) -> Option<u64> {
+    if n <= 1 {
+        return None;
+    }
+    let mut d: u64 = 2;
+    while d <= n / d {
+        if d == 1 << 10 && is_prime_u64(n) {
+            return Some(n);
+        }
+        if n.is_multiple_of(d) {
+            return Some(d);
+        }
+        d += if d == 2 { 1 } else { 2 };
+    }
+    Some(n)
+}
+
+/// Finds the greatest prime factor of a number without factorizing it completely.
+///
+/// Small factors are stripped off by trial division, and every time the rest changes it is checked with
+/// the deterministic Miller–Rabin test, so the search stops as soon as the rest is prime rather than running to its
+/// square root.
+///
+/// # Arguments
+///
+/// * `n` - The number to find the greatest prime factor of.
+///
+/// # Returns
+///
+/// `Some(p)` for the largest prime $p \mid n$, or `None` for `0` and `1`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::factorization::greatest_prime_factor;
+/// assert_eq!(greatest_prime_factor(600_851_475_143), Some(6857));
+/// assert_eq!(greatest_prime_factor(2 * 1_000_000_007), Some(1_000_000_007));
+/// assert_eq!(greatest_prime_factor(0), None);
+/// ```
+pub fn greatest_prime_factor(n: u64) -> Option<u64> {
+    if n <= 1 {
+        return None;
+    }
+    if is_prime_u64(n) {
+        return Some(n);
+    }
+    let mut m: u64 = n;
+    let mut largest: u64 = 1;
+    let mut d: u64 = 2;
+    while d <= m / d {
+        if m.is_multiple_of(d) {
+            largest = d;
+            while m.is_multiple_of(d) {
+                m /= d;
+            }
+            if m > 1 && is_prime_u64(m) {
+                return Some(m);
+            }
+        }
+        d += if d == 2 { 1 } else { 2 };
+    }
+    Some(largest.max(m))
+}
+
+/// Writes a number as $n = s^2 q$ with $q$ squarefree.
+///
+/// Trial division only runs while $d^3$ is at most the unfactored rest $m$. After that every prime factor of $m$
+/// exceeds $\sqrt[3]{m}$, so $m$ is $1$, a prime, a product of two distinct primes or the square of a prime, and
+/// it is squarefree unless it is a perfect square.
+///
+/// [A000188](https://oeis.org/A000188) ($s$) and [A007913](https://oeis.org/A007913) ($q$)
+///
+/// # Arguments
+///
+/// * `n` - The number to decompose.
+///
+/// # Returns
+///
+/// The pair `(s, q)` with $s^2 q = n$ and $q$ squarefree (`(0, 1)` for `0`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::factorization::square_part_decomposition;
+/// assert_eq!(square_part_decomposition(72), (6, 2)); // 72 = 6^2 * 2
+/// assert_eq!(square_part_decomposition(1_000_000_007), (1, 1_000_000_007));
+/// assert_eq!(square_part_decomposition(144), (12, 1));
+/// ```
+pub fn square_part_decomposition(n: u64) -> (u64, u64) {
+    if n == 0 {
+        return (0, 1);
+    }
+    let mut m: u64 = n;
+    let mut s: u64 = 1;
+    let mut q: u64 = 1;
+    let mut d: u64 = 2;
+    while d <= m / d / d {
+        if m.is_multiple_of(d) {
+            let mut e: u32 = 0;
+            while m.is_multiple_of(d) {
+                m /= d;
+                e += 1;
+            }
+            s *= d.pow(e / 2);
+            if e % 2 == 1 {
+                q *= d;
+            }
+        }
+        d += if d == 2 { 1 } else { 2 };
+    }
+    let root: u64 = m.isqrt();
+    if m > 1 && root * root == m {
+        s *= root;
+    } else {
+        q *= m;
+    }
+    (s, q)
+}
+
+/// Simplifies $\sqrt{n}$ to $a\sqrt{b}$ with $b$ squarefree.
+///
+/// This is [`square_part_decomposition`] read as a coefficient and a radicand, since $\sqrt{s^2 q} = s\sqrt{q}$.
+///
+/// # Arguments
+///
+/// * `n` - The number under the square root.
+///
+/// # Returns
+///
+/// The pair `(coefficient, radicand)` with $\sqrt{n} = \text{coefficient} \sqrt{\text{radicand}}$ (`(0, 1)` for
+/// `0`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::factorization::simplify_sqrt;
+/// assert_eq!(simplify_sqrt(50), (5, 2)); // sqrt(50) = 5 sqrt(2)
+/// assert_eq!(simplify_sqrt(12), (2, 3));
+/// ```
+pub fn simplify_sqrt(n: u64) -> (u64, u64) {
+    square_part_decomposition(n)
+}
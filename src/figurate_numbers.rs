@@ -0,0 +1,359 @@
+use crate::primes::is_prime_u64;
+
+/// Calculates the `n`-th `s`-gonal number.
+///
+/// $$P(s, n) = \frac{(s - 2)n^2 - (s - 4)n}{2}$$
+///
+/// so $P(3, n)$ are the triangular numbers, $P(4, n)$ the squares, $P(5, n)$ the pentagonal numbers, and so on.
+///
+/// # Arguments
+///
+/// * `s` - The number of sides of the polygon (at least 3).
+/// * `n` - The index of the polygonal number.
+///
+/// # Returns
+///
+/// The `n`-th `s`-gonal number.
+///
+/// # Panics
+///
+/// Panics if `s` is less than 3, or if the result does not fit in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::figurate_numbers::polygonal;
+/// assert_eq!(polygonal(3, 4), 10); // 1 + 2 + 3 + 4
+/// assert_eq!(polygonal(4, 5), 25);
+/// assert_eq!(polygonal(6, 143), 40755);
+/// ```
+pub fn polygonal(s: u32, n: u64) -> u64 {
+    if s < 3 {
+        panic!("a polygon has at least 3 sides");
+    }
+    let n: i128 = n as i128;
+    let s: i128 = s as i128;
+    (s - 2)
+        .checked_mul(n)
+        .and_then(|t| t.checked_mul(n))
+        .and_then(|t| t.checked_sub((s - 4) * n))
+        .and_then(|t| (t / 2).try_into().ok())
+        .expect("polygonal number does not fit in a u64")
+}
+
+/// Determines whether or not a number is `s`-gonal by inverting [`polygonal`].
+///
+/// Solving $P(s, n) = x$ for $n$ with the quadratic formula gives
+/// $$n = \frac{(s - 4) + \sqrt{(s - 4)^2 + 8(s - 2)x}}{2(s - 2)},$$
+/// so $x$ is `s`-gonal exactly when the discriminant is a perfect square and the division is exact.
+/// Everything is done with integers (and an exact integer square root), since floating point misrounds for large `x`.
+///
+/// # Arguments
+///
+/// * `s` - The number of sides of the polygon (at least 3).
+/// * `x` - The number to check.
+///
+/// # Returns
+///
+/// `Some(n)` if `x` is the `n`-th `s`-gonal number, otherwise `None` (`0` is counted as the 0th `s`-gonal number).
+///
+/// # Panics
+///
+/// Panics if `s` is less than 3.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::figurate_numbers::is_polygonal;
+/// assert_eq!(is_polygonal(3, 10), Some(4));
+/// assert_eq!(is_polygonal(5, 40755), Some(165));
+/// assert_eq!(is_polygonal(4, 26), None);
+/// ```
+pub fn is_polygonal(s: u32, x: u64) -> Option<u64> {
+    if s < 3 {
+        panic!("a polygon has at least 3 sides");
+    }
+    if x == 0 {
+        return Some(0);
+    }
+    let s: i128 = s as i128;
+    let discriminant: u128 = ((s - 4) * (s - 4)) as u128 + 8 * (s - 2) as u128 * x as u128;
+    let root: u128 = discriminant.isqrt();
+    if root * root != discriminant {
+        return None;
+    }
+    let numerator: i128 = root as i128 + (s - 4);
+    let denominator: i128 = 2 * (s - 2);
+    if numerator % denominator != 0 {
+        return None;
+    }
+    Some((numerator / denominator) as u64)
+}
+
+/// Determines whether or not a number is triangular ($\frac{n(n + 1)}{2}$ for some $n$).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::figurate_numbers::is_triangular;
+/// assert_eq!(is_triangular(40755), true);
+/// assert_eq!(is_triangular(40756), false);
+/// ```
+pub fn is_triangular(x: u64) -> bool {
+    is_polygonal(3, x).is_some()
+}
+
+/// Determines whether or not a number is a perfect square.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::figurate_numbers::is_square;
+/// assert_eq!(is_square(144), true);
+/// assert_eq!(is_square(145), false);
+/// ```
+pub fn is_square(x: u64) -> bool {
+    is_polygonal(4, x).is_some()
+}
+
+/// Determines whether or not a number is pentagonal ($\frac{n(3n - 1)}{2}$ for some $n$).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::figurate_numbers::is_pentagonal;
+/// assert_eq!(is_pentagonal(40755), true);
+/// assert_eq!(is_pentagonal(22), true);
+/// assert_eq!(is_pentagonal(23), false);
+/// ```
+pub fn is_pentagonal(x: u64) -> bool {
+    is_polygonal(5, x).is_some()
+}
+
+/// Determines whether or not a number is hexagonal ($n(2n - 1)$ for some $n$).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::figurate_numbers::is_hexagonal;
+/// assert_eq!(is_hexagonal(40755), true);
+/// assert_eq!(is_hexagonal(28), true);
+/// assert_eq!(is_hexagonal(10), false);
+/// ```
+pub fn is_hexagonal(x: u64) -> bool {
+    is_polygonal(6, x).is_some()
+}
+
+/// Determines whether or not a number is a perfect cube.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::figurate_numbers::is_cube;
+/// assert_eq!(is_cube(1728), true);
+/// assert_eq!(is_cube(1729), false);
+/// assert_eq!(is_cube(2642245 * 2642245 * 2642245), true);
+/// ```
+pub fn is_cube(x: u64) -> bool {
+    let root: u64 = integer_cbrt(x);
+    root * root * root == x
+}
+
+/// Calculates the integer cube root $\lfloor \sqrt[3]{x} \rfloor$.
+///
+/// The floating point cube root is within one of the answer, so it is corrected by checking its neighbours.
+///
+/// # Arguments
+///
+/// * `x` - The number to take the cube root of.
+///
+/// # Returns
+///
+/// The largest `r` with $r^3 \leq x$.
+fn integer_cbrt(x: u64) -> u64 {
+    let cube = |r: u64| (r as u128).pow(3);
+    let mut root: u64 = (x as f64).cbrt() as u64;
+    while cube(root) > x as u128 {
+        root -= 1;
+    }
+    while cube(root + 1) <= x as u128 {
+        root += 1;
+    }
+    root
+}
+
+/// A kind of number that can be a summand in [`is_sum_of`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SummandKind {
+    /// The primes $2, 3, 5, 7, \ldots$
+    Prime,
+    /// The squares $0, 1, 4, 9, \ldots$
+    Square,
+    /// The triangular numbers $0, 1, 3, 6, \ldots$
+    Triangular,
+    /// The cubes $0, 1, 8, 27, \ldots$
+    Cube,
+}
+
+impl SummandKind {
+    /// Checks if a number is of this kind.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The number to check.
+    ///
+    /// # Returns
+    ///
+    /// `true` if `x` is of this kind.
+    fn contains(&self, x: u64) -> bool {
+        match self {
+            SummandKind::Prime => is_prime_u64(x),
+            SummandKind::Square => is_square(x),
+            SummandKind::Triangular => is_triangular(x),
+            SummandKind::Cube => is_cube(x),
+        }
+    }
+
+    /// Calculates the smallest number of this kind that is larger than another.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The number to start after.
+    ///
+    /// # Returns
+    ///
+    /// The next number of this kind after `x`, or `None` if it does not fit in a `u64`.
+    fn next_after(&self, x: u64) -> Option<u64> {
+        match self {
+            SummandKind::Prime => {
+                let mut candidate: u64 = x.checked_add(1)?.max(2);
+                while !is_prime_u64(candidate) {
+                    candidate = candidate.checked_add(1)?;
+                }
+                Some(candidate)
+            }
+            SummandKind::Square => {
+                let root: u64 = x.isqrt() + 1;
+                root.checked_mul(root)
+            }
+            SummandKind::Triangular => {
+                // the index of the largest triangular number at most x, from 8x + 1 >= (2k + 1)^2
+                let k: u64 = ((8 * x as u128 + 1).isqrt() as u64 - 1) / 2 + 1;
+                (k as u128 * (k as u128 + 1) / 2).try_into().ok()
+            }
+            SummandKind::Cube => {
+                let root: u64 = integer_cbrt(x) + 1;
+                root.checked_mul(root)?.checked_mul(root)
+            }
+        }
+    }
+
+    /// Calculates the smallest number of this kind.
+    ///
+    /// # Returns
+    ///
+    /// `2` for the primes and `0` for the others.
+    fn first(&self) -> u64 {
+        match self {
+            SummandKind::Prime => 2,
+            _ => 0,
+        }
+    }
+}
+
+/// Finds a way to write a number as the sum of a number of one kind and a number of another kind.
+///
+/// This is [`is_sum_of_scaled`] with a scale of $1$, so it checks for example whether an even number is the sum
+/// of two primes (Goldbach's conjecture) or whether a number is the sum of two squares.
+///
+/// # Arguments
+///
+/// * `n` - The number to write as a sum.
+/// * `kind_a` - The kind of the first summand.
+/// * `kind_b` - The kind of the second summand.
+///
+/// # Returns
+///
+/// `Some((a, b))` with $n = a + b$, `a` of `kind_a` and `b` of `kind_b`, or `None` if there is no such pair.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::figurate_numbers::{is_sum_of, SummandKind};
+///
+/// assert_eq!(is_sum_of(28, SummandKind::Prime, SummandKind::Prime), Some((23, 5)));
+/// assert_eq!(is_sum_of(25, SummandKind::Square, SummandKind::Square), Some((25, 0)));
+/// assert_eq!(is_sum_of(21, SummandKind::Square, SummandKind::Square), None);
+/// ```
+pub fn is_sum_of(n: u64, kind_a: SummandKind, kind_b: SummandKind) -> Option<(u64, u64)> {
+    is_sum_of_scaled(n, kind_a, kind_b, 1)
+}
+
+/// Finds a way to write a number as $a + \text{scale} \cdot b$ with $a$ of one kind and $b$ of another kind.
+///
+/// Goldbach's other conjecture was that every odd composite is a prime plus twice a square, which is this with
+/// [`SummandKind::Prime`], [`SummandKind::Square`] and a scale of $2$ (it fails for $5777$ and $5993$).
+///
+/// Only the sparser of the two kinds is enumerated (cubes, then squares and triangular numbers, then primes),
+/// in increasing order, and the other summand is checked directly, so the pair found has the smallest possible
+/// summand of the sparser kind. Primes are checked with Miller–Rabin and the others with an exact integer root.
+///
+/// # Arguments
+///
+/// * `n` - The number to write as a sum.
+/// * `kind_a` - The kind of the first summand.
+/// * `kind_b` - The kind of the second summand.
+/// * `scale` - The factor the second summand is multiplied by.
+///
+/// # Returns
+///
+/// `Some((a, b))` with $n = a + \text{scale} \cdot b$, `a` of `kind_a` and `b` of `kind_b` (before scaling), or
+/// `None` if there is no such pair. Squares, triangular numbers and cubes include `0`.
+///
+/// # Panics
+///
+/// Panics if `scale` is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::figurate_numbers::{is_sum_of_scaled, SummandKind};
+///
+/// // 33 = 31 + 2 * 1^2
+/// assert_eq!(is_sum_of_scaled(33, SummandKind::Prime, SummandKind::Square, 2), Some((31, 1)));
+/// assert_eq!(is_sum_of_scaled(5777, SummandKind::Prime, SummandKind::Square, 2), None);
+/// ```
+pub fn is_sum_of_scaled(
+    n: u64,
+    kind_a: SummandKind,
+    kind_b: SummandKind,
+    scale: u64,
+) -> Option<(u64, u64)> {
+    if scale == 0 {
+        panic!("the scale must be positive");
+    }
+    let sparseness = |kind: SummandKind| match kind {
+        SummandKind::Cube => 0,
+        SummandKind::Square | SummandKind::Triangular => 1,
+        SummandKind::Prime => 2,
+    };
+    if sparseness(kind_b) <= sparseness(kind_a) {
+        let mut b: u64 = kind_b.first();
+        while let Some(scaled) = b.checked_mul(scale).filter(|scaled| *scaled <= n) {
+            if kind_a.contains(n - scaled) {
+                return Some((n - scaled, b));
+            }
+            b = kind_b.next_after(b)?;
+        }
+    } else {
+        let mut a: u64 = kind_a.first();
+        while a <= n {
+            let rest: u64 = n - a;
+            if rest.is_multiple_of(scale) && kind_b.contains(rest / scale) {
+                return Some((a, rest / scale));
+            }
+            a = kind_a.next_after(a)?;
+        }
+    }
+    None
+}
@@ -0,0 +1,300 @@
+use crate::factorization::factorize;
+use crate::modular::{mod_pow, smallest_quadratic_nonresidue};
+use crate::primes::is_probable_prime;
+use num::BigInt;
+
+/// A Gaussian integer $a + bi$ with $a, b \in \mathbb{Z}$.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::gaussian::GaussianInt;
+///
+/// let z: GaussianInt = GaussianInt::new(2, 1);
+/// assert_eq!(z * z.conjugate(), GaussianInt::new(5, 0));
+/// assert_eq!(z.norm(), 5);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GaussianInt {
+    /// The real part.
+    pub re: i64,
+    /// The imaginary part.
+    pub im: i64,
+}
+
+impl GaussianInt {
+    /// Creates the Gaussian integer `re + im i`.
+    ///
+    /// # Arguments
+    ///
+    /// * `re` - The real part.
+    /// * `im` - The imaginary part.
+    ///
+    /// # Returns
+    ///
+    /// The Gaussian integer `re + im i`.
+    pub fn new(re: i64, im: i64) -> GaussianInt {
+        GaussianInt { re, im }
+    }
+
+    /// Calculates the norm $N(a + bi) = a^2 + b^2$, which is multiplicative.
+    ///
+    /// # Returns
+    ///
+    /// The norm of the Gaussian integer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bens_number_theory::gaussian::GaussianInt;
+    /// assert_eq!(GaussianInt::new(3, -4).norm(), 25);
+    /// assert_eq!(GaussianInt::new(i64::MIN, i64::MIN).norm(), 2_u128.pow(127));
+    /// ```
+    pub fn norm(&self) -> u128 {
+        let re: u128 = self.re.unsigned_abs() as u128;
+        let im: u128 = self.im.unsigned_abs() as u128;
+        re * re + im * im
+    }
+
+    /// Calculates the complex conjugate $\overline{a + bi} = a - bi$.
+    ///
+    /// # Returns
+    ///
+    /// The conjugate of the Gaussian integer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the imaginary part is `i64::MIN`, since its negation does not fit in an `i64`.
+    pub fn conjugate(&self) -> GaussianInt {
+        GaussianInt::new(self.re, -self.im)
+    }
+
+    /// Checks if the Gaussian integer is a unit, meaning one of $1$, $-1$, $i$ and $-i$.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the norm is $1$.
+    pub fn is_unit(&self) -> bool {
+        self.norm() == 1
+    }
+
+    /// Calculates the associate (the Gaussian integer multiplied by a unit) whose real part is positive and
+    /// whose imaginary part is not negative.
+    ///
+    /// Every nonzero Gaussian integer has exactly one such associate, so comparing them checks whether two
+    /// Gaussian integers differ only by a unit.
+    ///
+    /// # Returns
+    ///
+    /// The canonical associate (`0` for `0`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the associate does not fit, which only happens when a part is `i64::MIN`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bens_number_theory::gaussian::GaussianInt;
+    /// assert_eq!(GaussianInt::new(-1, 2).canonical(), GaussianInt::new(2, 1));
+    /// assert_eq!(GaussianInt::new(0, -3).canonical(), GaussianInt::new(3, 0));
+    /// ```
+    pub fn canonical(&self) -> GaussianInt {
+        let mut z: GaussianInt = *self;
+        if z.re == 0 && z.im == 0 {
+            return z;
+        }
+        // multiplying by i rotates a quarter turn, so at most three rotations are needed
+        while !(z.re > 0 && z.im >= 0) {
+            z = GaussianInt::new(-z.im, z.re);
+        }
+        z
+    }
+
+    /// Checks if the Gaussian integer is a Gaussian prime.
+    ///
+    /// A Gaussian integer $a + bi$ is prime exactly when either
+    /// * both $a$ and $b$ are nonzero and $a^2 + b^2$ is a rational prime, or
+    /// * one of $a$ and $b$ is zero and the other is $\pm p$ for a rational prime $p \equiv 3 \pmod{4}$.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the Gaussian integer is a Gaussian prime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bens_number_theory::gaussian::GaussianInt;
+    /// assert!(GaussianInt::new(1, 1).is_gaussian_prime());
+    /// assert!(GaussianInt::new(0, -7).is_gaussian_prime());
+    /// assert!(!GaussianInt::new(5, 0).is_gaussian_prime()); // 5 = (2 + i)(2 - i)
+    /// ```
+    pub fn is_gaussian_prime(&self) -> bool {
+        if self.re == 0 || self.im == 0 {
+            let p: u64 = self.re.unsigned_abs().max(self.im.unsigned_abs());
+            return p % 4 == 3 && is_probable_prime(&BigInt::from(p));
+        }
+        is_probable_prime(&BigInt::from(self.norm()))
+    }
+
+    /// Calculates the factorization of the Gaussian integer into Gaussian primes.
+    ///
+    /// Every Gaussian prime divides exactly one rational prime $p$ dividing the norm:
+    /// * $p = 2$ gives the prime $1 + i$,
+    /// * $p \equiv 3 \pmod{4}$ stays prime and appears with half the exponent of $p$ in the norm,
+    /// * $p \equiv 1 \pmod{4}$ splits as $p = (a + bi)(a - bi)$ using [`sum_of_two_squares_prime`], and the
+    ///   exponents of the two factors are found by dividing.
+    ///
+    /// # Returns
+    ///
+    /// Vector of `(prime, exponent)` pairs with every prime in its [`canonical`](GaussianInt::canonical) form,
+    /// ordered by norm and then by real part, so that the Gaussian integer is a unit times the product of
+    /// the prime powers (empty for `0` and for units).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the norm does not fit in a `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bens_number_theory::gaussian::GaussianInt;
+    ///
+    /// // 2 = -i(1 + i)^2
+    /// assert_eq!(GaussianInt::new(2, 0).factorize(), vec![(GaussianInt::new(1, 1), 2)]);
+    /// // 5 = (1 + 2i)(2 + i) up to a unit
+    /// assert_eq!(
+    ///     GaussianInt::new(5, 0).factorize(),
+    ///     vec![(GaussianInt::new(1, 2), 1), (GaussianInt::new(2, 1), 1)]
+    /// );
+    /// ```
+    pub fn factorize(&self) -> Vec<(GaussianInt, u32)> {
+        let norm: u64 = match u64::try_from(self.norm()) {
+            Ok(norm) => norm,
+            Err(_) => panic!("the norm must fit in a u64"),
+        };
+        let mut factors: Vec<(GaussianInt, u32)> = vec![];
+        let mut z: GaussianInt = *self;
+        for (p, e) in factorize(norm) {
+            if p == 2 {
+                factors.push((GaussianInt::new(1, 1), e));
+            } else if p % 4 == 3 {
+                factors.push((GaussianInt::new(p as i64, 0), e / 2));
+            } else {
+                let (a, b): (u64, u64) = sum_of_two_squares_prime(p);
+                for pi in [
+                    GaussianInt::new(a as i64, b as i64),
+                    GaussianInt::new(b as i64, a as i64),
+                ] {
+                    let mut count: u32 = 0;
+                    while let Some(quotient) = z.exact_div(&pi) {
+                        z = quotient;
+                        count += 1;
+                    }
+                    if count > 0 {
+                        factors.push((pi, count));
+                    }
+                }
+            }
+        }
+        factors.sort_by_key(|(pi, _)| (pi.norm(), pi.re));
+        factors
+    }
+
+    /// Divides by another Gaussian integer if the division is exact.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The divisor.
+    ///
+    /// # Returns
+    ///
+    /// `Some(quotient)` if `other` divides the Gaussian integer, otherwise `None` (including when `other`
+    /// is `0` or the quotient does not fit).
+    fn exact_div(&self, other: &GaussianInt) -> Option<GaussianInt> {
+        let norm: i128 = other.norm() as i128;
+        if norm == 0 {
+            return None;
+        }
+        // z / w = z * conj(w) / N(w)
+        let (a, b): (i128, i128) = (self.re as i128, self.im as i128);
+        let (c, d): (i128, i128) = (other.re as i128, other.im as i128);
+        let re: i128 = a * c + b * d;
+        let im: i128 = b * c - a * d;
+        if re % norm != 0 || im % norm != 0 {
+            return None;
+        }
+        Some(GaussianInt::new(
+            i64::try_from(re / norm).ok()?,
+            i64::try_from(im / norm).ok()?,
+        ))
+    }
+}
+
+impl std::ops::Mul for GaussianInt {
+    type Output = GaussianInt;
+
+    /// Multiplies two Gaussian integers, $(a + bi)(c + di) = (ac - bd) + (ad + bc)i$.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the product overflows.
+    fn mul(self, other: GaussianInt) -> GaussianInt {
+        let re: Option<i64> = self
+            .re
+            .checked_mul(other.re)
+            .zip(self.im.checked_mul(other.im))
+            .and_then(|(ac, bd)| ac.checked_sub(bd));
+        let im: Option<i64> = self
+            .re
+            .checked_mul(other.im)
+            .zip(self.im.checked_mul(other.re))
+            .and_then(|(ad, bc)| ad.checked_add(bc));
+        match re.zip(im) {
+            Some((re, im)) => GaussianInt::new(re, im),
+            None => panic!("the product of the Gaussian integers overflowed"),
+        }
+    }
+}
+
+/// Writes a prime $p \equiv 1 \pmod{4}$ as a sum of two squares $p = a^2 + b^2$.
+///
+/// A square root $x$ of $-1$ modulo $p$ is found as $c^{(p - 1)/4}$ for a quadratic non-residue $c$, and the
+/// Euclidean algorithm on $p$ and $x$ is stopped at the first remainder below $\sqrt{p}$ (the
+/// Hermite–Serret algorithm).
+///
+/// # Arguments
+///
+/// * `p` - A prime congruent to $1$ modulo $4$.
+///
+/// # Returns
+///
+/// The pair `(a, b)` with $a^2 + b^2 = p$ and $a > b > 0$.
+///
+/// # Panics
+///
+/// Panics if `p` is not congruent to $1$ modulo $4$.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::gaussian::sum_of_two_squares_prime;
+/// assert_eq!(sum_of_two_squares_prime(5), (2, 1));
+/// assert_eq!(sum_of_two_squares_prime(13), (3, 2));
+/// assert_eq!(sum_of_two_squares_prime(1_000_000_009), (31_400, 3_747));
+/// ```
+pub fn sum_of_two_squares_prime(p: u64) -> (u64, u64) {
+    if p % 4 != 1 {
+        panic!("p must be congruent to 1 modulo 4");
+    }
+    let c: u64 = smallest_quadratic_nonresidue(p);
+    let mut a: u64 = p;
+    let mut b: u64 = mod_pow(c, (p - 1) / 4, p);
+    while b as u128 * b as u128 > p as u128 {
+        let r: u64 = a % b;
+        a = b;
+        b = r;
+    }
+    let other: u64 = (p - b * b).isqrt();
+    (other.max(b), other.min(b))
+}
@@ -1,10 +1,44 @@
+/// Functions related to aliquot sums (the sum of the proper divisors of a number)
+pub mod aliquot;
+/// Functions that check the primality implementations against each other
+pub mod consistency;
 /// Functions that mathematically generate mathematical constants
 pub mod constants;
+/// Structured events that report what the algorithms do internally
+#[cfg(feature = "diagnostics")]
+pub mod diagnostics;
+/// Functions related to the digits of numbers
+pub mod digits;
+/// Functions related to Dirichlet convolution of arithmetic functions
+pub mod dirichlet;
+/// Functions related to divisor counts and divisor sums
+pub mod divisor_functions;
+/// Functions that estimate the memory and output size of sieves
+pub mod estimate;
 /// Functions related to factorial generation
 pub mod factorials;
+/// Functions related to prime factorization
+pub mod factorization;
+/// Functions related to polygonal and other figurate numbers
+pub mod figurate_numbers;
+/// Gaussian integers and their factorization
+pub mod gaussian;
+/// Functions related to modular arithmetic
+pub mod modular;
 /// Functions related to perfect numbers
 pub mod perfect_numbers;
 /// Functions related to prime numbers
 pub mod primes;
+/// Searches for the numbers below a limit whose arithmetic functions satisfy some conditions
+pub mod range_query;
+/// A small exact fraction type for arithmetic on machine integers
+pub mod rational;
 /// Functions that generate mathematical sequences
 pub mod sequences;
+/// Functions related to summatory functions such as the Mertens function
+pub mod summatory;
+/// Textbook RSA with tiny keys, for education only
+#[cfg(feature = "rand")]
+pub mod toy_rsa;
+/// Functions related to the Ulam spiral
+pub mod ulam;
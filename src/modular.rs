@@ -0,0 +1,2414 @@
+use crate::dirichlet::multiplicative_sieve_with;
+use crate::factorization::{factorize, least_prime_factor};
+use crate::primes::{is_probable_prime, Prime};
+use crate::sequences::fibonacci_mod_u128;
+use num::BigInt;
+
+/// Calculates the greatest common divisor of two numbers.
+///
+/// This is [`gcd_binary`], which avoids the divisions of the Euclidean algorithm. For `u128` operands use
+/// [`gcd_lehmer`], and for many pairs at once [`gcd_batch`].
+///
+/// # Arguments
+///
+/// * `a` - The first number.
+/// * `b` - The second number.
+///
+/// # Returns
+///
+/// The largest number dividing both `a` and `b` (with $\gcd(a, 0) = a$).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::gcd;
+/// assert_eq!(gcd(12, 18), 6);
+/// assert_eq!(gcd(17, 5), 1);
+/// assert_eq!(gcd(0, 7), 7);
+/// ```
+#[inline]
+pub fn gcd(a: u64, b: u64) -> u64 {
+    gcd_binary(a, b)
+}
+
+/// Calculates the greatest common divisor of two numbers with Stein's binary algorithm.
+///
+/// The common power of $2$ is taken out with one `trailing_zeros`, and then the smaller odd number is repeatedly
+/// subtracted from the larger, with the powers of $2$ stripped from the difference, so only shifts and
+/// subtractions are needed.
+///
+/// # Arguments
+///
+/// * `a` - The first number.
+/// * `b` - The second number.
+///
+/// # Returns
+///
+/// The largest number dividing both `a` and `b` (with $\gcd(a, 0) = a$).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::gcd_binary;
+/// assert_eq!(gcd_binary(48, 180), 12);
+/// assert_eq!(gcd_binary(0, 0), 0);
+/// assert_eq!(gcd_binary(u64::MAX, u64::MAX - 1), 1);
+/// ```
+#[inline]
+pub fn gcd_binary(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        return a | b;
+    }
+    let shift: u32 = (a | b).trailing_zeros();
+    let mut a: u64 = a >> a.trailing_zeros();
+    let mut b: u64 = b;
+    loop {
+        b >>= b.trailing_zeros();
+        if a > b {
+            std::mem::swap(&mut a, &mut b);
+        }
+        b -= a;
+        if b == 0 {
+            return a << shift;
+        }
+    }
+}
+
+/// Calculates the greatest common divisor of two `u128`s with Lehmer's algorithm.
+///
+/// While the smaller number needs more than $64$ bits, the Euclidean steps are simulated on the leading $64$ bits
+/// of both numbers (Knuth's Algorithm L), collecting the quotients into a matrix of cofactors that is applied to
+/// the full numbers once, so several steps cost two `u128` multiplications instead of a `u128` division each. If
+/// the leading bits cannot decide even one quotient, a single Euclidean step is taken. The rest is left to
+/// [`gcd_binary`].
+///
+/// # Arguments
+///
+/// * `a` - The first number.
+/// * `b` - The second number.
+///
+/// # Returns
+///
+/// The largest number dividing both `a` and `b` (with $\gcd(a, 0) = a$).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::gcd_lehmer;
+/// assert_eq!(gcd_lehmer(12, 18), 6);
+/// assert_eq!(gcd_lehmer(u128::MAX, 1 << 64), 1);
+/// assert_eq!(gcd_lehmer(3 << 100, 9 << 90), 3 << 90);
+/// ```
+pub fn gcd_lehmer(a: u128, b: u128) -> u128 {
+    let (mut a, mut b): (u128, u128) = if a >= b { (a, b) } else { (b, a) };
+    while b > u64::MAX as u128 {
+        let shift: u32 = 64 - a.leading_zeros();
+        let mut x: i128 = (a >> shift) as i128;
+        let mut y: i128 = (b >> shift) as i128;
+        let (mut ca, mut cb, mut cc, mut cd): (i128, i128, i128, i128) = (1, 0, 0, 1);
+        while y + cc != 0 && y + cd != 0 {
+            let q: i128 = (x + ca) / (y + cc);
+            if q != (x + cb) / (y + cd) {
+                break;
+            }
+            (ca, cc) = (cc, ca - q * cc);
+            (cb, cd) = (cd, cb - q * cd);
+            (x, y) = (y, x - q * y);
+        }
+        if cb == 0 {
+            (a, b) = (b, a % b);
+        } else {
+            // the new values lie between 0 and a, so arithmetic modulo 2^128 gives them exactly
+            let combine = |s: i128, t: i128| -> u128 {
+                (s as u128)
+                    .wrapping_mul(a)
+                    .wrapping_add((t as u128).wrapping_mul(b))
+            };
+            (a, b) = (combine(ca, cb), combine(cc, cd));
+        }
+    }
+    if b == 0 {
+        return a;
+    }
+    // one division brings a down to 64 bits as well
+    gcd_binary(b as u64, (a % b) as u64) as u128
+}
+
+/// Calculates the greatest common divisor of many pairs of numbers.
+///
+/// The pairs are independent, so the loop has no dependency between iterations and the compiler is free to
+/// interleave them.
+///
+/// # Arguments
+///
+/// * `pairs` - The pairs $(a, b)$.
+///
+/// # Returns
+///
+/// Vector of $\gcd(a, b)$ for each pair, in the same order.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::gcd_batch;
+/// assert_eq!(gcd_batch(&[(12, 18), (7, 0), (35, 64)]), vec![6, 7, 1]);
+/// ```
+pub fn gcd_batch(pairs: &[(u64, u64)]) -> Vec<u64> {
+    pairs.iter().map(|(a, b)| gcd_binary(*a, *b)).collect()
+}
+
+/// Calculates $(a + b) \bmod m$ without overflowing, even when $a + b$ does not fit in a `u128`.
+///
+/// # Arguments
+///
+/// * `a` - The first number.
+/// * `b` - The second number.
+/// * `m` - The modulus.
+///
+/// # Returns
+///
+/// $(a + b) \bmod m$.
+///
+/// # Panics
+///
+/// Panics if `m` is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::addmod;
+/// assert_eq!(addmod(5, 9, 7), 0);
+/// assert_eq!(addmod(u128::MAX, u128::MAX, u128::MAX - 1), 2);
+/// ```
+pub fn addmod(a: u128, b: u128, m: u128) -> u128 {
+    if m == 0 {
+        panic!("the modulus must be positive");
+    }
+    let (a, b): (u128, u128) = (a % m, b % m);
+    // a + b >= m exactly when a >= m - b, and then a + b - m is a - (m - b)
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// Calculates $(a - b) \bmod m$ as a number from $0$ to $m - 1$, even when $b > a$.
+///
+/// # Arguments
+///
+/// * `a` - The number to subtract from.
+/// * `b` - The number to subtract.
+/// * `m` - The modulus.
+///
+/// # Returns
+///
+/// $(a - b) \bmod m$.
+///
+/// # Panics
+///
+/// Panics if `m` is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::submod;
+/// assert_eq!(submod(3, 5, 7), 5);
+/// assert_eq!(submod(0, u128::MAX, u128::MAX - 1), u128::MAX - 2);
+/// ```
+pub fn submod(a: u128, b: u128, m: u128) -> u128 {
+    if m == 0 {
+        panic!("the modulus must be positive");
+    }
+    let (a, b): (u128, u128) = (a % m, b % m);
+    if a >= b {
+        a - b
+    } else {
+        m - (b - a)
+    }
+}
+
+/// Calculates $ab \bmod m$ without overflowing, even when $ab$ does not fit in a `u128`.
+///
+/// When both numbers fit in a `u64` (or do after reducing them modulo a `u64` modulus) the product fits in a
+/// `u128` and is reduced directly. Otherwise the product is built by Russian peasant multiplication, adding the
+/// doublings of $a$ with [`addmod`] for each bit of $b$, which takes $O(\log b)$ steps.
+///
+/// # Arguments
+///
+/// * `a` - The first number.
+/// * `b` - The second number.
+/// * `m` - The modulus.
+///
+/// # Returns
+///
+/// $ab \bmod m$.
+///
+/// # Panics
+///
+/// Panics if `m` is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::mulmod;
+/// assert_eq!(mulmod(6, 7, 10), 2);
+/// assert_eq!(mulmod(u128::MAX, u128::MAX, u128::MAX - 1), 1);
+/// assert_eq!(mulmod(1 << 127, 4, u128::MAX), 2);
+/// ```
+pub fn mulmod(a: u128, b: u128, m: u128) -> u128 {
+    if m == 0 {
+        panic!("the modulus must be positive");
+    }
+    const LIMB: u128 = u64::MAX as u128;
+    if a <= LIMB && b <= LIMB {
+        return a * b % m;
+    }
+    let (mut a, mut b): (u128, u128) = (a % m, b % m);
+    if m <= LIMB {
+        return a * b % m;
+    }
+    let mut product: u128 = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            product = addmod(product, a, m);
+        }
+        a = addmod(a, a, m);
+        b >>= 1;
+    }
+    product
+}
+
+/// Calculates `base` raised to the power `exp` modulo a `u128` modulus by repeated squaring.
+///
+/// This is the shared core of [`mod_pow`], the `u128` Miller–Rabin rounds and the computations modulo $p^2$.
+///
+/// # Arguments
+///
+/// * `base` - The base.
+/// * `exp` - The exponent.
+/// * `m` - The modulus (positive).
+///
+/// # Returns
+///
+/// $\text{base}^{\text{exp}} \bmod m$.
+pub(crate) fn mod_pow_u128(base: u128, exp: u128, m: u128) -> u128 {
+    let mut result: u128 = 1 % m;
+    let mut b: u128 = base % m;
+    let mut e: u128 = exp;
+    while e > 0 {
+        if e & 1 == 1 {
+            result = mulmod(result, b, m);
+        }
+        b = mulmod(b, b, m);
+        e >>= 1;
+    }
+    result
+}
+
+/// Calculates `base` raised to the power `exp` modulo `m` by repeated squaring.
+///
+/// # Arguments
+///
+/// * `base` - The base.
+/// * `exp` - The exponent.
+/// * `m` - The modulus.
+///
+/// # Returns
+///
+/// $\text{base}^{\text{exp}} \bmod m$.
+///
+/// # Panics
+///
+/// Panics if `m` is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::mod_pow;
+/// assert_eq!(mod_pow(2, 10, 1000), 24);
+/// assert_eq!(mod_pow(3, 0, 7), 1);
+/// assert_eq!(mod_pow(5, 3, 1), 0);
+/// ```
+pub fn mod_pow(base: u64, exp: u64, m: u64) -> u64 {
+    if m == 0 {
+        panic!("the modulus must be positive");
+    }
+    mod_pow_u128(base as u128, exp as u128, m as u128) as u64
+}
+
+/// Calculates the multiplicative order of `a` modulo `n`, the smallest $k \geq 1$ with $a^k \equiv 1 \pmod{n}$.
+///
+/// The order divides Euler's totient $\varphi(n)$, so starting from $\varphi(n)$ every prime factor is divided
+/// out for as long as the power stays congruent to $1$.
+///
+/// # Arguments
+///
+/// * `a` - The number to find the order of.
+/// * `n` - The modulus.
+///
+/// # Returns
+///
+/// `Some(k)` with the order of `a`, or `None` if `a` is not coprime to `n` (or `n` is `0`).
+/// Every number has order `1` modulo `1`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::multiplicative_order;
+/// assert_eq!(multiplicative_order(10, 7), Some(6));
+/// assert_eq!(multiplicative_order(2, 7), Some(3));
+/// assert_eq!(multiplicative_order(4, 6), None);
+/// ```
+pub fn multiplicative_order(a: u64, n: u64) -> Option<u64> {
+    if n == 0 || gcd(a % n, n) != 1 {
+        return None;
+    }
+    let phi: u64 = euler_totient(n);
+    let mut order: u64 = phi;
+    for (p, _) in factorize(phi) {
+        while order.is_multiple_of(p) && mod_pow(a, order / p, n) == 1 {
+            order /= p;
+        }
+    }
+    Some(order)
+}
+
+/// Calculates Euler's totient function $\varphi(n)$, the number of $1 \leq k \leq n$ coprime to $n$.
+///
+/// If $n = \prod p_i^{e_i}$ then $\varphi(n) = n \prod \left(1 - \frac{1}{p_i}\right)$.
+///
+/// [A000010](https://oeis.org/A000010)
+///
+/// # Arguments
+///
+/// * `n` - The number to calculate the totient of.
+///
+/// # Returns
+///
+/// $\varphi(n)$ (`0` for `0`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::euler_totient;
+/// assert_eq!(euler_totient(36), 12);
+/// assert_eq!(euler_totient(13), 12);
+/// assert_eq!(euler_totient(1), 1);
+/// ```
+pub fn euler_totient(n: u64) -> u64 {
+    let mut phi: u64 = n;
+    for (p, _) in factorize(n) {
+        phi = phi / p * (p - 1);
+    }
+    phi
+}
+
+/// Calculates Jordan's totient function $J_k(n)$, the number of $k$-tuples in $\{1, \ldots, n\}^k$ whose
+/// greatest common divisor with $n$ is $1$.
+///
+/// If $n = \prod p_i^{e_i}$ then $J_k(n) = n^k \prod \left(1 - \frac{1}{p_i^k}\right)
+/// = \prod p_i^{k(e_i - 1)} (p_i^k - 1)$, so $J_1 = \varphi$.
+///
+/// [A007434](https://oeis.org/A007434) ($J_2$)
+///
+/// # Arguments
+///
+/// * `n` - The number to calculate the totient of.
+/// * `k` - The length of the tuples.
+///
+/// # Returns
+///
+/// $J_k(n)$ (`0` for `0`, and $J_0(n)$ is `1` for `1` and `0` otherwise).
+///
+/// # Panics
+///
+/// Panics if $J_k(n)$ does not fit in a `u128`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::jordan_totient;
+/// assert_eq!(jordan_totient(6, 2), 24);
+/// assert_eq!(jordan_totient(36, 1), 12);
+/// assert_eq!(jordan_totient(2, 127), 2_u128.pow(127) - 1);
+/// ```
+pub fn jordan_totient(n: u64, k: u32) -> u128 {
+    if n == 0 {
+        return 0;
+    }
+    let mut j: u128 = 1;
+    for (p, e) in factorize(n) {
+        let part: Option<u128> = (p as u128)
+            .checked_pow(k)
+            .and_then(|pk| pk.checked_pow(e - 1)?.checked_mul(pk - 1));
+        j = match part.and_then(|part| j.checked_mul(part)) {
+            Some(j) => j,
+            None => panic!("the Jordan totient overflowed"),
+        };
+    }
+    j
+}
+
+/// Calculates the Dedekind psi function $\psi(n)$, the index of the congruence subgroup $\Gamma_0(n)$ in the
+/// modular group.
+///
+/// If $n = \prod p_i^{e_i}$ then $\psi(n) = n \prod \left(1 + \frac{1}{p_i}\right)
+/// = \prod p_i^{e_i - 1} (p_i + 1)$.
+///
+/// [A001615](https://oeis.org/A001615)
+///
+/// # Arguments
+///
+/// * `n` - The number to calculate $\psi(n)$ of.
+///
+/// # Returns
+///
+/// $\psi(n)$ (`0` for `0`).
+///
+/// # Panics
+///
+/// Panics if $\psi(n)$ does not fit in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::dedekind_psi;
+/// assert_eq!(dedekind_psi(10), 18);
+/// assert_eq!(dedekind_psi(8), 12);
+/// assert_eq!(dedekind_psi(1), 1);
+/// ```
+pub fn dedekind_psi(n: u64) -> u64 {
+    if n == 0 {
+        return 0;
+    }
+    let mut psi: u64 = 1;
+    for (p, e) in factorize(n) {
+        let part: Option<u64> = p.pow(e - 1).checked_mul(p + 1);
+        psi = match part.and_then(|part| psi.checked_mul(part)) {
+            Some(psi) => psi,
+            None => panic!("the Dedekind psi function overflowed"),
+        };
+    }
+    psi
+}
+
+/// Calculates the Carmichael function $\lambda(n)$, the smallest $m \geq 1$ with $a^m \equiv 1 \pmod{n}$ for every
+/// $a$ coprime to $n$.
+///
+/// $\lambda$ is the least common multiple of $\lambda(p^e)$ over the prime powers dividing $n$, where
+/// $\lambda(p^e) = \varphi(p^e)$ except that $\lambda(2^e) = 2^{e - 2}$ for $e \geq 3$.
+///
+/// [A002322](https://oeis.org/A002322)
+///
+/// # Arguments
+///
+/// * `n` - The number to calculate $\lambda(n)$ of.
+///
+/// # Returns
+///
+/// $\lambda(n)$ (`0` for `0`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::carmichael_lambda;
+/// assert_eq!(carmichael_lambda(8), 2);
+/// assert_eq!(carmichael_lambda(15), 4);
+/// assert_eq!(carmichael_lambda(561), 80);
+/// ```
+pub fn carmichael_lambda(n: u64) -> u64 {
+    if n == 0 {
+        return 0;
+    }
+    let mut lambda: u64 = 1;
+    for (p, e) in factorize(n) {
+        let mut part: u64 = (p - 1) * p.pow(e - 1);
+        if p == 2 && e >= 3 {
+            part /= 2;
+        }
+        lambda = lambda / gcd(lambda, part) * part;
+    }
+    lambda
+}
+
+/// Calculates the inverse of `a` modulo `m` with the extended Euclidean algorithm.
+///
+/// # Arguments
+///
+/// * `a` - The number to invert.
+/// * `m` - The modulus.
+///
+/// # Returns
+///
+/// `Some(x)` with $0 \leq x < m$ and $ax \equiv 1 \pmod{m}$, or `None` if `a` is not coprime to `m` (or `m`
+/// is `0`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::mod_inverse;
+/// assert_eq!(mod_inverse(3, 11), Some(4)); // 3 * 4 = 12
+/// assert_eq!(mod_inverse(6, 9), None);
+/// ```
+pub fn mod_inverse(a: u64, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+    let (mut old_r, mut r): (i128, i128) = ((a % m) as i128, m as i128);
+    let (mut old_s, mut s): (i128, i128) = (1, 0);
+    while r != 0 {
+        let q: i128 = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+    }
+    if old_r != 1 {
+        return None;
+    }
+    Some(old_s.rem_euclid(m as i128) as u64)
+}
+
+/// Calculates Euler's totient of every number below a limit with a sieve.
+///
+/// The totients follow from $\varphi(p^k) = (p - 1) p^{k - 1}$ through [`multiplicative_sieve_with`].
+///
+/// # Arguments
+///
+/// * `limit` - The (exclusive) upper bound of the numbers.
+///
+/// # Returns
+///
+/// Vector where index `n` holds $\varphi(n)$ (and index `0` holds `0`).
+pub(crate) fn totient_sieve(limit: usize) -> Vec<u64> {
+    if limit == 0 {
+        return vec![];
+    }
+    multiplicative_sieve_with(limit - 1, |p, k| (p - 1) * p.pow(k - 1))
+}
+
+/// Finds every number up to a limit whose totient is `m`.
+///
+/// Since $\varphi(n) \geq \sqrt{n / 2}$, every solution is at most $2m^2$, so the totient sieve only runs up to
+/// the smaller of `limit` and $2m^2$.
+///
+/// # Arguments
+///
+/// * `m` - The totient value to invert.
+/// * `limit` - The (inclusive) upper bound of the search.
+///
+/// # Returns
+///
+/// Vector of every $n \leq \text{limit}$ with $\varphi(n) = m$, in increasing order.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::totient_inverse;
+/// assert_eq!(totient_inverse(8, 100), vec![15, 16, 20, 24, 30]);
+/// assert_eq!(totient_inverse(14, 1000), vec![]);
+/// ```
+pub fn totient_inverse(m: u64, limit: u64) -> Vec<u64> {
+    if m == 0 {
+        return vec![];
+    }
+    let bound: u64 = limit.min(2_u64.saturating_mul(m).saturating_mul(m));
+    let totients: Vec<u64> = totient_sieve(bound as usize + 1);
+    let mut preimages: Vec<u64> = vec![];
+    for (n, phi) in totients.iter().enumerate() {
+        if *phi == m {
+            preimages.push(n as u64);
+        }
+    }
+    preimages
+}
+
+/// Checks if a number is a totient, a value $\varphi(n)$ for some $n$.
+///
+/// Odd numbers above $1$ never are, since $\varphi(n)$ is even for $n > 2$. Otherwise, writing
+/// $\varphi(n) = \prod p^{k - 1}(p - 1)$, only the primes $p$ with $(p - 1) \mid m$ can divide a preimage, and
+/// a search over those primes (each used at most once, in increasing order) decides whether their
+/// contributions can multiply to `m`.
+///
+/// # Arguments
+///
+/// * `m` - The number to check.
+///
+/// # Returns
+///
+/// `true` if some $n$ has $\varphi(n) = m$.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::has_totient_preimage;
+/// assert!(has_totient_preimage(8));
+/// assert!(!has_totient_preimage(14)); // the smallest even nontotient
+/// assert!(!has_totient_preimage(9));
+/// ```
+pub fn has_totient_preimage(m: u64) -> bool {
+    if m == 1 {
+        return true;
+    }
+    if m == 0 || m % 2 == 1 {
+        return false;
+    }
+    let mut divisors: Vec<u64> = vec![1];
+    for (p, e) in factorize(m) {
+        let mut powers: Vec<u64> = vec![];
+        for d in &divisors {
+            let mut power: u64 = *d;
+            for _ in 0..e {
+                power *= p;
+                powers.push(power);
+            }
+        }
+        divisors.extend(powers);
+    }
+    let mut primes: Vec<u64> = divisors
+        .into_iter()
+        .filter(|d| is_probable_prime(&BigInt::from(d + 1)))
+        .map(|d| d + 1)
+        .collect();
+    primes.sort_unstable();
+    totient_preimage_search(m, &primes, 0)
+}
+
+/// Searches for a product of prime power totients $p^{k - 1}(p - 1)$ equal to `m` (see
+/// [`has_totient_preimage`]).
+///
+/// # Arguments
+///
+/// * `m` - The part of the totient still to be produced.
+/// * `primes` - The candidate primes, in increasing order.
+/// * `start` - The index of the first prime that may still be used.
+///
+/// # Returns
+///
+/// `true` if the remaining primes can produce `m`.
+fn totient_preimage_search(m: u64, primes: &[u64], start: usize) -> bool {
+    if m == 1 {
+        return true;
+    }
+    for (i, p) in primes.iter().enumerate().skip(start) {
+        if !m.is_multiple_of(p - 1) {
+            continue;
+        }
+        let mut rest: u64 = m / (p - 1);
+        loop {
+            if totient_preimage_search(rest, primes, i + 1) {
+                return true;
+            }
+            if !rest.is_multiple_of(*p) {
+                break;
+            }
+            rest /= p;
+        }
+    }
+    false
+}
+
+/// Calculates the Jacobi symbol $\left(\frac{a}{n}\right)$ for an odd positive $n$.
+///
+/// Uses quadratic reciprocity: factors of $2$ are pulled out of $a$ using $\left(\frac{2}{n}\right) = -1$
+/// exactly when $n \equiv 3, 5 \pmod 8$, and then $a$ and $n$ are swapped, flipping the sign when both are
+/// $3 \bmod 4$. When $n$ is prime this is the Legendre symbol.
+///
+/// # Arguments
+///
+/// * `a` - The top argument.
+/// * `n` - The bottom argument, which must be odd.
+///
+/// # Returns
+///
+/// $1$, $-1$, or $0$ (exactly when $\gcd(a, n) > 1$).
+///
+/// # Panics
+///
+/// Panics if `n` is even.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::jacobi_symbol;
+/// assert_eq!(jacobi_symbol(2, 7), 1); // 3^2 = 2 (mod 7)
+/// assert_eq!(jacobi_symbol(3, 7), -1);
+/// assert_eq!(jacobi_symbol(2, 15), 1); // but 2 is not a square modulo 15
+/// assert_eq!(jacobi_symbol(6, 9), 0);
+/// ```
+pub fn jacobi_symbol(a: u64, n: u64) -> i8 {
+    if n.is_multiple_of(2) {
+        panic!("the bottom argument of the Jacobi symbol must be odd");
+    }
+    let mut a: u64 = a % n;
+    let mut n: u64 = n;
+    let mut result: i8 = 1;
+    while a != 0 {
+        while a.is_multiple_of(2) {
+            a /= 2;
+            if n % 8 == 3 || n % 8 == 5 {
+                result = -result;
+            }
+        }
+        std::mem::swap(&mut a, &mut n);
+        if a % 4 == 3 && n % 4 == 3 {
+            result = -result;
+        }
+        a %= n;
+    }
+    if n == 1 {
+        result
+    } else {
+        0
+    }
+}
+
+/// Calculates the Kronecker symbol $\left(\frac{a}{n}\right)$, which extends the [`jacobi_symbol`] to every integer $n$.
+///
+/// Writing $n = u \cdot 2^e \cdot m$ with $u = \pm 1$ and $m$ odd and positive, the symbol is
+/// $\left(\frac{a}{u}\right) \left(\frac{a}{2}\right)^e \left(\frac{a}{m}\right)$, where
+/// * $\left(\frac{a}{-1}\right)$ is $-1$ for negative $a$ and $1$ otherwise,
+/// * $\left(\frac{a}{2}\right)$ is $0$ for even $a$, $1$ for $a \equiv \pm 1 \pmod 8$ and $-1$ for $a \equiv \pm 3 \pmod 8$,
+/// * and $\left(\frac{a}{0}\right)$ is $1$ for $a = \pm 1$ and $0$ otherwise.
+///
+/// # Arguments
+///
+/// * `a` - The top argument.
+/// * `n` - The bottom argument.
+///
+/// # Returns
+///
+/// $1$, $-1$, or $0$ (exactly when $\gcd(a, n) \neq 1$).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::kronecker_symbol;
+/// assert_eq!(kronecker_symbol(-7, 15), kronecker_symbol(8, 15));
+/// assert_eq!(kronecker_symbol(3, 8), -1);
+/// assert_eq!(kronecker_symbol(-1, -5), -1);
+/// assert_eq!(kronecker_symbol(1, 0), 1);
+/// ```
+pub fn kronecker_symbol(a: i64, n: i64) -> i8 {
+    let sign: i8 = if n < 0 && a < 0 { -1 } else { 1 };
+    sign * kronecker_symbol_unsigned(a, n.unsigned_abs())
+}
+
+/// Calculates the Kronecker symbol $\left(\frac{a}{n}\right)$ for a signed top and a nonnegative bottom argument.
+///
+/// This is [`kronecker_symbol`] without the $\left(\frac{a}{-1}\right)$ factor, and it accepts every `u64` bottom
+/// argument.
+///
+/// # Arguments
+///
+/// * `a` - The top argument.
+/// * `n` - The bottom argument.
+///
+/// # Returns
+///
+/// $1$, $-1$, or $0$.
+pub(crate) fn kronecker_symbol_unsigned(a: i64, n: u64) -> i8 {
+    if n == 0 {
+        return if a.unsigned_abs() == 1 { 1 } else { 0 };
+    }
+    let twos: u32 = n.trailing_zeros();
+    let odd: u64 = n >> twos;
+    let mut result: i8 = 1;
+    if twos > 0 {
+        let two: i8 = match a.rem_euclid(8) {
+            1 | 7 => 1,
+            3 | 5 => -1,
+            _ => 0,
+        };
+        if two == 0 {
+            return 0;
+        }
+        if twos % 2 == 1 {
+            result = two;
+        }
+    }
+    result * jacobi_symbol((a as i128).rem_euclid(odd as i128) as u64, odd)
+}
+
+/// Calculates the Legendre symbol $\left(\frac{a}{p}\right)$.
+///
+/// For an odd prime this is the [`jacobi_symbol`], which is $1$ exactly when $a$ is a nonzero square modulo $p$.
+/// Every odd number is a square modulo $2$, matching Euler's criterion $a^{(p - 1)/2} \bmod p$.
+///
+/// # Arguments
+///
+/// * `a` - The top argument.
+/// * `p` - The prime.
+///
+/// # Returns
+///
+/// $1$ if `a` is a nonzero square modulo `p`, $-1$ if it is not a square, and $0$ if $p \mid a$.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::legendre_symbol;
+/// use bens_number_theory::primes::Prime;
+///
+/// let p: Prime = Prime::new(7).unwrap();
+/// assert_eq!(legendre_symbol(2, p), 1);
+/// assert_eq!(legendre_symbol(3, p), -1);
+/// assert_eq!(legendre_symbol(14, p), 0);
+/// ```
+pub fn legendre_symbol(a: u64, p: Prime) -> i8 {
+    if *p == 2 {
+        return (a % 2) as i8;
+    }
+    jacobi_symbol(a, *p)
+}
+
+/// Evaluates Euler's criterion $a^{(p - 1)/2} \bmod p$, which for a prime $p$ is the Legendre symbol.
+///
+/// # Arguments
+///
+/// * `a` - The number to test.
+/// * `p` - The prime modulus.
+///
+/// # Returns
+///
+/// $1$ if `a` is a nonzero square modulo `p`, $-1$ if it is not a square, and $0$ if $p \mid a$. Modulo $2$ every
+/// odd number is a square.
+///
+/// # Panics
+///
+/// Panics if `p` is `0` or if the power is not $0$, $1$ or $-1$, which proves that `p` is not prime.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::euler_criterion;
+/// assert_eq!(euler_criterion(2, 7), 1);
+/// assert_eq!(euler_criterion(3, 41), -1);
+/// assert_eq!(euler_criterion(82, 41), 0);
+/// ```
+pub fn euler_criterion(a: u64, p: u64) -> i8 {
+    if p == 0 {
+        panic!("p must be prime");
+    }
+    if p == 2 {
+        return (a % 2) as i8;
+    }
+    let power: u64 = mod_pow(a, (p - 1) / 2, p);
+    if power == p - 1 {
+        -1
+    } else if power <= 1 {
+        power as i8
+    } else {
+        panic!("p must be prime");
+    }
+}
+
+/// Finds the smallest quadratic non-residue modulo an odd prime.
+///
+/// The Legendre symbol is multiplicative, so the smallest non-residue is itself prime and only primes are
+/// tested with [`euler_criterion`]. It is almost always tiny (at most $3 \ln(p)^2$ under GRH).
+///
+/// # Arguments
+///
+/// * `p` - The odd prime.
+///
+/// # Returns
+///
+/// The smallest $q$ whose Legendre symbol modulo `p` is $-1$.
+///
+/// # Panics
+///
+/// Panics if `p` is below $3$, since every unit modulo $2$ is a square.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::smallest_quadratic_nonresidue;
+/// assert_eq!(smallest_quadratic_nonresidue(7), 3);
+/// assert_eq!(smallest_quadratic_nonresidue(41), 3);
+/// assert_eq!(smallest_quadratic_nonresidue(71), 7);
+/// ```
+pub fn smallest_quadratic_nonresidue(p: u64) -> u64 {
+    if p < 3 {
+        panic!("p must be an odd prime");
+    }
+    let mut q: u64 = 2;
+    loop {
+        let mut d: u64 = 2;
+        while d * d <= q && !q.is_multiple_of(d) {
+            d += 1;
+        }
+        if d * d > q && euler_criterion(q, p) == -1 {
+            return q;
+        }
+        q += 1;
+    }
+}
+
+/// Solves a system of congruences $x \equiv a_i \pmod{m_i}$ with the Chinese remainder theorem.
+///
+/// The congruences are merged one at a time, and the moduli do not have to be coprime: two congruences
+/// are compatible exactly when $a_1 \equiv a_2 \pmod{\gcd(m_1, m_2)}$, and then they merge into one congruence
+/// modulo $\operatorname{lcm}(m_1, m_2)$.
+///
+/// # Arguments
+///
+/// * `congruences` - The `(a, m)` pairs, with every modulus positive.
+///
+/// # Returns
+///
+/// `Some((x, l))` where the solutions are exactly $x + kl$ for the least common multiple $l$ of the moduli,
+/// with $0 \leq x < l$ (and `Some((0, 1))` for no congruences), or `None` if the congruences contradict each
+/// other or $l$ does not fit in a `u64`.
+///
+/// # Panics
+///
+/// Panics if a modulus is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::chinese_remainder;
+/// assert_eq!(chinese_remainder(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// assert_eq!(chinese_remainder(&[(1, 4), (3, 6)]), Some((9, 12)));
+/// assert_eq!(chinese_remainder(&[(1, 4), (2, 6)]), None);
+/// ```
+pub fn chinese_remainder(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    let mut x: u64 = 0;
+    let mut l: u64 = 1;
+    for (a, m) in congruences {
+        if *m == 0 {
+            panic!("the moduli must be positive");
+        }
+        let a: u64 = a % m;
+        let g: u64 = gcd(l, *m);
+        let difference: i128 = a as i128 - x as i128;
+        if difference % g as i128 != 0 {
+            return None;
+        }
+        let step: u64 = m / g;
+        let new_l: u64 = l.checked_mul(step)?;
+        // x + l * t = a (mod m) means (l / g) * t = (a - x) / g (mod m / g)
+        let t: u128 = match mod_inverse((l / g) % step, step) {
+            Some(inverse) => mulmod(
+                (difference / g as i128).rem_euclid(step as i128) as u128,
+                inverse as u128,
+                step as u128,
+            ),
+            None => 0,
+        };
+        x = addmod(
+            x as u128,
+            mulmod(l as u128, t, new_l as u128),
+            new_l as u128,
+        ) as u64;
+        l = new_l;
+    }
+    Some((x, l))
+}
+
+/// Checks if a set of congruences is a covering system, meaning every integer satisfies at least one of them.
+///
+/// This is [`find_uncovered`] finding no witness, so the moduli do not have to be coprime and the least common
+/// multiple of the moduli is never walked through one residue at a time.
+///
+/// # Arguments
+///
+/// * `congruences` - The `(a, m)` pairs standing for $x \equiv a \pmod{m}$, with every modulus positive.
+///
+/// # Returns
+///
+/// `true` if every integer is covered (`false` for no congruences).
+///
+/// # Panics
+///
+/// Panics if a modulus is `0`, or if the search has to split a residue class modulo more than $2^{64}$ (which can
+/// only happen when the least common multiple of the moduli does not fit in a `u64`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::is_covering_system;
+/// assert!(is_covering_system(&[(0, 2), (0, 3), (1, 4), (5, 6), (7, 12)]));
+/// assert!(!is_covering_system(&[(0, 2), (0, 3), (1, 4), (5, 6)]));
+/// ```
+pub fn is_covering_system(congruences: &[(u64, u64)]) -> bool {
+    find_uncovered(congruences).is_none()
+}
+
+/// Finds the smallest non-negative integer that satisfies none of a set of congruences.
+///
+/// The search starts from the single class $0 \bmod 1$ and refines it. A class $r \bmod M$ meets
+/// $a \bmod m$ exactly when $r \equiv a \pmod{\gcd(M, m)}$ (the condition for the two to merge under the
+/// Chinese remainder theorem), and lies inside it when in addition $m \mid M$. A class lying inside some
+/// congruence is covered, and a class meeting none of them is uncovered, with $r$ as its smallest member.
+/// Otherwise the class is split modulo $pM$ for a prime $p$ dividing $m/\gcd(M, m)$ of the congruence with the
+/// smallest modulus that meets it, and only the congruences meeting the class are passed down. Classes whose
+/// smallest member is no better than a witness already found are skipped, so the witness is the smallest one.
+///
+/// # Arguments
+///
+/// * `congruences` - The `(a, m)` pairs standing for $x \equiv a \pmod{m}$, with every modulus positive.
+///
+/// # Returns
+///
+/// `Some` smallest uncovered integer $x \geq 0$ (which is below the least common multiple of the moduli), or
+/// `None` if the congruences form a covering system.
+///
+/// # Panics
+///
+/// Panics if a modulus is `0`, or if the search has to split a residue class modulo more than $2^{64}$ (which can
+/// only happen when the least common multiple of the moduli does not fit in a `u64`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::find_uncovered;
+/// assert_eq!(find_uncovered(&[(0, 2), (0, 3), (1, 4), (5, 6), (7, 12)]), None);
+/// assert_eq!(find_uncovered(&[(0, 2), (0, 3), (1, 4), (5, 6)]), Some(7));
+/// assert_eq!(find_uncovered(&[(1, 2), (2, 3)]), Some(0));
+/// ```
+pub fn find_uncovered(congruences: &[(u64, u64)]) -> Option<u64> {
+    let mut reduced: Vec<(u64, u64)> = vec![];
+    for (a, m) in congruences {
+        if *m == 0 {
+            panic!("the moduli must be positive");
+        }
+        reduced.push((a % m, *m));
+    }
+    reduced.sort_by_key(|(a, m)| (*m, *a));
+    let mut best: Option<u64> = None;
+    search_uncovered(0, 1, &reduced, &mut best);
+    best
+}
+
+/// Searches one residue class for the smallest integer that none of the congruences cover.
+///
+/// # Arguments
+///
+/// * `r` - The smallest non-negative member of the class.
+/// * `modulus` - The modulus $M$ of the class.
+/// * `congruences` - The reduced `(a, m)` pairs that may meet the class, sorted by modulus.
+/// * `best` - The smallest uncovered integer found so far, updated when a smaller one is found.
+fn search_uncovered(r: u64, modulus: u64, congruences: &[(u64, u64)], best: &mut Option<u64>) {
+    if best.is_some_and(|found| r >= found) {
+        return;
+    }
+    let mut meeting: Vec<(u64, u64)> = vec![];
+    for (a, m) in congruences {
+        let g: u64 = gcd(modulus, *m);
+        if r % g == a % g {
+            if modulus.is_multiple_of(*m) {
+                return;
+            }
+            meeting.push((*a, *m));
+        }
+    }
+    let m: u64 = match meeting.first() {
+        Some((_, m)) => *m,
+        None => {
+            *best = Some(r);
+            return;
+        }
+    };
+    let p: u64 = least_prime_factor(m / gcd(modulus, m)).unwrap();
+    let refined: u64 = match modulus.checked_mul(p) {
+        Some(refined) => refined,
+        None => panic!("the residue classes do not fit in a u64"),
+    };
+    let mut k: u64 = 0;
+    while k < p {
+        search_uncovered(r + k * modulus, refined, &meeting, best);
+        k += 1;
+    }
+}
+
+/// Evaluates a polynomial with integer coefficients modulo `m` by Horner's method.
+///
+/// # Arguments
+///
+/// * `f` - The coefficients, with `f[i]` the coefficient of $x^i$.
+/// * `x` - The point to evaluate at.
+/// * `m` - The modulus (positive).
+///
+/// # Returns
+///
+/// $f(x) \bmod m$.
+fn poly_eval_mod(f: &[i64], x: u64, m: u64) -> u64 {
+    let m: u128 = m as u128;
+    let x: u128 = x as u128 % m;
+    let mut value: u128 = 0;
+    for coefficient in f.iter().rev() {
+        value = addmod(
+            mulmod(value, x, m),
+            (*coefficient as i128).rem_euclid(m as i128) as u128,
+            m,
+        );
+    }
+    value as u64
+}
+
+/// Calculates the coefficients of the derivative of a polynomial.
+///
+/// # Arguments
+///
+/// * `f` - The coefficients, with `f[i]` the coefficient of $x^i$.
+///
+/// # Returns
+///
+/// The coefficients of $f'$ (reduced modulo nothing, so they are computed in `i128` and can be large).
+fn poly_derivative(f: &[i64]) -> Vec<i128> {
+    f.iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, coefficient)| i as i128 * *coefficient as i128)
+        .collect()
+}
+
+/// Evaluates the derivative of a polynomial modulo `m` (see [`poly_derivative`]).
+///
+/// # Arguments
+///
+/// * `f` - The coefficients, with `f[i]` the coefficient of $x^i$.
+/// * `x` - The point to evaluate at.
+/// * `m` - The modulus (positive).
+///
+/// # Returns
+///
+/// $f'(x) \bmod m$.
+fn poly_derivative_eval_mod(f: &[i64], x: u64, m: u64) -> u64 {
+    let m: u128 = m as u128;
+    let x: u128 = x as u128 % m;
+    let mut value: u128 = 0;
+    for coefficient in poly_derivative(f).iter().rev() {
+        value = addmod(
+            mulmod(value, x, m),
+            coefficient.rem_euclid(m as i128) as u128,
+            m,
+        );
+    }
+    value as u64
+}
+
+/// Lifts a simple root of a polynomial modulo a prime $p$ to a root modulo $p^k$ with Hensel's lemma.
+///
+/// If $f(r) \equiv 0 \pmod{p^j}$ and $f'(r) \not\equiv 0 \pmod p$, then $r - f(r)/f'(r)$ is the unique lift to a root
+/// modulo $p^{j + 1}$ (Newton's method, $p$-adically).
+///
+/// # Arguments
+///
+/// * `f` - The coefficients, with `f[i]` the coefficient of $x^i$.
+/// * `root` - A root of `f` modulo `p`.
+/// * `p` - The prime.
+/// * `k` - The exponent to lift to (at least `1`).
+///
+/// # Returns
+///
+/// `Some(r)` with $f(r) \equiv 0 \pmod{p^k}$, $r \equiv \text{root} \pmod p$ and $0 \leq r < p^k$, or `None` if
+/// `root` is not a simple root modulo `p` (it is not a root, or $f'(\text{root}) \equiv 0$), `k` is `0`, or $p^k$
+/// does not fit in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::hensel_lift;
+/// // x^2 - 2 has the root 3 modulo 7, which lifts to 108 modulo 7^3 (108^2 = 11664 = 34 * 343 + 2)
+/// assert_eq!(hensel_lift(&[-2, 0, 1], 3, 7, 3), Some(108));
+/// // 1 is a double root of (x - 1)^2 modulo 3
+/// assert_eq!(hensel_lift(&[1, -2, 1], 1, 3, 2), None);
+/// ```
+pub fn hensel_lift(f: &[i64], root: u64, p: u64, k: u32) -> Option<u64> {
+    if k == 0 || p < 2 {
+        return None;
+    }
+    let modulus: u64 = p.checked_pow(k)?;
+    let root: u64 = root % p;
+    if poly_eval_mod(f, root, p) != 0 || poly_derivative_eval_mod(f, root, p) == 0 {
+        return None;
+    }
+    let mut r: u64 = root;
+    let mut current: u64 = p;
+    while current < modulus {
+        current *= p;
+        r = hensel_step(r, current, &|x, m| poly_eval_mod(f, x, m), &|x, m| {
+            poly_derivative_eval_mod(f, x, m)
+        });
+    }
+    Some(r)
+}
+
+/// Lifts a simple root to the next power of $p$ with one Newton step, $r - f(r)/f'(r)$.
+///
+/// # Arguments
+///
+/// * `r` - A root modulo $p^j$ with $f'(r) \not\equiv 0 \pmod p$.
+/// * `next` - The modulus $p^{j + 1}$ to lift to.
+/// * `eval` - Evaluates $f(x) \bmod m$ as `eval(x, m)`.
+/// * `derivative` - Evaluates $f'(x) \bmod m$ as `derivative(x, m)`.
+///
+/// # Returns
+///
+/// The unique root modulo `next` congruent to `r` modulo $p^j$.
+fn hensel_step(
+    r: u64,
+    next: u64,
+    eval: &dyn Fn(u64, u64) -> u64,
+    derivative: &dyn Fn(u64, u64) -> u64,
+) -> u64 {
+    let value: u64 = eval(r, next);
+    let inverse: u64 =
+        mod_inverse(derivative(r, next), next).expect("f'(r) is a unit modulo every power of p");
+    let correction: u128 = mulmod(value as u128, inverse as u128, next as u128);
+    submod(r as u128, correction, next as u128) as u64
+}
+
+/// Lifts every root of a function modulo $p$ to all of its roots modulo $p^k$.
+///
+/// A simple root lifts uniquely with [`hensel_step`], while a singular root ($f'(r) \equiv 0 \pmod p$) lifts to
+/// either none or all $p$ of $r + tp^j$, so those are checked one by one.
+///
+/// # Arguments
+///
+/// * `roots` - The roots modulo `p`.
+/// * `p` - The prime.
+/// * `modulus` - The power $p^k$ to lift to.
+/// * `eval` - Evaluates $f(x) \bmod m$ as `eval(x, m)`.
+/// * `derivative` - Evaluates $f'(x) \bmod m$ as `derivative(x, m)`.
+///
+/// # Returns
+///
+/// Vector of every root modulo `modulus`, in increasing order.
+fn lift_roots(
+    roots: Vec<u64>,
+    p: u64,
+    modulus: u64,
+    eval: &dyn Fn(u64, u64) -> u64,
+    derivative: &dyn Fn(u64, u64) -> u64,
+) -> Vec<u64> {
+    let mut roots: Vec<u64> = roots;
+    let mut current: u64 = p;
+    while current < modulus {
+        let next: u64 = current * p;
+        let mut lifted: Vec<u64> = vec![];
+        for r in roots {
+            if derivative(r, p) != 0 {
+                lifted.push(hensel_step(r, next, eval, derivative));
+            } else {
+                let mut t: u64 = 0;
+                while t < p {
+                    let candidate: u64 = r + t * current;
+                    if eval(candidate, next) == 0 {
+                        lifted.push(candidate);
+                    }
+                    t += 1;
+                }
+            }
+        }
+        roots = lifted;
+        current = next;
+    }
+    roots.sort_unstable();
+    roots
+}
+
+/// Finds every root of a polynomial modulo a prime power $p^k$.
+///
+/// The roots modulo $p$ are found by trying every residue, and then lifted one power of $p$ at a time. A
+/// simple root lifts uniquely as in [`hensel_lift`], while a singular root ($f'(r) \equiv 0 \pmod p$) lifts
+/// to either none or all $p$ of $r + tp^j$, so those are checked one by one.
+///
+/// # Arguments
+///
+/// * `f` - The coefficients, with `f[i]` the coefficient of $x^i$.
+/// * `p` - The prime.
+/// * `k` - The exponent.
+///
+/// # Returns
+///
+/// Vector of every $r$ with $0 \leq r < p^k$ and $f(r) \equiv 0 \pmod{p^k}$, in increasing order.
+///
+/// # Panics
+///
+/// Panics if `p` is less than $2$ or $p^k$ does not fit in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::roots_mod_prime_power;
+/// assert_eq!(roots_mod_prime_power(&[-2, 0, 1], 7, 2), vec![10, 39]); // square roots of 2 modulo 49
+/// assert_eq!(roots_mod_prime_power(&[0, 0, 1], 3, 2), vec![0, 3, 6]); // x^2 = 0 modulo 9
+/// ```
+pub fn roots_mod_prime_power(f: &[i64], p: u64, k: u32) -> Vec<u64> {
+    if p < 2 {
+        panic!("p must be a prime");
+    }
+    let modulus: u64 = match p.checked_pow(k) {
+        Some(modulus) => modulus,
+        None => panic!("p^k must fit in a u64"),
+    };
+    if k == 0 {
+        return vec![0];
+    }
+    let roots: Vec<u64> = (0..p).filter(|r| poly_eval_mod(f, *r, p) == 0).collect();
+    lift_roots(
+        roots,
+        p,
+        modulus,
+        &|x, m| poly_eval_mod(f, x, m),
+        &|x, m| poly_derivative_eval_mod(f, x, m),
+    )
+}
+
+/// Finds every root of a polynomial modulo any positive `m`.
+///
+/// The roots modulo each prime power in the factorization of `m` come from [`roots_mod_prime_power`], and
+/// every combination of them is glued together with [`chinese_remainder`].
+///
+/// # Arguments
+///
+/// * `f` - The coefficients, with `f[i]` the coefficient of $x^i$.
+/// * `m` - The modulus.
+///
+/// # Returns
+///
+/// Vector of every $r$ with $0 \leq r < m$ and $f(r) \equiv 0 \pmod m$, in increasing order.
+///
+/// # Panics
+///
+/// Panics if `m` is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::roots_mod_m;
+/// assert_eq!(roots_mod_m(&[-1, 0, 1], 15), vec![1, 4, 11, 14]); // square roots of 1 modulo 15
+/// assert_eq!(roots_mod_m(&[1, 0, 1], 12), vec![]); // -1 is not a square modulo 4
+/// ```
+pub fn roots_mod_m(f: &[i64], m: u64) -> Vec<u64> {
+    if m == 0 {
+        panic!("the modulus must be positive");
+    }
+    combine_roots(
+        factorize(m)
+            .into_iter()
+            .map(|(p, e)| (roots_mod_prime_power(f, p, e), p.pow(e)))
+            .collect(),
+    )
+}
+
+/// Combines the roots modulo coprime moduli into the roots modulo their product, by gluing every
+/// combination together with [`chinese_remainder`].
+///
+/// # Arguments
+///
+/// * `parts` - `(roots, modulus)` pairs, with the moduli pairwise coprime.
+///
+/// # Returns
+///
+/// Vector of the roots modulo the product of the moduli, in increasing order.
+fn combine_roots(parts: Vec<(Vec<u64>, u64)>) -> Vec<u64> {
+    let mut combined: Vec<(u64, u64)> = vec![(0, 1)];
+    for (roots, modulus) in parts {
+        let mut next: Vec<(u64, u64)> = vec![];
+        for (x, l) in &combined {
+            for r in &roots {
+                next.extend(chinese_remainder(&[(*x, *l), (*r, modulus)]));
+            }
+        }
+        combined = next;
+    }
+    let mut roots: Vec<u64> = combined.into_iter().map(|(x, _)| x).collect();
+    roots.sort_unstable();
+    roots
+}
+
+/// Finds the smallest primitive root modulo a prime, a generator of the multiplicative group.
+///
+/// $g$ is a primitive root exactly when $g^{(p - 1)/q} \not\equiv 1 \pmod p$ for every prime $q \mid p - 1$, and the
+/// smallest one is almost always tiny.
+///
+/// # Arguments
+///
+/// * `p` - The prime.
+///
+/// # Returns
+///
+/// `Some(g)` for the smallest primitive root, or `None` if `p` is not prime.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::primitive_root;
+/// assert_eq!(primitive_root(7), Some(3));
+/// assert_eq!(primitive_root(2), Some(1));
+/// assert_eq!(primitive_root(9), None);
+/// ```
+pub fn primitive_root(p: u64) -> Option<u64> {
+    if !is_probable_prime(&BigInt::from(p)) {
+        return None;
+    }
+    if p == 2 {
+        return Some(1);
+    }
+    let primes: Vec<u64> = factorize(p - 1).into_iter().map(|(q, _)| q).collect();
+    let mut g: u64 = 2;
+    while primes.iter().any(|q| mod_pow(g, (p - 1) / q, p) == 1) {
+        g += 1;
+    }
+    Some(g)
+}
+
+/// Calculates a discrete logarithm with the baby-step giant-step algorithm.
+///
+/// With $s = \lceil \sqrt{m} \rceil$, the baby steps $g^j$ for $j < s$ are stored, and then $a g^{-si}$ is looked up
+/// for $i = 0, 1, \dots$, so this takes $O(\sqrt{m})$ time and memory.
+///
+/// # Arguments
+///
+/// * `g` - The base, which must be a unit modulo `m`.
+/// * `a` - The target.
+/// * `m` - The modulus (positive).
+///
+/// # Returns
+///
+/// `Some(y)` for the smallest $y \geq 0$ with $g^y \equiv a \pmod m$, or `None` if there is none (or `g` is not a
+/// unit).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::discrete_log;
+/// assert_eq!(discrete_log(3, 13, 17), Some(4)); // 3^4 = 81 = 13 (mod 17)
+/// assert_eq!(discrete_log(2, 3, 7), None); // 2 only generates 1, 2 and 4
+/// ```
+pub fn discrete_log(g: u64, a: u64, m: u64) -> Option<u64> {
+    let a: u64 = a % m;
+    let step: u64 = (m as f64).sqrt().ceil() as u64 + 1;
+    let mut baby: std::collections::HashMap<u64, u64> = std::collections::HashMap::new();
+    let mut power: u64 = 1 % m;
+    let mut j: u64 = 0;
+    while j < step {
+        baby.entry(power).or_insert(j);
+        power = mulmod(power as u128, g as u128, m as u128) as u64;
+        j += 1;
+    }
+    let giant: u64 = mod_inverse(mod_pow(g, step, m), m)?;
+    let mut current: u64 = a;
+    let mut i: u64 = 0;
+    while i < step {
+        if let Some(j) = baby.get(&current) {
+            return Some(i * step + j);
+        }
+        current = mulmod(current as u128, giant as u128, m as u128) as u64;
+        i += 1;
+    }
+    None
+}
+
+/// Calculates a square root modulo an odd prime with the Tonelli–Shanks algorithm.
+///
+/// Writing $p - 1 = 2^s q$ with $q$ odd, the guess $a^{(q + 1)/2}$ is off by a $2^s$-th root of unity, which is
+/// corrected one power of $2$ at a time using powers of a quadratic non-residue.
+///
+/// # Arguments
+///
+/// * `a` - The number to take the square root of.
+/// * `p` - The prime.
+///
+/// # Returns
+///
+/// `Some(r)` with $r^2 \equiv a \pmod p$ and $r \leq p - r$, or `None` if `a` is not a square modulo `p`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::tonelli_shanks;
+/// assert_eq!(tonelli_shanks(2, 7), Some(3)); // 3^2 = 9 = 2 (mod 7)
+/// assert_eq!(tonelli_shanks(10, 13), Some(6)); // 6^2 = 36 = 10 (mod 13)
+/// assert_eq!(tonelli_shanks(3, 7), None);
+/// ```
+pub fn tonelli_shanks(a: u64, p: u64) -> Option<u64> {
+    let a: u64 = a % p;
+    if a == 0 || p == 2 {
+        return Some(a);
+    }
+    if mod_pow(a, (p - 1) / 2, p) != 1 {
+        return None;
+    }
+    let mul = |x: u64, y: u64| -> u64 { mulmod(x as u128, y as u128, p as u128) as u64 };
+    let s: u32 = (p - 1).trailing_zeros();
+    let q: u64 = (p - 1) >> s;
+    let z: u64 = smallest_quadratic_nonresidue(p);
+    let mut m: u32 = s;
+    let mut c: u64 = mod_pow(z, q, p);
+    let mut t: u64 = mod_pow(a, q, p);
+    let mut r: u64 = mod_pow(a, q.div_ceil(2), p);
+    while t != 1 {
+        // the order of t is 2^i for some i < m
+        let mut i: u32 = 0;
+        let mut square: u64 = t;
+        while square != 1 {
+            square = mul(square, square);
+            i += 1;
+        }
+        let mut b: u64 = c;
+        let mut j: u32 = 0;
+        while j + 1 < m - i {
+            b = mul(b, b);
+            j += 1;
+        }
+        m = i;
+        c = mul(b, b);
+        t = mul(t, c);
+        r = mul(r, b);
+    }
+    Some(r.min(p - r))
+}
+
+/// Calculates a square root modulo a prime with [`tonelli_shanks`], for a modulus already known to be prime.
+///
+/// # Arguments
+///
+/// * `a` - The number to take the square root of.
+/// * `p` - The prime.
+///
+/// # Returns
+///
+/// `Some(r)` with $r^2 \equiv a \pmod p$ and $r \leq p - r$, or `None` if `a` is not a square modulo `p`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::sqrt_mod_prime;
+/// use bens_number_theory::primes::Prime;
+/// assert_eq!(sqrt_mod_prime(10, Prime::new(13).unwrap()), Some(6));
+/// ```
+///
+/// A composite modulus is rejected at compile time:
+///
+/// ```compile_fail
+/// use bens_number_theory::modular::sqrt_mod_prime;
+/// sqrt_mod_prime(4, 15);
+/// ```
+pub fn sqrt_mod_prime(a: u64, p: Prime) -> Option<u64> {
+    tonelli_shanks(a, *p)
+}
+
+/// Checks if a number is a $k$-th power residue modulo a prime, meaning $x^k \equiv a \pmod p$ has a solution.
+///
+/// For $a \not\equiv 0$ the $k$-th powers are exactly the $d$-th powers with $d = \gcd(k, p - 1)$, which are the
+/// solutions of $a^{(p - 1)/d} \equiv 1$ (Euler's criterion for $k = 2$).
+///
+/// # Arguments
+///
+/// * `a` - The number to check.
+/// * `k` - The power (positive).
+/// * `p` - The prime.
+///
+/// # Returns
+///
+/// `true` if some $x$ has $x^k \equiv a \pmod p$.
+///
+/// # Panics
+///
+/// Panics if `k` is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::is_kth_power_residue;
+/// assert!(is_kth_power_residue(8, 3, 13)); // 2^3
+/// assert!(!is_kth_power_residue(2, 3, 13));
+/// assert!(is_kth_power_residue(2, 3, 11)); // every number is a cube modulo 11
+/// ```
+pub fn is_kth_power_residue(a: u64, k: u64, p: u64) -> bool {
+    if k == 0 {
+        panic!("k must be positive");
+    }
+    let a: u64 = a % p;
+    a == 0 || mod_pow(a, (p - 1) / gcd(k, p - 1), p) == 1
+}
+
+/// Finds every solution of $x^k \equiv a \pmod p$ for a prime $p$.
+///
+/// With a primitive root $g$ (see [`primitive_root`]) and $a = g^i$ (see [`discrete_log`]), writing $x = g^y$
+/// turns the congruence into the linear $ky \equiv i \pmod{p - 1}$, which has $d = \gcd(k, p - 1)$ solutions if
+/// $d \mid i$ and none otherwise.
+///
+/// # Arguments
+///
+/// * `a` - The right-hand side.
+/// * `k` - The power (positive).
+/// * `p` - The prime.
+///
+/// # Returns
+///
+/// Vector of every $x$ with $0 \leq x < p$ and $x^k \equiv a \pmod p$, in increasing order.
+///
+/// # Panics
+///
+/// Panics if `k` is `0` or `p` is not prime.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::kth_power_residue_solve;
+/// assert_eq!(kth_power_residue_solve(8, 3, 13), vec![2, 5, 6]);
+/// assert_eq!(kth_power_residue_solve(2, 3, 13), vec![]);
+/// ```
+pub fn kth_power_residue_solve(a: u64, k: u64, p: u64) -> Vec<u64> {
+    let g: u64 = match primitive_root(p) {
+        Some(g) => g,
+        None => panic!("p must be prime"),
+    };
+    if !is_kth_power_residue(a, k, p) {
+        return vec![];
+    }
+    let a: u64 = a % p;
+    if a == 0 {
+        return vec![0];
+    }
+    let order: u64 = p - 1;
+    let index: u64 = discrete_log(g, a, p).expect("a primitive root generates every unit");
+    let d: u64 = gcd(k, order);
+    let reduced: u64 = order / d;
+    // (k / d) y = index / d (mod (p - 1) / d)
+    let y: u64 = match mod_inverse((k / d) % reduced, reduced) {
+        Some(inverse) => mulmod((index / d) as u128, inverse as u128, reduced as u128) as u64,
+        None => 0,
+    };
+    let mut solutions: Vec<u64> = (0..d).map(|t| mod_pow(g, y + t * reduced, p)).collect();
+    solutions.sort_unstable();
+    solutions
+}
+
+/// Finds every solution of $x^k \equiv a \pmod m$ for any positive modulus.
+///
+/// The solutions modulo each prime $p \mid m$ come from [`kth_power_residue_solve`], they are lifted to the
+/// full power of $p$ as in [`roots_mod_prime_power`], and the prime powers are glued together with
+/// [`chinese_remainder`]. Lifting a singular root (when $p \mid k$ or $p \mid a$) tries all $p$ candidates,
+/// so that case is only fast for small $p$.
+///
+/// # Arguments
+///
+/// * `a` - The right-hand side.
+/// * `k` - The power (positive).
+/// * `m` - The modulus.
+///
+/// # Returns
+///
+/// Vector of every $x$ with $0 \leq x < m$ and $x^k \equiv a \pmod m$, in increasing order.
+///
+/// # Panics
+///
+/// Panics if `k` or `m` is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::kth_roots_mod;
+/// assert_eq!(kth_roots_mod(1, 2, 8), vec![1, 3, 5, 7]);
+/// assert_eq!(kth_roots_mod(8, 3, 169), vec![2, 44, 123]);
+/// ```
+pub fn kth_roots_mod(a: u64, k: u64, m: u64) -> Vec<u64> {
+    if k == 0 {
+        panic!("k must be positive");
+    }
+    if m == 0 {
+        panic!("the modulus must be positive");
+    }
+    let mut parts: Vec<(Vec<u64>, u64)> = vec![];
+    for (p, e) in factorize(m) {
+        let modulus: u64 = p.pow(e);
+        let eval = |x: u64, n: u64| -> u64 {
+            submod(mod_pow(x, k, n) as u128, a as u128, n as u128) as u64
+        };
+        let derivative = |x: u64, n: u64| -> u64 {
+            mulmod(k as u128, mod_pow(x, k - 1, n) as u128, n as u128) as u64
+        };
+        let roots: Vec<u64> = kth_power_residue_solve(a, k, p);
+        parts.push((lift_roots(roots, p, modulus, &eval, &derivative), modulus));
+    }
+    combine_roots(parts)
+}
+
+/// Calculates the multiplicative order of `a` modulo the prime power $p^k$.
+///
+/// The order modulo $p^k$ is the order modulo $p$ times a power of $p$, so starting from the order modulo $p$ it
+/// is multiplied by $p$ until the power is congruent to $1$ modulo $p^k$, which takes at most $k$ steps.
+///
+/// # Arguments
+///
+/// * `a` - The number to find the order of.
+/// * `p` - The prime.
+/// * `k` - The exponent (positive).
+///
+/// # Returns
+///
+/// `Some(order)` with the order of `a` modulo $p^k$, or `None` if $p \mid a$.
+///
+/// # Panics
+///
+/// Panics if `p` is not prime, if `k` is `0` or if $p^k$ does not fit in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::multiplicative_order_prime_power;
+/// assert_eq!(multiplicative_order_prime_power(2, 3, 4), Some(54));
+/// assert_eq!(multiplicative_order_prime_power(3, 2, 5), Some(8));
+/// assert_eq!(multiplicative_order_prime_power(10, 5, 2), None);
+/// ```
+pub fn multiplicative_order_prime_power(a: u64, p: u64, k: u32) -> Option<u64> {
+    if !is_probable_prime(&BigInt::from(p)) {
+        panic!("p must be prime");
+    }
+    if k == 0 {
+        panic!("k must be positive");
+    }
+    let modulus: u64 = match p.checked_pow(k) {
+        Some(modulus) => modulus,
+        None => panic!("p^k must fit in a u64"),
+    };
+    let mut order: u64 = multiplicative_order(a, p)?;
+    while mod_pow(a, order, modulus) != 1 {
+        order *= p;
+    }
+    Some(order)
+}
+
+/// Calculates the multiplicative order of `a` modulo `m` from the Carmichael function.
+///
+/// Every order divides $\lambda(m)$, which is usually much smaller than $\varphi(m)$, so starting from
+/// $\lambda(m)$ every prime factor is divided out for as long as the power stays congruent to $1$.
+///
+/// # Arguments
+///
+/// * `a` - The number to find the order of.
+/// * `m` - The modulus.
+///
+/// # Returns
+///
+/// `Some(k)` with the order of `a`, or `None` if `a` is not coprime to `m` (or `m` is `0`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::order_mod;
+/// assert_eq!(order_mod(2, 15), Some(4));
+/// assert_eq!(order_mod(7, 561), Some(80));
+/// assert_eq!(order_mod(6, 9), None);
+/// ```
+pub fn order_mod(a: u64, m: u64) -> Option<u64> {
+    if m == 0 || gcd(a % m, m) != 1 {
+        return None;
+    }
+    let lambda: u64 = carmichael_lambda(m);
+    let mut order: u64 = lambda;
+    for (q, _) in factorize(lambda) {
+        while order.is_multiple_of(q) && mod_pow(a, order / q, m) == 1 {
+            order /= q;
+        }
+    }
+    Some(order)
+}
+
+/// Calculates the multiplicative order of `a` modulo every prime power dividing `m`.
+///
+/// By the Chinese remainder theorem the order modulo `m` is the least common multiple of these orders.
+///
+/// # Arguments
+///
+/// * `a` - The number to find the orders of.
+/// * `m` - The modulus.
+///
+/// # Returns
+///
+/// Vector of `(p, e, order)` for every $p^e \| m$ in increasing order of `p`, where `order` is the order of `a`
+/// modulo $p^e$ (empty for `1`).
+///
+/// # Panics
+///
+/// Panics if `m` is `0` or `a` is not coprime to `m`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::order_profile;
+/// assert_eq!(order_profile(2, 63), vec![(3, 2, 6), (7, 1, 3)]);
+/// assert_eq!(order_profile(5, 1), vec![]);
+/// ```
+pub fn order_profile(a: u64, m: u64) -> Vec<(u64, u32, u64)> {
+    if m == 0 || gcd(a % m, m) != 1 {
+        panic!("a must be coprime to a positive modulus");
+    }
+    factorize(m)
+        .into_iter()
+        .map(|(p, e)| {
+            let order: u64 = multiplicative_order_prime_power(a, p, e).unwrap();
+            (p, e, order)
+        })
+        .collect()
+}
+
+/// Checks if `a` is a primitive root modulo `m`, meaning its order is $\varphi(m)$.
+///
+/// Primitive roots only exist modulo $1$, $2$, $4$, $p^k$ and $2p^k$ for odd primes $p$.
+///
+/// # Arguments
+///
+/// * `a` - The number to check.
+/// * `m` - The modulus.
+///
+/// # Returns
+///
+/// `true` if `a` is coprime to `m` and generates every unit modulo `m` (`false` for `m = 0`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::is_primitive_root;
+/// assert!(is_primitive_root(3, 7));
+/// assert!(is_primitive_root(2, 25));
+/// assert!(!is_primitive_root(2, 7));
+/// assert!(!is_primitive_root(5, 12)); // there are no primitive roots modulo 12
+/// ```
+pub fn is_primitive_root(a: u64, m: u64) -> bool {
+    order_mod(a, m) == Some(euler_totient(m))
+}
+
+/// Checks if two numbers are coprime, meaning $\gcd(a, b) = 1$.
+///
+/// # Arguments
+///
+/// * `a` - The first number.
+/// * `b` - The second number.
+///
+/// # Returns
+///
+/// `true` if `a` and `b` share no prime factor (`0` is only coprime to `1`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::are_coprime;
+/// assert!(are_coprime(8, 15));
+/// assert!(!are_coprime(12, 18));
+/// assert!(are_coprime(0, 1));
+/// ```
+pub fn are_coprime(a: u64, b: u64) -> bool {
+    gcd(a, b) == 1
+}
+
+/// Iterates over the numbers below `n` that are coprime to it.
+///
+/// Instead of taking $n$ gcds, the multiples of each distinct prime factor of `n` are crossed off a table of the
+/// numbers below `n`, which then takes $O(n)$ memory.
+///
+/// # Arguments
+///
+/// * `n` - The number to be coprime to.
+///
+/// # Returns
+///
+/// An iterator over every $1 \leq k < n$ with $\gcd(k, n) = 1$ in increasing order, which has $\varphi(n)$ items
+/// for $n \geq 2$ (and none for `0` and `1`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::coprimes_below;
+/// assert_eq!(coprimes_below(12).collect::<Vec<u64>>(), vec![1, 5, 7, 11]);
+/// assert_eq!(coprimes_below(1).count(), 0);
+/// ```
+pub fn coprimes_below(n: u64) -> impl Iterator<Item = u64> {
+    let mut coprime: Vec<bool> = vec![true; n as usize];
+    if n > 1 {
+        for (p, _) in factorize(n) {
+            let mut multiple: u64 = 0;
+            while multiple < n {
+                coprime[multiple as usize] = false;
+                multiple += p;
+            }
+        }
+    }
+    (1..n).filter(move |k| coprime[*k as usize])
+}
+
+/// Counts the numbers in a range that are coprime to `n` by inclusion–exclusion.
+///
+/// If $p_1, \dots, p_\omega$ are the distinct prime factors of $n$, the count of $0 \leq k < x$ with no $p_i \mid k$
+/// is $\sum_{d} \mu(d) \lceil x/d \rceil$ over the $2^\omega$ products $d$ of subsets of the $p_i$, which takes
+/// $O(2^\omega)$ time however long the range is (and $\omega \leq 15$ for a `u64`).
+///
+/// # Arguments
+///
+/// * `lo` - The (inclusive) start of the range.
+/// * `hi` - The (exclusive) end of the range.
+/// * `n` - The number to be coprime to.
+///
+/// # Returns
+///
+/// The number of $\text{lo} \leq k < \text{hi}$ with $\gcd(k, n) = 1$ (`0` when $\text{lo} \geq \text{hi}$).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::count_coprime_in_range;
+/// assert_eq!(count_coprime_in_range(1, 13, 12), 4);
+/// assert_eq!(count_coprime_in_range(0, 1_000_000_000_000, 30), 266_666_666_666);
+/// ```
+pub fn count_coprime_in_range(lo: u64, hi: u64, n: u64) -> u64 {
+    if lo >= hi {
+        return 0;
+    }
+    if n == 0 {
+        // only 1 is coprime to 0
+        return (lo <= 1 && 1 < hi) as u64;
+    }
+    let primes: Vec<u64> = factorize(n).into_iter().map(|(p, _)| p).collect();
+    let count_below = |x: u64| -> i128 {
+        let mut total: i128 = 0;
+        let mut subset: u32 = 0;
+        while subset < 1 << primes.len() {
+            let mut d: u64 = 1;
+            let mut i: usize = 0;
+            while i < primes.len() {
+                if subset >> i & 1 == 1 {
+                    d *= primes[i];
+                }
+                i += 1;
+            }
+            let multiples: i128 = x.div_ceil(d) as i128;
+            if subset.count_ones().is_multiple_of(2) {
+                total += multiples;
+            } else {
+                total -= multiples;
+            }
+            subset += 1;
+        }
+        total
+    };
+    (count_below(hi) - count_below(lo)) as u64
+}
+
+/// The factorials with the factors of $p$ removed, modulo $p^e$, for [`binomial_mod_prime_power`].
+///
+/// Writing the numbers coprime to $p$ as $pq + j$ with $1 \leq j < p$, each block of $p$ of them multiplies to
+/// $B(q) = \prod_{j = 1}^{p - 1} (pq + j)$. As a polynomial in $q$, the coefficient of $q^t$ in $B$ (and in any
+/// product of shifts of it) is divisible by $p^t$, so modulo $p^e$ only the coefficients below degree $e$ matter.
+/// The product $B(0) B(1) \cdots B(Q - 1)$ is then built with $O(\log Q)$ polynomial doublings instead of $Q$
+/// multiplications.
+struct PrimePowerFactorials {
+    /// The prime $p$.
+    p: u64,
+    /// The modulus $p^e$.
+    modulus: u64,
+    /// The coefficients of $B(q)$ modulo $p^e$, up to degree $e - 1$.
+    block: Vec<u64>,
+    /// `binomials[t][s]` is $\binom{t}{s}$ modulo $p^e$ for $t < e$.
+    binomials: Vec<Vec<u64>>,
+}
+
+impl PrimePowerFactorials {
+    /// Prepares the factorials modulo $p^e$, which takes $O(pe)$ time to build $B$ unless no factorial needs a
+    /// whole block.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - The prime.
+    /// * `e` - The exponent (at least `1`).
+    /// * `modulus` - The modulus $p^e$.
+    /// * `largest` - The largest number whose factorial will be needed.
+    ///
+    /// # Returns
+    ///
+    /// The prepared factorials.
+    fn new(p: u64, e: u32, modulus: u64, largest: u64) -> PrimePowerFactorials {
+        let e: usize = e as usize;
+        let mut binomials: Vec<Vec<u64>> = vec![vec![1 % modulus]; e];
+        let mut t: usize = 1;
+        while t < e {
+            let mut row: Vec<u64> = vec![1 % modulus; t + 1];
+            let mut s: usize = 1;
+            while s < t {
+                row[s] = addmod(
+                    binomials[t - 1][s - 1] as u128,
+                    binomials[t - 1][s] as u128,
+                    modulus as u128,
+                ) as u64;
+                s += 1;
+            }
+            binomials[t] = row;
+            t += 1;
+        }
+        let mut factorials: PrimePowerFactorials = PrimePowerFactorials {
+            p,
+            modulus,
+            block: vec![],
+            binomials,
+        };
+        let mut block: Vec<u64> = vec![0; e];
+        block[0] = 1 % modulus;
+        let mut j: u64 = 1;
+        while j < p && largest >= p {
+            // multiply by the linear polynomial j + pq
+            block = factorials.multiply(&block, &[j % modulus, p % modulus]);
+            j += 1;
+        }
+        factorials.block = block;
+        factorials
+    }
+
+    /// Multiplies two polynomials modulo $p^e$, dropping the terms of degree $e$ and up.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The coefficients of the first polynomial, lowest degree first.
+    /// * `b` - The coefficients of the second polynomial.
+    ///
+    /// # Returns
+    ///
+    /// The $e$ lowest coefficients of the product.
+    fn multiply(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        let e: usize = self.binomials.len();
+        let modulus: u128 = self.modulus as u128;
+        let mut product: Vec<u64> = vec![0; e];
+        for (s, x) in a.iter().enumerate() {
+            for (t, y) in b.iter().enumerate().take(e.saturating_sub(s)) {
+                let term: u128 = mulmod(*x as u128, *y as u128, modulus);
+                product[s + t] = addmod(product[s + t] as u128, term, modulus) as u64;
+            }
+        }
+        product
+    }
+
+    /// Shifts a polynomial, turning $a(q)$ into $a(q + m)$ modulo $p^e$.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The coefficients of the polynomial, lowest degree first, of degree below $e$.
+    /// * `m` - The shift.
+    ///
+    /// # Returns
+    ///
+    /// The coefficients of $a(q + m)$.
+    fn shift(&self, a: &[u64], m: u64) -> Vec<u64> {
+        let modulus: u128 = self.modulus as u128;
+        let mut shifted: Vec<u64> = vec![0; a.len()];
+        for (t, coefficient) in a.iter().enumerate() {
+            // (q + m)^t contributes binomial(t, s) m^(t - s) q^s
+            let mut power: u128 = 1 % modulus;
+            let mut s: usize = t + 1;
+            while s > 0 {
+                s -= 1;
+                let term: u128 = mulmod(
+                    mulmod(*coefficient as u128, power, modulus),
+                    self.binomials[t][s] as u128,
+                    modulus,
+                );
+                shifted[s] = addmod(shifted[s] as u128, term, modulus) as u64;
+                power = mulmod(power, m as u128, modulus);
+            }
+        }
+        shifted
+    }
+
+    /// Calculates the product of the numbers from $1$ to $r$ that are coprime to $p$, modulo $p^e$.
+    ///
+    /// # Arguments
+    ///
+    /// * `r` - The upper bound of the numbers.
+    ///
+    /// # Returns
+    ///
+    /// $\prod_{i \leq r,\, p \nmid i} i \bmod p^e$.
+    fn coprime_product(&self, r: u64) -> u64 {
+        let blocks: u64 = r / self.p;
+        // product[q] holds B(0) B(1) ... B(count - 1) shifted by q, so its constant term is the product
+        let mut product: Vec<u64> = vec![0; self.binomials.len()];
+        product[0] = 1 % self.modulus;
+        let mut count: u64 = 0;
+        let mut bit: u32 = u64::BITS - blocks.leading_zeros();
+        while bit > 0 {
+            bit -= 1;
+            product = self.multiply(&product, &self.shift(&product, count));
+            count *= 2;
+            if blocks >> bit & 1 == 1 {
+                product = self.multiply(&product, &self.shift(&self.block, count));
+                count += 1;
+            }
+        }
+        let modulus: u128 = self.modulus as u128;
+        let mut value: u128 = product[0] as u128;
+        let start: u128 = mulmod(blocks as u128, self.p as u128, modulus);
+        let mut j: u64 = 1;
+        while j <= r % self.p {
+            value = mulmod(value, addmod(start, j as u128, modulus), modulus);
+            j += 1;
+        }
+        value as u64
+    }
+
+    /// Calculates $n!$ with every factor of $p$ removed, modulo $p^e$.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number to take the factorial of.
+    ///
+    /// # Returns
+    ///
+    /// $n! / p^{v_p(n!)} \bmod p^e$, using $n! = \left(\prod_{i \leq n,\, p \nmid i} i\right) p^{\lfloor n/p \rfloor}
+    /// \lfloor n/p \rfloor!$.
+    fn factorial_without_p(&self, n: u64) -> u64 {
+        let mut value: u128 = 1 % self.modulus as u128;
+        let mut m: u64 = n;
+        while m > 0 {
+            value = mulmod(value, self.coprime_product(m) as u128, self.modulus as u128);
+            m /= self.p;
+        }
+        value as u64
+    }
+}
+
+/// Calculates the exponent of a prime in $n!$ with Legendre's formula $\sum_{i \geq 1} \lfloor n/p^i \rfloor$.
+///
+/// # Arguments
+///
+/// * `n` - The number to take the factorial of.
+/// * `p` - The prime.
+///
+/// # Returns
+///
+/// The exponent of `p` in $n!$.
+fn factorial_valuation(n: u64, p: u64) -> u64 {
+    let mut exponent: u64 = 0;
+    let mut m: u64 = n / p;
+    while m > 0 {
+        exponent += m;
+        m /= p;
+    }
+    exponent
+}
+
+/// Calculates the binomial coefficient $\binom{n}{k}$ modulo a prime power $p^e$.
+///
+/// This generalizes Lucas' theorem (which only covers $e = 1$) as in Granville's "Binomial coefficients modulo
+/// prime powers": $\binom{n}{k} = p^v \frac{(n!)_p}{(k!)_p ((n - k)!)_p}$ where $v$ is the exponent of $p$ in
+/// $\binom{n}{k}$ and $(m!)_p$ is $m!$ with the factors of $p$ removed, which is a unit modulo $p^e$. The
+/// factorials take $O(pe + e^2 \log^2 n)$ time (or $O(n)$ when $n < p$), so `p` itself should be small (up to a few
+/// million) unless $n$ is, but $n$ and $e$ can be as large as fits, such as $\binom{10^{18}}{10^9} \bmod 2^{63}$.
+///
+/// # Arguments
+///
+/// * `n` - The size of the set.
+/// * `k` - The size of the subsets.
+/// * `p` - A prime.
+/// * `e` - The exponent of the modulus.
+///
+/// # Returns
+///
+/// $\binom{n}{k} \bmod p^e$ (`0` when $k > n$).
+///
+/// # Panics
+///
+/// Panics if `p` is less than `2` or $p^e$ does not fit in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::binomial_mod_prime_power;
+/// assert_eq!(binomial_mod_prime_power(10, 3, 2, 3), 0); // 120 = 8 * 15
+/// assert_eq!(binomial_mod_prime_power(10, 3, 7, 2), 120 % 49);
+/// assert_eq!(binomial_mod_prime_power(3, 5, 3, 1), 0);
+/// ```
+pub fn binomial_mod_prime_power(n: u64, k: u64, p: u64, e: u32) -> u64 {
+    if p < 2 {
+        panic!("p must be a prime");
+    }
+    let modulus: u64 = match p.checked_pow(e) {
+        Some(modulus) => modulus,
+        None => panic!("the prime power must fit in a u64"),
+    };
+    if k > n || modulus == 1 {
+        return 0;
+    }
+    let valuation: u64 =
+        factorial_valuation(n, p) - factorial_valuation(k, p) - factorial_valuation(n - k, p);
+    if valuation >= e as u64 {
+        return 0;
+    }
+    let factorials: PrimePowerFactorials = PrimePowerFactorials::new(p, e, modulus, n);
+    let numerator: u64 = factorials.factorial_without_p(n);
+    let denominator: u128 = mulmod(
+        factorials.factorial_without_p(k) as u128,
+        factorials.factorial_without_p(n - k) as u128,
+        modulus as u128,
+    );
+    let inverse: u64 = mod_inverse(denominator as u64, modulus)
+        .expect("the factorials without p are coprime to p");
+    let unit: u128 = mulmod(numerator as u128, inverse as u128, modulus as u128);
+    mulmod(unit, p.pow(valuation as u32) as u128, modulus as u128) as u64
+}
+
+/// Calculates the binomial coefficient $\binom{n}{k}$ modulo any number.
+///
+/// The binomial coefficient is found modulo each prime power dividing `m` with
+/// [`binomial_mod_prime_power`] and the results are combined with [`chinese_remainder`].
+///
+/// # Arguments
+///
+/// * `n` - The size of the set.
+/// * `k` - The size of the subsets.
+/// * `m` - The modulus, whose prime factors should be small (see [`binomial_mod_prime_power`]).
+///
+/// # Returns
+///
+/// $\binom{n}{k} \bmod m$ (`0` when $k > n$).
+///
+/// # Panics
+///
+/// Panics if `m` is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::binomial_mod;
+/// assert_eq!(binomial_mod(10, 3, 100), 20);
+/// assert_eq!(binomial_mod(1_000_000_000_000_000_000, 1_000_000_000, 1_000_000_000), 0);
+/// assert_eq!(binomial_mod(52, 5, 1 << 32), 2_598_960);
+/// ```
+pub fn binomial_mod(n: u64, k: u64, m: u64) -> u64 {
+    if m == 0 {
+        panic!("the modulus must be positive");
+    }
+    let congruences: Vec<(u64, u64)> = factorize(m)
+        .into_iter()
+        .map(|(p, e)| (binomial_mod_prime_power(n, k, p, e), p.pow(e)))
+        .collect();
+    match chinese_remainder(&congruences) {
+        Some((x, _)) => x,
+        None => unreachable!("prime powers of different primes are coprime"),
+    }
+}
+
+/// Calculates the Fermat quotient $q_p(a) = \frac{a^{p - 1} - 1}{p} \bmod p$.
+///
+/// By Fermat's little theorem $p \mid a^{p - 1} - 1$, so the quotient is an integer, and it is found modulo $p$ from
+/// $a^{p - 1} \bmod p^2$. It is $0$ exactly when $p$ is a Wieferich prime to the base $a$ (see
+/// [`is_wieferich_to_base`]).
+///
+/// # Arguments
+///
+/// * `a` - The base, not divisible by `p`.
+/// * `p` - The prime.
+///
+/// # Returns
+///
+/// $q_p(a)$, in $0 \leq q_p(a) < p$.
+///
+/// # Panics
+///
+/// Panics if `p` divides `a`, or if $a^{p - 1} \not\equiv 1 \pmod p$, which proves that `p` is not prime.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::fermat_quotient;
+/// assert_eq!(fermat_quotient(2, 7), 2); // (64 - 1) / 7 = 9
+/// assert_eq!(fermat_quotient(3, 5), 1); // (81 - 1) / 5 = 16
+/// assert_eq!(fermat_quotient(2, 1093), 0);
+/// ```
+pub fn fermat_quotient(a: u64, p: u64) -> u64 {
+    if p < 2 {
+        panic!("p must be prime");
+    }
+    if a.is_multiple_of(p) {
+        panic!("a must not be divisible by p");
+    }
+    let square: u128 = p as u128 * p as u128;
+    let power: u128 = mod_pow_u128(a as u128, (p - 1) as u128, square);
+    if power % p as u128 != 1 {
+        panic!("p must be prime");
+    }
+    ((power - 1) / p as u128) as u64
+}
+
+/// Checks if a prime is a Wieferich prime to a base, meaning $a^{p - 1} \equiv 1 \pmod{p^2}$.
+///
+/// The usual Wieferich primes are those to the base $2$, of which only $1093$ and $3511$ are known. This is the
+/// same as the Fermat quotient $q_p(a)$ being $0$ (see [`fermat_quotient`]).
+///
+/// [A001220](https://oeis.org/A001220)
+///
+/// # Arguments
+///
+/// * `p` - The prime.
+/// * `a` - The base.
+///
+/// # Returns
+///
+/// `true` if $a^{p - 1} \equiv 1 \pmod{p^2}$ (never when $p \mid a$).
+///
+/// # Panics
+///
+/// Panics if `p` is shown not to be prime (see [`fermat_quotient`]).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::is_wieferich_to_base;
+/// assert!(is_wieferich_to_base(1093, 2));
+/// assert!(is_wieferich_to_base(11, 3)); // 3^10 = 59049 = 1 + 488 * 121
+/// assert!(!is_wieferich_to_base(13, 2));
+/// assert!(!is_wieferich_to_base(3, 6));
+/// ```
+pub fn is_wieferich_to_base(p: u64, a: u64) -> bool {
+    if p >= 2 && a.is_multiple_of(p) {
+        return false;
+    }
+    fermat_quotient(a, p) == 0
+}
+
+/// Checks if a prime is a Wall–Sun–Sun prime, meaning $p^2 \mid F_{p - \left(\frac{5}{p}\right)}$.
+///
+/// Every prime divides $F_{p - \left(\frac{5}{p}\right)}$. By quadratic reciprocity the Legendre symbol
+/// $\left(\frac{5}{p}\right)$ is $1$ for $p \equiv \pm 1 \pmod 5$ and $-1$ for $p \equiv \pm 2 \pmod 5$ (which is
+/// also the Kronecker symbol for $p = 2$), and $0$ for $p = 5$. The Fibonacci number is found modulo $p^2$ by fast
+/// doubling (see [`fibonacci_mod`](crate::sequences::fibonacci_mod)). No Wall–Sun–Sun prime is known.
+///
+/// # Arguments
+///
+/// * `p` - The prime.
+///
+/// # Returns
+///
+/// `true` if $p^2$ divides $F_{p - \left(\frac{5}{p}\right)}$.
+///
+/// # Panics
+///
+/// Panics if $p \nmid F_{p - \left(\frac{5}{p}\right)}$, which proves that `p` is not prime.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::modular::wall_sun_sun_check;
+/// assert!(!wall_sun_sun_check(7)); // F_8 = 21 = 3 * 7
+/// assert!(!wall_sun_sun_check(5)); // F_5 = 5
+/// assert!(!wall_sun_sun_check(1_000_003));
+/// ```
+pub fn wall_sun_sun_check(p: u64) -> bool {
+    if p < 2 {
+        panic!("p must be prime");
+    }
+    let index: u64 = match p % 5 {
+        0 => p,
+        1 | 4 => p - 1,
+        _ => p + 1,
+    };
+    let square: u128 = p as u128 * p as u128;
+    let fibonacci: u128 = fibonacci_mod_u128(index, square);
+    if !fibonacci.is_multiple_of(p as u128) {
+        panic!("p must be prime");
+    }
+    fibonacci == 0
+}
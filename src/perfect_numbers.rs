@@ -1,4 +1,6 @@
-use super::primes::{generate_primes, is_prime};
+use super::primes::{generate_primes, is_prime_u64};
+use crate::divisor_functions::{divisor_sum, sigma_sieve, unitary_divisor_sum};
+use crate::modular::{euler_totient, gcd, totient_sieve};
 
 /// Uses the Euclid-Euler theorem to calculate even perfect numbers.
 ///
@@ -33,7 +35,7 @@ where
     let primes: Vec<T> = generate_primes(n);
     let mut nums: Vec<T> = vec![];
     for prime in primes {
-        if is_prime(2_i32.pow(prime.to_u32().unwrap()) - 1) {
+        if is_prime_u64(2_u64.pow(prime.to_u32().unwrap()) - 1) {
             nums.push(
                 T::from_u32(
                     2_u32.pow((prime - T::one()).to_u32().unwrap())
@@ -106,6 +108,7 @@ where
 /// use bens_number_theory::perfect_numbers::divisors;
 /// assert_eq!(divisors(10), vec![1, 2, 5, 10]);
 /// assert_eq!(divisors(20), vec![1, 2, 4, 5, 10, 20]);
+/// assert_eq!(divisors(4), vec![1, 2, 4]);
 /// ```
 pub fn divisors<T>(n: T) -> Vec<T>
 where
@@ -120,7 +123,7 @@ where
 {
     let mut d: Vec<T> = vec![];
     let mut i: T = T::one();
-    while i < n / T::from_i32(2).unwrap() {
+    while i <= n / i {
         if n % i == T::zero() {
             d.push(i);
             d.push(n / i);
@@ -131,3 +134,421 @@ where
     d.dedup();
     d
 }
+
+/// The largest number [`is_semiperfect`] builds a bitset of sums for.
+const SEMIPERFECT_MAX: u64 = 1 << 30;
+
+/// Determines whether or not a number is semiperfect (or pseudoperfect).
+///
+/// `n` is semiperfect if some subset of its proper divisors sums to `n` (e.g. $12 = 2 + 4 + 6$).
+///
+/// Deficient numbers ($\sigma(n) < 2n$) can never be semiperfect, so they are rejected right away.
+/// Otherwise the sums reachable with the proper divisors are tracked in a bitset of $n + 1$ bits,
+/// which takes $O(n \cdot \tau(n) / 64)$ time and $O(n / 64)$ words of memory.
+///
+/// [A005835](https://oeis.org/A005835)
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// Boolean representing whether `n` is semiperfect.
+///
+/// # Panics
+///
+/// Panics if `n` is abundant but not perfect and above $2^{30}$, since its bitset would take over 128 MiB, or if
+/// $\sigma(n)$ does not fit in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::perfect_numbers::is_semiperfect;
+/// assert_eq!(is_semiperfect(12), true); // 2 + 4 + 6
+/// assert_eq!(is_semiperfect(6), true); // perfect numbers are semiperfect
+/// assert_eq!(is_semiperfect(70), false);
+/// ```
+pub fn is_semiperfect(n: u64) -> bool {
+    if n == 0 {
+        return false;
+    }
+    let sigma: u128 = divisor_sum(n) as u128;
+    if sigma < 2 * n as u128 {
+        return false;
+    }
+    if sigma == 2 * n as u128 {
+        return true;
+    }
+    if n > SEMIPERFECT_MAX {
+        panic!("the bitset of sums for n would be too large");
+    }
+    let mut proper_divisors: Vec<u64> = divisors(n);
+    proper_divisors.pop();
+
+    // bit s of `reachable` is set when s is a sum of distinct proper divisors
+    let words: usize = n as usize / 64 + 1;
+    let mut reachable: Vec<u64> = vec![0; words];
+    reachable[0] = 1;
+    // go from the largest divisor down, so n is usually reached sooner
+    for d in proper_divisors.into_iter().rev() {
+        shift_or(&mut reachable, d as usize);
+        if reachable[n as usize / 64] >> (n % 64) & 1 == 1 {
+            return true;
+        }
+    }
+    false
+}
+
+/// Sets `bits |= bits << shift` on a bitset stored as little-endian words (bits shifted past the end are dropped).
+///
+/// # Arguments
+///
+/// * `bits` - The bitset to update.
+/// * `shift` - How many bits to shift by.
+fn shift_or(bits: &mut [u64], shift: usize) {
+    let word_shift: usize = shift / 64;
+    let bit_shift: u32 = (shift % 64) as u32;
+    let mut i: usize = bits.len();
+    while i > word_shift {
+        i -= 1;
+        let source: usize = i - word_shift;
+        let mut shifted: u64 = bits[source] << bit_shift;
+        if bit_shift > 0 && source > 0 {
+            shifted |= bits[source - 1] >> (64 - bit_shift);
+        }
+        bits[i] |= shifted;
+    }
+}
+
+/// Determines whether or not a number is weird.
+///
+/// `n` is weird if it is abundant ($\sigma(n) > 2n$) but not semiperfect,
+/// so its proper divisors add up to more than `n` but no subset of them adds up to exactly `n`.
+///
+/// [A006037](https://oeis.org/A006037)
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// Boolean representing whether `n` is weird.
+///
+/// # Panics
+///
+/// Panics if `n` is abundant and above $2^{30}$ (see [`is_semiperfect`]), or if $\sigma(n)$ does not fit in a
+/// `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::perfect_numbers::is_weird;
+/// assert_eq!(is_weird(70), true);
+/// assert_eq!(is_weird(12), false); // abundant, but 2 + 4 + 6 = 12
+/// assert_eq!(is_weird(10), false); // deficient
+/// ```
+pub fn is_weird(n: u64) -> bool {
+    n != 0 && divisor_sum(n) as u128 > 2 * n as u128 && !is_semiperfect(n)
+}
+
+/// Generates the weird numbers below a given limit.
+///
+/// # Arguments
+///
+/// * `limit` - The (exclusive) upper bound of the numbers to check.
+///
+/// # Returns
+///
+/// Vector of all weird numbers less than `limit`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::perfect_numbers::weird_numbers_below;
+/// assert_eq!(weird_numbers_below(1000), vec![70, 836]);
+/// ```
+pub fn weird_numbers_below(limit: u64) -> Vec<u64> {
+    let mut weird: Vec<u64> = vec![];
+    let mut n: u64 = 1;
+    while n < limit {
+        if is_weird(n) {
+            weird.push(n);
+        }
+        n += 1;
+    }
+    weird
+}
+
+/// Determines whether or not a number is multiperfect, and if so, of which order.
+///
+/// `n` is $k$-perfect if $\sigma(n) = kn$, so the perfect numbers are exactly the 2-perfect numbers
+/// (e.g. $\sigma(120) = 360 = 3 \cdot 120$, so $120$ is 3-perfect).
+///
+/// Note: By this definition $1$ is the only 1-perfect number, since $\sigma(1) = 1$.
+///
+/// [A007691](https://oeis.org/A007691)
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// `Some(k)` if $\sigma(n) = kn$, otherwise `None`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::perfect_numbers::is_multiperfect;
+/// assert_eq!(is_multiperfect(120), Some(3));
+/// assert_eq!(is_multiperfect(28), Some(2));
+/// assert_eq!(is_multiperfect(1), Some(1));
+/// assert_eq!(is_multiperfect(7), None);
+/// ```
+pub fn is_multiperfect(n: u64) -> Option<u32> {
+    if n == 0 {
+        return None;
+    }
+    let sigma: u64 = divisor_sum(n);
+    if sigma.is_multiple_of(n) {
+        Some((sigma / n) as u32)
+    } else {
+        None
+    }
+}
+
+/// Determines whether or not a number is unitary perfect, meaning it is the sum of its proper unitary divisors.
+///
+/// Equivalently $\sigma^*(n) = 2n$, where $\sigma^*$ is the [`unitary_divisor_sum`]. Only five unitary perfect
+/// numbers are known, and every one of them is even.
+///
+/// [A002827](https://oeis.org/A002827)
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// Boolean representing whether `n` is unitary perfect.
+///
+/// # Panics
+///
+/// Panics if $\sigma^*(n)$ does not fit in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::perfect_numbers::is_unitary_perfect;
+/// assert_eq!(is_unitary_perfect(6), true); // 1 + 2 + 3
+/// assert_eq!(is_unitary_perfect(60), true); // 1 + 3 + 4 + 5 + 12 + 15 + 20
+/// assert_eq!(is_unitary_perfect(28), false);
+/// ```
+pub fn is_unitary_perfect(n: u64) -> bool {
+    n != 0 && unitary_divisor_sum(n) as u128 == 2 * n as u128
+}
+
+/// Generates the multiperfect numbers below a given limit, along with their order.
+///
+/// Every $\sigma(n)$ below the limit is filled in with a single divisor-sum sieve.
+///
+/// # Arguments
+///
+/// * `limit` - The (exclusive) upper bound of the numbers to check.
+///
+/// # Returns
+///
+/// Vector of `(n, k)` pairs where `n` is a `k`-perfect number less than `limit` (starting with `(1, 1)`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::perfect_numbers::multiperfect_numbers_below;
+/// assert_eq!(multiperfect_numbers_below(400), vec![(1, 1), (6, 2), (28, 2), (120, 3)]);
+/// ```
+pub fn multiperfect_numbers_below(limit: u64) -> Vec<(u64, u32)> {
+    let sums: Vec<u64> = sigma_sieve(limit.saturating_sub(1) as usize);
+    let mut found: Vec<(u64, u32)> = vec![];
+    let mut n: usize = 1;
+    while n < sums.len() {
+        if sums[n].is_multiple_of(n as u64) {
+            found.push((n as u64, (sums[n] / n as u64) as u32));
+        }
+        n += 1;
+    }
+    found
+}
+
+/// Calculates the abundancy index $\frac{\sigma(n)}{n}$ of a number as an exact fraction.
+///
+/// # Arguments
+///
+/// * `n` - The number to find the abundancy index of (must not be `0`).
+///
+/// # Returns
+///
+/// `(numerator, denominator)` of $\frac{\sigma(n)}{n}$ in lowest terms.
+///
+/// # Panics
+///
+/// Panics if `n` is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::perfect_numbers::abundancy_index;
+/// assert_eq!(abundancy_index(6), (2, 1)); // perfect
+/// assert_eq!(abundancy_index(12), (7, 3)); // 28 / 12
+/// ```
+pub fn abundancy_index(n: u64) -> (u64, u64) {
+    if n == 0 {
+        panic!("the abundancy index of 0 is undefined");
+    }
+    let sigma: u64 = divisor_sum(n);
+    let g: u64 = gcd(sigma, n);
+    (sigma / g, n / g)
+}
+
+/// Determines whether or not two numbers are a friendly pair (they have the same abundancy index).
+///
+/// # Arguments
+///
+/// * `a` - The first number.
+/// * `b` - The second number.
+///
+/// # Returns
+///
+/// Boolean representing whether $\frac{\sigma(a)}{a} = \frac{\sigma(b)}{b}$.
+///
+/// # Panics
+///
+/// Panics if either number is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::perfect_numbers::friendly_pair;
+/// assert_eq!(friendly_pair(6, 28), true); // both perfect
+/// assert_eq!(friendly_pair(30, 140), true); // both 12/5
+/// assert_eq!(friendly_pair(6, 12), false);
+/// ```
+pub fn friendly_pair(a: u64, b: u64) -> bool {
+    abundancy_index(a) == abundancy_index(b)
+}
+
+/// Checks the sufficient condition for a number to be solitary (have no friendly pair), $\gcd(n, \sigma(n)) = 1$.
+///
+/// Note: This is only a certificate. Numbers failing it (like $10$) may still be solitary,
+/// it just can't be shown this way.
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// Boolean representing whether `n` and $\sigma(n)$ are coprime, which proves `n` is solitary.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::perfect_numbers::solitary_certificate;
+/// assert_eq!(solitary_certificate(9), true); // sigma(9) = 13
+/// assert_eq!(solitary_certificate(10), false); // sigma(10) = 18
+/// ```
+pub fn solitary_certificate(n: u64) -> bool {
+    n != 0 && gcd(n, divisor_sum(n)) == 1
+}
+
+/// Determines whether or not a number is a perfect totient number, meaning it is the sum of its iterated
+/// totients $\varphi(n) + \varphi(\varphi(n)) + \dots + 1$.
+///
+/// Since $\varphi(n) < n$ for $n > 1$, the iteration always reaches $1$.
+///
+/// [A082897](https://oeis.org/A082897)
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// Boolean representing whether `n` is a perfect totient number (`false` for `0` and `1`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::perfect_numbers::is_perfect_totient;
+/// assert_eq!(is_perfect_totient(9), true); // 6 + 2 + 1
+/// assert_eq!(is_perfect_totient(15), true); // 8 + 4 + 2 + 1
+/// assert_eq!(is_perfect_totient(10), false); // 4 + 2 + 1
+/// ```
+pub fn is_perfect_totient(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    let mut sum: u64 = 0;
+    let mut current: u64 = n;
+    while current > 1 {
+        current = euler_totient(current);
+        sum += current;
+        if sum > n {
+            return false;
+        }
+    }
+    sum == n
+}
+
+/// Generates the perfect totient numbers below a given limit.
+///
+/// The iterated totient sums are memoized, since the sum for $n$ is $\varphi(n)$ plus the (already known)
+/// sum for $\varphi(n) < n$.
+///
+/// # Arguments
+///
+/// * `limit` - The (exclusive) upper bound of the numbers to check.
+///
+/// # Returns
+///
+/// Vector of all perfect totient numbers less than `limit`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::perfect_numbers::perfect_totient_numbers_below;
+/// assert_eq!(perfect_totient_numbers_below(100), vec![3, 9, 15, 27, 39, 81]);
+/// ```
+pub fn perfect_totient_numbers_below(limit: u64) -> Vec<u64> {
+    let totients: Vec<u64> = totient_sieve(limit as usize);
+    let mut sums: Vec<u64> = vec![0; totients.len()];
+    let mut found: Vec<u64> = vec![];
+    let mut n: usize = 2;
+    while n < totients.len() {
+        let phi: u64 = totients[n];
+        sums[n] = phi + sums[phi as usize];
+        if sums[n] == n as u64 {
+            found.push(n as u64);
+        }
+        n += 1;
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shift_or_test() {
+        let mut bits: Vec<u64> = vec![0b101, 0];
+        shift_or(&mut bits, 63);
+        assert_eq!(bits, vec![(1 << 63) | 0b101, 0b10]);
+        // a shift of whole words, with the top word dropped
+        shift_or(&mut bits, 64);
+        assert_eq!(bits, vec![(1 << 63) | 0b101, (1 << 63) | 0b111]);
+        shift_or(&mut bits, 128);
+        assert_eq!(bits, vec![(1 << 63) | 0b101, (1 << 63) | 0b111]);
+    }
+}
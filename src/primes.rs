@@ -1,3 +1,13 @@
+use crate::estimate::prime_count_upper_bound;
+use crate::factorization::{as_perfect_power, factorize, is_semiprime};
+use crate::modular::{
+    addmod, discrete_log, gcd, is_covering_system, jacobi_symbol, kronecker_symbol_unsigned,
+    mod_inverse, mod_pow, mod_pow_u128, mulmod, multiplicative_order, submod,
+};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
 /// Check if a given number is prime.
 ///
 /// This function takes a number `n`
@@ -10,13 +20,17 @@
 ///
 /// A boolean value indicating whether the number is prime (`true`) or not (`false`).
 ///
+/// This generates every prime up to $n/2$ first, so [`check_prime`] should be used instead.
+///
 /// # Examples
 ///
 /// ```
+/// # #![allow(deprecated)]
 /// use bens_number_theory::primes::is_prime;
 /// assert_eq!(is_prime(9_i128), false);
 /// assert_eq!(is_prime(11_u8), true);
 /// ```
+#[deprecated(since = "0.6.0", note = "use `check_prime` instead")]
 pub fn is_prime<T>(n: T) -> bool
 where
     T: num::traits::Zero
@@ -54,12 +68,16 @@ where
 
 /// Generates a list of prime numbers using the Sieve of Eratosthenes algorithm.
 ///
+/// The output is allocated exactly once, with room for [`prime_count_upper_bound`] primes. Only when that
+/// reservation cannot be made (the limit does not fit in a `u64`, or the allocator refuses the memory) does the
+/// vector start empty and grow as usual, instead of aborting before the first prime.
+///
 /// # Arguments
 ///
 /// * `limit`
 ///     * The `limit` parameter specifies the upper limit up to which you want to generate prime numbers.
 ///     * The function `generate_primes` will generate all prime numbers up to this limit and return
-/// them as a vector.
+///       them as a vector.
 ///
 /// # Returns
 ///
@@ -89,7 +107,13 @@ where
     if limit < T::zero() {
         panic!();
     }
-    let mut p: Vec<T> = vec![T::from_i32(2).unwrap(), T::from_u32(3).unwrap()];
+    let mut p: Vec<T> = Vec::new();
+    if let Some(capacity) = generate_primes_capacity(limit.to_u64()) {
+        // a refused reservation leaves the vector to grow as usual
+        let _ = p.try_reserve_exact(capacity);
+    }
+    p.push(T::from_i32(2).unwrap());
+    p.push(T::from_u32(3).unwrap());
     let mut n: T = T::from_i32(5).unwrap();
     while n < limit {
         if is_prime_list(n, p.clone()) {
@@ -100,6 +124,20 @@ where
     p
 }
 
+/// Chooses how many primes [`generate_primes`] reserves room for.
+///
+/// # Arguments
+///
+/// * `limit` - The limit, or `None` if it does not fit in a `u64`.
+///
+/// # Returns
+///
+/// `Some` of [`prime_count_upper_bound`] (at least $2$, for the primes pushed first), or `None` if the limit or
+/// the bound does not fit, in which case nothing is reserved.
+fn generate_primes_capacity(limit: Option<u64>) -> Option<usize> {
+    usize::try_from(prime_count_upper_bound(limit?).max(2)).ok()
+}
+
 /// Check if a given number is prime using an efficient method optimized for in-order generation.
 ///
 /// This function takes a number `n` and a vector of prime numbers `p`.
@@ -160,15 +198,19 @@ where
 ///
 /// Boolean value indicating whether the input number `m` is a prime or not.
 ///
+/// This trial divides by every odd number up to $n/2$, so [`check_prime`] should be used instead.
+///
 /// # Examples
 ///
 /// ```
+/// # #![allow(deprecated)]
 /// use bens_number_theory::primes::is_prime_lazy;
 /// assert_eq!(is_prime_lazy(2_), true);
 /// assert_eq!(is_prime_lazy(3_u128), true);
 /// assert_eq!(is_prime_lazy(4_i128), false);
 /// assert_eq!(is_prime_lazy(5_i32), true);
 /// ```
+#[deprecated(since = "0.6.0", note = "use `check_prime` instead")]
 pub fn is_prime_lazy<T>(n: T) -> bool
 where
     T: num::traits::Zero
@@ -203,6 +245,7 @@ where
 /// Checks if a given number is a Mersenne prime.
 ///
 /// A Mersenne prime is defined as $\forall p \in \mathbb{N}$, if $p$ is prime, then $2^p - 1$ is also prime.
+/// This is [`classify_mersenne`] reduced to whether the answer is [`MersenneClass::MersennePrime`].
 ///
 /// Arguments:
 ///
@@ -233,8 +276,4733 @@ where
         + std::cmp::PartialOrd
         + Copy,
 {
-    if is_prime_lazy(m) && is_prime_lazy((m + T::one()).to_usize().unwrap().ilog2()) {
+    match m.to_u128() {
+        Some(m) => matches!(classify_mersenne(m), MersenneClass::MersennePrime { .. }),
+        None => false,
+    }
+}
+
+/// What [`classify_mersenne`] found out about a number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MersenneClass {
+    /// The number is not $2^p - 1$ for any $p \geq 2$.
+    NotMersenneForm,
+    /// The number is $2^p - 1$ with $p$ composite, so it is composite too (since $2^a - 1 \mid 2^{ab} - 1$).
+    ExponentComposite {
+        /// The exponent.
+        p: u32,
+    },
+    /// The number is $2^p - 1$ with $p$ prime, but it is composite.
+    CompositeMersenne {
+        /// The exponent.
+        p: u32,
+        /// The smallest factor, if one was found among the small candidates $2kp + 1$.
+        known_factor: Option<u128>,
+    },
+    /// The number is a Mersenne prime $2^p - 1$.
+    MersennePrime {
+        /// The exponent.
+        p: u32,
+    },
+}
+
+/// Works out whether a number is a Mersenne number $2^p - 1$, and if so whether it is prime and why not.
+///
+/// The exponent is read off the bit length, a prime exponent is settled by [`lucas_lehmer`], and a composite
+/// $M_p$ is trial divided by the candidates $q = 2kp + 1$ with $q \equiv \pm 1 \pmod 8$ for $k \leq 2^{16}$,
+/// which are the only possible prime factors of $M_p$.
+///
+/// # Arguments
+///
+/// * `m` - The number to classify.
+///
+/// # Returns
+///
+/// The [`MersenneClass`] of `m`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::{classify_mersenne, MersenneClass};
+/// assert_eq!(classify_mersenne(8191), MersenneClass::MersennePrime { p: 13 });
+/// assert_eq!(
+///     classify_mersenne(2047),
+///     MersenneClass::CompositeMersenne { p: 11, known_factor: Some(23) }
+/// );
+/// assert_eq!(classify_mersenne(15), MersenneClass::ExponentComposite { p: 4 });
+/// assert_eq!(classify_mersenne(12), MersenneClass::NotMersenneForm);
+/// ```
+pub fn classify_mersenne(m: u128) -> MersenneClass {
+    const FACTOR_SEARCH: u128 = 1 << 16;
+    if m < 3 || (m & m.wrapping_add(1)) != 0 {
+        return MersenneClass::NotMersenneForm;
+    }
+    let p: u32 = 128 - m.leading_zeros();
+    if !is_prime_u64(p as u64) {
+        return MersenneClass::ExponentComposite { p };
+    }
+    if p == 2 || lucas_lehmer(p) {
+        return MersenneClass::MersennePrime { p };
+    }
+    let mut known_factor: Option<u128> = None;
+    let mut k: u128 = 1;
+    while k <= FACTOR_SEARCH {
+        let q: u128 = 2 * k * p as u128 + 1;
+        if q.saturating_mul(q) > m {
+            break;
+        }
+        if (q % 8 == 1 || q % 8 == 7) && m.is_multiple_of(q) {
+            known_factor = Some(q);
+            break;
+        }
+        k += 1;
+    }
+    MersenneClass::CompositeMersenne { p, known_factor }
+}
+
+/// Checks if the Mersenne number $M_p = 2^p - 1$ is prime using the Lucas–Lehmer test.
+///
+/// For an odd prime $p$, $M_p$ is prime exactly when $s_{p - 2} \equiv 0 \pmod{M_p}$, where $s_0 = 4$ and
+/// $s_{i + 1} = s_i^2 - 2$. Reducing modulo $2^p - 1$ only needs a shift and an addition.
+///
+/// # Arguments
+///
+/// * `p` - The exponent.
+///
+/// # Returns
+///
+/// `true` if $2^p - 1$ is prime.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::lucas_lehmer;
+/// assert!(lucas_lehmer(127));
+/// assert!(!lucas_lehmer(11)); // 2047 = 23 * 89
+/// ```
+pub fn lucas_lehmer(p: u32) -> bool {
+    // without a cancellation flag the test always finishes
+    lucas_lehmer_with_progress(p, None, None) == Ok(true)
+}
+
+/// Runs the Lucas–Lehmer test like [`lucas_lehmer`], with progress reports and a cancellation flag.
+///
+/// Each of the $p - 2$ squarings of a $p$-bit number is expensive for large $p$, so the flag is checked before
+/// every squaring, while progress is only reported at most $1025$ times in total.
+///
+/// # Arguments
+///
+/// * `p` - The exponent.
+/// * `progress` - Called with `(done, total)` where `done` counts the squarings so far out of `total`,
+///   increasing and ending at `(total, total)`.
+/// * `cancel` - A flag that stops the computation once set (from another thread, or from `progress`).
+///
+/// # Returns
+///
+/// `Ok(true)` if $2^p - 1$ is prime, `Ok(false)` if not, or `Err(Cancelled)` if the flag was set before the
+/// test finished.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::lucas_lehmer_with_progress;
+/// use std::cell::Cell;
+///
+/// let last: Cell<(u64, u64)> = Cell::new((0, 0));
+/// let report = |done: u64, total: u64| last.set((done, total));
+/// assert_eq!(lucas_lehmer_with_progress(521, Some(&report), None), Ok(true));
+/// assert_eq!(last.get(), (519, 519));
+/// ```
+pub fn lucas_lehmer_with_progress(
+    p: u32,
+    progress: Option<&dyn Fn(u64, u64)>,
+    cancel: Option<&AtomicBool>,
+) -> Result<bool, Cancelled> {
+    let cancelled = || cancel.is_some_and(|flag| flag.load(Ordering::Relaxed));
+    if cancelled() {
+        return Err(Cancelled);
+    }
+    let report = |done: u64, total: u64| {
+        if let Some(report) = progress {
+            report(done, total);
+        }
+    };
+    if p == 2 {
+        report(0, 0);
+        return Ok(true);
+    }
+    if !is_probable_prime(&num::BigInt::from(p)) {
+        report(0, 0);
+        return Ok(false);
+    }
+    let total: u64 = p as u64 - 2;
+    let interval: u64 = total.div_ceil(1024).max(1);
+    let mersenne: num::BigUint = (num::BigUint::from(1_u32) << p) - 1_u32;
+    let mut s: num::BigUint = num::BigUint::from(4_u32);
+    let mut done: u64 = 0;
+    while done < total {
+        if cancelled() {
+            return Err(Cancelled);
+        }
+        // adding M_p first keeps s^2 - 2 from going negative
+        let mut square: num::BigUint = &s * &s + &mersenne - 2_u32;
+        while square.bits() > p as u64 {
+            square = (&square & &mersenne) + (&square >> p);
+        }
+        if square == mersenne {
+            square = num::BigUint::from(0_u32);
+        }
+        s = square;
+        done += 1;
+        if done.is_multiple_of(interval) || done == total {
+            report(done, total);
+        }
+    }
+    Ok(s == num::BigUint::from(0_u32))
+}
+
+/// Checks if a number $N = k \cdot 2^n - 1$ is prime using the Lucas–Lehmer–Riesel test.
+///
+/// With $u_0 = V_k(P, 1) \bmod N$, the Lucas $V$ sequence for a suitable $P$, and $u_{i + 1} = u_i^2 - 2$, $N$ is
+/// prime exactly when $u_{n - 2} \equiv 0 \pmod N$. When $3 \nmid k$, either $3 \mid N$ (so $N$ is composite
+/// unless it is $3$) or $N \equiv 1 \pmod 3$ and $P = 4$ works, which for $k = 1$ is the Lucas–Lehmer test with
+/// $u_0 = 4$. When $3 \mid k$, $P$ is found with Rödseth's method, as the smallest $P \geq 3$ with
+/// $\left(\frac{P - 2}{N}\right) = 1$ and $\left(\frac{P + 2}{N}\right) = -1$. The arithmetic is done with
+/// `BigUint`, so there is no size ceiling, but each of the $n - 2$ squarings is of an $(n + \log_2 k)$-bit number.
+///
+/// # Arguments
+///
+/// * `k` - The odd multiplier, below $2^n$.
+/// * `n` - The exponent, at least $2$.
+///
+/// # Returns
+///
+/// `true` if $k \cdot 2^n - 1$ is prime.
+///
+/// # Panics
+///
+/// Panics if `k` is even, if `n` is below $2$ or if `k` is not below $2^n$.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::is_riesel_prime;
+/// assert!(is_riesel_prime(3, 2)); // 11
+/// assert!(is_riesel_prime(5, 4)); // 79
+/// assert!(!is_riesel_prime(5, 3)); // 39 = 3 * 13
+/// assert!(is_riesel_prime(1, 127)); // the Mersenne prime 2^127 - 1
+/// // 509203 is a Riesel number, so 509203 * 2^n - 1 is never prime
+/// assert!(!is_riesel_prime(509_203, 20));
+/// ```
+pub fn is_riesel_prime(k: u64, n: u32) -> bool {
+    use num::{BigUint, ToPrimitive, Zero};
+
+    if k.is_multiple_of(2) {
+        panic!("k must be odd");
+    }
+    if n < 2 {
+        panic!("n must be at least 2");
+    }
+    if n < 64 && k >= 1 << n {
+        panic!("k must be below 2^n");
+    }
+    let number: BigUint = (BigUint::from(k) << n) - 1_u32;
+    let p: u64 = if !k.is_multiple_of(3) {
+        if (&number % 3_u32).is_zero() {
+            return number == BigUint::from(3_u32);
+        }
+        4
+    } else {
+        let mut p: u64 = 3;
+        loop {
+            let below: i8 = jacobi_symbol_biguint(p - 2, &number);
+            let above: i8 = jacobi_symbol_biguint(p + 2, &number);
+            if below == 0 || above == 0 {
+                // p - 2 or p + 2 shares a factor with N, so N can only be prime if it is that factor
+                return number
+                    .to_u64()
+                    .is_some_and(|m| m <= p + 2 && is_prime_u64(m));
+            }
+            if below == 1 && above == -1 {
+                break p;
+            }
+            p += 1;
+        }
+    };
+    let two: BigUint = BigUint::from(2_u32);
+    let p_big: BigUint = BigUint::from(p) % &number;
+    // V_j and V_{j + 1}, with V_{2j} = V_j^2 - 2 and V_{2j + 1} = V_j V_{j + 1} - P
+    let (mut v, mut w): (BigUint, BigUint) = (two.clone() % &number, p_big.clone());
+    let mut bit: u32 = 64 - k.leading_zeros();
+    while bit > 0 {
+        bit -= 1;
+        let middle: BigUint = (&v * &w + &number - &p_big) % &number;
+        if (k >> bit) & 1 == 1 {
+            v = middle;
+            w = (&w * &w + &number - &two) % &number;
+        } else {
+            w = middle;
+            v = (&v * &v + &number - &two) % &number;
+        }
+    }
+    let mut u: BigUint = v;
+    let mut i: u32 = 0;
+    while i < n - 2 {
+        u = (&u * &u + &number - &two) % &number;
+        i += 1;
+    }
+    u.is_zero()
+}
+
+/// Calculates the Jacobi symbol $\left(\frac{a}{n}\right)$ of a small number over a `BigUint`.
+///
+/// The factors of $2$ are taken out with the second supplement, and quadratic reciprocity then turns it into
+/// $\left(\frac{n \bmod a}{a}\right)$, which [`jacobi_symbol`](crate::modular::jacobi_symbol) handles.
+///
+/// # Arguments
+///
+/// * `a` - The top argument.
+/// * `n` - The bottom argument, which must be odd.
+///
+/// # Returns
+///
+/// $-1$, $0$ or $1$.
+fn jacobi_symbol_biguint(a: u64, n: &num::BigUint) -> i8 {
+    use num::ToPrimitive;
+
+    let n_mod_8: u64 = (n % 8_u32).to_u64().unwrap();
+    let mut a: u64 = a;
+    if a == 0 {
+        return if *n == num::BigUint::from(1_u32) {
+            1
+        } else {
+            0
+        };
+    }
+    let mut result: i8 = 1;
+    while a.is_multiple_of(2) {
+        a /= 2;
+        if n_mod_8 == 3 || n_mod_8 == 5 {
+            result = -result;
+        }
+    }
+    if a == 1 {
+        return result;
+    }
+    if a % 4 == 3 && n_mod_8 % 4 == 3 {
+        result = -result;
+    }
+    result * jacobi_symbol((n % a).to_u64().unwrap(), a)
+}
+
+/// Checks if a given (arbitrarily large) number is a probable prime using the Miller–Rabin test.
+///
+/// Write $n - 1 = 2^s d$ with $d$ odd. For each base $a$, $n$ passes if $a^d \equiv 1 \pmod n$
+/// or $a^{2^r d} \equiv -1 \pmod n$ for some $0 \le r < s$. A prime passes for every base.
+///
+/// The bases used are the first 13 primes ($2$ through $41$), which makes the answer deterministic for every
+/// $n < 3317044064679887385961981$ (about $3.3 \cdot 10^{24}$). Above that a composite could in principle
+/// slip through, hence *probable*.
+///
+/// # Arguments
+///
+/// * `n` - The number to check for primality.
+///
+/// # Returns
+///
+/// A boolean value indicating whether the number is (probably) prime (`true`) or not (`false`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::is_probable_prime;
+/// use num::BigInt;
+/// use std::str::FromStr;
+///
+/// assert_eq!(is_probable_prime(&BigInt::from(97)), true);
+/// assert_eq!(is_probable_prime(&BigInt::from(561)), false); // a Carmichael number
+/// assert_eq!(is_probable_prime(&BigInt::from_str("170141183460469231731687303715884105727").unwrap()), true); // 2^127 - 1
+/// ```
+pub fn is_probable_prime(n: &num::BigInt) -> bool {
+    use num::BigInt;
+
+    const BASES: [u32; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+    if *n < BigInt::from(2) {
+        return false;
+    }
+    for base in BASES {
+        if *n == BigInt::from(base) {
+            return true;
+        }
+        if (n % base) == BigInt::from(0) {
+            return false;
+        }
+    }
+
+    let one: BigInt = BigInt::from(1);
+    let n_minus_one: BigInt = n - 1;
+    let s: u64 = n_minus_one.trailing_zeros().unwrap();
+    let d: BigInt = &n_minus_one >> s;
+    'bases: for base in BASES {
+        let mut x: BigInt = BigInt::from(base).modpow(&d, n);
+        if x == one || x == n_minus_one {
+            continue;
+        }
+        let mut r: u64 = 1;
+        while r < s {
+            x = (&x * &x) % n;
+            if x == n_minus_one {
+                continue 'bases;
+            }
+            r += 1;
+        }
+        return false;
+    }
+    true
+}
+
+/// Checks many numbers for primality at once, sharing one sieve between all of the small ones.
+///
+/// The values up to $2^{24}$ are answered by looking them up in a single sieve that only runs up to the largest
+/// of them. Larger values are trial divided by the primes below $100$ and then run through Miller–Rabin with
+/// the same twelve bases as [`is_probable_prime`], using `u128` arithmetic instead of `BigInt`, which is
+/// deterministic for every `u64`.
+///
+/// # Arguments
+///
+/// * `values` - The numbers to check.
+///
+/// # Returns
+///
+/// Vector with `true` at index `i` exactly when `values[i]` is prime.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::are_prime;
+/// assert_eq!(are_prime(&[7, 1, 561, 1_000_000_007]), vec![true, false, false, true]);
+/// assert_eq!(are_prime(&[]), vec![]);
+/// ```
+pub fn are_prime(values: &[u64]) -> Vec<bool> {
+    const SIEVE_LIMIT: u64 = 1 << 24;
+    let largest_small: u64 = values
+        .iter()
+        .copied()
+        .filter(|n| *n <= SIEVE_LIMIT)
+        .max()
+        .unwrap_or(0);
+    let flags: Vec<bool> = prime_flags(largest_small as usize);
+    values
+        .iter()
+        .map(|n| {
+            if *n <= SIEVE_LIMIT {
+                flags[*n as usize]
+            } else {
+                is_prime_u64(*n)
+            }
+        })
+        .collect()
+}
+
+/// Checks if a `u64` is prime by trial division by the primes below $100$ followed by Miller–Rabin.
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// `true` if `n` is prime (deterministically).
+pub(crate) fn is_prime_u64(n: u64) -> bool {
+    const SMALL_PRIMES: [u64; 25] = [
+        2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89,
+        97,
+    ];
+    if n < 2 {
+        return false;
+    }
+    for p in SMALL_PRIMES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+    n < 100 * 100 || is_strong_prp_u64(n)
+}
+
+/// The first twelve primes, which are the Miller–Rabin bases of [`mr_witnesses_for`].
+const MR_BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// The published thresholds below which the first few primes are enough as Miller–Rabin bases, as
+/// `(threshold, number of bases)` pairs.
+///
+/// The thresholds are the smallest strong pseudoprimes to all of the bases ([A014233](https://oeis.org/A014233)).
+/// A number of bases that gives the same threshold as one fewer is left out.
+const MR_THRESHOLDS: [(u64, usize); 8] = [
+    (2_047, 1),
+    (1_373_653, 2),
+    (25_326_001, 3),
+    (3_215_031_751, 4),
+    (2_152_302_898_747, 5),
+    (3_474_749_660_383, 6),
+    (341_550_071_728_321, 7),
+    (3_825_123_056_546_413_051, 9),
+];
+
+/// Finds the smallest known set of Miller–Rabin bases that tells the primes from the composites below a bound.
+///
+/// The sets are the first few primes, chosen from the published thresholds (the smallest strong pseudoprime to
+/// the bases $2, 3, \ldots, p_k$, [A014233](https://oeis.org/A014233)). The first twelve primes are enough for
+/// every `u64`, since the smallest strong pseudoprime to all of them is above $2^{64}$.
+///
+/// # Arguments
+///
+/// * `bound` - The exclusive upper bound of the numbers to test.
+///
+/// # Returns
+///
+/// The bases, in increasing order, such that an odd $n$ with $\max(\text{bases}) < n < \text{bound}$ is prime
+/// exactly when it is a strong probable prime to each of them.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::mr_witnesses_for;
+/// assert_eq!(mr_witnesses_for(1_000_000), &[2, 3]);
+/// assert_eq!(mr_witnesses_for(1_373_653), &[2, 3]);
+/// assert_eq!(mr_witnesses_for(1_373_654), &[2, 3, 5]);
+/// assert_eq!(mr_witnesses_for(u64::MAX).len(), 12);
+/// ```
+pub fn mr_witnesses_for(bound: u64) -> &'static [u64] {
+    for (threshold, count) in MR_THRESHOLDS {
+        if bound <= threshold {
+            return &MR_BASES[..count];
+        }
+    }
+    &MR_BASES
+}
+
+/// Runs Miller–Rabin on an odd number with the bases from [`mr_witnesses_for`].
+///
+/// # Arguments
+///
+/// * `n` - An odd number above $37$.
+///
+/// # Returns
+///
+/// `true` if `n` is a strong probable prime to every base, which for a `u64` means it is prime.
+fn is_strong_prp_u64(n: u64) -> bool {
+    let s: u32 = (n - 1).trailing_zeros();
+    let d: u64 = (n - 1) >> s;
+    mr_witnesses_for(n + 1)
+        .iter()
+        .all(|base| is_strong_prp_to_base(n, d, s, *base))
+}
+
+/// Runs one round of Miller–Rabin, checking that $a^d \equiv 1$ or $a^{2^r d} \equiv -1 \pmod{n}$ for some
+/// $0 \leq r < s$.
+///
+/// # Arguments
+///
+/// * `n` - An odd number above $2$.
+/// * `d` - The odd part of $n - 1$.
+/// * `s` - The number of factors of $2$ in $n - 1$.
+/// * `base` - The base $a$.
+///
+/// # Returns
+///
+/// `true` if `n` is a strong probable prime to the base.
+fn is_strong_prp_to_base(n: u64, d: u64, s: u32, base: u64) -> bool {
+    let mut x: u64 = mod_pow(base, d, n);
+    if x == 1 || x == n - 1 {
         return true;
     }
+    let mut r: u32 = 1;
+    while r < s {
+        x = mulmod(x as u128, x as u128, n as u128) as u64;
+        if x == n - 1 {
+            return true;
+        }
+        r += 1;
+    }
+    #[cfg(feature = "diagnostics")]
+    crate::diagnostics::emit(|| crate::diagnostics::Event::WitnessFailed {
+        n: n as u128,
+        base: base as u128,
+    });
     false
 }
+
+/// The exclusive bound of the numbers [`PrimalityStrategy::Table`] can look up.
+const PRIME_TABLE_LIMIT: u128 = 1 << 16;
+
+/// The ways [`check_prime_with`] can check a number for primality.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::PrimalityStrategy;
+/// assert_eq!(PrimalityStrategy::for_number(97), PrimalityStrategy::Table);
+/// assert_eq!(PrimalityStrategy::for_number(1 << 16), PrimalityStrategy::MillerRabin);
+/// assert_eq!(PrimalityStrategy::for_number(1 << 64), PrimalityStrategy::BailliePsw);
+/// assert!(!PrimalityStrategy::MillerRabin.supports(1 << 64));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PrimalityStrategy {
+    /// Looks the number up in a bitset of the primes below $2^{16}$, sieved the first time it is needed.
+    Table,
+    /// Trial division by the primes below $100$ followed by Miller–Rabin with the bases of [`mr_witnesses_for`],
+    /// which is deterministic for every `u64`.
+    MillerRabin,
+    /// The Baillie–PSW test (a strong probable prime test to base $2$ and a strong Lucas test) followed by
+    /// Miller–Rabin with the bases $3$ through $37$. No composite is known to pass Baillie–PSW alone.
+    BailliePsw,
+}
+
+impl PrimalityStrategy {
+    /// Chooses the fastest strategy for a number, which is the one [`check_prime`] uses.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number to be checked.
+    ///
+    /// # Returns
+    ///
+    /// [`Table`](PrimalityStrategy::Table) below $2^{16}$, [`MillerRabin`](PrimalityStrategy::MillerRabin) up to
+    /// `u64::MAX` and [`BailliePsw`](PrimalityStrategy::BailliePsw) above that.
+    pub fn for_number(n: u128) -> PrimalityStrategy {
+        if n < PRIME_TABLE_LIMIT {
+            PrimalityStrategy::Table
+        } else if n <= u64::MAX as u128 {
+            PrimalityStrategy::MillerRabin
+        } else {
+            PrimalityStrategy::BailliePsw
+        }
+    }
+
+    /// Checks whether the strategy can be used on a number.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number to be checked.
+    ///
+    /// # Returns
+    ///
+    /// `true` if `n` is below $2^{16}$ for [`Table`](PrimalityStrategy::Table), fits in a `u64` for
+    /// [`MillerRabin`](PrimalityStrategy::MillerRabin), and always for
+    /// [`BailliePsw`](PrimalityStrategy::BailliePsw).
+    pub fn supports(self, n: u128) -> bool {
+        match self {
+            PrimalityStrategy::Table => n < PRIME_TABLE_LIMIT,
+            PrimalityStrategy::MillerRabin => n <= u64::MAX as u128,
+            PrimalityStrategy::BailliePsw => true,
+        }
+    }
+}
+
+/// Checks if a number is prime, choosing the method from its size.
+///
+/// This is the recommended primality check of the crate. Numbers below $2^{16}$ are looked up in a table, those
+/// that fit in a `u64` get deterministic Miller–Rabin, and larger ones get Baillie–PSW with extra Miller–Rabin
+/// rounds (see [`PrimalityStrategy`]).
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// `true` if `n` is prime.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::check_prime;
+/// assert!(check_prime(65_521));
+/// assert!(!check_prime(561));
+/// assert!(check_prime(18_446_744_073_709_551_557)); // the largest prime below 2^64
+/// assert!(check_prime((1 << 127) - 1));
+/// assert!(!check_prime((1 << 64) + 1));
+/// ```
+pub fn check_prime(n: u128) -> bool {
+    check_prime_with(n, PrimalityStrategy::for_number(n))
+}
+
+/// Checks if a number is prime with a chosen [`PrimalityStrategy`].
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+/// * `strategy` - The method to use.
+///
+/// # Returns
+///
+/// `true` if `n` is prime.
+///
+/// # Panics
+///
+/// Panics if the strategy does not [support](PrimalityStrategy::supports) `n`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::{check_prime_with, PrimalityStrategy};
+/// assert!(check_prime_with(65_537, PrimalityStrategy::MillerRabin));
+/// assert!(check_prime_with(65_537, PrimalityStrategy::BailliePsw));
+/// assert!(!check_prime_with(5_459, PrimalityStrategy::Table)); // a strong Lucas pseudoprime
+/// ```
+pub fn check_prime_with(n: u128, strategy: PrimalityStrategy) -> bool {
+    if !strategy.supports(n) {
+        panic!("the strategy {strategy:?} does not support {n}");
+    }
+    match strategy {
+        PrimalityStrategy::Table => {
+            static TABLE: std::sync::OnceLock<Vec<u64>> = std::sync::OnceLock::new();
+            let table: &[u64] = TABLE.get_or_init(|| prime_bitset(PRIME_TABLE_LIMIT as u64 - 1));
+            table[(n / 64) as usize] >> (n % 64) & 1 == 1
+        }
+        PrimalityStrategy::MillerRabin => is_prime_u64(n as u64),
+        PrimalityStrategy::BailliePsw => is_baillie_psw_prime(n),
+    }
+}
+
+/// Runs Baillie–PSW on a number followed by Miller–Rabin with the bases $3$ through $37$.
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// `true` if `n` is prime or passes every round.
+fn is_baillie_psw_prime(n: u128) -> bool {
+    for p in MR_BASES {
+        if n == p as u128 {
+            return true;
+        }
+        if n.is_multiple_of(p as u128) {
+            return false;
+        }
+    }
+    if n < 41 * 41 {
+        return n > 1;
+    }
+    let s: u32 = (n - 1).trailing_zeros();
+    let d: u128 = (n - 1) >> s;
+    is_strong_prp_to_base_u128(n, d, s, 2)
+        && is_strong_lucas_prp_u128(n)
+        && MR_BASES[1..]
+            .iter()
+            .all(|base| is_strong_prp_to_base_u128(n, d, s, *base as u128))
+}
+
+/// Runs one round of Miller–Rabin on a `u128`, like [`is_strong_prp_to_base`].
+///
+/// # Arguments
+///
+/// * `n` - The odd number being tested.
+/// * `d` - The odd part of $n - 1$.
+/// * `s` - The exponent with $n - 1 = 2^s d$.
+/// * `base` - The base $a$, below `n`.
+///
+/// # Returns
+///
+/// `true` if `n` is a strong probable prime to the base.
+fn is_strong_prp_to_base_u128(n: u128, d: u128, s: u32, base: u128) -> bool {
+    let mut x: u128 = mod_pow_u128(base, d, n);
+    if x == 1 || x == n - 1 {
+        return true;
+    }
+    let mut r: u32 = 1;
+    while r < s {
+        x = mulmod(x, x, n);
+        if x == n - 1 {
+            return true;
+        }
+        r += 1;
+    }
+    #[cfg(feature = "diagnostics")]
+    crate::diagnostics::emit(|| crate::diagnostics::Event::WitnessFailed { n, base });
+    false
+}
+
+/// Finds which of the given bases a number is a strong probable prime to.
+///
+/// For a composite `n` these are its strong liars, the bases for which a Miller–Rabin round wrongly reports
+/// `n` as probably prime. A prime is a strong probable prime to every base it does not divide, so for a prime
+/// every such base is returned even though none of them is lying. A base divisible by `n` is never returned.
+///
+/// # Arguments
+///
+/// * `n` - An odd number above $2$.
+/// * `bases` - The bases to check, which are reduced modulo `n`.
+///
+/// # Returns
+///
+/// Vector of the bases (as given) to which `n` is a strong probable prime, in the order they were given.
+///
+/// # Panics
+///
+/// Panics if `n` is even or below $3$.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::strong_prp_bases;
+///
+/// // 2047 = 23 * 89 is the smallest strong pseudoprime to the base 2
+/// assert_eq!(strong_prp_bases(2047, 2..8), vec![2, 4]);
+/// // 1373653 = 829 * 1657 fools both 2 and 3
+/// assert_eq!(strong_prp_bases(1_373_653, [2, 3, 5]), vec![2, 3]);
+/// ```
+pub fn strong_prp_bases(n: u64, bases: impl IntoIterator<Item = u64>) -> Vec<u64> {
+    if n < 3 || n.is_multiple_of(2) {
+        panic!("n must be odd and at least 3");
+    }
+    let s: u32 = (n - 1).trailing_zeros();
+    let d: u64 = (n - 1) >> s;
+    bases
+        .into_iter()
+        .filter(|base| !base.is_multiple_of(n) && is_strong_prp_to_base(n, d, s, *base))
+        .collect()
+}
+
+/// Finds the smallest base to which an odd composite number is a strong pseudoprime.
+///
+/// Every odd composite has one, since $n - 1 \equiv -1 \pmod{n}$ is always a strong liar, so the search checks
+/// the bases $2, 3, \ldots, n - 1$ in order.
+///
+/// # Arguments
+///
+/// * `n` - The number to find the smallest strong liar of.
+///
+/// # Returns
+///
+/// `Some(a)` with $a \geq 2$ the least strong liar for `n`, or `None` if `n` is prime, even or below $9$.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::smallest_strong_liar;
+/// assert_eq!(smallest_strong_liar(9), Some(8));
+/// assert_eq!(smallest_strong_liar(2047), Some(2));
+/// assert_eq!(smallest_strong_liar(25), Some(7));
+/// assert_eq!(smallest_strong_liar(13), None);
+/// ```
+pub fn smallest_strong_liar(n: u64) -> Option<u64> {
+    if n < 9 || n.is_multiple_of(2) || is_prime_u64(n) {
+        return None;
+    }
+    let s: u32 = (n - 1).trailing_zeros();
+    let d: u64 = (n - 1) >> s;
+    (2..n).find(|base| is_strong_prp_to_base(n, d, s, *base))
+}
+
+/// Finds a small set of bases for which Miller–Rabin is correct on every number below a limit.
+///
+/// Miller–Rabin with a set of bases declares an odd $n > 2$ prime when it is a strong probable prime to every base
+/// not divisible by $n$ (see [`strong_prp_bases`]), so it is correct exactly when no odd composite fools all of the
+/// bases. The set is built greedily as a set cover: each round streams the odd composites below the limit from a
+/// segmented sieve, counts how many of those that fool the bases chosen so far also fool each remaining candidate,
+/// and adds the candidate that leaves the fewest. Nothing is kept between rounds except the chosen bases, so memory
+/// stays at $O(\sqrt{\text{limit}})$, but a greedy cover is not always the smallest one.
+///
+/// # Arguments
+///
+/// * `limit` - The exclusive upper bound of the numbers Miller–Rabin has to be correct on.
+/// * `candidate_bases` - The bases to choose from, with ties going to the one listed first.
+///
+/// # Returns
+///
+/// Vector of the chosen bases in increasing order (empty if no odd composite is below the limit).
+///
+/// # Panics
+///
+/// Panics if even all of the candidates together are fooled by some odd composite below the limit.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::minimal_witness_set;
+///
+/// // 2047 fools the base 2, so a second base is needed below 2^16
+/// assert_eq!(minimal_witness_set(2047, &[2, 3, 5]), vec![2]);
+/// assert_eq!(minimal_witness_set(1 << 16, &[2, 3, 5]), vec![2, 3]);
+/// ```
+pub fn minimal_witness_set(limit: u64, candidate_bases: &[u64]) -> Vec<u64> {
+    let mut chosen: Vec<u64> = vec![];
+    let mut remaining: Vec<u64> = candidate_bases.to_vec();
+    loop {
+        let mut survivors: u64 = 0;
+        let mut fooled: Vec<u64> = vec![0; remaining.len()];
+        for_each_odd_composite_below(limit, |n| {
+            let s: u32 = (n - 1).trailing_zeros();
+            let d: u64 = (n - 1) >> s;
+            let fools =
+                |base: &u64| !base.is_multiple_of(n) && is_strong_prp_to_base(n, d, s, *base);
+            if chosen.iter().all(fools) {
+                survivors += 1;
+                for (i, base) in remaining.iter().enumerate() {
+                    if fools(base) {
+                        fooled[i] += 1;
+                    }
+                }
+            }
+        });
+        if survivors == 0 {
+            chosen.sort();
+            return chosen;
+        }
+        let mut best: usize = 0;
+        for (i, count) in fooled.iter().enumerate() {
+            if *count < fooled[best] {
+                best = i;
+            }
+        }
+        if remaining.is_empty() || fooled[best] == survivors {
+            panic!("no set of the candidate bases is enough below the limit");
+        }
+        chosen.push(remaining.remove(best));
+    }
+}
+
+/// Calls a function on every odd composite below a limit, in increasing order, using a segmented sieve.
+///
+/// # Arguments
+///
+/// * `limit` - The exclusive upper bound.
+/// * `f` - The function to call on each odd composite.
+fn for_each_odd_composite_below(limit: u64, mut f: impl FnMut(u64)) {
+    const BLOCK: u64 = 1 << 18;
+    // 9 is the smallest odd composite
+    if limit <= 9 {
+        return;
+    }
+    let base: Vec<u64> = sieving_primes(limit);
+    let mut low: u64 = 9;
+    while low < limit {
+        let high: u64 = low.saturating_add(BLOCK).min(limit);
+        let mut segment: SieveSegment = SieveSegment::new(low, high);
+        for p in &base {
+            segment.mark_multiples(*p);
+        }
+        let mut primes = segment.unmarked().peekable();
+        // the blocks start at 9 and have an even size, so every block starts at an odd number
+        let mut n: u64 = low;
+        while n < high {
+            if primes.next_if_eq(&n).is_none() {
+                f(n);
+            }
+            n += 2;
+        }
+        low = high;
+    }
+}
+
+/// Finds the prime closest to a number.
+///
+/// The candidates $n, n - 1, n + 1, n - 2, n + 2, \dots$ are checked in that order, so a tie between a prime below
+/// and a prime above goes to the smaller one. Above the largest `u64` prime, $2^{64} - 59$, the upward search would
+/// overflow, so it only looks downward there.
+///
+/// # Arguments
+///
+/// * `n` - The number to search around.
+///
+/// # Returns
+///
+/// The nearest prime to `n` (`2` for `0` and `1`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::nearest_prime;
+/// assert_eq!(nearest_prime(10), 11);
+/// assert_eq!(nearest_prime(15), 13); // 13 and 17 are both 2 away
+/// assert_eq!(nearest_prime(1_500_000), 1_500_007);
+/// assert_eq!(nearest_prime(u64::MAX), 18_446_744_073_709_551_557);
+/// ```
+pub fn nearest_prime(n: u64) -> u64 {
+    let mut distance: u64 = 0;
+    loop {
+        if let Some(below) = n.checked_sub(distance) {
+            if is_prime_u64(below) {
+                return below;
+            }
+        }
+        if let Some(above) = n.checked_add(distance) {
+            if is_prime_u64(above) {
+                return above;
+            }
+        }
+        distance += 1;
+    }
+}
+
+/// Calculates the distance from a number to the nearest prime.
+///
+/// # Arguments
+///
+/// * `n` - The number to measure from.
+///
+/// # Returns
+///
+/// $|n - p|$ for the [`nearest_prime`] $p$, which is `0` exactly when `n` is prime.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::prime_distance;
+/// assert_eq!(prime_distance(13), 0);
+/// assert_eq!(prime_distance(0), 2);
+/// assert_eq!(prime_distance(120), 7); // 113 and 127
+/// ```
+pub fn prime_distance(n: u64) -> u64 {
+    nearest_prime(n).abs_diff(n)
+}
+
+/// Lists the primes used as hash table capacities, each the smallest prime at least twice the one before.
+///
+/// The ladder starts at $5$ and stops at the last entry whose double still fits in a `u64`, so consecutive
+/// entries differ by a factor between $2$ and $2.2$. It is built the first time it is needed and then shared.
+///
+/// # Returns
+///
+/// The ladder, in increasing order.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::hash_prime_ladder;
+/// assert_eq!(hash_prime_ladder()[..6], [5, 11, 23, 47, 97, 197]);
+/// ```
+pub fn hash_prime_ladder() -> &'static [u64] {
+    static LADDER: std::sync::OnceLock<Vec<u64>> = std::sync::OnceLock::new();
+    LADDER.get_or_init(|| {
+        let mut ladder: Vec<u64> = vec![5];
+        while let Some(double) = ladder[ladder.len() - 1].checked_mul(2) {
+            match smallest_prime_at_least(double) {
+                Some(p) => ladder.push(p),
+                None => break,
+            }
+        }
+        ladder
+    })
+}
+
+/// Finds the smallest prime that is at least a given number.
+///
+/// # Arguments
+///
+/// * `n` - The lower bound.
+///
+/// # Returns
+///
+/// `Some(p)` for the smallest prime $p \geq n$, or `None` if it does not fit in a `u64`.
+fn smallest_prime_at_least(n: u64) -> Option<u64> {
+    let mut candidate: u64 = n;
+    while !is_prime_u64(candidate) {
+        candidate = candidate.checked_add(1)?;
+    }
+    Some(candidate)
+}
+
+/// Chooses a prime capacity for a hash table that has to hold at least a given number of buckets.
+///
+/// The answer is looked up in [`hash_prime_ladder`] with a binary search, so no primality test is run on a
+/// rehash. Requests above the ladder fall back to searching for the next prime.
+///
+/// # Arguments
+///
+/// * `at_least` - The smallest acceptable capacity.
+///
+/// # Returns
+///
+/// The first ladder entry that is at least `at_least`, or above the ladder the smallest prime that is.
+///
+/// # Panics
+///
+/// Panics if `at_least` is above the largest `u64` prime, $2^{64} - 59$.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::good_hash_prime;
+/// assert_eq!(good_hash_prime(0), 5);
+/// assert_eq!(good_hash_prime(100), 197);
+/// assert_eq!(good_hash_prime(1_500_000), 1_646_237);
+/// ```
+pub fn good_hash_prime(at_least: u64) -> u64 {
+    let ladder: &[u64] = hash_prime_ladder();
+    match ladder.get(ladder.partition_point(|p| *p < at_least)) {
+        Some(p) => *p,
+        None => match smallest_prime_at_least(at_least) {
+            Some(p) => p,
+            None => panic!("there is no u64 prime that large"),
+        },
+    }
+}
+
+/// Chooses the capacity a hash table should grow to from its current capacity.
+///
+/// # Arguments
+///
+/// * `current` - The current capacity.
+///
+/// # Returns
+///
+/// The first entry of [`hash_prime_ladder`] above `current`, which is roughly double it. Above the ladder, where
+/// doubling would overflow, this is the largest `u64` prime.
+///
+/// # Panics
+///
+/// Panics if `current` is already the largest `u64` prime or above it.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::next_prime_capacity;
+/// assert_eq!(next_prime_capacity(5), 11);
+/// assert_eq!(next_prime_capacity(100), 197);
+/// ```
+pub fn next_prime_capacity(current: u64) -> u64 {
+    const LARGEST_PRIME: u64 = u64::MAX - 58;
+    if current >= LARGEST_PRIME {
+        panic!("there is no u64 prime above the current capacity");
+    }
+    let ladder: &[u64] = hash_prime_ladder();
+    match ladder.get(ladder.partition_point(|p| *p <= current)) {
+        Some(p) => *p,
+        None => LARGEST_PRIME,
+    }
+}
+
+/// Marks the primes up to a limit with the Sieve of Eratosthenes.
+///
+/// # Arguments
+///
+/// * `limit` - The largest number to sieve.
+///
+/// # Returns
+///
+/// Vector of length `limit + 1` where index `i` is `true` exactly when `i` is prime.
+pub(crate) fn prime_flags(limit: usize) -> Vec<bool> {
+    let mut flags: Vec<bool> = vec![true; limit + 1];
+    flags[0] = false;
+    if limit >= 1 {
+        flags[1] = false;
+    }
+    let mut p: usize = 2;
+    while p * p <= limit {
+        if flags[p] {
+            let mut multiple: usize = p * p;
+            while multiple <= limit {
+                flags[multiple] = false;
+                multiple += p;
+            }
+        }
+        p += 1;
+    }
+    flags
+}
+
+/// Marks the primes up to a limit with the Sieve of Eratosthenes, using one bit per number.
+///
+/// This needs an eighth of the memory of a `Vec<bool>`, which matters for limits in the billions (see
+/// [`sieve_memory_bytes`](crate::estimate::sieve_memory_bytes)).
+///
+/// # Arguments
+///
+/// * `limit` - The largest number to sieve.
+///
+/// # Returns
+///
+/// Vector of $\lceil (\text{limit} + 1) / 64 \rceil$ words where bit `i % 64` of word `i / 64` is set exactly when
+/// `i` is prime.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::prime_bitset;
+/// let bits: Vec<u64> = prime_bitset(100);
+/// assert_eq!(bits.len(), 2);
+/// assert_eq!(bits[0], 0x28208a20a08a28ac); // 2, 3, 5, 7, 11, ...
+/// assert!(bits[97 / 64] >> (97 % 64) & 1 == 1);
+/// ```
+pub fn prime_bitset(limit: u64) -> Vec<u64> {
+    let words: usize = (limit as usize + 1).div_ceil(64);
+    let mut bits: Vec<u64> = vec![u64::MAX; words];
+    // clear 0, 1 and everything past the limit
+    bits[0] &= !0b11;
+    let spare: u64 = words as u64 * 64 - (limit + 1);
+    bits[words - 1] &= u64::MAX >> spare;
+    let mut p: u64 = 2;
+    while p * p <= limit {
+        if bits[(p / 64) as usize] >> (p % 64) & 1 == 1 {
+            let mut multiple: u64 = p * p;
+            while multiple <= limit {
+                bits[(multiple / 64) as usize] &= !(1 << (multiple % 64));
+                multiple += p;
+            }
+        }
+        p += 1;
+    }
+    bits
+}
+
+/// The primes up to a limit, as an immutable bitset that is cheap to clone.
+///
+/// Clones share the same bits, so a [`PrimeSet`] handed out by [`SyncPrimeCache::snapshot`] costs nothing to keep
+/// and never changes, even as the cache grows.
+#[derive(Debug, Clone)]
+pub struct PrimeSet {
+    limit: u64,
+    bits: Arc<Vec<u64>>,
+}
+
+impl PrimeSet {
+    /// Sieves the primes up to a limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - The largest number covered by the set.
+    ///
+    /// # Returns
+    ///
+    /// The set of the primes up to `limit`, built with [`prime_bitset`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bens_number_theory::primes::PrimeSet;
+    /// let set: PrimeSet = PrimeSet::new(100);
+    /// assert_eq!(set.contains(97), Some(true));
+    /// assert_eq!(set.contains(91), Some(false));
+    /// assert_eq!(set.contains(101), None);
+    /// ```
+    pub fn new(limit: u64) -> PrimeSet {
+        PrimeSet {
+            limit,
+            bits: Arc::new(prime_bitset(limit)),
+        }
+    }
+
+    /// The largest number covered by the set.
+    ///
+    /// # Returns
+    ///
+    /// The limit the set was sieved to.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Checks if a number is one of the primes in the set.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number to check.
+    ///
+    /// # Returns
+    ///
+    /// `Some(true)` if `n` is prime, `Some(false)` if it is not, or `None` if it is above the limit.
+    pub fn contains(&self, n: u64) -> Option<bool> {
+        if n > self.limit {
+            return None;
+        }
+        Some(self.bits[(n / 64) as usize] >> (n % 64) & 1 == 1)
+    }
+
+    /// Counts the primes in the set.
+    ///
+    /// # Returns
+    ///
+    /// $\pi(\text{limit})$.
+    pub fn count(&self) -> u64 {
+        self.bits.iter().map(|word| word.count_ones() as u64).sum()
+    }
+}
+
+/// The largest limit [`SyncPrimeCache`] grows its sieve to (about 16 MiB of bits).
+const SYNC_CACHE_MAX_LIMIT: u64 = 1 << 27;
+
+/// A prime sieve that grows on demand and can be shared between threads.
+///
+/// Queries only take a read lock on the current [`PrimeSet`] to look up one bit, so they run concurrently and never
+/// wait for a sieve to be built. A query above the limit grows the sieve: the growing thread takes a separate growth
+/// lock, checks the limit again (another thread may have grown it past the number meanwhile), sieves the new set
+/// without holding the read lock, and then swaps it in under a brief write lock. Threads that need a larger sieve
+/// while one is being built wait on the growth lock and usually find the new sieve large enough. The limit at least
+/// doubles with each growth, so the sieve is rebuilt at most about $\log_2$ of the final limit times. Numbers above
+/// $2^{27}$ never grow the sieve and are answered with [`check_prime`].
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::SyncPrimeCache;
+///
+/// let cache: SyncPrimeCache = SyncPrimeCache::new(100);
+/// std::thread::scope(|scope| {
+///     for t in 0..4 {
+///         let cache: &SyncPrimeCache = &cache;
+///         scope.spawn(move || assert!(!cache.is_prime(1_000 + t)));
+///     }
+/// });
+/// assert!(cache.is_prime(997));
+/// assert!(cache.snapshot().limit() >= 1_003);
+/// ```
+#[derive(Debug)]
+pub struct SyncPrimeCache {
+    set: RwLock<PrimeSet>,
+    growth: Mutex<()>,
+    growths: AtomicUsize,
+}
+
+impl SyncPrimeCache {
+    /// Creates a cache with the primes up to a limit already sieved.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - The initial limit of the sieve.
+    ///
+    /// # Returns
+    ///
+    /// The cache.
+    pub fn new(limit: u64) -> SyncPrimeCache {
+        SyncPrimeCache {
+            set: RwLock::new(PrimeSet::new(limit.min(SYNC_CACHE_MAX_LIMIT))),
+            growth: Mutex::new(()),
+            growths: AtomicUsize::new(0),
+        }
+    }
+
+    /// Checks if a number is prime, growing the sieve if it is above the current limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number to check.
+    ///
+    /// # Returns
+    ///
+    /// `true` if `n` is prime.
+    pub fn is_prime(&self, n: u64) -> bool {
+        if let Some(prime) = self.snapshot().contains(n) {
+            return prime;
+        }
+        if n > SYNC_CACHE_MAX_LIMIT {
+            return check_prime(n as u128);
+        }
+        let _growing = self.growth.lock().unwrap_or_else(|e| e.into_inner());
+        let current: PrimeSet = self.snapshot();
+        if let Some(prime) = current.contains(n) {
+            return prime;
+        }
+        let grown: PrimeSet = PrimeSet::new(n.max(2 * current.limit()).min(SYNC_CACHE_MAX_LIMIT));
+        let prime: bool = grown.contains(n).unwrap();
+        *self.set.write().unwrap_or_else(|e| e.into_inner()) = grown;
+        self.growths.fetch_add(1, Ordering::Relaxed);
+        prime
+    }
+
+    /// Takes the current sieve, which stays valid however the cache grows afterwards.
+    ///
+    /// # Returns
+    ///
+    /// The primes up to the current limit.
+    pub fn snapshot(&self) -> PrimeSet {
+        self.set.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Counts how many times the sieve has been grown.
+    ///
+    /// # Returns
+    ///
+    /// The number of sieves built after the first.
+    pub fn growth_count(&self) -> usize {
+        self.growths.load(Ordering::Relaxed)
+    }
+}
+
+/// Finds the prime power underlying a number, which is the information behind the von Mangoldt function.
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// `Some((p, k))` if $n = p^k$ for a prime $p$ and $k \geq 1$, otherwise `None`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::von_mangoldt_exact;
+/// assert_eq!(von_mangoldt_exact(81), Some((3, 4)));
+/// assert_eq!(von_mangoldt_exact(7), Some((7, 1)));
+/// assert_eq!(von_mangoldt_exact(12), None);
+/// assert_eq!(von_mangoldt_exact(1), None);
+/// ```
+pub fn von_mangoldt_exact(n: u64) -> Option<(u64, u32)> {
+    is_prime_power(n)
+}
+
+/// Checks if a number is a prime power $p^k$ with $k \geq 1$.
+///
+/// Rather than factorizing, the number is written as a perfect power $b^k$ with the largest possible $k$
+/// using [`as_perfect_power`], and only the base $b$ (or the number itself) is checked with
+/// [`is_probable_prime`].
+///
+/// [A246655](https://oeis.org/A246655)
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// `Some((p, k))` if $n = p^k$ for a prime $p$ and $k \geq 1$, otherwise `None` (including for `0` and `1`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::is_prime_power;
+/// assert_eq!(is_prime_power(8192), Some((2, 13)));
+/// assert_eq!(is_prime_power(97), Some((97, 1)));
+/// assert_eq!(is_prime_power(36), None); // 6^2
+/// assert_eq!(is_prime_power(1), None);
+/// ```
+pub fn is_prime_power(n: u64) -> Option<(u64, u32)> {
+    let (base, exponent): (u64, u32) = as_perfect_power(n).unwrap_or((n, 1));
+    if is_probable_prime(&num::BigInt::from(base)) {
+        Some((base, exponent))
+    } else {
+        None
+    }
+}
+
+/// Iterates over the prime powers below a limit, in increasing order.
+///
+/// The primes are found with a segmented sieve in blocks of $2^{16}$ as the iterator advances, and merged with
+/// a min-heap holding the next higher power $p^k$ ($k \geq 2$) of each prime seen so far. Only primes up to
+/// $\sqrt{\text{limit}}$ have higher powers below the limit, so the sieving primes, the block and the heap all
+/// stay at $O(\sqrt{\text{limit}})$ memory, and the sieving primes are the only work done before the first item.
+///
+/// # Arguments
+///
+/// * `limit` - The exclusive upper bound.
+///
+/// # Returns
+///
+/// An iterator over every prime power $p^k$ ($k \geq 1$) less than `limit`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::prime_powers_below;
+/// let powers: Vec<u64> = prime_powers_below(20).collect();
+/// assert_eq!(powers, vec![2, 3, 4, 5, 7, 8, 9, 11, 13, 16, 17, 19]);
+/// ```
+pub fn prime_powers_below(limit: u64) -> impl Iterator<Item = u64> {
+    const SEGMENT: u64 = 1 << 16;
+    let base: Vec<u64> = if limit > 2 {
+        sieving_primes(limit)
+    } else {
+        vec![]
+    };
+    let mut segment: Vec<bool> = vec![true; SEGMENT as usize];
+    // the block [low, high) has been sieved, and the primes below `next` have been passed on
+    let mut low: u64 = 2;
+    let mut high: u64 = 2;
+    let mut next: u64 = 2;
+    let mut heap: std::collections::BinaryHeap<std::cmp::Reverse<(u64, u64)>> =
+        std::collections::BinaryHeap::new();
+    std::iter::from_fn(move || {
+        loop {
+            while next < high && !segment[(next - low) as usize] {
+                next += 1;
+            }
+            if next < high || high >= limit {
+                break;
+            }
+            low = high;
+            high = limit.min(low.saturating_add(SEGMENT));
+            sieve_segment(low, high, &base, &mut segment);
+        }
+        let prime: Option<u64> = if next < high { Some(next) } else { None };
+        let (power, p): (u64, u64) = match heap.peek() {
+            Some(std::cmp::Reverse((power, p))) if prime.is_none_or(|prime| *power < prime) => {
+                let (power, p): (u64, u64) = (*power, *p);
+                heap.pop();
+                (power, p)
+            }
+            _ => {
+                let p: u64 = prime?;
+                next += 1;
+                (p, p)
+            }
+        };
+        if let Some(higher) = power.checked_mul(p) {
+            if higher < limit {
+                heap.push(std::cmp::Reverse((higher, p)));
+            }
+        }
+        Some(power)
+    })
+}
+
+/// Calculates the von Mangoldt function of a number.
+///
+/// $$\Lambda(n) :=\begin{cases}
+///     \ln p & \text{if } n = p^k \text{ for a prime } p \text{ and } k \geq 1 \\\\
+///     0 & \text{otherwise}
+/// \end{cases}$$
+///
+/// # Arguments
+///
+/// * `n` - The number to calculate $\Lambda(n)$ of.
+///
+/// # Returns
+///
+/// $\Lambda(n)$, exactly `0.0` when `n` is not a prime power.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::von_mangoldt;
+/// assert_eq!(von_mangoldt(8), 2_f64.ln());
+/// assert_eq!(von_mangoldt(10), 0.0);
+/// ```
+pub fn von_mangoldt(n: u64) -> f64 {
+    match von_mangoldt_exact(n) {
+        Some((p, _)) => (p as f64).ln(),
+        None => 0.0,
+    }
+}
+
+/// Calculates the first Chebyshev function, the sum of the logarithms of the primes up to `x`.
+///
+/// $$\vartheta(x) = \sum_{p \leq x} \ln p$$
+///
+/// The prime number theorem is equivalent to $\vartheta(x) \sim x$.
+///
+/// # Arguments
+///
+/// * `x` - The upper bound of the sum.
+///
+/// # Returns
+///
+/// $\vartheta(x)$.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::chebyshev_theta;
+/// assert!((chebyshev_theta(10) - 210_f64.ln()).abs() < 1e-9); // 2 * 3 * 5 * 7 = 210
+/// ```
+pub fn chebyshev_theta(x: u64) -> f64 {
+    let flags: Vec<bool> = prime_flags(x as usize);
+    let mut theta: f64 = 0.0;
+    for (p, is_prime) in flags.iter().enumerate() {
+        if *is_prime {
+            theta += (p as f64).ln();
+        }
+    }
+    theta
+}
+
+/// Calculates the second Chebyshev function, the sum of the von Mangoldt function up to `x`.
+///
+/// $$\psi(x) = \sum_{n \leq x} \Lambda(n) = \sum_{p^k \leq x} \ln p$$
+///
+/// Each prime $p$ contributes $\ln p$ once for every power $p^k \leq x$.
+///
+/// # Arguments
+///
+/// * `x` - The upper bound of the sum.
+///
+/// # Returns
+///
+/// $\psi(x)$.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::chebyshev_psi;
+/// // the prime powers up to 10 are 2, 3, 4, 5, 7, 8 and 9, so psi(10) = ln(2^3 * 3^2 * 5 * 7)
+/// assert!((chebyshev_psi(10) - 2520_f64.ln()).abs() < 1e-9);
+/// ```
+pub fn chebyshev_psi(x: u64) -> f64 {
+    let flags: Vec<bool> = prime_flags(x as usize);
+    let mut psi: f64 = 0.0;
+    for (p, is_prime) in flags.iter().enumerate() {
+        if *is_prime {
+            let p: u64 = p as u64;
+            let mut power: u64 = p;
+            let mut count: u32 = 1;
+            while power <= x / p {
+                power *= p;
+                count += 1;
+            }
+            psi += count as f64 * (p as f64).ln();
+        }
+    }
+    psi
+}
+
+/// Calculates the first Ramanujan primes.
+///
+/// The $n$-th Ramanujan prime $R_n$ is the smallest number such that $\pi(x) - \pi(x/2) \geq n$ for every
+/// $x \geq R_n$ ([A104272](https://oeis.org/A104272)). Since $R_n < p_{3n}$, the function
+/// $s(x) = \pi(x) - \pi(x/2)$ is sieved up to a bound on $p_{3n}$ and then scanned backwards: $s$ only moves by
+/// steps of $1$, so $R_n$ is one more than the largest $x$ with $s(x) = n - 1$.
+///
+/// # Arguments
+///
+/// * `count` - The number of Ramanujan primes to calculate.
+///
+/// # Returns
+///
+/// Vector of $R_1, \dots, R_{\text{count}}$.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::ramanujan_primes;
+/// assert_eq!(ramanujan_primes(5), vec![2, 11, 17, 29, 41]);
+/// ```
+pub fn ramanujan_primes(count: usize) -> Vec<u64> {
+    if count == 0 {
+        return vec![];
+    }
+    // p_m < m (ln m + ln ln m) for m >= 6
+    let m: f64 = (3 * count).max(6) as f64;
+    let limit: usize = (m * (m.ln() + m.ln().ln())).ceil() as usize;
+    let flags: Vec<bool> = prime_flags(limit);
+    let mut pi: Vec<usize> = vec![0; limit + 1];
+    for x in 1..=limit {
+        pi[x] = pi[x - 1] + flags[x] as usize;
+    }
+
+    let mut ramanujan: Vec<u64> = vec![0; count];
+    let mut found: usize = 0;
+    let mut x: usize = limit;
+    while found < count && x > 0 {
+        let s: usize = pi[x] - pi[x / 2];
+        if s < count && ramanujan[s] == 0 {
+            ramanujan[s] = x as u64 + 1;
+            found += 1;
+        }
+        x -= 1;
+    }
+    ramanujan
+}
+
+/// Checks if a number is a Ramanujan prime (see [`ramanujan_primes`]).
+///
+/// A prime $p$ can only be $R_n$ for $n = \pi(p) - \pi(p/2)$, so the first $n$ Ramanujan primes are
+/// calculated and compared against.
+///
+/// # Arguments
+///
+/// * `p` - The number to check.
+///
+/// # Returns
+///
+/// `true` if `p` is a Ramanujan prime.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::is_ramanujan_prime;
+/// assert!(is_ramanujan_prime(11));
+/// assert!(!is_ramanujan_prime(13));
+/// assert!(!is_ramanujan_prime(12));
+/// ```
+pub fn is_ramanujan_prime(p: u64) -> bool {
+    let flags: Vec<bool> = prime_flags(p as usize);
+    if !flags[p as usize] {
+        return false;
+    }
+    let n: usize = flags[p as usize / 2 + 1..].iter().filter(|f| **f).count();
+    ramanujan_primes(n).last() == Some(&p)
+}
+
+/// Checks if a number is a Chen prime, a prime $p$ where $p + 2$ is either a prime or a semiprime.
+///
+/// [A109611](https://oeis.org/A109611)
+///
+/// # Arguments
+///
+/// * `p` - The number to check.
+///
+/// # Returns
+///
+/// `true` if `p` is a Chen prime. If $p + 2$ does not fit in a `u64` the result is `false`, although no
+/// prime is close enough to `u64::MAX` for this to happen.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::is_chen_prime;
+/// assert!(is_chen_prime(7)); // 9 = 3 * 3
+/// assert!(!is_chen_prime(43)); // 45 = 3 * 3 * 5
+/// assert!(!is_chen_prime(9));
+/// ```
+pub fn is_chen_prime(p: u64) -> bool {
+    let next: u64 = match p.checked_add(2) {
+        Some(next) => next,
+        None => return false,
+    };
+    is_probable_prime(&num::BigInt::from(p))
+        && (is_probable_prime(&num::BigInt::from(next)) || is_semiprime(next))
+}
+
+/// Lists the Chen primes below a limit (see [`is_chen_prime`]).
+///
+/// # Arguments
+///
+/// * `limit` - The exclusive upper bound.
+///
+/// # Returns
+///
+/// Vector of the Chen primes below `limit`, in increasing order.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::chen_primes_below;
+/// assert_eq!(chen_primes_below(50), vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 47]);
+/// ```
+pub fn chen_primes_below(limit: u64) -> Vec<u64> {
+    let flags: Vec<bool> = prime_flags(limit as usize + 2);
+    let mut chen: Vec<u64> = vec![];
+    for p in 2..limit {
+        if flags[p as usize] && (flags[p as usize + 2] || is_semiprime(p + 2)) {
+            chen.push(p);
+        }
+    }
+    chen
+}
+
+/// The outcome of [`is_proth_prime`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProthResult {
+    /// The number is prime, and the witness $a$ satisfies $a^{(n - 1)/2} \equiv -1 \pmod n$.
+    Prime(u64),
+    /// The number is a composite Proth number.
+    Composite,
+    /// The number is not of the form $k \cdot 2^m + 1$ with $k$ odd and $k < 2^m$.
+    NotProthForm,
+}
+
+/// Checks if a Proth number $n = k \cdot 2^m + 1$ (with $k$ odd and $k < 2^m$) is prime using Proth's theorem.
+///
+/// By Proth's theorem $n$ is prime exactly when some $a$ has $a^{(n - 1)/2} \equiv -1 \pmod n$, so the witness
+/// $a$ proves primality on its own. Composites are ruled out with [`is_probable_prime`] (deterministic in the
+/// `u64` range) first, and for primes half of all $a$ work, so the search for a witness is short.
+///
+/// [A080076](https://oeis.org/A080076)
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// [`ProthResult::Prime`] with the smallest witness, [`ProthResult::Composite`], or
+/// [`ProthResult::NotProthForm`] if `n` is not a Proth number.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::{is_proth_prime, ProthResult};
+/// assert_eq!(is_proth_prime(13), ProthResult::Prime(2)); // 3 * 2^2 + 1
+/// assert_eq!(is_proth_prime(2_u64.pow(32) + 1), ProthResult::Composite); // 641 * 6700417
+/// assert_eq!(is_proth_prime(7), ProthResult::NotProthForm); // 3 * 2^1 + 1, but 3 > 2
+/// ```
+pub fn is_proth_prime(n: u64) -> ProthResult {
+    if n < 3 || n.is_multiple_of(2) {
+        return ProthResult::NotProthForm;
+    }
+    let m: u32 = (n - 1).trailing_zeros();
+    let k: u64 = (n - 1) >> m;
+    if m >= 64 || k >= 1 << m {
+        return ProthResult::NotProthForm;
+    }
+    if !is_probable_prime(&num::BigInt::from(n)) {
+        return ProthResult::Composite;
+    }
+    let mut a: u64 = 2;
+    while mod_pow(a, (n - 1) / 2, n) != n - 1 {
+        a += 1;
+    }
+    ProthResult::Prime(a)
+}
+
+/// Lists the Proth primes below a limit (see [`is_proth_prime`]).
+///
+/// Only the Proth numbers are tested, found by running over every $m$ and odd $k < 2^m$.
+///
+/// # Arguments
+///
+/// * `limit` - The exclusive upper bound.
+///
+/// # Returns
+///
+/// Vector of the Proth primes below `limit`, in increasing order.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::proth_primes_below;
+/// assert_eq!(proth_primes_below(200), vec![3, 5, 13, 17, 41, 97, 113, 193]);
+/// ```
+pub fn proth_primes_below(limit: u64) -> Vec<u64> {
+    let mut primes: Vec<u64> = vec![];
+    let mut m: u32 = 1;
+    // the smallest Proth number with exponent m is 2^m + 1
+    while m < 64 && (1_u64 << m) < limit.saturating_sub(1) {
+        let mut k: u64 = 1;
+        while k < 1 << m {
+            let n: u64 = match k.checked_mul(1 << m) {
+                Some(product) if product < limit - 1 => product + 1,
+                _ => break,
+            };
+            if let ProthResult::Prime(_) = is_proth_prime(n) {
+                primes.push(n);
+            }
+            k += 2;
+        }
+        m += 1;
+    }
+    primes.sort_unstable();
+    primes
+}
+
+/// The bound below which [`screen_sierpinski`] and [`screen_riesel`] look for primes dividing whole residue classes.
+const SCREEN_PRIME_LIMIT: u64 = 1000;
+
+/// Every modulus used by [`SierpinskiScreen::covering_set`] divides this, which keeps the covering checks small.
+const COVERING_MODULUS: u64 = 5040;
+
+/// A residue class of exponents $n$ for which a small prime divides every $k \cdot 2^n \pm 1$.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DivisorClass {
+    /// The prime.
+    pub prime: u64,
+    /// The smallest exponent in the class.
+    pub residue: u64,
+    /// The modulus of the class, the multiplicative order of $2$ modulo `prime`.
+    pub modulus: u64,
+}
+
+/// How the search of [`screen_sierpinski`] or [`screen_riesel`] ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScreenResult {
+    /// The number for the exponent `n` is prime, and no smaller exponent gave a prime.
+    PrimeFound {
+        /// The smallest exponent giving a prime.
+        n: u32,
+    },
+    /// No exponent up to the given one gave a prime.
+    NoPrimeUpTo(u32),
+}
+
+/// What [`screen_sierpinski`] or [`screen_riesel`] found out about a multiplier $k$.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SierpinskiScreen {
+    /// The result of the search for a prime.
+    pub result: ScreenResult,
+    /// For each odd prime below $1000$ that divides some of the numbers, the exponents for which it does, in
+    /// increasing order of the primes.
+    pub classes: Vec<DivisorClass>,
+}
+
+impl SierpinskiScreen {
+    /// Finds a set of the small primes whose residue classes together contain every exponent.
+    ///
+    /// Such a covering set proves that no exponent gives a prime, as every number then has a small prime factor
+    /// (the numbers for the smallest exponents may be the small primes themselves). Only classes whose modulus
+    /// divides $5040$ are used. The shortest run of them, in increasing order of the primes, that forms a covering
+    /// system (see [`is_covering_system`](crate::modular::is_covering_system)) is taken, and the primes that are
+    /// not needed are then dropped, largest first.
+    ///
+    /// # Returns
+    ///
+    /// `Some` of the primes in increasing order, or `None` if the classes found do not cover every exponent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bens_number_theory::primes::screen_sierpinski;
+    /// let screen = screen_sierpinski(78_557, 100);
+    /// assert_eq!(screen.covering_set(), Some(vec![3, 5, 7, 13, 19, 37, 73]));
+    /// assert_eq!(screen_sierpinski(3, 100).covering_set(), None);
+    /// ```
+    pub fn covering_set(&self) -> Option<Vec<u64>> {
+        let mut used: Vec<DivisorClass> = self
+            .classes
+            .iter()
+            .filter(|class| COVERING_MODULUS.is_multiple_of(class.modulus))
+            .copied()
+            .collect();
+        let covers = |classes: &[DivisorClass]| -> bool {
+            let congruences: Vec<(u64, u64)> = classes
+                .iter()
+                .map(|class| (class.residue, class.modulus))
+                .collect();
+            is_covering_system(&congruences)
+        };
+        let mut length: usize = 0;
+        while !covers(&used[..length]) {
+            if length == used.len() {
+                return None;
+            }
+            length += 1;
+        }
+        used.truncate(length);
+        let mut i: usize = used.len();
+        while i > 0 {
+            i -= 1;
+            let class: DivisorClass = used.remove(i);
+            if !covers(&used) {
+                used.insert(i, class);
+            }
+        }
+        Some(used.iter().map(|class| class.prime).collect())
+    }
+}
+
+/// Searches for the smallest $n \geq 1$ making $k \cdot 2^n + 1$ prime, as a test of whether $k$ is a Sierpiński
+/// number.
+///
+/// A Sierpiński number is an odd $k$ for which $k \cdot 2^n + 1$ is composite for every $n$, the smallest known
+/// being $78557$. Usually this is because of a covering set of small primes, so the residue classes of $n$ for which
+/// each odd prime $p < 1000$ divides the number are worked out first: as $p \mid k \cdot 2^n + 1$ exactly when
+/// $2^n \equiv -k^{-1} \pmod p$, they are single classes modulo the order of $2$. Exponents in one of those classes
+/// are skipped, and the rest are tested with Proth's theorem once $k < 2^n$: $N = k \cdot 2^n + 1$ is prime exactly
+/// when $a^{(N - 1)/2} \equiv -1 \pmod N$ for a quadratic non-residue $a$, found with the Jacobi symbol. Below that
+/// the number is less than $2^{128}$ and [`check_prime`] is used.
+///
+/// [A046067](https://oeis.org/A046067)
+///
+/// # Arguments
+///
+/// * `k` - The odd multiplier.
+/// * `n_max` - The largest exponent to try.
+///
+/// # Returns
+///
+/// The result of the search together with the residue classes of the small primes.
+///
+/// # Panics
+///
+/// Panics if `k` is even.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::{screen_sierpinski, ScreenResult};
+/// assert_eq!(screen_sierpinski(3, 100).result, ScreenResult::PrimeFound { n: 1 }); // 7
+/// assert_eq!(screen_sierpinski(7, 100).result, ScreenResult::PrimeFound { n: 2 }); // 29
+/// // 5 divides 78557 * 2^n + 1 for every n = 1 (mod 4), and other primes take care of the other exponents
+/// let screen = screen_sierpinski(78_557, 1000);
+/// assert_eq!(screen.result, ScreenResult::NoPrimeUpTo(1000));
+/// assert!(screen.classes.iter().any(|class| (class.prime, class.residue, class.modulus) == (5, 1, 4)));
+/// ```
+pub fn screen_sierpinski(k: u64, n_max: u32) -> SierpinskiScreen {
+    screen_k_times_power_of_two(k, n_max, true)
+}
+
+/// Searches for the smallest $n \geq 1$ making $k \cdot 2^n - 1$ prime, as a test of whether $k$ is a Riesel number.
+///
+/// This is [`screen_sierpinski`] for the numbers $k \cdot 2^n - 1$, where $p \mid k \cdot 2^n - 1$ exactly when
+/// $2^n \equiv k^{-1} \pmod p$. The smallest known Riesel number is $509203$. Once $n \geq 2$ and $k < 2^n$ the
+/// numbers that are left are tested with the Lucas–Lehmer–Riesel test ([`is_riesel_prime`]), and below that with
+/// [`check_prime`].
+///
+/// [A046069](https://oeis.org/A046069)
+///
+/// # Arguments
+///
+/// * `k` - The odd multiplier.
+/// * `n_max` - The largest exponent to try.
+///
+/// # Returns
+///
+/// The result of the search together with the residue classes of the small primes.
+///
+/// # Panics
+///
+/// Panics if `k` is even.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::{screen_riesel, ScreenResult};
+/// assert_eq!(screen_riesel(3, 100).result, ScreenResult::PrimeFound { n: 1 }); // 5
+/// assert_eq!(screen_riesel(1, 100).result, ScreenResult::PrimeFound { n: 2 }); // 3
+/// let screen = screen_riesel(509_203, 500);
+/// assert_eq!(screen.result, ScreenResult::NoPrimeUpTo(500));
+/// assert_eq!(screen.covering_set(), Some(vec![3, 5, 7, 13, 17, 241]));
+/// ```
+pub fn screen_riesel(k: u64, n_max: u32) -> SierpinskiScreen {
+    screen_k_times_power_of_two(k, n_max, false)
+}
+
+/// Runs [`screen_sierpinski`] or [`screen_riesel`].
+///
+/// # Arguments
+///
+/// * `k` - The odd multiplier.
+/// * `n_max` - The largest exponent to try.
+/// * `plus` - `true` for the numbers $k \cdot 2^n + 1$, `false` for $k \cdot 2^n - 1$.
+///
+/// # Returns
+///
+/// The result of the search together with the residue classes of the small primes.
+fn screen_k_times_power_of_two(k: u64, n_max: u32, plus: bool) -> SierpinskiScreen {
+    if k.is_multiple_of(2) {
+        panic!("k must be odd");
+    }
+    let mut classes: Vec<DivisorClass> = vec![];
+    for p in generate_primes(SCREEN_PRIME_LIMIT).into_iter().skip(1) {
+        // a prime dividing k divides none of the numbers
+        if let Some(inverse) = mod_inverse(k % p, p) {
+            let target: u64 = if plus { p - inverse } else { inverse };
+            if let Some(residue) = discrete_log(2, target, p) {
+                classes.push(DivisorClass {
+                    prime: p,
+                    residue,
+                    modulus: multiplicative_order(2, p).unwrap(),
+                });
+            }
+        }
+    }
+    let mut n: u32 = 1;
+    while n <= n_max {
+        // the number itself while it still fits, to tell a small prime apart from a multiple of it
+        let small: Option<u128> = if n < 64 {
+            let shifted: u128 = (k as u128) << n;
+            Some(if plus { shifted + 1 } else { shifted - 1 })
+        } else {
+            None
+        };
+        let divided: bool = classes.iter().any(|class| {
+            n as u64 % class.modulus == class.residue && small != Some(class.prime as u128)
+        });
+        let fits_fast_path: bool = n >= 64 || k < 1 << n;
+        let prime: bool = !divided
+            && match (plus, fits_fast_path) {
+                (true, true) => is_proth_number_prime(k, n),
+                (false, true) if n >= 2 => is_riesel_prime(k, n),
+                _ => check_prime(small.unwrap()),
+            };
+        if prime {
+            return SierpinskiScreen {
+                result: ScreenResult::PrimeFound { n },
+                classes,
+            };
+        }
+        n += 1;
+    }
+    SierpinskiScreen {
+        result: ScreenResult::NoPrimeUpTo(n_max),
+        classes,
+    }
+}
+
+/// Checks if a Proth number $N = k \cdot 2^n + 1$ (with $k$ odd and $k < 2^n$) of any size is prime using Proth's
+/// theorem.
+///
+/// For a quadratic non-residue $a$ of $N$, meaning $\left(\frac{a}{N}\right) = -1$, $N$ is prime exactly when
+/// $a^{(N - 1)/2} \equiv -1 \pmod N$. A perfect square has no such $a$, so it is ruled out first.
+///
+/// # Arguments
+///
+/// * `k` - The odd multiplier, below $2^n$.
+/// * `n` - The exponent.
+///
+/// # Returns
+///
+/// `true` if $k \cdot 2^n + 1$ is prime.
+fn is_proth_number_prime(k: u64, n: u32) -> bool {
+    use num::{BigUint, ToPrimitive};
+
+    let number: BigUint = (BigUint::from(k) << n) + 1_u32;
+    let root: BigUint = number.sqrt();
+    if &root * &root == number {
+        return false;
+    }
+    let mut a: u64 = 3;
+    loop {
+        match jacobi_symbol_biguint(a, &number) {
+            -1 => break,
+            // a shares a factor with N, so N can only be prime if it is that factor
+            0 => return number.to_u64().is_some_and(|m| m <= a && is_prime_u64(m)),
+            _ => a += 1,
+        }
+    }
+    let minus_one: BigUint = &number - 1_u32;
+    BigUint::from(a).modpow(&(&minus_one >> 1), &number) == minus_one
+}
+
+/// Calculates the Cullen number $C_n = n \cdot 2^n + 1$.
+///
+/// [A002064](https://oeis.org/A002064)
+///
+/// # Arguments
+///
+/// * `n` - The index of the Cullen number.
+///
+/// # Returns
+///
+/// `Some(C_n)`, or `None` if it does not fit in a `u128`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::cullen_number;
+/// assert_eq!(cullen_number(3), Some(25));
+/// assert_eq!(cullen_number(141), None);
+/// ```
+pub fn cullen_number(n: u32) -> Option<u128> {
+    (n as u128)
+        .checked_mul(1_u128.checked_shl(n)?)?
+        .checked_add(1)
+}
+
+/// Calculates the Woodall number $W_n = n \cdot 2^n - 1$.
+///
+/// [A003261](https://oeis.org/A003261)
+///
+/// # Arguments
+///
+/// * `n` - The index of the Woodall number.
+///
+/// # Returns
+///
+/// `Some(W_n)`, or `None` if it does not fit in a `u128` (including $W_0 = -1$).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::woodall_number;
+/// assert_eq!(woodall_number(3), Some(23));
+/// assert_eq!(woodall_number(0), None);
+/// ```
+pub fn woodall_number(n: u32) -> Option<u128> {
+    (n as u128)
+        .checked_mul(1_u128.checked_shl(n)?)?
+        .checked_sub(1)
+}
+
+/// Checks if a number is a Cullen prime, a prime of the form $n \cdot 2^n + 1$ (see [`cullen_number`]).
+///
+/// The index $n$ is found by walking up the (rapidly growing) Cullen numbers, and the value is then checked
+/// with [`is_probable_prime`].
+///
+/// # Arguments
+///
+/// * `value` - The number to check.
+///
+/// # Returns
+///
+/// `true` if `value` is a Cullen number and a probable prime.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::is_cullen_prime;
+/// assert!(is_cullen_prime(3)); // 1 * 2^1 + 1
+/// assert!(!is_cullen_prime(25)); // 3 * 2^3 + 1 = 5^2
+/// assert!(!is_cullen_prime(5)); // prime, but not a Cullen number
+/// ```
+pub fn is_cullen_prime(value: u128) -> bool {
+    let mut n: u32 = 1;
+    while let Some(cullen) = cullen_number(n) {
+        if cullen >= value {
+            return cullen == value && is_probable_prime(&num::BigInt::from(value));
+        }
+        n += 1;
+    }
+    false
+}
+
+/// Checks if a number is a Woodall prime, a prime of the form $n \cdot 2^n - 1$ (see [`woodall_number`]).
+///
+/// The index $n$ is found by walking up the (rapidly growing) Woodall numbers, and the value is then checked
+/// with [`is_probable_prime`].
+///
+/// # Arguments
+///
+/// * `value` - The number to check.
+///
+/// # Returns
+///
+/// `true` if `value` is a Woodall number and a probable prime.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::is_woodall_prime;
+/// assert!(is_woodall_prime(7)); // 2 * 2^2 - 1
+/// assert!(is_woodall_prime(383)); // 6 * 2^6 - 1
+/// assert!(!is_woodall_prime(63)); // 4 * 2^4 - 1 = 3^2 * 7
+/// ```
+pub fn is_woodall_prime(value: u128) -> bool {
+    let mut n: u32 = 1;
+    while let Some(woodall) = woodall_number(n) {
+        if woodall >= value {
+            return woodall == value && is_probable_prime(&num::BigInt::from(value));
+        }
+        n += 1;
+    }
+    false
+}
+
+/// Calculates the primorial $p_k\# = p_1 p_2 \cdots p_k$, the product of the first $k$ primes.
+///
+/// [A002110](https://oeis.org/A002110)
+///
+/// # Arguments
+///
+/// * `k` - The number of primes in the product.
+///
+/// # Returns
+///
+/// `Some(p_k#)` (with $p_0\# = 1$), or `None` if it does not fit in a `u128`, which happens from $k = 27$ on.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::primorial;
+/// assert_eq!(primorial(0), Some(1));
+/// assert_eq!(primorial(4), Some(210)); // 2 * 3 * 5 * 7
+/// assert_eq!(primorial(27), None);
+/// ```
+pub fn primorial(k: usize) -> Option<u128> {
+    let mut product: u128 = 1;
+    let mut count: usize = 0;
+    let mut p: u64 = 2;
+    while count < k {
+        if is_prime_u64(p) {
+            product = product.checked_mul(p as u128)?;
+            count += 1;
+        }
+        p += 1;
+    }
+    Some(product)
+}
+
+/// Calculates the Fortunate number of the $k$-th primorial, the smallest $m > 1$ such that $p_k\# + m$ is prime.
+///
+/// Every prime up to $p_k$ divides $p_k\#$, so $p_k\# + m$ is composite for $1 < m \leq p_k$ and the Fortunate
+/// number is at least $p_{k + 1}$. The candidates are computed in `u128` and checked with [`check_prime`].
+///
+/// [A005235](https://oeis.org/A005235)
+///
+/// # Arguments
+///
+/// * `k` - The number of primes in the primorial, from $1$ to $26$ (see [`primorial`]).
+///
+/// # Returns
+///
+/// The Fortunate number $m$.
+///
+/// # Panics
+///
+/// Panics if `k` is $0$ or the primorial does not fit in a `u128`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::fortunate_number;
+/// assert_eq!(fortunate_number(1), 3); // 2 + 1 = 3 is prime, but m = 1 is excluded
+/// assert_eq!(fortunate_number(4), 13); // 210 + 13 = 223
+/// ```
+pub fn fortunate_number(k: usize) -> u64 {
+    if k == 0 {
+        panic!("the Fortunate numbers start at k = 1");
+    }
+    let product: u128 = match primorial(k) {
+        Some(product) => product,
+        None => panic!("the primorial of {k} primes does not fit in a u128"),
+    };
+    let mut m: u64 = 2;
+    loop {
+        match product.checked_add(m as u128) {
+            Some(candidate) if check_prime(candidate) => return m,
+            Some(_) => m += 1,
+            None => panic!("p_{k} # + {m} does not fit in a u128"),
+        }
+    }
+}
+
+/// Calculates the first Fortunate numbers (see [`fortunate_number`]).
+///
+/// # Arguments
+///
+/// * `count` - The number of Fortunate numbers, at most $26$.
+///
+/// # Returns
+///
+/// Vector of the Fortunate numbers of $p_1\#, \ldots, p_{\text{count}}\#$.
+///
+/// # Panics
+///
+/// Panics if `count` is above $26$.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::fortunate_numbers;
+/// assert_eq!(fortunate_numbers(6), vec![3, 5, 7, 13, 23, 17]);
+/// ```
+pub fn fortunate_numbers(count: usize) -> Vec<u64> {
+    (1..=count).map(fortunate_number).collect()
+}
+
+/// Checks Fortune's conjecture, that every Fortunate number is prime, on the first Fortunate numbers.
+///
+/// # Arguments
+///
+/// * `count` - The number of Fortunate numbers to check, at most $26$.
+///
+/// # Returns
+///
+/// `true` if each of the first `count` Fortunate numbers is prime.
+///
+/// # Panics
+///
+/// Panics if `count` is above $26$.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::all_fortunate_prime_below;
+/// assert!(all_fortunate_prime_below(26));
+/// ```
+pub fn all_fortunate_prime_below(count: usize) -> bool {
+    fortunate_numbers(count)
+        .into_iter()
+        .all(|m| check_prime(m as u128))
+}
+
+/// A Pocklington–Lehmer certificate that a number $n$ is prime, produced by [`prove_prime_n_minus_1`].
+///
+/// It records a factored part $F$ and a cofactor $R$ with $n - 1 = FR$ and $F > \sqrt{n}$, and for every prime
+/// $q \mid F$ a witness $a$ with $a^{n - 1} \equiv 1 \pmod n$ and $\gcd(a^{(n - 1)/q} - 1, n) = 1$. Together these
+/// force every prime factor of $n$ to be above $\sqrt{n}$, so $n$ is prime.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrimalityCertificate {
+    /// The number proven prime.
+    pub n: u64,
+    /// The fully factored part $F$ of $n - 1$.
+    pub factored: u64,
+    /// The unfactored rest $R = (n - 1) / F$.
+    pub cofactor: u64,
+    /// `(q, a)` pairs giving a witness `a` for every distinct prime `q` dividing `factored`.
+    pub witnesses: Vec<(u64, u64)>,
+}
+
+/// The bound up to which [`prove_prime_n_minus_1`] trial divides $n - 1$.
+const POCKLINGTON_TRIAL_BOUND: u64 = 1 << 20;
+
+/// Tries to prove a number prime with the Pocklington–Lehmer $n - 1$ test.
+///
+/// $n - 1$ is trial divided by the numbers up to $2^{20}$ until the factored part $F$ passes $\sqrt{n}$, and if
+/// that is not enough a prime cofactor is taken in whole. A witness is then searched for each prime factor of $F$
+/// (see [`PrimalityCertificate`]). Both `n` and the cofactor are first screened with the deterministic `u64`
+/// Miller–Rabin test, so composites are rejected without a witness search.
+///
+/// Every $n - 1 < 2^{40}$ is either factored far enough or leaves a prime cofactor, so below $2^{40}$ this proves
+/// every prime except $2$.
+///
+/// # Arguments
+///
+/// * `n` - The number to prove prime.
+///
+/// # Returns
+///
+/// `Some(certificate)` if `n` is prime and enough of $n - 1$ could be factored, otherwise `None` (always for
+/// composites, and for $2$, since $n - 1 = 1$ has no prime factors).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::{prove_prime_n_minus_1, verify_certificate};
+///
+/// let certificate = prove_prime_n_minus_1(1_000_000_007).unwrap();
+/// assert!(verify_certificate(&certificate));
+/// assert_eq!(prove_prime_n_minus_1(561), None); // Carmichael number 3 * 11 * 17
+/// ```
+pub fn prove_prime_n_minus_1(n: u64) -> Option<PrimalityCertificate> {
+    if n < 3 || !is_prime_u64(n) {
+        return None;
+    }
+    let mut factored: u64 = 1;
+    let mut cofactor: u64 = n - 1;
+    let mut primes: Vec<u64> = vec![];
+    let mut d: u64 = 2;
+    while d <= POCKLINGTON_TRIAL_BOUND
+        && d * d <= cofactor
+        && (factored as u128) * (factored as u128) <= n as u128
+    {
+        if cofactor.is_multiple_of(d) {
+            primes.push(d);
+            while cofactor.is_multiple_of(d) {
+                cofactor /= d;
+                factored *= d;
+            }
+        }
+        d += 1;
+    }
+    if (factored as u128) * (factored as u128) <= n as u128 {
+        if cofactor == 1 || !is_prime_u64(cofactor) {
+            return None;
+        }
+        primes.push(cofactor);
+        factored *= cofactor;
+        cofactor = 1;
+    }
+    let mut witnesses: Vec<(u64, u64)> = vec![];
+    for q in primes {
+        let mut a: u64 = 2;
+        while mod_pow(a, (n - 1) / q, n) == 1 {
+            a += 1;
+        }
+        witnesses.push((q, a));
+    }
+    Some(PrimalityCertificate {
+        n,
+        factored,
+        cofactor,
+        witnesses,
+    })
+}
+
+/// Checks a [`PrimalityCertificate`] from scratch.
+///
+/// Every condition is re-checked: $FR = n - 1$, $F^2 > n$, the primes listed are prime (with
+/// [`is_probable_prime`], deterministic in the `u64` range) and account for all of $F$, and every witness
+/// satisfies both congruences.
+///
+/// # Arguments
+///
+/// * `certificate` - The certificate to check.
+///
+/// # Returns
+///
+/// `true` if the certificate proves that `certificate.n` is prime.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::{prove_prime_n_minus_1, verify_certificate};
+///
+/// let mut certificate = prove_prime_n_minus_1(97).unwrap();
+/// assert!(verify_certificate(&certificate));
+/// certificate.n = 91;
+/// assert!(!verify_certificate(&certificate));
+/// ```
+pub fn verify_certificate(certificate: &PrimalityCertificate) -> bool {
+    let n: u64 = certificate.n;
+    if n < 3
+        || certificate.factored.checked_mul(certificate.cofactor) != Some(n - 1)
+        || (certificate.factored as u128) * (certificate.factored as u128) <= n as u128
+    {
+        return false;
+    }
+    let mut rest: u64 = certificate.factored;
+    for (q, a) in &certificate.witnesses {
+        if !is_probable_prime(&num::BigInt::from(*q)) || !rest.is_multiple_of(*q) {
+            return false;
+        }
+        while rest.is_multiple_of(*q) {
+            rest /= q;
+        }
+        if mod_pow(*a, n - 1, n) != 1 {
+            return false;
+        }
+        let power: u64 = mod_pow(*a, (n - 1) / q, n);
+        let reduced: u64 = if power == 0 { n - 1 } else { power - 1 };
+        if gcd(reduced, n) != 1 {
+            return false;
+        }
+    }
+    rest == 1
+}
+
+/// A Pratt certificate that a number is prime, produced by [`pratt_certificate`].
+///
+/// A number $p$ is prime exactly when some $g$ has order $p - 1$ modulo $p$ (a primitive root), which holds when
+/// $g^{p - 1} \equiv 1$ and $g^{(p - 1)/q} \not\equiv 1 \pmod p$ for every prime $q \mid p - 1$. The primes $q$ are
+/// proven the same way, so the certificate is a tree whose leaves are the primes $2$ and $3$.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PrattCert {
+    /// The prime $2$ or $3$, taken as known.
+    Base(u64),
+    /// The prime `p` with the primitive root `root`, and the certified prime factorization of $p - 1$ as
+    /// `(certificate, exponent)` pairs.
+    Node {
+        /// The prime being certified.
+        p: u64,
+        /// A primitive root modulo `p`.
+        root: u64,
+        /// The prime factors of $p - 1$ with their exponents.
+        factors: Vec<(PrattCert, u32)>,
+    },
+}
+
+impl PrattCert {
+    /// The prime the certificate is for.
+    ///
+    /// # Returns
+    ///
+    /// The certified prime.
+    pub fn prime(&self) -> u64 {
+        match self {
+            PrattCert::Base(p) => *p,
+            PrattCert::Node { p, .. } => *p,
+        }
+    }
+}
+
+/// Builds a Pratt certificate that a number is prime.
+///
+/// The prime factors of $p - 1$ are found with [`factorize`], the smallest primitive root is searched for,
+/// and every prime factor is certified recursively.
+///
+/// # Arguments
+///
+/// * `p` - The number to prove prime.
+///
+/// # Returns
+///
+/// `Some(certificate)` if `p` is prime, otherwise `None`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::{pratt_certificate, verify_pratt, PrattCert};
+///
+/// let certificate: PrattCert = pratt_certificate(7).unwrap();
+/// // 3 is a primitive root of 7, and 7 - 1 = 2 * 3
+/// assert_eq!(
+///     certificate,
+///     PrattCert::Node { p: 7, root: 3, factors: vec![(PrattCert::Base(2), 1), (PrattCert::Base(3), 1)] }
+/// );
+/// assert!(verify_pratt(&certificate));
+/// assert_eq!(pratt_certificate(9), None);
+/// ```
+pub fn pratt_certificate(p: u64) -> Option<PrattCert> {
+    if p == 2 || p == 3 {
+        return Some(PrattCert::Base(p));
+    }
+    if p < 2 || !is_probable_prime(&num::BigInt::from(p)) {
+        return None;
+    }
+    let prime_factors: Vec<(u64, u32)> = factorize(p - 1);
+    let mut root: u64 = 2;
+    while prime_factors
+        .iter()
+        .any(|(q, _)| mod_pow(root, (p - 1) / q, p) == 1)
+    {
+        root += 1;
+    }
+    let mut factors: Vec<(PrattCert, u32)> = vec![];
+    for (q, e) in prime_factors {
+        factors.push((pratt_certificate(q)?, e));
+    }
+    Some(PrattCert::Node { p, root, factors })
+}
+
+/// Checks a [`PrattCert`] from scratch, recursively.
+///
+/// # Arguments
+///
+/// * `certificate` - The certificate to check.
+///
+/// # Returns
+///
+/// `true` if the certificate proves that [`certificate.prime()`](PrattCert::prime) is prime.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::{pratt_certificate, verify_pratt, PrattCert};
+///
+/// assert!(verify_pratt(&pratt_certificate(1_000_003).unwrap()));
+/// // 2 has order 3 modulo 7, so it is not a primitive root
+/// let fake: PrattCert =
+///     PrattCert::Node { p: 7, root: 2, factors: vec![(PrattCert::Base(2), 1), (PrattCert::Base(3), 1)] };
+/// assert!(!verify_pratt(&fake));
+/// ```
+pub fn verify_pratt(certificate: &PrattCert) -> bool {
+    let (p, root, factors): (u64, u64, &Vec<(PrattCert, u32)>) = match certificate {
+        PrattCert::Base(p) => return *p == 2 || *p == 3,
+        PrattCert::Node { p, root, factors } => (*p, *root, factors),
+    };
+    if p < 2 {
+        return false;
+    }
+    let mut product: u64 = 1;
+    for (factor, e) in factors {
+        let q: u64 = factor.prime();
+        product = match q
+            .checked_pow(*e)
+            .and_then(|power| product.checked_mul(power))
+        {
+            Some(product) => product,
+            None => return false,
+        };
+        if !verify_pratt(factor) || mod_pow(root, (p - 1) / q, p) == 1 {
+            return false;
+        }
+    }
+    product == p - 1 && mod_pow(root, p - 1, p) == 1
+}
+
+/// Finds the Lucas sequence parameters for `n` by Selfridge's Method A.
+///
+/// $D$ runs through $5, -7, 9, -11, \dots$ until the Kronecker symbol $\left(\frac{D}{n}\right)$ is $-1$, and
+/// then $P = 1$ and $Q = (1 - D)/4$ (so that $D = P^2 - 4Q$). No such $D$ exists when `n` is a perfect square, so
+/// if none of the first $32$ candidates works `n` is checked for being a square, and the search only continues if
+/// it is not. Candidates with $\left(\frac{D}{n}\right) = 0$ are skipped like any other.
+///
+/// # Arguments
+///
+/// * `n` - The number to find parameters for (normally odd).
+///
+/// # Returns
+///
+/// `Some((D, P, Q))` for the first $D$ found, or `None` if `n` is a perfect square (including `0` and `1`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::selfridge_parameters;
+/// assert_eq!(selfridge_parameters(323), Some((5, 1, -1)));
+/// assert_eq!(selfridge_parameters(5459), Some((-7, 1, 2)));
+/// assert_eq!(selfridge_parameters(1159), Some((13, 1, -3)));
+/// assert_eq!(selfridge_parameters(361), None);
+/// ```
+pub fn selfridge_parameters(n: u64) -> Option<(i64, i64, i64)> {
+    const SELFRIDGE_SQUARE_CHECK: u32 = 32;
+    let mut d: i64 = 5;
+    let mut tried: u32 = 0;
+    loop {
+        if kronecker_symbol_unsigned(d, n) == -1 {
+            return Some((d, 1, (1 - d) / 4));
+        }
+        tried += 1;
+        if tried == SELFRIDGE_SQUARE_CHECK {
+            let root: u64 = n.isqrt();
+            if root * root == n {
+                return None;
+            }
+        }
+        d = if d > 0 { -d - 2 } else { -d + 2 };
+    }
+}
+
+/// Finds [`selfridge_parameters`] reduced modulo `n`, for the Lucas-style tests.
+///
+/// # Arguments
+///
+/// * `n` - An odd number above $1$ that is not a perfect square.
+///
+/// # Returns
+///
+/// `Some((D, Q))` reduced modulo `n`, or `None` if a candidate before $D$ shares a factor with `n`, which proves
+/// `n` composite unless the candidate is $\pm n$.
+fn selfridge_parameters_mod(n: u64) -> Option<(u64, u64)> {
+    let (d, _, q): (i64, i64, i64) = selfridge_parameters(n)?;
+    let mut k: u64 = 5;
+    while k < d.unsigned_abs() {
+        if k != n && gcd(k, n) != 1 {
+            return None;
+        }
+        k += 2;
+    }
+    let reduced: u64 = (d as i128).rem_euclid(n as i128) as u64;
+    Some((reduced, (q as i128).rem_euclid(n as i128) as u64))
+}
+
+/// Calculates the Lucas sequences $U_k$ and $V_k$ for $P = 1$ modulo `n`, along with $Q^k$.
+///
+/// The index is built up bit by bit with $U_{2k} = U_k V_k$, $V_{2k} = V_k^2 - 2Q^k$,
+/// $U_{k + 1} = (U_k + V_k)/2$ and $V_{k + 1} = (DU_k + V_k)/2$, where halving is done by adding `n` to odd
+/// values (so `n` must be odd). Products are taken in `u128`.
+///
+/// # Arguments
+///
+/// * `k` - The index.
+/// * `d` - The discriminant $D = P^2 - 4Q$, reduced modulo `n`.
+/// * `q` - The parameter $Q$, reduced modulo `n`.
+/// * `n` - The odd modulus.
+///
+/// # Returns
+///
+/// `(U_k, V_k, Q^k)` modulo `n`.
+fn lucas_sequence(k: u64, d: u64, q: u64, n: u64) -> (u64, u64, u64) {
+    let (u, v, qk): (u128, u128, u128) =
+        lucas_sequence_u128(k as u128, d as u128, q as u128, n as u128);
+    (u as u64, v as u64, qk as u64)
+}
+
+/// Calculates the Lucas sequences of [`lucas_sequence`] for a `u128` modulus.
+///
+/// Halving an odd value uses $(x + n)/2 = \lfloor x/2 \rfloor + \lfloor n/2 \rfloor + 1$, which cannot overflow.
+///
+/// # Arguments
+///
+/// * `k` - The index.
+/// * `d` - The discriminant $D = P^2 - 4Q$, reduced modulo `n`.
+/// * `q` - The parameter $Q$, reduced modulo `n`.
+/// * `n` - The odd modulus.
+///
+/// # Returns
+///
+/// `(U_k, V_k, Q^k)` modulo `n`.
+fn lucas_sequence_u128(k: u128, d: u128, q: u128, n: u128) -> (u128, u128, u128) {
+    let half = |x: u128| -> u128 {
+        if x.is_multiple_of(2) {
+            x / 2
+        } else {
+            x / 2 + n / 2 + 1
+        }
+    };
+    let (mut u, mut v, mut qk): (u128, u128, u128) = (0, 2 % n, 1 % n);
+    let mut bit: u32 = 128 - k.leading_zeros();
+    while bit > 0 {
+        bit -= 1;
+        u = mulmod(u, v, n);
+        v = submod(mulmod(v, v, n), addmod(qk, qk, n), n);
+        qk = mulmod(qk, qk, n);
+        if (k >> bit) & 1 == 1 {
+            let next_u: u128 = half(addmod(u, v, n));
+            let next_v: u128 = half(addmod(mulmod(d, u, n), v, n));
+            u = next_u;
+            v = next_v;
+            qk = mulmod(qk, q, n);
+        }
+    }
+    (u, v, qk)
+}
+
+/// Calculates the Jacobi symbol $\left(\frac{a}{n}\right)$ for a `u128`, like
+/// [`jacobi_symbol`](crate::modular::jacobi_symbol).
+///
+/// # Arguments
+///
+/// * `a` - The top argument.
+/// * `n` - The bottom argument, which must be odd.
+///
+/// # Returns
+///
+/// $-1$, $0$ or $1$.
+fn jacobi_symbol_u128(a: u128, n: u128) -> i8 {
+    let mut a: u128 = a % n;
+    let mut n: u128 = n;
+    let mut result: i8 = 1;
+    while a != 0 {
+        while a.is_multiple_of(2) {
+            a /= 2;
+            if n % 8 == 3 || n % 8 == 5 {
+                result = -result;
+            }
+        }
+        std::mem::swap(&mut a, &mut n);
+        if a % 4 == 3 && n % 4 == 3 {
+            result = -result;
+        }
+        a %= n;
+    }
+    if n == 1 {
+        result
+    } else {
+        0
+    }
+}
+
+/// Checks if a `u128` is a strong Lucas probable prime, like [`is_strong_lucas_prp`].
+///
+/// # Arguments
+///
+/// * `n` - An odd number above $37$ with no prime factor up to $37$.
+///
+/// # Returns
+///
+/// `true` if `n` is a strong Lucas probable prime with Selfridge's parameters.
+fn is_strong_lucas_prp_u128(n: u128) -> bool {
+    let root: u128 = n.isqrt();
+    if root * root == n {
+        return false;
+    }
+    let reduce = |x: i128| -> u128 {
+        if x >= 0 {
+            x as u128 % n
+        } else {
+            (n - x.unsigned_abs() % n) % n
+        }
+    };
+    let mut d: i128 = 5;
+    loop {
+        match jacobi_symbol_u128(reduce(d), n) {
+            -1 => break,
+            // n is above |D|, so it shares a factor with D
+            0 => return false,
+            _ => d = if d > 0 { -d - 2 } else { -d + 2 },
+        }
+    }
+    let (reduced_d, reduced_q): (u128, u128) = (reduce(d), reduce((1 - d) / 4));
+    let s: u32 = (n + 1).trailing_zeros();
+    let (u, mut v, mut qk): (u128, u128, u128) =
+        lucas_sequence_u128((n + 1) >> s, reduced_d, reduced_q, n);
+    if u == 0 || v == 0 {
+        return true;
+    }
+    let mut r: u32 = 1;
+    while r < s {
+        v = submod(mulmod(v, v, n), addmod(qk, qk, n), n);
+        qk = mulmod(qk, qk, n);
+        if v == 0 {
+            return true;
+        }
+        r += 1;
+    }
+    false
+}
+
+/// Handles the cases shared by the Lucas-style tests: small and even numbers and perfect squares.
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// `Some(answer)` if the answer is already known, otherwise `None` for odd non-squares above $1$.
+fn lucas_screen(n: u64) -> Option<bool> {
+    if n < 2 {
+        return Some(false);
+    }
+    if n == 2 {
+        return Some(true);
+    }
+    let root: u64 = n.isqrt();
+    if n.is_multiple_of(2) || root * root == n {
+        return Some(false);
+    }
+    None
+}
+
+/// Checks if a number is a Lucas probable prime with Selfridge's parameters.
+///
+/// An odd prime $n$ with $\left(\frac{D}{n}\right) = -1$ divides $U_{n + 1}$ (see [`is_strong_lucas_prp`]
+/// for the choice of $D$). Composites passing are Lucas pseudoprimes ([A217120](https://oeis.org/A217120)),
+/// starting with $323$ and $377$.
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// `true` if `n` is a Lucas probable prime.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::is_lucas_prp;
+/// assert!(is_lucas_prp(101));
+/// assert!(is_lucas_prp(323)); // 17 * 19
+/// assert!(!is_lucas_prp(325));
+/// ```
+pub fn is_lucas_prp(n: u64) -> bool {
+    if let Some(answer) = lucas_screen(n) {
+        return answer;
+    }
+    let (d, q): (u64, u64) = match selfridge_parameters_mod(n) {
+        Some(parameters) => parameters,
+        None => return false,
+    };
+    let (u, _, _): (u64, u64, u64) = lucas_sequence(n + 1, d, q, n);
+    u == 0
+}
+
+/// Checks if a number is a strong Lucas probable prime with Selfridge's Method A parameters.
+///
+/// $D$ is the first of $5, -7, 9, -11, \dots$ with $\left(\frac{D}{n}\right) = -1$, $P = 1$ and $Q = (1 - D)/4$.
+/// Writing $n + 1 = 2^s d$ with $d$ odd, an odd prime $n$ has $U_d \equiv 0$ or $V_{2^r d} \equiv 0 \pmod n$ for
+/// some $0 \le r < s$. Perfect squares are screened out first, since no such $D$ exists for them.
+///
+/// Composites passing are strong Lucas pseudoprimes ([A217255](https://oeis.org/A217255)), starting with
+/// $5459$ and $5777$. None of them is known to also be a strong probable prime to base $2$, which is what
+/// makes this the second half of the Baillie–PSW test.
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// `true` if `n` is a strong Lucas probable prime.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::is_strong_lucas_prp;
+/// assert!(is_strong_lucas_prp(101));
+/// assert!(!is_strong_lucas_prp(323)); // a Lucas pseudoprime, but not a strong one
+/// assert!(!is_strong_lucas_prp(2047)); // a strong pseudoprime to base 2
+/// ```
+pub fn is_strong_lucas_prp(n: u64) -> bool {
+    if let Some(answer) = lucas_screen(n) {
+        return answer;
+    }
+    let (d, q): (u64, u64) = match selfridge_parameters_mod(n) {
+        Some(parameters) => parameters,
+        None => return false,
+    };
+    let s: u32 = (n + 1).trailing_zeros();
+    let (u, mut v, mut qk): (u64, u64, u64) = lucas_sequence((n + 1) >> s, d, q, n);
+    if u == 0 || v == 0 {
+        return true;
+    }
+    let m: u128 = n as u128;
+    let mut r: u32 = 1;
+    while r < s {
+        v = submod(mulmod(v as u128, v as u128, m), 2 * qk as u128, m) as u64;
+        qk = mulmod(qk as u128, qk as u128, m) as u64;
+        if v == 0 {
+            return true;
+        }
+        r += 1;
+    }
+    false
+}
+
+/// Checks if a number is a Frobenius probable prime for $x^2 - x + Q$ with Selfridge's parameters.
+///
+/// With $D$ and $Q$ chosen as in [`is_strong_lucas_prp`] and $\gcd(n, 2QD) = 1$, an odd prime $n$ satisfies
+/// $x^{n + 1} \equiv Q$ in $\mathbb{Z}_n[x]/(x^2 - x + Q)$, which is the same as $U_{n + 1} \equiv 0$ and
+/// $V_{n + 1} \equiv 2Q \pmod n$. This rejects some strong Lucas pseudoprimes (like $5459$) but not all (like
+/// $5777$).
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// `true` if `n` is a Frobenius probable prime.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::is_frobenius_prp;
+/// assert!(is_frobenius_prp(101));
+/// assert!(!is_frobenius_prp(5459));
+/// assert!(!is_frobenius_prp(2047));
+/// ```
+pub fn is_frobenius_prp(n: u64) -> bool {
+    if let Some(answer) = lucas_screen(n) {
+        return answer;
+    }
+    let (d, q): (u64, u64) = match selfridge_parameters_mod(n) {
+        Some(parameters) => parameters,
+        None => return false,
+    };
+    if gcd(n, q) != 1 {
+        return false;
+    }
+    let (u, v, _): (u64, u64, u64) = lucas_sequence(n + 1, d, q, n);
+    u == 0 && v as u128 == mulmod(2, q as u128, n as u128)
+}
+
+/// Lists the primes in an arithmetic progression below a limit.
+///
+/// The progression is checked against a single sieve up to `limit`. When $\gcd(\text{first}, \text{step}) > 1$
+/// every term shares that factor, so at most one term (`first` itself) can be prime.
+///
+/// # Arguments
+///
+/// * `first` - The first term of the progression.
+/// * `step` - The common difference.
+/// * `limit` - The exclusive upper bound.
+///
+/// # Returns
+///
+/// Vector of the primes of the form $\text{first} + k \cdot \text{step}$ below `limit`, in increasing order.
+///
+/// # Panics
+///
+/// Panics if `step` is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::primes_in_ap;
+/// assert_eq!(primes_in_ap(1, 10, 100), vec![11, 31, 41, 61, 71]);
+/// assert_eq!(primes_in_ap(3, 6, 100), vec![3]);
+/// ```
+pub fn primes_in_ap(first: u64, step: u64, limit: u64) -> Vec<u64> {
+    if step == 0 {
+        panic!("the step must be positive");
+    }
+    let mut primes: Vec<u64> = vec![];
+    if first >= limit {
+        return primes;
+    }
+    let flags: Vec<bool> = prime_flags(limit as usize - 1);
+    let mut term: u64 = first;
+    while term < limit {
+        if flags[term as usize] {
+            primes.push(term);
+        }
+        term = match term.checked_add(step) {
+            Some(next) => next,
+            None => break,
+        };
+    }
+    primes
+}
+
+/// Counts the primes below a limit that are congruent to `residue` modulo `modulus`, in a single sieve pass.
+///
+/// By Dirichlet's theorem the primes are split evenly between the $\varphi(m)$ classes coprime to $m$ in the
+/// long run, although classes of non-residues tend to stay slightly ahead (Chebyshev's bias).
+///
+/// # Arguments
+///
+/// * `modulus` - The modulus.
+/// * `residue` - The residue class (reduced modulo `modulus`).
+/// * `limit` - The exclusive upper bound.
+///
+/// # Returns
+///
+/// The number of primes $p < \text{limit}$ with $p \equiv \text{residue} \pmod{\text{modulus}}$.
+///
+/// # Panics
+///
+/// Panics if `modulus` is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::count_primes_in_residue_class;
+/// assert_eq!(count_primes_in_residue_class(4, 1, 100), 11);
+/// assert_eq!(count_primes_in_residue_class(4, 3, 100), 13);
+/// ```
+pub fn count_primes_in_residue_class(modulus: u64, residue: u64, limit: u64) -> u64 {
+    if modulus == 0 {
+        panic!("the modulus must be positive");
+    }
+    let residue: u64 = residue % modulus;
+    let flags: Vec<bool> = prime_flags((limit as usize).max(1) - 1);
+    let mut count: u64 = 0;
+    for (n, prime) in flags.iter().enumerate() {
+        if *prime && n as u64 % modulus == residue {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Lists the primes in a residue class in increasing order, without an upper bound.
+///
+/// Blocks of $2^{16}$ numbers are sieved one at a time as the iterator is advanced, with the sieving primes
+/// extended whenever a block needs more of them, and only the terms of the class are read from each block. When
+/// $g = \gcd(\text{residue}, \text{modulus}) > 1$ every term is a multiple of $g$, so the only prime the class
+/// can hold is $g$ itself, and the iterator yields just that (if it is prime and in the class). Otherwise the
+/// class holds infinitely many primes by Dirichlet's theorem, and the iterator only ends at `u64::MAX`.
+///
+/// # Arguments
+///
+/// * `modulus` - The modulus.
+/// * `residue` - The residue class (reduced modulo `modulus`).
+///
+/// # Returns
+///
+/// An iterator of the primes $p \equiv \text{residue} \pmod{\text{modulus}}$.
+///
+/// # Panics
+///
+/// Panics if `modulus` is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::primes_in_class;
+/// let primes: Vec<u64> = primes_in_class(10, 3).take(5).collect();
+/// assert_eq!(primes, vec![3, 13, 23, 43, 53]);
+/// assert_eq!(primes_in_class(4, 2).collect::<Vec<u64>>(), vec![2]);
+/// assert_eq!(primes_in_class(6, 0).count(), 0);
+/// ```
+pub fn primes_in_class(modulus: u64, residue: u64) -> impl Iterator<Item = u64> {
+    if modulus == 0 {
+        panic!("the modulus must be positive");
+    }
+    let residue: u64 = residue % modulus;
+    let common: u64 = gcd(residue, modulus);
+    let (low, pending): (Option<u64>, Vec<u64>) = if common == 1 {
+        (Some(0), vec![])
+    } else if common % modulus == residue && is_prime_u64(common) {
+        (None, vec![common])
+    } else {
+        (None, vec![])
+    };
+    ClassPrimes {
+        modulus,
+        residue,
+        low,
+        base: vec![],
+        base_limit: 0,
+        pending: pending.into_iter(),
+    }
+}
+
+/// The iterator behind [`primes_in_class`].
+struct ClassPrimes {
+    /// The modulus of the class.
+    modulus: u64,
+    /// The residue of the class, below `modulus`.
+    residue: u64,
+    /// The start of the next block to sieve, or `None` once there are no more.
+    low: Option<u64>,
+    /// The sieving primes, enough for every number below `base_limit`.
+    base: Vec<u64>,
+    /// The exclusive bound of the numbers `base` can sieve.
+    base_limit: u64,
+    /// The primes of the class found in the last block and not yet returned.
+    pending: std::vec::IntoIter<u64>,
+}
+
+impl Iterator for ClassPrimes {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        const BLOCK: u64 = 1 << 16;
+        loop {
+            if let Some(p) = self.pending.next() {
+                return Some(p);
+            }
+            let low: u64 = self.low?;
+            let high: u64 = low.saturating_add(BLOCK);
+            if high > self.base_limit {
+                // doubling the bound means the sieving primes are only rebuilt a logarithmic number of times
+                self.base_limit = high.saturating_mul(2);
+                self.base = sieving_primes(self.base_limit);
+            }
+            let mut segment: SieveSegment = SieveSegment::new(low, high);
+            for p in &self.base {
+                segment.mark_multiples(*p);
+            }
+            let position: u64 = low % self.modulus;
+            let offset: u64 = if self.residue >= position {
+                self.residue - position
+            } else {
+                self.residue + (self.modulus - position)
+            };
+            let mut found: Vec<u64> = vec![];
+            let mut term: Option<u64> = low.checked_add(offset);
+            while let Some(n) = term.filter(|n| *n < high) {
+                if !segment.is_marked(n) {
+                    found.push(n);
+                }
+                term = n.checked_add(self.modulus);
+            }
+            self.pending = found.into_iter();
+            self.low = if high == u64::MAX { None } else { Some(high) };
+        }
+    }
+}
+
+/// Counts the primes below a limit in every residue class of a modulus at once, in one segmented sieve pass.
+///
+/// The classes coprime to the modulus share the primes evenly in the long run (Dirichlet's theorem), while the
+/// others hold at most one prime each (see [`primes_in_class`]). Comparing the counts shows Chebyshev's bias, the
+/// tendency of the classes of quadratic non-residues to stay ahead.
+///
+/// # Arguments
+///
+/// * `modulus` - The modulus $q$.
+/// * `limit` - The exclusive upper bound.
+///
+/// # Returns
+///
+/// Vector with $\pi(\text{limit}; q, a)$, the number of primes below `limit` congruent to $a$ modulo $q$, at
+/// index $a$ for every $0 \leq a < q$.
+///
+/// # Panics
+///
+/// Panics if `modulus` is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::class_counts_below;
+/// assert_eq!(class_counts_below(4, 100), vec![0, 11, 1, 13]);
+/// assert_eq!(class_counts_below(3, 100), vec![1, 11, 13]);
+/// ```
+pub fn class_counts_below(modulus: u64, limit: u64) -> Vec<u64> {
+    if modulus == 0 {
+        panic!("the modulus must be positive");
+    }
+    let mut counts: Vec<u64> = vec![0; modulus as usize];
+    for_each_prime_below(limit, |p| counts[(p % modulus) as usize] += 1);
+    counts
+}
+
+/// Finds an arithmetic progression of `length` primes below a limit.
+///
+/// Every pair of primes $b < c$ is tried as the last two terms, with the earlier terms checked against a
+/// sieve, so this is meant for small lengths (up to about $6$). By the Green–Tao theorem progressions of
+/// every length exist.
+///
+/// # Arguments
+///
+/// * `limit` - The exclusive upper bound of the terms.
+/// * `length` - The number of terms.
+///
+/// # Returns
+///
+/// `Some(progression)` with the smallest possible last term (and then the smallest first term), or `None`
+/// if there is no such progression below `limit`.
+///
+/// # Panics
+///
+/// Panics if `length` is less than $2$.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::longest_ap_of_primes_below;
+/// assert_eq!(longest_ap_of_primes_below(100, 3), Some(vec![3, 5, 7]));
+/// assert_eq!(longest_ap_of_primes_below(100, 5), Some(vec![5, 11, 17, 23, 29]));
+/// assert_eq!(longest_ap_of_primes_below(100, 6), None); // the first is 7, 37, ..., 157
+/// ```
+pub fn longest_ap_of_primes_below(limit: u64, length: usize) -> Option<Vec<u64>> {
+    if length < 2 {
+        panic!("the progression must have at least 2 terms");
+    }
+    let flags: Vec<bool> = prime_flags((limit as usize).max(1) - 1);
+    let primes: Vec<u64> = (0..flags.len() as u64)
+        .filter(|n| flags[*n as usize])
+        .collect();
+    for (i, c) in primes.iter().enumerate() {
+        for b in &primes[..i] {
+            let step: u64 = c - b;
+            let span: u64 = match step.checked_mul(length as u64 - 1) {
+                Some(span) if span <= *c => span,
+                _ => continue,
+            };
+            let first: u64 = c - span;
+            let progression: Vec<u64> = (0..length as u64).map(|k| first + k * step).collect();
+            if progression.iter().all(|term| flags[*term as usize]) {
+                return Some(progression);
+            }
+        }
+    }
+    None
+}
+
+/// Counts how many consecutive $n = 0, 1, 2, \dots$ make $n^2 + an + b$ prime.
+///
+/// Negative values (and $0$ and $1$) count as not prime.
+///
+/// # Arguments
+///
+/// * `a` - The linear coefficient.
+/// * `b` - The constant term.
+///
+/// # Returns
+///
+/// The number of leading values of the polynomial that are prime.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::consecutive_prime_run;
+/// assert_eq!(consecutive_prime_run(1, 41), 40); // Euler's polynomial
+/// assert_eq!(consecutive_prime_run(-79, 1601), 80);
+/// assert_eq!(consecutive_prime_run(0, 4), 0);
+/// ```
+pub fn consecutive_prime_run(a: i64, b: i64) -> u32 {
+    quadratic_prime_run(a, b, &[])
+}
+
+/// Counts the leading prime values of $n^2 + an + b$ (see [`consecutive_prime_run`]), looking values up in
+/// a sieve when they are small enough and using [`is_probable_prime`] otherwise.
+///
+/// # Arguments
+///
+/// * `a` - The linear coefficient.
+/// * `b` - The constant term.
+/// * `flags` - Primality flags from [`prime_flags`] (possibly empty).
+///
+/// # Returns
+///
+/// The number of leading values of the polynomial that are prime.
+fn quadratic_prime_run(a: i64, b: i64, flags: &[bool]) -> u32 {
+    let mut n: i128 = 0;
+    loop {
+        let value: i128 = n * n + a as i128 * n + b as i128;
+        let prime: bool = match u64::try_from(value) {
+            Ok(value) if (value as usize) < flags.len() => flags[value as usize],
+            Ok(value) => is_probable_prime(&num::BigInt::from(value)),
+            Err(_) => value > 0 && is_probable_prime(&num::BigInt::from(value)),
+        };
+        if !prime {
+            return n as u32;
+        }
+        n += 1;
+    }
+}
+
+/// Searches a box of coefficients for the quadratic $n^2 + an + b$ with the longest run of prime values
+/// (see [`consecutive_prime_run`]).
+///
+/// The values are looked up in a sieve up to $2^{20}$, since almost every run ends after a few small values.
+///
+/// # Arguments
+///
+/// * `a_range` - The linear coefficients to try.
+/// * `b_range` - The constant terms to try.
+///
+/// # Returns
+///
+/// `(a, b, run)` for the longest run, taking the smallest `a` and then the smallest `b` on ties (`(0, 0, 0)`
+/// if a range is empty).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::best_quadratic_coefficients;
+/// assert_eq!(best_quadratic_coefficients(-10..=10, 0..=50), (-5, 47, 43)); // Euler's polynomial, shifted
+/// ```
+pub fn best_quadratic_coefficients(
+    a_range: std::ops::RangeInclusive<i64>,
+    b_range: std::ops::RangeInclusive<i64>,
+) -> (i64, i64, u32) {
+    let flags: Vec<bool> = prime_flags(1 << 20);
+    let mut best: (i64, i64, u32) = (0, 0, 0);
+    let mut found: bool = false;
+    for a in a_range {
+        for b in b_range.clone() {
+            let run: u32 = quadratic_prime_run(a, b, &flags);
+            if !found || run > best.2 {
+                best = (a, b, run);
+                found = true;
+            }
+        }
+    }
+    best
+}
+
+/// The error returned when a long computation is stopped through its cancellation flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl std::fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "the computation was cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// Calls a function on every prime below a limit, in increasing order, using a segmented sieve.
+///
+/// The primes up to $\sqrt{\text{limit}}$ are sieved once, and then blocks of $2^{16}$ numbers are sieved with
+/// them one at a time, so memory stays at $O(\sqrt{\text{limit}})$.
+///
+/// # Arguments
+///
+/// * `limit` - The exclusive upper bound.
+/// * `f` - The function to call on each prime.
+pub(crate) fn for_each_prime_below(limit: u64, mut f: impl FnMut(u64)) {
+    // without a cancellation flag the sieve always finishes
+    let _: Result<(), Cancelled> = sieve_primes_below(limit, &mut f, None, None);
+}
+
+/// Runs the segmented sieve behind [`for_each_prime_below`], reporting progress and checking for cancellation
+/// once per block.
+///
+/// # Arguments
+///
+/// * `limit` - The exclusive upper bound.
+/// * `f` - The function to call on each prime.
+/// * `progress` - Called with `(done, limit)` after every block, where `done` is how far the sieve has got.
+/// * `cancel` - Checked before every block.
+///
+/// # Returns
+///
+/// `Ok(())` once every prime has been passed to `f`, or `Err(Cancelled)` if the flag was set first.
+fn sieve_primes_below(
+    limit: u64,
+    f: &mut dyn FnMut(u64),
+    progress: Option<&dyn Fn(u64, u64)>,
+    cancel: Option<&AtomicBool>,
+) -> Result<(), Cancelled> {
+    const SEGMENT: u64 = 1 << 16;
+    let cancelled = || cancel.is_some_and(|flag| flag.load(Ordering::Relaxed));
+    if cancelled() {
+        return Err(Cancelled);
+    }
+    if limit > 2 {
+        let base: Vec<u64> = sieving_primes(limit);
+        let mut low: u64 = 2;
+        let mut segment: Vec<bool> = vec![true; SEGMENT as usize];
+        while low < limit {
+            if cancelled() {
+                return Err(Cancelled);
+            }
+            let high: u64 = limit.min(low.saturating_add(SEGMENT));
+            sieve_segment(low, high, &base, &mut segment);
+            for n in low..high {
+                if segment[(n - low) as usize] {
+                    f(n);
+                }
+            }
+            low = high;
+            if low < limit {
+                if let Some(report) = progress {
+                    report(low, limit);
+                }
+            }
+        }
+    }
+    if let Some(report) = progress {
+        report(limit, limit);
+    }
+    Ok(())
+}
+
+/// Finds the primes needed to sieve every number below a limit, which are those up to $\sqrt{\text{limit} - 1}$.
+///
+/// # Arguments
+///
+/// * `limit` - The exclusive upper bound of the numbers to be sieved (at least `1`).
+///
+/// # Returns
+///
+/// Vector of the primes $p$ with $p^2 < \text{limit}$, in increasing order.
+pub(crate) fn sieving_primes(limit: u64) -> Vec<u64> {
+    let root: u64 = (limit - 1).isqrt();
+    let flags: Vec<bool> = prime_flags(root as usize);
+    (2..=root).filter(|p| flags[*p as usize]).collect()
+}
+
+/// Sieves one block of numbers with the primes up to the square root of its end.
+///
+/// # Arguments
+///
+/// * `low` - The first number of the block (at least `2`).
+/// * `high` - The exclusive end of the block.
+/// * `base` - The sieving primes in increasing order, including every prime $p$ with $p^2 < \text{high}$.
+/// * `segment` - Filled so that index `i` is `true` exactly when `low + i` is prime, for `i < high - low`.
+pub(crate) fn sieve_segment(low: u64, high: u64, base: &[u64], segment: &mut [bool]) {
+    segment.fill(true);
+    for p in base {
+        if p * p >= high {
+            break;
+        }
+        let mut multiple: u64 = (p * p).max(low.div_ceil(*p) * p);
+        while multiple < high {
+            segment[(multiple - low) as usize] = false;
+            multiple += p;
+        }
+    }
+    #[cfg(feature = "diagnostics")]
+    crate::diagnostics::emit(|| crate::diagnostics::Event::SieveSegmentDone {
+        lo: low,
+        hi: high,
+        count: segment[..(high - low) as usize]
+            .iter()
+            .filter(|prime| **prime)
+            .count() as u64,
+    });
+}
+
+/// Generates the primes below a limit like [`generate_primes`], with progress reports and a cancellation flag.
+///
+/// The primes come from a segmented sieve, so progress is reported and the flag is checked once every $2^{16}$
+/// numbers, which keeps the overhead negligible even for limits around $10^9$.
+///
+/// # Arguments
+///
+/// * `limit` - The exclusive upper bound.
+/// * `progress` - Called with `(done, limit)` as the sieve advances, with `done` increasing and ending at
+///   `limit`.
+/// * `cancel` - A flag that stops the computation once set (from another thread, or from `progress`).
+///
+/// # Returns
+///
+/// `Ok` with every prime below `limit` in increasing order, or `Err(Cancelled)` if the flag was set before the
+/// sieve finished.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::{generate_primes_with_progress, Cancelled};
+/// use std::cell::Cell;
+/// use std::sync::atomic::AtomicBool;
+///
+/// let calls: Cell<u64> = Cell::new(0);
+/// let report = |_done: u64, _total: u64| calls.set(calls.get() + 1);
+/// let primes: Vec<u64> = generate_primes_with_progress(200_000, Some(&report), None).unwrap();
+/// assert_eq!(primes.len(), 17984);
+/// assert_eq!(calls.get(), 4);
+///
+/// let cancel: AtomicBool = AtomicBool::new(true);
+/// assert_eq!(generate_primes_with_progress(200_000, None, Some(&cancel)), Err(Cancelled));
+/// ```
+pub fn generate_primes_with_progress(
+    limit: u64,
+    progress: Option<&dyn Fn(u64, u64)>,
+    cancel: Option<&AtomicBool>,
+) -> Result<Vec<u64>, Cancelled> {
+    let mut primes: Vec<u64> = vec![];
+    sieve_primes_below(limit, &mut |p: u64| primes.push(p), progress, cancel)?;
+    Ok(primes)
+}
+
+/// The outcome of [`SegmentedSieveRun::run_next_segment`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentResult {
+    /// A segment was sieved.
+    Sieved {
+        /// The position of the segment, starting at `0`.
+        index: u64,
+        /// The first number of the segment.
+        low: u64,
+        /// The exclusive end of the segment.
+        high: u64,
+        /// The number of primes in the segment.
+        count: u64,
+        /// The sum of the primes in the segment.
+        sum: u128,
+    },
+    /// Every segment has already been sieved.
+    Finished,
+}
+
+/// A segmented sieve of the primes below a limit that runs one segment at a time and can be saved and resumed.
+///
+/// The run keeps the sieving primes, the index of the next segment and the running count and sum of the primes
+/// found so far. [`checkpoint`](SegmentedSieveRun::checkpoint) writes all of these out, so a computation toward
+/// $10^{11}$ can be persisted every few segments and picked up again with
+/// [`resume`](SegmentedSieveRun::resume) after a crash.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::{SegmentResult, SegmentedSieveRun};
+///
+/// let mut run: SegmentedSieveRun = SegmentedSieveRun::new(1000, 100);
+/// run.run_next_segment();
+/// let mut saved: Vec<u8> = vec![];
+/// run.checkpoint(&mut saved).unwrap();
+///
+/// let mut resumed: SegmentedSieveRun = SegmentedSieveRun::new(1000, 100);
+/// resumed.resume(saved.as_slice()).unwrap();
+/// while resumed.run_next_segment() != SegmentResult::Finished {}
+/// assert_eq!(resumed.count(), 168);
+/// assert_eq!(resumed.sum(), 76127);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SegmentedSieveRun {
+    limit: u64,
+    segment_size: u64,
+    base: Vec<u64>,
+    next_index: u64,
+    count: u64,
+    sum: u128,
+    segment: Vec<bool>,
+}
+
+impl SegmentedSieveRun {
+    /// The bytes every checkpoint starts with.
+    const MAGIC: [u8; 8] = *b"BNTSIEVE";
+    /// The version of the checkpoint format, bumped whenever the layout changes.
+    const VERSION: u32 = 1;
+
+    /// Starts a run over the numbers below `limit`, sieving the primes up to $\sqrt{\text{limit}}$ straight away.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - The exclusive upper bound.
+    /// * `segment_size` - How many numbers each segment covers.
+    ///
+    /// # Returns
+    ///
+    /// A run with no segments sieved yet. Segment `i` covers the numbers from $2 + i \cdot \text{segment\_size}$.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `segment_size` is `0`.
+    pub fn new(limit: u64, segment_size: u64) -> SegmentedSieveRun {
+        if segment_size == 0 {
+            panic!("the segment size must be positive");
+        }
+        let base: Vec<u64> = if limit > 2 {
+            sieving_primes(limit)
+        } else {
+            vec![]
+        };
+        SegmentedSieveRun {
+            limit,
+            segment_size,
+            base,
+            next_index: 0,
+            count: 0,
+            sum: 0,
+            segment: vec![],
+        }
+    }
+
+    /// Sieves the next segment and adds its primes to the running totals.
+    ///
+    /// # Returns
+    ///
+    /// The index, bounds, count and sum of the segment just sieved, or [`SegmentResult::Finished`] once every
+    /// number below the limit has been covered.
+    pub fn run_next_segment(&mut self) -> SegmentResult {
+        if self.is_finished() {
+            return SegmentResult::Finished;
+        }
+        let index: u64 = self.next_index;
+        let low: u64 = 2 + index * self.segment_size;
+        let high: u64 = self.limit.min(low.saturating_add(self.segment_size));
+        self.segment.resize((high - low) as usize, true);
+        sieve_segment(low, high, &self.base, &mut self.segment);
+        let mut count: u64 = 0;
+        let mut sum: u128 = 0;
+        for n in low..high {
+            if self.segment[(n - low) as usize] {
+                count += 1;
+                sum += n as u128;
+            }
+        }
+        self.next_index += 1;
+        self.count += count;
+        self.sum += sum;
+        SegmentResult::Sieved {
+            index,
+            low,
+            high,
+            count,
+            sum,
+        }
+    }
+
+    /// Checks if every segment has been sieved.
+    ///
+    /// # Returns
+    ///
+    /// `true` once [`run_next_segment`](SegmentedSieveRun::run_next_segment) has nothing left to do.
+    pub fn is_finished(&self) -> bool {
+        self.limit <= 2 || 2 + self.next_index.saturating_mul(self.segment_size) >= self.limit
+    }
+
+    /// Gets the number of segments sieved so far.
+    ///
+    /// # Returns
+    ///
+    /// The index of the next segment.
+    pub fn segments_done(&self) -> u64 {
+        self.next_index
+    }
+
+    /// Gets the number of primes found so far.
+    ///
+    /// # Returns
+    ///
+    /// The count of the primes in every segment sieved so far.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Gets the sum of the primes found so far.
+    ///
+    /// # Returns
+    ///
+    /// The sum of the primes in every segment sieved so far.
+    pub fn sum(&self) -> u128 {
+        self.sum
+    }
+
+    /// Writes the state of the run so that it can be continued with [`resume`](SegmentedSieveRun::resume).
+    ///
+    /// The checkpoint is the magic bytes `BNTSIEVE`, the format version, the limit, the segment size, the next
+    /// segment index, the count, the sum, and the sieving primes (preceded by how many there are), with every
+    /// number in little-endian order.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Where to write the checkpoint.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())`, or the first error from `writer`.
+    pub fn checkpoint(&self, mut writer: impl std::io::Write) -> std::io::Result<()> {
+        writer.write_all(&Self::MAGIC)?;
+        writer.write_all(&Self::VERSION.to_le_bytes())?;
+        for value in [self.limit, self.segment_size, self.next_index, self.count] {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        writer.write_all(&self.sum.to_le_bytes())?;
+        writer.write_all(&(self.base.len() as u64).to_le_bytes())?;
+        for p in &self.base {
+            writer.write_all(&p.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Continues from a checkpoint written by [`checkpoint`](SegmentedSieveRun::checkpoint).
+    ///
+    /// The checkpoint must come from a run with the same limit and segment size as this one, and its sieving
+    /// primes must match, so that the resumed run finishes exactly as an uninterrupted one would.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Where to read the checkpoint from.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` with the progress of the checkpoint restored, or an error of kind
+    /// [`InvalidData`](std::io::ErrorKind::InvalidData) if the checkpoint has the wrong magic bytes or version or
+    /// does not match this run (the run is left unchanged on any error).
+    pub fn resume(&mut self, mut reader: impl std::io::Read) -> std::io::Result<()> {
+        let invalid = |message: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, message);
+        let mut magic: [u8; 8] = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if magic != Self::MAGIC {
+            return Err(invalid("not a sieve checkpoint"));
+        }
+        let mut version: [u8; 4] = [0; 4];
+        reader.read_exact(&mut version)?;
+        if u32::from_le_bytes(version) != Self::VERSION {
+            return Err(invalid("unsupported checkpoint version"));
+        }
+        let mut read_u64 = || -> std::io::Result<u64> {
+            let mut bytes: [u8; 8] = [0; 8];
+            reader.read_exact(&mut bytes)?;
+            Ok(u64::from_le_bytes(bytes))
+        };
+        let limit: u64 = read_u64()?;
+        let segment_size: u64 = read_u64()?;
+        let next_index: u64 = read_u64()?;
+        let count: u64 = read_u64()?;
+        if limit != self.limit || segment_size != self.segment_size {
+            return Err(invalid(
+                "the checkpoint is for a different limit or segment size",
+            ));
+        }
+        let sum: u128 = read_u64()? as u128 | (read_u64()? as u128) << 64;
+        if read_u64()? != self.base.len() as u64 {
+            return Err(invalid("the checkpoint has the wrong sieving primes"));
+        }
+        for p in &self.base {
+            if read_u64()? != *p {
+                return Err(invalid("the checkpoint has the wrong sieving primes"));
+            }
+        }
+        self.next_index = next_index;
+        self.count = count;
+        self.sum = sum;
+        Ok(())
+    }
+}
+
+/// A block of consecutive numbers with one bit per number, for sieving by any set of numbers.
+///
+/// Every number starts unmarked, except $0$ and $1$, which start marked because no sieve of the primes should
+/// leave them. [`mark_multiples`](SieveSegment::mark_multiples) then marks the proper multiples of each sieving
+/// number, so marking the multiples of every prime up to $\sqrt{\text{hi}}$ leaves exactly the primes unmarked,
+/// and marking those of only some primes leaves the numbers with no smaller factor among them.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::SieveSegment;
+///
+/// let mut segment: SieveSegment = SieveSegment::new(90, 110);
+/// for p in [2, 3, 5, 7] {
+///     segment.mark_multiples(p);
+/// }
+/// assert_eq!(segment.unmarked().collect::<Vec<u64>>(), vec![97, 101, 103, 107, 109]);
+/// assert_eq!(segment.count_unmarked(), 5);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SieveSegment {
+    lo: u64,
+    hi: u64,
+    marked: Vec<u64>,
+}
+
+impl SieveSegment {
+    /// Creates a segment covering the numbers from `lo` up to (but not including) `hi`.
+    ///
+    /// # Arguments
+    ///
+    /// * `lo` - The first number of the segment.
+    /// * `hi` - The exclusive end of the segment.
+    ///
+    /// # Returns
+    ///
+    /// A segment with only $0$ and $1$ marked (empty if `hi` is at most `lo`).
+    pub fn new(lo: u64, hi: u64) -> SieveSegment {
+        let hi: u64 = hi.max(lo);
+        let mut segment: SieveSegment = SieveSegment {
+            lo,
+            hi,
+            marked: vec![0; (hi - lo).div_ceil(64) as usize],
+        };
+        for n in lo..hi.min(2) {
+            segment.mark(n);
+        }
+        segment
+    }
+
+    /// Marks a number of the segment.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number to mark, with $\text{lo} \leq n < \text{hi}$.
+    fn mark(&mut self, n: u64) {
+        let i: u64 = n - self.lo;
+        self.marked[(i / 64) as usize] |= 1 << (i % 64);
+    }
+
+    /// Checks whether a number of the segment is marked.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number to check, with $\text{lo} \leq n < \text{hi}$.
+    ///
+    /// # Returns
+    ///
+    /// `true` if `n` has been marked.
+    fn is_marked(&self, n: u64) -> bool {
+        let i: u64 = n - self.lo;
+        self.marked[(i / 64) as usize] >> (i % 64) & 1 == 1
+    }
+
+    /// Marks every proper multiple $kp$ with $k \geq 2$ of a number that lies in the segment.
+    ///
+    /// `p` itself is left alone, so it survives its own sieving the way a prime does.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - The number whose multiples are marked.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is below $2$.
+    pub fn mark_multiples(&mut self, p: u64) {
+        if p < 2 {
+            panic!("the sieving number must be at least 2");
+        }
+        let first: Option<u64> = self
+            .lo
+            .div_ceil(p)
+            .checked_mul(p)
+            .zip(p.checked_mul(2))
+            .map(|(first, double)| first.max(double));
+        let mut multiple: u64 = match first {
+            Some(first) => first,
+            None => return,
+        };
+        while multiple < self.hi {
+            self.mark(multiple);
+            multiple = match multiple.checked_add(p) {
+                Some(next) => next,
+                None => return,
+            };
+        }
+    }
+
+    /// Lists the numbers of the segment that are still unmarked.
+    ///
+    /// # Returns
+    ///
+    /// An iterator of the unmarked numbers in increasing order.
+    pub fn unmarked(&self) -> impl Iterator<Item = u64> + '_ {
+        let len: u64 = self.hi - self.lo;
+        self.marked.iter().enumerate().flat_map(move |(w, word)| {
+            let mut free: u64 = !word;
+            std::iter::from_fn(move || {
+                if free == 0 {
+                    return None;
+                }
+                let i: u64 = w as u64 * 64 + free.trailing_zeros() as u64;
+                if i >= len {
+                    return None;
+                }
+                free &= free - 1;
+                Some(self.lo + i)
+            })
+        })
+    }
+
+    /// Counts the numbers of the segment that are still unmarked.
+    ///
+    /// # Returns
+    ///
+    /// The number of unmarked numbers.
+    pub fn count_unmarked(&self) -> u64 {
+        let marked: u64 = self
+            .marked
+            .iter()
+            .map(|word| word.count_ones() as u64)
+            .sum();
+        self.hi - self.lo - marked
+    }
+}
+
+/// Finds the primes in a range with a segmented sieve.
+///
+/// The primes up to $\sqrt{\text{hi}}$ are found first, and then blocks of $2^{18}$ numbers are sieved with them
+/// using [`SieveSegment`], so memory stays at $O(\sqrt{\text{hi}})$ however long the range is.
+///
+/// # Arguments
+///
+/// * `lo` - The (inclusive) lower bound.
+/// * `hi` - The (exclusive) upper bound.
+///
+/// # Returns
+///
+/// Vector of the primes $p$ with $\text{lo} \leq p < \text{hi}$, in increasing order.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::primes_in_range;
+/// assert_eq!(primes_in_range(0, 20), vec![2, 3, 5, 7, 11, 13, 17, 19]);
+/// assert_eq!(
+///     primes_in_range(1_000_000_000, 1_000_000_050),
+///     vec![1_000_000_007, 1_000_000_009, 1_000_000_021, 1_000_000_033]
+/// );
+/// ```
+pub fn primes_in_range(lo: u64, hi: u64) -> Vec<u64> {
+    let mut primes: Vec<u64> = vec![];
+    for_each_prime_in_range(lo, hi, |p| primes.push(p));
+    primes
+}
+
+/// Calls a function on every prime in a range, in increasing order, using the segmented sieve of
+/// [`primes_in_range`].
+///
+/// # Arguments
+///
+/// * `lo` - The (inclusive) lower bound.
+/// * `hi` - The exclusive upper bound.
+/// * `f` - The function to call on each prime.
+pub(crate) fn for_each_prime_in_range(lo: u64, hi: u64, mut f: impl FnMut(u64)) {
+    const BLOCK: u64 = 1 << 18;
+    if hi <= lo {
+        return;
+    }
+    let base: Vec<u64> = sieving_primes(hi);
+    let mut low: u64 = lo;
+    while low < hi {
+        let high: u64 = low.saturating_add(BLOCK).min(hi);
+        let mut segment: SieveSegment = SieveSegment::new(low, high);
+        for p in &base {
+            segment.mark_multiples(*p);
+        }
+        #[cfg(feature = "diagnostics")]
+        crate::diagnostics::emit(|| crate::diagnostics::Event::SieveSegmentDone {
+            lo: low,
+            hi: high,
+            count: segment.unmarked().count() as u64,
+        });
+        segment.unmarked().for_each(&mut f);
+        low = high;
+    }
+}
+
+/// Calculates the sum of the primes below a limit.
+///
+/// Small limits use [`sum_primes_below_sieve`] and larger ones use [`sum_primes_below_lucy`].
+///
+/// # Arguments
+///
+/// * `limit` - The exclusive upper bound.
+///
+/// # Returns
+///
+/// The sum of every prime less than `limit`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::sum_primes_below;
+/// assert_eq!(sum_primes_below(10), 17);
+/// assert_eq!(sum_primes_below(2_000_000), 142_913_828_922);
+/// ```
+pub fn sum_primes_below(limit: u64) -> u128 {
+    if limit < 1 << 16 {
+        sum_primes_below_sieve(limit)
+    } else {
+        sum_primes_below_lucy(limit)
+    }
+}
+
+/// Calculates the sum of the primes below a limit by adding them up from a segmented sieve.
+///
+/// # Arguments
+///
+/// * `limit` - The exclusive upper bound.
+///
+/// # Returns
+///
+/// The sum of every prime less than `limit`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::sum_primes_below_sieve;
+/// assert_eq!(sum_primes_below_sieve(100), 1060);
+/// ```
+pub fn sum_primes_below_sieve(limit: u64) -> u128 {
+    let mut sum: u128 = 0;
+    for_each_prime_below(limit, |p| sum += p as u128);
+    sum
+}
+
+/// Calculates the sum of the primes below a limit in $O(n^{3/4})$ time with Lucy_Hedgehog's method.
+///
+/// Let $S(v, p)$ be the sum of the numbers from $2$ to $v$ that are prime or have no prime factor up to $p$.
+/// Sieving out the prime $p$ removes $p$ times the numbers left up to $v/p$ that are not smaller primes:
+/// $$S(v, p) = S(v, p - 1) - p \left(S(\lfloor v/p \rfloor, p - 1) - S(p - 1, p - 1)\right).$$
+/// Only the $O(\sqrt{n})$ values $v = \lfloor n/i \rfloor$ are ever needed, and after every $p \le \sqrt{n}$,
+/// $S(n, p)$ is the sum of the primes.
+///
+/// # Arguments
+///
+/// * `limit` - The exclusive upper bound.
+///
+/// # Returns
+///
+/// The sum of every prime less than `limit`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::sum_primes_below_lucy;
+/// assert_eq!(sum_primes_below_lucy(100), 1060);
+/// assert_eq!(sum_primes_below_lucy(10_000_000_000), 2_220_822_432_581_729_238);
+/// ```
+pub fn sum_primes_below_lucy(limit: u64) -> u128 {
+    if limit <= 2 {
+        return 0;
+    }
+    let n: u64 = limit - 1;
+    let root: u64 = n.isqrt();
+    let triangle = |v: u64| -> u128 { v as u128 * (v as u128 + 1) / 2 - 1 };
+    // small[v] = S(v) for v <= root, large[i] = S(n / i) for i <= root
+    let mut small: Vec<u128> = vec![0; root as usize + 1];
+    let mut large: Vec<u128> = vec![0; root as usize + 1];
+    let mut v: u64 = 1;
+    while v <= root {
+        small[v as usize] = triangle(v);
+        large[v as usize] = triangle(n / v);
+        v += 1;
+    }
+    let mut p: u64 = 2;
+    while p <= root {
+        if small[p as usize] != small[p as usize - 1] {
+            let below: u128 = small[p as usize - 1];
+            let square: u64 = p * p;
+            let mut i: u64 = 1;
+            while i <= root && n / i >= square {
+                let quotient: u64 = n / i / p;
+                let rest: u128 = if quotient <= root {
+                    small[quotient as usize]
+                } else {
+                    large[(i * p) as usize]
+                };
+                large[i as usize] -= p as u128 * (rest - below);
+                i += 1;
+            }
+            let mut v: u64 = root;
+            while v >= square {
+                small[v as usize] -= p as u128 * (small[(v / p) as usize] - below);
+                v -= 1;
+            }
+        }
+        p += 1;
+    }
+    large[1]
+}
+
+/// Counts the primes up to a number with the Lucy Hedgehog method.
+///
+/// This is [`sum_primes_below_lucy`] with every prime weighted by $1$ instead of by itself: after sieving with
+/// the primes up to $p$, the count $C(v)$ of numbers in $[2, v]$ without a smaller prime factor drops by
+/// $C(\lfloor v/p \rfloor) - C(p - 1)$. This takes $O(x^{3/4})$ time and $O(\sqrt{x})$ memory.
+///
+/// [A000720](https://oeis.org/A000720)
+///
+/// # Arguments
+///
+/// * `x` - The (inclusive) upper bound.
+///
+/// # Returns
+///
+/// $\pi(x)$, the number of primes $p \leq x$.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::prime_pi;
+/// assert_eq!(prime_pi(10), 4);
+/// assert_eq!(prime_pi(1_000_000_000), 50_847_534);
+/// ```
+pub fn prime_pi(x: u64) -> u64 {
+    if x < 2 {
+        return 0;
+    }
+    let root: u64 = x.isqrt();
+    // small[v] = C(v) for v <= root, large[i] = C(x / i) for i <= root
+    let mut small: Vec<u64> = vec![0; root as usize + 1];
+    let mut large: Vec<u64> = vec![0; root as usize + 1];
+    let mut v: u64 = 1;
+    while v <= root {
+        small[v as usize] = v - 1;
+        large[v as usize] = x / v - 1;
+        v += 1;
+    }
+    let mut p: u64 = 2;
+    while p <= root {
+        if small[p as usize] != small[p as usize - 1] {
+            let below: u64 = small[p as usize - 1];
+            let square: u64 = p * p;
+            let mut i: u64 = 1;
+            while i <= root && x / i >= square {
+                let quotient: u64 = x / i / p;
+                let rest: u64 = if quotient <= root {
+                    small[quotient as usize]
+                } else {
+                    large[(i * p) as usize]
+                };
+                large[i as usize] -= rest - below;
+                i += 1;
+            }
+            let mut v: u64 = root;
+            while v >= square {
+                small[v as usize] -= small[(v / p) as usize] - below;
+                v -= 1;
+            }
+        }
+        p += 1;
+    }
+    large[1]
+}
+
+/// Lists the twin prime pairs $(p, p + 2)$ with $p + 2$ below a limit.
+///
+/// # Arguments
+///
+/// * `limit` - The exclusive upper bound of both primes.
+///
+/// # Returns
+///
+/// Vector of the twin prime pairs, in increasing order.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::twin_primes_below;
+/// assert_eq!(twin_primes_below(20), vec![(3, 5), (5, 7), (11, 13), (17, 19)]);
+/// ```
+pub fn twin_primes_below(limit: u64) -> Vec<(u64, u64)> {
+    let mut pairs: Vec<(u64, u64)> = vec![];
+    let mut previous: u64 = 0;
+    for_each_prime_below(limit, |p| {
+        if previous > 0 && p == previous + 2 {
+            pairs.push((previous, p));
+        }
+        previous = p;
+    });
+    pairs
+}
+
+/// Counts the twin prime pairs $(p, p + 2)$ with $p + 2$ below a limit, in one segmented sieve pass.
+///
+/// [A007508](https://oeis.org/A007508)
+///
+/// # Arguments
+///
+/// * `limit` - The exclusive upper bound of both primes.
+///
+/// # Returns
+///
+/// The number of twin prime pairs below `limit`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::count_twin_primes_below;
+/// assert_eq!(count_twin_primes_below(1000), 35);
+/// assert_eq!(count_twin_primes_below(1_000_000), 8169);
+/// ```
+pub fn count_twin_primes_below(limit: u64) -> u64 {
+    let mut count: u64 = 0;
+    let mut previous: u64 = 0;
+    for_each_prime_below(limit, |p| {
+        if previous > 0 && p == previous + 2 {
+            count += 1;
+        }
+        previous = p;
+    });
+    count
+}
+
+/// Calculates the partial sum of Brun's constant, $\sum \left(\frac{1}{p} + \frac{1}{p + 2}\right)$ over the twin
+/// prime pairs below a limit.
+///
+/// The full sum converges (Brun's theorem) to $B_2 \approx 1.902$, but very slowly. The terms are added with
+/// Kahan summation, so the rounding error stays constant instead of growing with the number of pairs.
+///
+/// # Arguments
+///
+/// * `limit` - The exclusive upper bound of both primes.
+///
+/// # Returns
+///
+/// The partial sum over the twin prime pairs below `limit`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::brun_constant_partial;
+/// let sum: f64 = brun_constant_partial(10);
+/// assert!((sum - (1.0 / 3.0 + 2.0 / 5.0 + 1.0 / 7.0)).abs() < 1e-15);
+/// ```
+pub fn brun_constant_partial(limit: u64) -> f64 {
+    let mut sum: f64 = 0.0;
+    let mut compensation: f64 = 0.0;
+    let mut previous: u64 = 0;
+    for_each_prime_below(limit, |p| {
+        if previous > 0 && p == previous + 2 {
+            let term: f64 = 1.0 / previous as f64 + 1.0 / p as f64 - compensation;
+            let next: f64 = sum + term;
+            compensation = (next - sum) - term;
+            sum = next;
+        }
+        previous = p;
+    });
+    sum
+}
+
+/// Summary statistics of the gaps between consecutive primes in a range, found by [`gap_stats`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GapStats {
+    /// The smallest gap.
+    pub min: u64,
+    /// The largest gap.
+    pub max: u64,
+    /// The average gap.
+    pub mean: f64,
+    /// The number of gaps, one less than the number of primes in the range.
+    pub count: u64,
+}
+
+/// Counts how often each gap between consecutive primes occurs in a range.
+///
+/// The primes come from a segmented sieve one block at a time, so only the previous prime is kept rather than
+/// the list of all of them. Only gaps between two primes that are both in the range count.
+///
+/// # Arguments
+///
+/// * `lo` - The (inclusive) lower bound.
+/// * `hi` - The exclusive upper bound.
+///
+/// # Returns
+///
+/// A map from each gap size to the number of times it occurs, empty if the range holds fewer than two primes.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::gap_histogram;
+/// use std::collections::BTreeMap;
+///
+/// // 2, 3, 5, 7, 11, 13, 17, 19, 23, 29
+/// let histogram: BTreeMap<u64, u64> = gap_histogram(0, 30);
+/// assert_eq!(histogram, BTreeMap::from([(1, 1), (2, 4), (4, 3), (6, 1)]));
+/// assert!(gap_histogram(24, 29).is_empty());
+/// ```
+pub fn gap_histogram(lo: u64, hi: u64) -> BTreeMap<u64, u64> {
+    let mut histogram: BTreeMap<u64, u64> = BTreeMap::new();
+    let mut previous: Option<u64> = None;
+    for_each_prime_in_range(lo, hi, |p| {
+        if let Some(q) = previous {
+            *histogram.entry(p - q).or_insert(0) += 1;
+        }
+        previous = Some(p);
+    });
+    histogram
+}
+
+/// Calculates the smallest, largest and average gap between consecutive primes in a range.
+///
+/// # Arguments
+///
+/// * `lo` - The (inclusive) lower bound.
+/// * `hi` - The exclusive upper bound.
+///
+/// # Returns
+///
+/// The [`GapStats`] of the gaps [`gap_histogram`] counts, or `None` if the range holds fewer than two primes.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::{gap_stats, GapStats};
+///
+/// let stats: GapStats = gap_stats(0, 30).unwrap();
+/// assert_eq!((stats.min, stats.max, stats.count), (1, 6, 9));
+/// assert_eq!(stats.mean, 3.0); // (29 - 2) / 9
+/// assert_eq!(gap_stats(24, 29), None);
+/// ```
+pub fn gap_stats(lo: u64, hi: u64) -> Option<GapStats> {
+    let histogram: BTreeMap<u64, u64> = gap_histogram(lo, hi);
+    let min: u64 = *histogram.keys().next()?;
+    let max: u64 = *histogram.keys().next_back()?;
+    let count: u64 = histogram.values().sum();
+    let total: u64 = histogram.iter().map(|(gap, times)| gap * times).sum();
+    Some(GapStats {
+        min,
+        max,
+        mean: total as f64 / count as f64,
+        count,
+    })
+}
+
+/// Checks whether a prime $k$-tuple pattern is admissible, meaning that for every prime $q$ the offsets miss at
+/// least one residue class modulo $q$.
+///
+/// Only the primes $q \leq k$ need checking, since $k$ offsets cannot cover more than $k$ classes. An inadmissible
+/// pattern has some $n + o$ divisible by $q$ for every $n$, so it can only be all prime when that number is $q$
+/// itself, while an admissible one is conjectured (Hardy–Littlewood) to occur infinitely often.
+///
+/// # Arguments
+///
+/// * `offsets` - The offsets $o_1, \ldots, o_k$ of the pattern $n + o_1, \ldots, n + o_k$.
+///
+/// # Returns
+///
+/// `true` if the pattern is admissible.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::is_admissible_tuple;
+/// assert!(is_admissible_tuple(&[0, 2, 6]));
+/// assert!(is_admissible_tuple(&[0, 4, 6]));
+/// // one of n, n + 2 and n + 4 is always a multiple of 3
+/// assert!(!is_admissible_tuple(&[0, 2, 4]));
+/// ```
+pub fn is_admissible_tuple(offsets: &[u64]) -> bool {
+    let k: u64 = offsets.len() as u64;
+    let mut q: u64 = 2;
+    while q <= k {
+        if is_prime_u64(q) {
+            let mut covered: Vec<bool> = vec![false; q as usize];
+            for o in offsets {
+                covered[(o % q) as usize] = true;
+            }
+            if covered.iter().all(|hit| *hit) {
+                return false;
+            }
+        }
+        q += 1;
+    }
+    true
+}
+
+/// Finds the first occurrence of a prime $k$-tuple pattern, the smallest $n$ for which every $n + o$ is prime.
+///
+/// Blocks of $2^{18}$ starting points are sieved together with the largest offset past their end, so each
+/// candidate is checked by looking up the sieve. The pattern does not need to be admissible (see
+/// [`is_admissible_tuple`]), in which case the only possible occurrences are the few small ones where one of the
+/// numbers is the prime dividing the pattern.
+///
+/// # Arguments
+///
+/// * `offsets` - The offsets $o_1, \ldots, o_k$ of the pattern $n + o_1, \ldots, n + o_k$.
+/// * `search_limit` - The exclusive upper bound of $n$.
+///
+/// # Returns
+///
+/// The smallest $n$ below `search_limit` for which every $n + o$ is prime, or `None` if there is none.
+///
+/// # Panics
+///
+/// Panics if `offsets` is empty or if `search_limit` plus the largest offset overflows a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::first_occurrence;
+/// assert_eq!(first_occurrence(&[0, 2, 6, 8], 1000), Some(5));
+/// assert_eq!(first_occurrence(&[0, 2, 6, 8, 12, 18, 20, 26], 1000), Some(11));
+/// // 3, 5, 7 is the only prime triple of the form n, n + 2, n + 4
+/// assert_eq!(first_occurrence(&[0, 2, 4], 1000), Some(3));
+/// assert_eq!(first_occurrence(&[0, 2, 4], 3), None);
+/// ```
+pub fn first_occurrence(offsets: &[u64], search_limit: u64) -> Option<u64> {
+    const BLOCK: u64 = 1 << 18;
+    let first: u64 = match offsets.iter().min() {
+        Some(first) => *first,
+        None => panic!("the pattern must have at least one offset"),
+    };
+    let span: u64 = *offsets.iter().max().unwrap_or(&first);
+    let end: u64 = match search_limit.checked_add(span) {
+        Some(end) => end,
+        None => panic!("the search limit plus the largest offset must fit in a u64"),
+    };
+    if search_limit == 0 {
+        return None;
+    }
+    let base: Vec<u64> = sieving_primes(end);
+    let mut low: u64 = 0;
+    while low < search_limit {
+        let high: u64 = low.saturating_add(BLOCK).min(search_limit);
+        let mut segment: SieveSegment = SieveSegment::new(low, high + span);
+        for p in &base {
+            segment.mark_multiples(*p);
+        }
+        let found: Option<u64> = segment
+            .unmarked()
+            .take_while(|m| *m < high + first)
+            .filter(|m| *m >= low + first)
+            .map(|m| m - first)
+            .find(|n| offsets.iter().all(|o| !segment.is_marked(n + o)));
+        if found.is_some() {
+            return found;
+        }
+        low = high;
+    }
+    None
+}
+
+/// The bound on the primes [`Prime::index`] counts up to, which keeps [`prime_pi`] to about $2^{30}$ steps.
+const PRIME_INDEX_LIMIT: u64 = 1 << 40;
+
+/// A number known to be prime.
+///
+/// The only ways to build one are [`Prime::new`], which checks primality, and [`Prime::new_unchecked`], so
+/// functions taking a `Prime` (like [`legendre_symbol`](crate::modular::legendre_symbol) and
+/// [`sqrt_mod_prime`](crate::modular::sqrt_mod_prime)) do not need to check their input. With the `serde` feature it
+/// serializes as the bare number, and deserializing a number that is not prime fails.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::Prime;
+///
+/// let p: Prime = Prime::new(13).unwrap();
+/// assert_eq!(*p + 1, 14);
+/// assert_eq!(p.next(), Prime::new(17));
+/// assert_eq!(p.index(), Some(6));
+/// assert_eq!(Prime::new(15), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(transparent))]
+pub struct Prime(u64);
+
+impl Prime {
+    /// Wraps a number after checking that it is prime.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number to wrap.
+    ///
+    /// # Returns
+    ///
+    /// `Some(prime)` if `n` is prime, otherwise `None`.
+    pub fn new(n: u64) -> Option<Prime> {
+        if is_prime_u64(n) {
+            Some(Prime(n))
+        } else {
+            None
+        }
+    }
+
+    /// Wraps a number without checking that it is prime.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number to wrap, which the caller guarantees is prime. Functions given a composite `Prime`
+    ///   may return wrong results.
+    ///
+    /// # Returns
+    ///
+    /// The wrapped number.
+    pub fn new_unchecked(n: u64) -> Prime {
+        Prime(n)
+    }
+
+    /// Finds the next larger prime.
+    ///
+    /// # Returns
+    ///
+    /// `Some(prime)` with the smallest prime above this one, or `None` if it does not fit in a `u64`.
+    pub fn next(&self) -> Option<Prime> {
+        let mut n: u64 = self.0.checked_add(1)?;
+        loop {
+            if let Some(prime) = Prime::new(n) {
+                return Some(prime);
+            }
+            n = n.checked_add(1)?;
+        }
+    }
+
+    /// Finds the position of the prime in the sequence of primes, counting $2$ as the first.
+    ///
+    /// The position is $\pi(p)$, computed with [`prime_pi`] in $O(p^{3/4})$ time and $O(\sqrt{p})$ memory. That is
+    /// out of reach near the top of the `u64` range, so primes above $2^{40}$ have no index.
+    ///
+    /// # Returns
+    ///
+    /// `Some` of $\pi(p)$, or `None` if the prime is above $2^{40}$.
+    pub fn index(&self) -> Option<u64> {
+        if self.0 > PRIME_INDEX_LIMIT {
+            return None;
+        }
+        Some(prime_pi(self.0))
+    }
+}
+
+impl std::ops::Deref for Prime {
+    type Target = u64;
+
+    fn deref(&self) -> &u64 {
+        &self.0
+    }
+}
+
+impl From<Prime> for u64 {
+    fn from(p: Prime) -> u64 {
+        p.0
+    }
+}
+
+impl std::fmt::Display for Prime {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Prime {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Prime, D::Error> {
+        let n: u64 = u64::deserialize(deserializer)?;
+        match Prime::new(n) {
+            Some(prime) => Ok(prime),
+            None => Err(serde::de::Error::custom(format!("{} is not prime", n))),
+        }
+    }
+}
+
+/// Runs the Lucas–Lehmer test on $2^p - 1$ for an exponent already known to be prime.
+///
+/// # Arguments
+///
+/// * `p` - The prime exponent.
+///
+/// # Returns
+///
+/// `true` if $2^p - 1$ is prime.
+///
+/// # Panics
+///
+/// Panics if `p` does not fit in a `u32`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::{lucas_lehmer_prime, Prime};
+/// assert!(lucas_lehmer_prime(Prime::new(61).unwrap()));
+/// assert!(!lucas_lehmer_prime(Prime::new(67).unwrap()));
+/// ```
+pub fn lucas_lehmer_prime(p: Prime) -> bool {
+    match u32::try_from(*p) {
+        Ok(p) => lucas_lehmer(p),
+        Err(_) => panic!("the exponent must fit in a u32"),
+    }
+}
+
+/// Generates a random prime with exactly `bits` bits.
+///
+/// Random odd numbers with the top bit set are drawn until one passes [`is_probable_prime`], which is
+/// deterministic in the `u64` range. By the prime number theorem this takes about $0.35 \times \text{bits}$
+/// draws on average.
+///
+/// # Arguments
+///
+/// * `bits` - The number of bits of the prime, from 2 to 64.
+/// * `rng` - The source of randomness.
+///
+/// # Returns
+///
+/// A prime $p$ with $2^{\text{bits} - 1} \leq p < 2^{\text{bits}}$.
+///
+/// # Panics
+///
+/// Panics if `bits` is not between 2 and 64.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::{check_prime, random_prime};
+///
+/// let p: u64 = random_prime(16, &mut rand::rngs::mock::StepRng::new(12345, 6789));
+/// assert!(check_prime(p as u128));
+/// assert!((1 << 15..1 << 16).contains(&p));
+/// ```
+#[cfg(feature = "rand")]
+pub fn random_prime<R: rand::Rng + ?Sized>(bits: u32, rng: &mut R) -> u64 {
+    if !(2..=64).contains(&bits) {
+        panic!("bits must be between 2 and 64");
+    }
+    if bits == 2 {
+        return if rng.gen::<bool>() { 2 } else { 3 };
+    }
+    let top: u64 = 1 << (bits - 1);
+    loop {
+        let candidate: u64 = (rng.gen::<u64>() & (top - 1)) | top | 1;
+        if is_probable_prime(&num::BigInt::from(candidate)) {
+            return candidate;
+        }
+    }
+}
+
+/// Finds the prime below a limit that is the sum of the most consecutive primes (Project Euler problem 50).
+///
+/// The primes below the limit come from one sieve and their prefix sums make every window sum a subtraction.
+/// The window lengths are tried from the longest whose smallest sum (starting at $2$) is below the limit down, so
+/// the first window that sums to a prime is the answer.
+///
+/// # Arguments
+///
+/// * `limit` - The exclusive upper bound of the prime.
+///
+/// # Returns
+///
+/// The pair `(prime, terms)`, taking the smallest prime when several are sums of the same number of terms.
+///
+/// # Panics
+///
+/// Panics if `limit` is at most `2`, since there are no primes below it.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::longest_consecutive_prime_sum_below;
+/// assert_eq!(longest_consecutive_prime_sum_below(100), (41, 6)); // 2 + 3 + 5 + 7 + 11 + 13
+/// assert_eq!(longest_consecutive_prime_sum_below(1000), (953, 21));
+/// ```
+pub fn longest_consecutive_prime_sum_below(limit: u64) -> (u64, usize) {
+    if limit <= 2 {
+        panic!("there are no primes below the limit");
+    }
+    let primes: Vec<u64> = primes_in_range(0, limit);
+    let mut is_prime: Vec<bool> = vec![false; limit as usize];
+    for p in &primes {
+        is_prime[*p as usize] = true;
+    }
+    // prefix[k] is the sum of the first k primes, and no window of k primes sums to less
+    let mut prefix: Vec<u64> = vec![0];
+    for p in &primes {
+        prefix.push(prefix[prefix.len() - 1] + p);
+    }
+    let mut terms: usize = prefix.partition_point(|sum| *sum < limit) - 1;
+    while terms > 0 {
+        let mut start: usize = 0;
+        while start + terms < prefix.len() && prefix[start + terms] - prefix[start] < limit {
+            let sum: u64 = prefix[start + terms] - prefix[start];
+            if is_prime[sum as usize] {
+                return (sum, terms);
+            }
+            start += 1;
+        }
+        terms -= 1;
+    }
+    unreachable!("a single prime is a sum of one consecutive prime")
+}
+
+/// Finds every way to write a number as a sum of consecutive primes.
+///
+/// The sum of a window of the primes up to $n$ is kept while the window slides: its end moves right while the sum
+/// is below $n$ and its start moves right otherwise, so every window summing to $n$ is seen once.
+///
+/// # Arguments
+///
+/// * `n` - The number to write as a sum.
+///
+/// # Returns
+///
+/// Vector of the windows `(i, j)` of the primes $p_0 = 2, p_1 = 3, \ldots$ with $p_i + \cdots + p_{j - 1} = n$, in
+/// increasing order of `i` (a prime $n = p_i$ has the window `(i, i + 1)`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::primes::consecutive_prime_sum_representations;
+///
+/// // 41 = 2 + 3 + 5 + 7 + 11 + 13 = 11 + 13 + 17 = 41
+/// assert_eq!(consecutive_prime_sum_representations(41), vec![(0, 6), (4, 7), (12, 13)]);
+/// assert_eq!(consecutive_prime_sum_representations(4), vec![]);
+/// ```
+pub fn consecutive_prime_sum_representations(n: u64) -> Vec<(usize, usize)> {
+    let primes: Vec<u64> = primes_in_range(0, n.saturating_add(1));
+    let mut windows: Vec<(usize, usize)> = vec![];
+    let mut start: usize = 0;
+    let mut end: usize = 0;
+    let mut sum: u64 = 0;
+    while start < primes.len() {
+        if sum < n && end < primes.len() {
+            sum += primes[end];
+            end += 1;
+        } else {
+            if sum == n {
+                windows.push((start, end));
+            }
+            sum -= primes[start];
+            start += 1;
+        }
+    }
+    windows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_primes_capacity_test() {
+        assert_eq!(generate_primes_capacity(Some(1)), Some(2));
+        assert_eq!(
+            generate_primes_capacity(Some(100_000)),
+            Some(prime_count_upper_bound(100_000) as usize)
+        );
+        // a u128 limit above u64::MAX reserves nothing
+        assert_eq!(
+            generate_primes_capacity(u64::try_from(u128::MAX).ok()),
+            None
+        );
+        // the bound for u64::MAX is far more memory than can be had, so the reservation fails instead of aborting
+        let capacity: usize = generate_primes_capacity(Some(u64::MAX)).unwrap();
+        let mut primes: Vec<u64> = vec![];
+        assert!(primes.try_reserve_exact(capacity).is_err());
+    }
+}
@@ -0,0 +1,249 @@
+use crate::factorization::smallest_prime_factor_sieve;
+
+/// A condition on a value of an arithmetic function.
+type Condition<'a> = Box<dyn Fn(u64) -> bool + 'a>;
+
+/// A condition on a prime factorization given as `(prime, exponent)` pairs.
+type FactorizationCondition<'a> = Box<dyn Fn(&[(u64, u32)]) -> bool + 'a>;
+
+/// A condition on one of the arithmetic functions of a number.
+enum Filter<'a> {
+    /// A condition on the number itself.
+    N(Condition<'a>),
+    /// A condition on the sum of the divisors $\sigma(n)$.
+    Sigma(Condition<'a>),
+    /// A condition on the number of divisors $\tau(n)$.
+    Tau(Condition<'a>),
+    /// A condition on Euler's totient $\varphi(n)$.
+    Phi(Condition<'a>),
+    /// A condition on the prime factorization.
+    Factorization(FactorizationCondition<'a>),
+}
+
+/// A search over the numbers below a limit for those whose arithmetic functions satisfy some conditions.
+///
+/// Conditions are added with the `filter` methods and checked in the order they were added, stopping at the first
+/// one that fails. If any condition needs more than the number itself, a single smallest prime factor sieve is run
+/// up to the limit, and $\sigma(n)$, $\tau(n)$ and $\varphi(n)$ all come from the factorization it gives, so no
+/// function is recomputed by trial division and no separate sieve is needed for each one.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::range_query::numbers_below;
+///
+/// // sigma(n) is prime only for prime powers, starting with sigma(2) = 3 and sigma(4) = 7
+/// let sigma_prime: Vec<u64> = numbers_below(100)
+///     .filter_sigma(|s| bens_number_theory::primes::check_prime(s as u128))
+///     .collect();
+/// assert_eq!(sigma_prime, vec![2, 4, 9, 16, 25, 64]);
+///
+/// // the squarefree numbers with four divisors are the products of two distinct primes
+/// let count: u64 = numbers_below(30)
+///     .filter_tau(|t| t == 4)
+///     .filter_factorization(|factors| factors.iter().all(|(_, e)| *e == 1))
+///     .count();
+/// assert_eq!(count, 7); // 6, 10, 14, 15, 21, 22, 26
+/// ```
+pub struct RangeQuery<'a> {
+    limit: u64,
+    filters: Vec<Filter<'a>>,
+}
+
+/// Starts a [`RangeQuery`] over the numbers $1 \leq n < \text{limit}$.
+///
+/// # Arguments
+///
+/// * `limit` - The (exclusive) upper bound of the numbers.
+///
+/// # Returns
+///
+/// A query with no conditions, which accepts every number in the range.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::range_query::numbers_below;
+/// assert_eq!(numbers_below(5).collect(), vec![1, 2, 3, 4]);
+/// ```
+pub fn numbers_below<'a>(limit: u64) -> RangeQuery<'a> {
+    RangeQuery {
+        limit,
+        filters: vec![],
+    }
+}
+
+impl<'a> RangeQuery<'a> {
+    /// Keeps the numbers $n$ satisfying a condition.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - The condition, called with $n$.
+    ///
+    /// # Returns
+    ///
+    /// The query with the condition added.
+    pub fn filter(mut self, condition: impl Fn(u64) -> bool + 'a) -> RangeQuery<'a> {
+        self.filters.push(Filter::N(Box::new(condition)));
+        self
+    }
+
+    /// Keeps the numbers whose divisor sum $\sigma(n)$ satisfies a condition.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - The condition, called with $\sigma(n)$.
+    ///
+    /// # Returns
+    ///
+    /// The query with the condition added.
+    pub fn filter_sigma(mut self, condition: impl Fn(u64) -> bool + 'a) -> RangeQuery<'a> {
+        self.filters.push(Filter::Sigma(Box::new(condition)));
+        self
+    }
+
+    /// Keeps the numbers whose divisor count $\tau(n)$ satisfies a condition.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - The condition, called with $\tau(n)$.
+    ///
+    /// # Returns
+    ///
+    /// The query with the condition added.
+    pub fn filter_tau(mut self, condition: impl Fn(u64) -> bool + 'a) -> RangeQuery<'a> {
+        self.filters.push(Filter::Tau(Box::new(condition)));
+        self
+    }
+
+    /// Keeps the numbers whose totient $\varphi(n)$ satisfies a condition.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - The condition, called with $\varphi(n)$.
+    ///
+    /// # Returns
+    ///
+    /// The query with the condition added.
+    pub fn filter_phi(mut self, condition: impl Fn(u64) -> bool + 'a) -> RangeQuery<'a> {
+        self.filters.push(Filter::Phi(Box::new(condition)));
+        self
+    }
+
+    /// Keeps the numbers whose prime factorization satisfies a condition.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - The condition, called with the `(prime, exponent)` pairs of $n$ in increasing order of the
+    ///   primes (empty for $1$).
+    ///
+    /// # Returns
+    ///
+    /// The query with the condition added.
+    pub fn filter_factorization(
+        mut self,
+        condition: impl Fn(&[(u64, u32)]) -> bool + 'a,
+    ) -> RangeQuery<'a> {
+        self.filters
+            .push(Filter::Factorization(Box::new(condition)));
+        self
+    }
+
+    /// Calls a function on every number in the range that satisfies all of the conditions.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The function to call on each number, in increasing order.
+    pub fn for_each(&self, mut f: impl FnMut(u64)) {
+        if self.limit <= 1 {
+            return;
+        }
+        let factored: bool = self
+            .filters
+            .iter()
+            .any(|filter| !matches!(filter, Filter::N(_)));
+        let spf: Vec<u64> = if factored {
+            smallest_prime_factor_sieve(self.limit as usize - 1)
+        } else {
+            vec![]
+        };
+        let mut factors: Vec<(u64, u32)> = vec![];
+        let mut n: u64 = 1;
+        while n < self.limit {
+            if factored {
+                factors.clear();
+                let mut m: u64 = n;
+                while m > 1 {
+                    let p: u64 = spf[m as usize];
+                    let mut e: u32 = 0;
+                    while m.is_multiple_of(p) {
+                        m /= p;
+                        e += 1;
+                    }
+                    factors.push((p, e));
+                }
+            }
+            if self
+                .filters
+                .iter()
+                .all(|filter| accepts(filter, n, &factors))
+            {
+                f(n);
+            }
+            n += 1;
+        }
+    }
+
+    /// Finds every number in the range that satisfies all of the conditions.
+    ///
+    /// # Returns
+    ///
+    /// Vector of the numbers in increasing order.
+    pub fn collect(&self) -> Vec<u64> {
+        let mut found: Vec<u64> = vec![];
+        self.for_each(|n| found.push(n));
+        found
+    }
+
+    /// Counts the numbers in the range that satisfy all of the conditions.
+    ///
+    /// # Returns
+    ///
+    /// The number of numbers found.
+    pub fn count(&self) -> u64 {
+        let mut count: u64 = 0;
+        self.for_each(|_| count += 1);
+        count
+    }
+}
+
+/// Checks one condition of a [`RangeQuery`] on a number.
+///
+/// # Arguments
+///
+/// * `filter` - The condition.
+/// * `n` - The number.
+/// * `factors` - The prime factorization of `n` (only filled in when some condition needs it).
+///
+/// # Returns
+///
+/// `true` if `n` satisfies the condition.
+fn accepts(filter: &Filter, n: u64, factors: &[(u64, u32)]) -> bool {
+    match filter {
+        Filter::N(condition) => condition(n),
+        Filter::Sigma(condition) => condition(
+            factors
+                .iter()
+                .map(|(p, e)| (p.pow(e + 1) - 1) / (p - 1))
+                .product(),
+        ),
+        Filter::Tau(condition) => condition(factors.iter().map(|(_, e)| *e as u64 + 1).product()),
+        Filter::Phi(condition) => condition(
+            factors
+                .iter()
+                .map(|(p, e)| (p - 1) * p.pow(e - 1))
+                .product(),
+        ),
+        Filter::Factorization(condition) => condition(factors),
+    }
+}
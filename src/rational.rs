@@ -0,0 +1,358 @@
+use crate::modular::gcd_lehmer;
+use std::cmp::Ordering;
+
+/// A fraction in lowest terms, with a signed 128-bit numerator and a positive 128-bit denominator.
+///
+/// This is a small exact type for the arithmetic functions that need fractions of machine integers, rather than a
+/// replacement for `num::BigRational`. Every value is kept reduced, so two fractions are equal exactly when their
+/// numerators and denominators are, and the arithmetic is checked, returning `None` when a result does not fit.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::rational::Ratio64;
+///
+/// let half: Ratio64 = Ratio64::new(1, 2).unwrap();
+/// let third: Ratio64 = Ratio64::new(-2, 6).unwrap(); // -1/3
+/// assert_eq!(half.checked_add(third), Ratio64::new(1, 6).ok());
+/// assert_eq!(half.checked_mul(third).unwrap().to_string(), "-1/6");
+/// assert!(third < half);
+/// assert!(Ratio64::new(1, 0).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ratio64 {
+    /// The numerator, which carries the sign.
+    num: i128,
+    /// The denominator, which is positive and coprime to the numerator.
+    den: u128,
+}
+
+/// The error returned when a [`Ratio64`] is given a denominator of $0$.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZeroDenominator;
+
+impl std::fmt::Display for ZeroDenominator {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "the denominator of a fraction cannot be zero")
+    }
+}
+
+impl std::error::Error for ZeroDenominator {}
+
+/// Applies a sign to a magnitude.
+///
+/// # Arguments
+///
+/// * `negative` - Whether the value is negative.
+/// * `magnitude` - The absolute value.
+///
+/// # Returns
+///
+/// `Some` signed value, or `None` if it does not fit in an `i128`.
+fn signed(negative: bool, magnitude: u128) -> Option<i128> {
+    if negative {
+        0_i128.checked_sub_unsigned(magnitude)
+    } else {
+        i128::try_from(magnitude).ok()
+    }
+}
+
+/// Multiplies a signed number by an unsigned one.
+///
+/// # Arguments
+///
+/// * `a` - The signed factor.
+/// * `b` - The unsigned factor.
+///
+/// # Returns
+///
+/// `Some(a * b)`, or `None` if it does not fit in an `i128`.
+fn scale(a: i128, b: u128) -> Option<i128> {
+    signed(a < 0, a.unsigned_abs().checked_mul(b)?)
+}
+
+/// Multiplies two `u128`s into a 256-bit product.
+///
+/// # Arguments
+///
+/// * `a` - The first factor.
+/// * `b` - The second factor.
+///
+/// # Returns
+///
+/// The `(high, low)` halves of $ab$.
+fn widening_mul(a: u128, b: u128) -> (u128, u128) {
+    let mask: u128 = u64::MAX as u128;
+    let (a_high, a_low): (u128, u128) = (a >> 64, a & mask);
+    let (b_high, b_low): (u128, u128) = (b >> 64, b & mask);
+    let low: u128 = a_low * b_low;
+    let middle_1: u128 = a_high * b_low;
+    let middle_2: u128 = a_low * b_high;
+    let high: u128 = a_high * b_high;
+    // the bits 64 to 127 of the product, with what they carry into the high half
+    let middle: u128 = (low >> 64) + (middle_1 & mask) + (middle_2 & mask);
+    (
+        high + (middle_1 >> 64) + (middle_2 >> 64) + (middle >> 64),
+        (middle << 64) | (low & mask),
+    )
+}
+
+impl Ratio64 {
+    /// Creates a fraction and reduces it to lowest terms.
+    ///
+    /// # Arguments
+    ///
+    /// * `num` - The numerator.
+    /// * `den` - The denominator.
+    ///
+    /// # Returns
+    ///
+    /// `Ok` with $\text{num}/\text{den}$ reduced, or `Err(ZeroDenominator)` if `den` is $0$.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bens_number_theory::rational::{Ratio64, ZeroDenominator};
+    /// let ratio: Ratio64 = Ratio64::new(-12, 18).unwrap();
+    /// assert_eq!((ratio.numer(), ratio.denom()), (-2, 3));
+    /// assert_eq!(Ratio64::new(0, 5), Ok(Ratio64::from_integer(0)));
+    /// assert_eq!(Ratio64::new(3, 0), Err(ZeroDenominator));
+    /// ```
+    pub fn new(num: i128, den: u128) -> Result<Ratio64, ZeroDenominator> {
+        if den == 0 {
+            return Err(ZeroDenominator);
+        }
+        if num == 0 {
+            return Ok(Ratio64::from_integer(0));
+        }
+        let g: u128 = gcd_lehmer(num.unsigned_abs(), den);
+        Ok(Ratio64 {
+            // dividing the magnitude by g leaves it in range
+            num: signed(num < 0, num.unsigned_abs() / g).unwrap(),
+            den: den / g,
+        })
+    }
+
+    /// Creates a fraction with a denominator of $1$.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The integer.
+    ///
+    /// # Returns
+    ///
+    /// $n/1$.
+    pub fn from_integer(n: i128) -> Ratio64 {
+        Ratio64 { num: n, den: 1 }
+    }
+
+    /// The numerator in lowest terms, which carries the sign of the fraction.
+    pub fn numer(&self) -> i128 {
+        self.num
+    }
+
+    /// The denominator in lowest terms, which is always positive.
+    pub fn denom(&self) -> u128 {
+        self.den
+    }
+
+    /// Adds or subtracts two fractions.
+    ///
+    /// With $g = \gcd(b, d)$ the sum is $\frac{a (d/g) \pm c (b/g)}{b (d/g)}$, which only has factors of $g$ left to
+    /// cancel, so the intermediate values stay about as small as the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The second fraction.
+    /// * `subtract` - Whether to subtract `other` instead of adding it.
+    ///
+    /// # Returns
+    ///
+    /// `Some` result, or `None` if it (or the numerator before the factors of $g$ are cancelled) does not fit.
+    fn checked_add_sub(self, other: Ratio64, subtract: bool) -> Option<Ratio64> {
+        let g: u128 = gcd_lehmer(self.den, other.den);
+        let left: i128 = scale(self.num, other.den / g)?;
+        let right: i128 = scale(other.num, self.den / g)?;
+        let num: i128 = if subtract {
+            left.checked_sub(right)?
+        } else {
+            left.checked_add(right)?
+        };
+        if num == 0 {
+            return Some(Ratio64::from_integer(0));
+        }
+        let h: u128 = gcd_lehmer(num.unsigned_abs(), g);
+        Some(Ratio64 {
+            num: signed(num < 0, num.unsigned_abs() / h).unwrap(),
+            den: (self.den / g).checked_mul(other.den / h)?,
+        })
+    }
+
+    /// Adds two fractions.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The fraction to add.
+    ///
+    /// # Returns
+    ///
+    /// `Some` sum, or `None` if it overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bens_number_theory::rational::Ratio64;
+    /// let sixth: Ratio64 = Ratio64::new(1, 6).unwrap();
+    /// let tenth: Ratio64 = Ratio64::new(1, 10).unwrap();
+    /// assert_eq!(sixth.checked_add(tenth), Ratio64::new(4, 15).ok());
+    /// assert_eq!(Ratio64::from_integer(i128::MAX).checked_add(sixth), None);
+    /// ```
+    pub fn checked_add(self, other: Ratio64) -> Option<Ratio64> {
+        self.checked_add_sub(other, false)
+    }
+
+    /// Subtracts one fraction from another.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The fraction to subtract.
+    ///
+    /// # Returns
+    ///
+    /// `Some` difference, or `None` if it overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bens_number_theory::rational::Ratio64;
+    /// let sixth: Ratio64 = Ratio64::new(1, 6).unwrap();
+    /// let half: Ratio64 = Ratio64::new(1, 2).unwrap();
+    /// assert_eq!(sixth.checked_sub(half), Ratio64::new(-1, 3).ok());
+    /// ```
+    pub fn checked_sub(self, other: Ratio64) -> Option<Ratio64> {
+        self.checked_add_sub(other, true)
+    }
+
+    /// Multiplies two fractions.
+    ///
+    /// Each numerator is reduced against the other denominator first, so the product comes out in lowest terms
+    /// and overflows only if the reduced result does not fit.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The fraction to multiply by.
+    ///
+    /// # Returns
+    ///
+    /// `Some` product, or `None` if it overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bens_number_theory::rational::Ratio64;
+    /// let ratio: Ratio64 = Ratio64::new(-4, 9).unwrap();
+    /// assert_eq!(ratio.checked_mul(Ratio64::new(3, 8).unwrap()), Ratio64::new(-1, 6).ok());
+    /// ```
+    pub fn checked_mul(self, other: Ratio64) -> Option<Ratio64> {
+        let g1: u128 = gcd_lehmer(self.num.unsigned_abs(), other.den);
+        let g2: u128 = gcd_lehmer(other.num.unsigned_abs(), self.den);
+        let magnitude: u128 =
+            (self.num.unsigned_abs() / g1).checked_mul(other.num.unsigned_abs() / g2)?;
+        Some(Ratio64 {
+            num: signed((self.num < 0) != (other.num < 0), magnitude)?,
+            den: (self.den / g2).checked_mul(other.den / g1)?,
+        })
+    }
+
+    /// Divides one fraction by another.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The fraction to divide by.
+    ///
+    /// # Returns
+    ///
+    /// `Some` quotient, or `None` if `other` is $0$ or the quotient overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bens_number_theory::rational::Ratio64;
+    /// let ratio: Ratio64 = Ratio64::new(3, 4).unwrap();
+    /// assert_eq!(ratio.checked_div(Ratio64::new(-9, 2).unwrap()), Ratio64::new(-1, 6).ok());
+    /// assert_eq!(ratio.checked_div(Ratio64::from_integer(0)), None);
+    /// ```
+    pub fn checked_div(self, other: Ratio64) -> Option<Ratio64> {
+        if other.num == 0 {
+            return None;
+        }
+        let g1: u128 = gcd_lehmer(self.num.unsigned_abs(), other.num.unsigned_abs());
+        let g2: u128 = gcd_lehmer(self.den, other.den);
+        let magnitude: u128 = (self.num.unsigned_abs() / g1).checked_mul(other.den / g2)?;
+        Some(Ratio64 {
+            num: signed((self.num < 0) != (other.num < 0), magnitude)?,
+            den: (self.den / g2).checked_mul(other.num.unsigned_abs() / g1)?,
+        })
+    }
+
+    /// Negates a fraction.
+    ///
+    /// # Returns
+    ///
+    /// `Some(-self)`, or `None` if the numerator is `i128::MIN`.
+    pub fn checked_neg(self) -> Option<Ratio64> {
+        Some(Ratio64 {
+            num: self.num.checked_neg()?,
+            den: self.den,
+        })
+    }
+
+    /// Converts a fraction to the nearest `f64` (up to the rounding of the numerator and denominator).
+    ///
+    /// # Returns
+    ///
+    /// The value of the fraction as an `f64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bens_number_theory::rational::Ratio64;
+    /// assert_eq!(Ratio64::new(-3, 8).unwrap().to_f64(), -0.375);
+    /// ```
+    pub fn to_f64(self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+}
+
+impl Ord for Ratio64 {
+    /// Compares $a/b$ with $c/d$ by comparing $ad$ with $cb$ in 256 bits, so it never overflows.
+    fn cmp(&self, other: &Ratio64) -> Ordering {
+        let (left_negative, right_negative): (bool, bool) = (self.num < 0, other.num < 0);
+        if left_negative != right_negative {
+            return if left_negative {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            };
+        }
+        let left: (u128, u128) = widening_mul(self.num.unsigned_abs(), other.den);
+        let right: (u128, u128) = widening_mul(other.num.unsigned_abs(), self.den);
+        if left_negative {
+            right.cmp(&left)
+        } else {
+            left.cmp(&right)
+        }
+    }
+}
+
+impl PartialOrd for Ratio64 {
+    fn partial_cmp(&self, other: &Ratio64) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::fmt::Display for Ratio64 {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}/{}", self.num, self.den)
+    }
+}
@@ -1,3 +1,5 @@
+use crate::modular::{addmod, gcd, mulmod, submod};
+use crate::primes::prime_flags;
 use num::BigInt;
 
 /// Calculates a vector of numbers representing the Lucas Sequence.
@@ -365,3 +367,875 @@ pub fn ben_sequence(n: BigInt) -> Vec<BigInt> {
     }
     nums
 }
+
+/// Iterator over the Collatz ($3n + 1$) trajectory of a number, created by [`collatz_sequence`].
+///
+/// If a term of the trajectory is too large to fit in a `u64`, the iterator stops early
+/// instead of overflowing, and [`CollatzSequence::overflowed`] returns `true`.
+#[derive(Debug, Clone)]
+pub struct CollatzSequence {
+    /// The next term to yield, or `None` once the trajectory has ended.
+    next: Option<u64>,
+    /// Whether the trajectory was cut short by a term that does not fit in a `u64`.
+    overflowed: bool,
+}
+
+impl CollatzSequence {
+    /// Returns whether the trajectory was cut short because a term did not fit in a `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bens_number_theory::sequences::collatz_sequence;
+    ///
+    /// let mut sequence = collatz_sequence(u64::MAX);
+    /// assert_eq!(sequence.next(), Some(u64::MAX));
+    /// assert_eq!(sequence.next(), None); // 3 * (2^64 - 1) + 1 does not fit
+    /// assert!(sequence.overflowed());
+    /// ```
+    pub fn overflowed(&self) -> bool {
+        self.overflowed
+    }
+}
+
+impl Iterator for CollatzSequence {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let current: u64 = self.next?;
+        self.next = match current {
+            1 => None,
+            _ if current.is_multiple_of(2) => Some(current / 2),
+            _ => {
+                let next: Option<u64> = current.checked_mul(3).and_then(|m| m.checked_add(1));
+                self.overflowed = next.is_none();
+                next
+            }
+        };
+        Some(current)
+    }
+}
+
+/// Generates the Collatz ($3n + 1$) trajectory of a number.
+///
+/// The Collatz map is defined as:
+/// $$C(n) :=\begin{cases}
+///     n / 2   & \text{if } n \equiv 0 \pmod 2; \\\\
+///     3n + 1  & \text{if } n \equiv 1 \pmod 2.
+/// \end{cases}$$
+///
+/// The Collatz conjecture states that repeatedly applying the map to any positive integer eventually reaches $1$.
+///
+/// # Arguments
+///
+/// * `n` - The starting number of the trajectory.
+///
+/// # Returns
+///
+/// An iterator yielding `n`, $C(n)$, $C(C(n))$, ... down to (and including) 1.
+/// The trajectory of `0` is empty.
+///
+/// # Example
+///
+/// ```
+/// use bens_number_theory::sequences::collatz_sequence;
+///
+/// let sequence: Vec<u64> = collatz_sequence(6).collect();
+/// assert_eq!(sequence, [6, 3, 10, 5, 16, 8, 4, 2, 1]);
+/// ```
+pub fn collatz_sequence(n: u64) -> CollatzSequence {
+    CollatzSequence {
+        next: if n == 0 { None } else { Some(n) },
+        overflowed: false,
+    }
+}
+
+/// Calculates the number of steps the Collatz trajectory of a number takes to reach 1.
+///
+/// The trajectory is followed using `u128` values, so starting values whose trajectories
+/// climb above `u64::MAX` are still handled.
+///
+/// [A006577](https://oeis.org/A006577)
+///
+/// # Arguments
+///
+/// * `n` - The starting number of the trajectory.
+///
+/// # Returns
+///
+/// The number of applications of the Collatz map needed to reach 1 (`0` for both `0` and `1`).
+///
+/// # Panics
+///
+/// Panics if the trajectory climbs above `u128::MAX`.
+///
+/// # Example
+///
+/// ```
+/// use bens_number_theory::sequences::collatz_length;
+///
+/// assert_eq!(collatz_length(6), 8);
+/// assert_eq!(collatz_length(27), 111);
+/// ```
+pub fn collatz_length(n: u64) -> u32 {
+    if n == 0 {
+        return 0;
+    }
+    collatz_steps_until(n as u128, 1).0
+}
+
+/// Follows the Collatz trajectory of `n` until it drops below `bound` (or reaches 1).
+///
+/// # Arguments
+///
+/// * `n` - The starting number of the trajectory.
+/// * `bound` - The trajectory stops at the first term less than `bound`.
+///
+/// # Returns
+///
+/// The number of steps taken and the term the trajectory stopped at.
+///
+/// # Panics
+///
+/// Panics if the trajectory climbs above `u128::MAX`.
+///
+/// # Example
+///
+/// ```
+/// fn collatz_steps_until(n: u128, bound: u128) -> (u32, u128) {
+///     let mut steps: u32 = 0;
+///     let mut m: u128 = n;
+///     while m >= bound && m != 1 {
+///         m = if m.is_multiple_of(2) { m / 2 } else { 3 * m + 1 };
+///         steps += 1;
+///     }
+///     (steps, m)
+/// }
+///
+/// assert_eq!(collatz_steps_until(7, 7), (11, 5)); // 7 -> 22 -> 11 -> 34 -> 17 -> 52 -> 26 -> 13 -> 40 -> 20 -> 10 -> 5
+/// ```
+fn collatz_steps_until(n: u128, bound: u128) -> (u32, u128) {
+    let mut steps: u32 = 0;
+    let mut m: u128 = n;
+    while m >= bound && m != 1 {
+        m = if m.is_multiple_of(2) {
+            m / 2
+        } else {
+            m.checked_mul(3)
+                .and_then(|t| t.checked_add(1))
+                .expect("Collatz trajectory exceeded u128::MAX")
+        };
+        steps += 1;
+    }
+    (steps, m)
+}
+
+/// Finds the starting number below a limit with the longest Collatz trajectory.
+///
+/// The lengths of all smaller starting numbers are remembered, so each trajectory only needs to be
+/// followed until it drops below its starting value.
+///
+/// # Arguments
+///
+/// * `limit` - The (exclusive) upper bound of the starting numbers to check.
+///
+/// # Returns
+///
+/// The starting number with the longest trajectory (the smallest one on ties) and its length
+/// in steps (see [`collatz_length`]), or `(0, 0)` if `limit <= 1`.
+///
+/// # Example
+///
+/// ```
+/// use bens_number_theory::sequences::longest_collatz_below;
+///
+/// assert_eq!(longest_collatz_below(10), (9, 19));
+/// ```
+pub fn longest_collatz_below(limit: u64) -> (u64, u32) {
+    if limit <= 1 {
+        return (0, 0);
+    }
+    let mut lengths: Vec<u32> = vec![0; limit as usize];
+    let mut best: (u64, u32) = (1, 0);
+    let mut n: u64 = 2;
+    while n < limit {
+        let (steps, stop): (u32, u128) = collatz_steps_until(n as u128, n as u128);
+        let length: u32 = steps + lengths[stop as usize];
+        lengths[n as usize] = length;
+        if length > best.1 {
+            best = (n, length);
+        }
+        n += 1;
+    }
+    best
+}
+
+/// Generates the Fibonacci numbers $F_0, F_1, \dots, F_{186}$, which are all of the ones that fit in a `u128`.
+///
+/// # Returns
+///
+/// Vector where index `i` holds $F_i$.
+///
+/// # Example
+///
+/// ```
+/// fn fibonacci_u128() -> Vec<u128> {
+///     let mut f: Vec<u128> = vec![0, 1];
+///     while let Some(next) = f[f.len() - 2].checked_add(f[f.len() - 1]) {
+///         f.push(next);
+///     }
+///     f
+/// }
+///
+/// assert_eq!(fibonacci_u128().len(), 187);
+/// ```
+fn fibonacci_u128() -> Vec<u128> {
+    let mut f: Vec<u128> = vec![0, 1];
+    while let Some(next) = f[f.len() - 2].checked_add(f[f.len() - 1]) {
+        f.push(next);
+    }
+    f
+}
+
+/// Calculates the Zeckendorf representation of a number.
+///
+/// Zeckendorf's theorem states that every positive integer can be written uniquely as a sum of
+/// Fibonacci numbers, no two of which are consecutive. The representation is found greedily,
+/// by repeatedly taking the largest Fibonacci number that still fits.
+///
+/// This uses the indexing $F_0 = 0$, $F_1 = 1$, $F_2 = 1$, $F_3 = 2$, ..., so the indices used are always at least $2$.
+///
+/// # Arguments
+///
+/// * `n` - The number to represent.
+///
+/// # Returns
+///
+/// Vector of the indices $i$ of the Fibonacci numbers $F_i$ summing to `n`, in decreasing order (empty for `0`).
+///
+/// # Example
+///
+/// ```
+/// use bens_number_theory::sequences::zeckendorf;
+///
+/// assert_eq!(zeckendorf(100), vec![11, 6, 4]); // 89 + 8 + 3
+/// assert_eq!(zeckendorf(0), vec![]);
+/// ```
+pub fn zeckendorf(n: u128) -> Vec<u32> {
+    let f: Vec<u128> = fibonacci_u128();
+    let mut indices: Vec<u32> = vec![];
+    let mut remaining: u128 = n;
+    let mut i: usize = f.len() - 1;
+    while remaining > 0 {
+        if f[i] <= remaining {
+            remaining -= f[i];
+            indices.push(i as u32);
+            // the next Fibonacci number down can never fit, so skip it
+            i -= 1;
+        }
+        i -= 1;
+    }
+    indices
+}
+
+/// Reconstructs a number from its Zeckendorf representation.
+///
+/// # Arguments
+///
+/// * `indices` - The indices of the Fibonacci numbers to add up (in any order), using the same indexing as [`zeckendorf`].
+///
+/// # Returns
+///
+/// `Some` sum of the Fibonacci numbers, or `None` if the indices are not a valid Zeckendorf representation
+/// (an index below 2, a repeated or consecutive index) or the sum does not fit in a `u128`.
+///
+/// # Example
+///
+/// ```
+/// use bens_number_theory::sequences::from_zeckendorf;
+///
+/// assert_eq!(from_zeckendorf(&[11, 6, 4]), Some(100));
+/// assert_eq!(from_zeckendorf(&[5, 4]), None); // 5 + 3 uses consecutive Fibonacci numbers
+/// ```
+pub fn from_zeckendorf(indices: &[u32]) -> Option<u128> {
+    let f: Vec<u128> = fibonacci_u128();
+    let mut sorted: Vec<u32> = indices.to_vec();
+    sorted.sort();
+    for pair in sorted.windows(2) {
+        if pair[1] - pair[0] < 2 {
+            return None;
+        }
+    }
+    let mut sum: u128 = 0;
+    for i in sorted {
+        if i < 2 {
+            return None;
+        }
+        sum = sum.checked_add(*f.get(i as usize)?)?;
+    }
+    Some(sum)
+}
+
+/// Writes a number in the Fibonacci base (Zeckendorf representation as a string of 0s and 1s).
+///
+/// Digit $i$ (counting from the right, starting at $2$) is $1$ exactly when $F_i$ is part of the Zeckendorf
+/// representation, so no two adjacent digits are both $1$.
+///
+/// # Arguments
+///
+/// * `n` - The number to represent.
+///
+/// # Returns
+///
+/// Vector of the digits, most significant first (`[0]` for `0`).
+///
+/// # Example
+///
+/// ```
+/// use bens_number_theory::sequences::fibonacci_base_digits;
+///
+/// assert_eq!(fibonacci_base_digits(100), vec![1, 0, 0, 0, 0, 1, 0, 1, 0, 0]);
+/// assert_eq!(fibonacci_base_digits(4), vec![1, 0, 1]); // 3 + 1
+/// ```
+pub fn fibonacci_base_digits(n: u128) -> Vec<u8> {
+    let indices: Vec<u32> = zeckendorf(n);
+    if indices.is_empty() {
+        return vec![0];
+    }
+    let mut digits: Vec<u8> = vec![0; indices[0] as usize - 1];
+    for i in indices {
+        digits[i as usize - 2] = 1;
+    }
+    digits.reverse();
+    digits
+}
+
+/// Calculates the `n`-th term of a linear recurrence modulo `m` using matrix exponentiation.
+///
+/// The recurrence of order $k$ is
+/// $$a_n = c_1 a_{n-1} + c_2 a_{n-2} + \dots + c_k a_{n-k},$$
+/// which can be written as multiplying the vector $(a_{n-1}, \dots, a_{n-k})$ by the $k \times k$ companion matrix
+/// $$\begin{pmatrix}
+///     c_1 & c_2 & \cdots & c_{k-1} & c_k \\\\
+///     1 & 0 & \cdots & 0 & 0 \\\\
+///     \vdots & & \ddots & & \vdots \\\\
+///     0 & 0 & \cdots & 1 & 0
+/// \end{pmatrix}.$$
+/// Raising the matrix to the power $n - k + 1$ by repeated squaring takes $O(k^3 \log n)$ time.
+///
+/// # Arguments
+///
+/// * `coeffs` - The coefficients $c_1, \dots, c_k$.
+/// * `initial` - The first $k$ terms $a_0, \dots, a_{k-1}$.
+/// * `n` - The index of the term to calculate.
+/// * `m` - The modulus.
+///
+/// # Returns
+///
+/// $a_n \bmod m$.
+///
+/// # Panics
+///
+/// Panics if `m` is `0`, or if `coeffs` and `initial` have different lengths.
+///
+/// # Example
+///
+/// ```
+/// use bens_number_theory::sequences::linear_recurrence_mod;
+///
+/// // Fibonacci: F(n) = F(n - 1) + F(n - 2)
+/// assert_eq!(linear_recurrence_mod(&[1, 1], &[0, 1], 10, 1000), 55);
+/// // a(n) = 2a(n - 1) with a(0) = 1
+/// assert_eq!(linear_recurrence_mod(&[2], &[1], 40, 1000), 2_u64.pow(40) % 1000);
+/// ```
+pub fn linear_recurrence_mod(coeffs: &[u64], initial: &[u64], n: u64, m: u64) -> u64 {
+    if m == 0 {
+        panic!("the modulus must be positive");
+    }
+    if coeffs.len() != initial.len() {
+        panic!("a recurrence of order k needs k coefficients and k initial terms");
+    }
+    let k: usize = coeffs.len();
+    if k == 0 {
+        return 0;
+    }
+    if n < k as u64 {
+        return initial[n as usize] % m;
+    }
+
+    let mut companion: Vec<Vec<u64>> = vec![vec![0; k]; k];
+    for (j, c) in coeffs.iter().enumerate() {
+        companion[0][j] = c % m;
+    }
+    for i in 1..k {
+        companion[i][i - 1] = 1 % m;
+    }
+    let power: Vec<Vec<u64>> = matrix_pow_mod(companion, n - (k as u64 - 1), m);
+
+    // the state vector is (a(k - 1), ..., a(0)), and the top row of the power gives a(n)
+    let mut term: u128 = 0;
+    for j in 0..k {
+        term = addmod(
+            term,
+            mulmod(power[0][j] as u128, initial[k - 1 - j] as u128, m as u128),
+            m as u128,
+        );
+    }
+    term as u64
+}
+
+/// Multiplies two square matrices modulo `m`.
+///
+/// # Arguments
+///
+/// * `a` - The left matrix.
+/// * `b` - The right matrix.
+/// * `m` - The modulus.
+///
+/// # Returns
+///
+/// The matrix product $AB \bmod m$.
+fn matrix_mul_mod(a: &[Vec<u64>], b: &[Vec<u64>], m: u64) -> Vec<Vec<u64>> {
+    let k: usize = a.len();
+    let mut product: Vec<Vec<u64>> = vec![vec![0; k]; k];
+    for i in 0..k {
+        for l in 0..k {
+            if a[i][l] == 0 {
+                continue;
+            }
+            for j in 0..k {
+                let term: u128 = mulmod(a[i][l] as u128, b[l][j] as u128, m as u128);
+                product[i][j] = addmod(product[i][j] as u128, term, m as u128) as u64;
+            }
+        }
+    }
+    product
+}
+
+/// Raises a square matrix to a power modulo `m` by repeated squaring.
+///
+/// # Arguments
+///
+/// * `matrix` - The matrix to raise to a power.
+/// * `exponent` - The power.
+/// * `m` - The modulus.
+///
+/// # Returns
+///
+/// The matrix power $A^e \bmod m$.
+fn matrix_pow_mod(matrix: Vec<Vec<u64>>, exponent: u64, m: u64) -> Vec<Vec<u64>> {
+    let k: usize = matrix.len();
+    let mut result: Vec<Vec<u64>> = vec![vec![0; k]; k];
+    for (i, row) in result.iter_mut().enumerate() {
+        row[i] = 1 % m;
+    }
+    let mut base: Vec<Vec<u64>> = matrix;
+    let mut e: u64 = exponent;
+    while e > 0 {
+        if e & 1 == 1 {
+            result = matrix_mul_mod(&result, &base, m);
+        }
+        base = matrix_mul_mod(&base, &base, m);
+        e >>= 1;
+    }
+    result
+}
+
+/// Calculates the `n`-th Fibonacci number modulo `m` (see [`fibonacci_sequence`]).
+///
+/// This is [`linear_recurrence_mod`] for $F_n = F_{n-1} + F_{n-2}$, done by fast doubling instead of with
+/// matrices.
+///
+/// # Panics
+///
+/// Panics if `m` is `0`.
+///
+/// # Example
+///
+/// ```
+/// use bens_number_theory::sequences::fibonacci_mod;
+///
+/// assert_eq!(fibonacci_mod(1_000_000_000_000_000_000, 1_000_000_007), 209783453);
+/// ```
+pub fn fibonacci_mod(n: u64, m: u64) -> u64 {
+    if m == 0 {
+        panic!("the modulus must be positive");
+    }
+    fibonacci_mod_u128(n, m as u128) as u64
+}
+
+/// Calculates the Fibonacci number $F_n$ modulo a `u128` modulus by fast doubling.
+///
+/// With $F_{2k} = F_k (2F_{k + 1} - F_k)$ and $F_{2k + 1} = F_k^2 + F_{k + 1}^2$ the bits of $n$ are read from the
+/// top, which takes $O(\log n)$ multiplications.
+///
+/// # Arguments
+///
+/// * `n` - The index.
+/// * `m` - The modulus (positive).
+///
+/// # Returns
+///
+/// $F_n \bmod m$.
+pub(crate) fn fibonacci_mod_u128(n: u64, m: u128) -> u128 {
+    // F_k and F_{k + 1}
+    let (mut f, mut g): (u128, u128) = (0, 1 % m);
+    let mut bit: u32 = 64 - n.leading_zeros();
+    while bit > 0 {
+        bit -= 1;
+        let doubled: u128 = mulmod(f, submod(addmod(g, g, m), f, m), m);
+        let next: u128 = addmod(mulmod(f, f, m), mulmod(g, g, m), m);
+        if (n >> bit) & 1 == 1 {
+            (f, g) = (next, addmod(doubled, next, m));
+        } else {
+            (f, g) = (doubled, next);
+        }
+    }
+    f
+}
+
+/// Calculates the `n`-th Lucas number modulo `m` (see [`lucas_sequence`] and [`linear_recurrence_mod`]).
+///
+/// # Example
+///
+/// ```
+/// use bens_number_theory::sequences::lucas_mod;
+///
+/// assert_eq!(lucas_mod(4, 100), 7);
+/// ```
+pub fn lucas_mod(n: u64, m: u64) -> u64 {
+    linear_recurrence_mod(&[1, 1], &[2, 1], n, m)
+}
+
+/// Calculates the `n`-th Pell number modulo `m`, where $P_0 = 0$, $P_1 = 1$ and $P_n = 2P_{n-1} + P_{n-2}$.
+///
+/// [A000129](https://oeis.org/A000129)
+///
+/// # Example
+///
+/// ```
+/// use bens_number_theory::sequences::pell_mod;
+///
+/// assert_eq!(pell_mod(5, 1000), 29); // 0, 1, 2, 5, 12, 29
+/// ```
+pub fn pell_mod(n: u64, m: u64) -> u64 {
+    linear_recurrence_mod(&[2, 1], &[0, 1], n, m)
+}
+
+/// Calculates the `n`-th tribonacci number modulo `m`, where $T_0 = T_1 = 0$, $T_2 = 1$ and
+/// $T_n = T_{n-1} + T_{n-2} + T_{n-3}$.
+///
+/// [A000073](https://oeis.org/A000073)
+///
+/// # Example
+///
+/// ```
+/// use bens_number_theory::sequences::tribonacci_mod;
+///
+/// assert_eq!(tribonacci_mod(7, 1000), 13); // 0, 0, 1, 1, 2, 4, 7, 13
+/// ```
+pub fn tribonacci_mod(n: u64, m: u64) -> u64 {
+    linear_recurrence_mod(&[1, 1, 1], &[0, 0, 1], n, m)
+}
+
+/// Lists the lucky numbers below a limit.
+///
+/// Starting from the odd numbers, the sieve repeatedly takes the next surviving number $k$ (after $1$) and
+/// deletes every $k$-th surviving number ([A000959](https://oeis.org/A000959)).
+/// The survivors are kept in a Fenwick tree, so finding and deleting the $j$-th survivor takes $O(\log n)$
+/// time, and deleting from the end keeps the positions of the earlier survivors unchanged.
+///
+/// # Arguments
+///
+/// * `limit` - The exclusive upper bound.
+///
+/// # Returns
+///
+/// Vector of the lucky numbers below `limit`, in increasing order.
+///
+/// # Example
+///
+/// ```
+/// use bens_number_theory::sequences::lucky_numbers;
+///
+/// assert_eq!(lucky_numbers(35), vec![1, 3, 7, 9, 13, 15, 21, 25, 31, 33]);
+/// ```
+pub fn lucky_numbers(limit: u64) -> Vec<u64> {
+    // the odd numbers 1, 3, ..., below the limit survive the first pass
+    let size: usize = (limit / 2) as usize;
+    let mut tree: Vec<u32> = vec![0; size + 1];
+    for i in 1..=size {
+        tree[i] += 1;
+        let parent: usize = i + (i & i.wrapping_neg());
+        if parent <= size {
+            tree[parent] += tree[i];
+        }
+    }
+
+    let mut remaining: usize = size;
+    let mut position: usize = 2;
+    while position <= remaining {
+        let k: usize = 2 * fenwick_find(&tree, position) - 1;
+        if k > remaining {
+            break;
+        }
+        let mut j: usize = remaining / k * k;
+        while j >= k {
+            let index: usize = fenwick_find(&tree, j);
+            fenwick_remove(&mut tree, index);
+            remaining -= 1;
+            j -= k;
+        }
+        position += 1;
+    }
+
+    let mut lucky: Vec<u64> = vec![];
+    for j in 1..=remaining {
+        lucky.push(2 * fenwick_find(&tree, j) as u64 - 1);
+    }
+    lucky
+}
+
+/// Finds the index of the `j`-th remaining element in a Fenwick tree of zeros and ones.
+///
+/// # Arguments
+///
+/// * `tree` - The Fenwick tree, indexed from `1`.
+/// * `j` - The position of the element to find, counting from `1`.
+///
+/// # Returns
+///
+/// The smallest index whose prefix sum is `j`.
+fn fenwick_find(tree: &[u32], j: usize) -> usize {
+    let mut index: usize = 0;
+    let mut rest: u32 = j as u32;
+    let mut step: usize = (tree.len() - 1).next_power_of_two();
+    while step > 0 {
+        if index + step < tree.len() && tree[index + step] < rest {
+            index += step;
+            rest -= tree[index];
+        }
+        step /= 2;
+    }
+    index + 1
+}
+
+/// Removes the element at an index of a Fenwick tree of zeros and ones.
+///
+/// # Arguments
+///
+/// * `tree` - The Fenwick tree, indexed from `1`.
+/// * `index` - The index of the element to remove.
+fn fenwick_remove(tree: &mut [u32], index: usize) {
+    let mut i: usize = index;
+    while i < tree.len() {
+        tree[i] -= 1;
+        i += i & i.wrapping_neg();
+    }
+}
+
+/// Checks if a number is a lucky number (see [`lucky_numbers`]).
+///
+/// # Arguments
+///
+/// * `n` - The number to check.
+///
+/// # Returns
+///
+/// `true` if `n` is a lucky number.
+///
+/// # Panics
+///
+/// Panics if `n` is `u64::MAX`, since the sieve runs up to $n + 1$.
+///
+/// # Example
+///
+/// ```
+/// use bens_number_theory::sequences::is_lucky;
+///
+/// assert!(is_lucky(7));
+/// assert!(!is_lucky(5)); // deleted as the third survivor
+/// ```
+pub fn is_lucky(n: u64) -> bool {
+    let limit: u64 = match n.checked_add(1) {
+        Some(limit) => limit,
+        None => panic!("the sieve limit n + 1 does not fit in a u64"),
+    };
+    n % 2 == 1 && lucky_numbers(limit).last() == Some(&n)
+}
+
+/// Lists the lucky numbers below a limit that are also prime.
+///
+/// [A031157](https://oeis.org/A031157)
+///
+/// # Arguments
+///
+/// * `limit` - The exclusive upper bound.
+///
+/// # Returns
+///
+/// Vector of the lucky primes below `limit`, in increasing order.
+///
+/// # Example
+///
+/// ```
+/// use bens_number_theory::sequences::lucky_primes_below;
+///
+/// assert_eq!(lucky_primes_below(40), vec![3, 7, 13, 31, 37]);
+/// ```
+pub fn lucky_primes_below(limit: u64) -> Vec<u64> {
+    let flags: Vec<bool> = prime_flags(limit as usize);
+    lucky_numbers(limit)
+        .into_iter()
+        .filter(|n| flags[*n as usize])
+        .collect()
+}
+
+/// Calculates Stern's diatomic sequence, also known as the fusc function.
+///
+/// It is defined by $\operatorname{fusc}(0) = 0$, $\operatorname{fusc}(1) = 1$,
+/// $\operatorname{fusc}(2n) = \operatorname{fusc}(n)$ and
+/// $\operatorname{fusc}(2n + 1) = \operatorname{fusc}(n) + \operatorname{fusc}(n + 1)$
+/// ([A002487](https://oeis.org/A002487)). See [`fusc_pair`] for how it is computed.
+///
+/// # Arguments
+///
+/// * `n` - The index.
+///
+/// # Returns
+///
+/// $\operatorname{fusc}(n)$.
+///
+/// # Example
+///
+/// ```
+/// use bens_number_theory::sequences::fusc;
+///
+/// let terms: Vec<u64> = (0..12).map(fusc).collect();
+/// assert_eq!(terms, vec![0, 1, 1, 2, 1, 3, 2, 3, 1, 4, 3, 5]);
+/// ```
+pub fn fusc(n: u64) -> u64 {
+    fusc_pair(n).0
+}
+
+/// Calculates $\operatorname{fusc}(n)$ and $\operatorname{fusc}(n + 1)$ together.
+///
+/// Starting from $(\operatorname{fusc}(0), \operatorname{fusc}(1)) = (0, 1)$, the bits of `n` are read from the
+/// most significant, and each one takes the pair for $k$ to the pair for $2k$ or $2k + 1$ using the recurrence,
+/// so this takes one step per bit. The values never overflow, since $\operatorname{fusc}(n)$ is at most a
+/// Fibonacci number with index about the bit length of `n`.
+///
+/// # Arguments
+///
+/// * `n` - The index.
+///
+/// # Returns
+///
+/// $(\operatorname{fusc}(n), \operatorname{fusc}(n + 1))$.
+fn fusc_pair(n: u64) -> (u64, u64) {
+    let (mut a, mut b): (u64, u64) = (0, 1);
+    let mut bit: u32 = 64 - n.leading_zeros();
+    while bit > 0 {
+        bit -= 1;
+        if (n >> bit) & 1 == 1 {
+            a += b;
+        } else {
+            b += a;
+        }
+    }
+    (a, b)
+}
+
+/// Finds the `n`-th term of the Calkin–Wilf sequence, which lists every positive rational exactly once.
+///
+/// The sequence is the breadth-first order of the Calkin–Wilf tree, where $\frac{p}{q}$ has the children
+/// $\frac{p}{p + q}$ and $\frac{p + q}{q}$, and its `n`-th term is
+/// $\frac{\operatorname{fusc}(n)}{\operatorname{fusc}(n + 1)}$ (see [`fusc`]). Every term is in lowest terms.
+///
+/// # Arguments
+///
+/// * `n` - The position, starting at $1$ for $\frac{1}{1}$.
+///
+/// # Returns
+///
+/// The numerator and denominator of the term.
+///
+/// # Panics
+///
+/// Panics if `n` is `0`.
+///
+/// # Example
+///
+/// ```
+/// use bens_number_theory::sequences::calkin_wilf;
+///
+/// let terms: Vec<(u64, u64)> = (1..8).map(calkin_wilf).collect();
+/// assert_eq!(terms, vec![(1, 1), (1, 2), (2, 1), (1, 3), (3, 2), (2, 3), (3, 1)]);
+/// assert_eq!(calkin_wilf(u64::MAX), (64, 1));
+/// ```
+pub fn calkin_wilf(n: u64) -> (u64, u64) {
+    if n == 0 {
+        panic!("the Calkin-Wilf sequence starts at 1");
+    }
+    fusc_pair(n)
+}
+
+/// Finds the position of a positive rational in the Calkin–Wilf sequence, the inverse of [`calkin_wilf`].
+///
+/// The path from $\frac{p}{q}$ up to the root of the Calkin–Wilf tree goes to the parent $\frac{p - q}{q}$ when
+/// $p > q$ (a right child, bit $1$) and to $\frac{p}{q - p}$ when $p < q$ (a left child, bit $0$). These are the
+/// steps of the subtractive Euclidean algorithm, so each run of equal bits is taken at once with a division, and
+/// the bits of the position are a $1$ followed by the path from the root, read as the run lengths of the
+/// continued fraction of $\frac{p}{q}$.
+///
+/// # Arguments
+///
+/// * `p` - The numerator.
+/// * `q` - The denominator.
+///
+/// # Returns
+///
+/// The position $n \geq 1$ with `calkin_wilf(n)` equal to $\frac{p}{q}$ in lowest terms.
+///
+/// # Panics
+///
+/// Panics if `p` or `q` is `0`, or if the position does not fit in a `u64`.
+///
+/// # Example
+///
+/// ```
+/// use bens_number_theory::sequences::calkin_wilf_index;
+///
+/// assert_eq!(calkin_wilf_index(1, 1), 1);
+/// assert_eq!(calkin_wilf_index(3, 2), 5);
+/// assert_eq!(calkin_wilf_index(6, 4), 5);
+/// assert_eq!(calkin_wilf_index(64, 1), u64::MAX);
+/// ```
+pub fn calkin_wilf_index(p: u64, q: u64) -> u64 {
+    if p == 0 || q == 0 {
+        panic!("the numerator and denominator must be positive");
+    }
+    let common: u64 = gcd(p, q);
+    let (mut p, mut q): (u64, u64) = (p / common, q / common);
+    let mut path: u64 = 0;
+    let mut length: u32 = 0;
+    while p != q {
+        let (steps, bit): (u64, u64) = if p > q {
+            let steps: u64 = (p - 1) / q;
+            p -= steps * q;
+            (steps, 1)
+        } else {
+            let steps: u64 = (q - 1) / p;
+            q -= steps * p;
+            (steps, 0)
+        };
+        if steps >= (64 - length) as u64 {
+            panic!("the position does not fit in a u64");
+        }
+        if bit == 1 {
+            path |= ((1 << steps) - 1) << length;
+        }
+        length += steps as u32;
+    }
+    1 << length | path
+}
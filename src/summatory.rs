@@ -0,0 +1,316 @@
+use crate::dirichlet::multiplicative_sieve_with;
+use crate::modular::totient_sieve;
+
+/// Enumerates the blocks of consecutive $k$ on which $\lfloor n/k \rfloor$ is constant.
+///
+/// There are fewer than $2\sqrt{n}$ blocks, since either $k \leq \sqrt{n}$ or $\lfloor n/k \rfloor < \sqrt{n}$.
+/// The block starting at $k$ ends at $\lfloor n / \lfloor n/k \rfloor \rfloor$, so sums of the form
+/// $\sum_{k=1}^{n} f(\lfloor n/k \rfloor)$ only need one evaluation per block.
+///
+/// # Arguments
+///
+/// * `n` - The number being divided.
+///
+/// # Returns
+///
+/// An iterator of `(value, lo, hi)` with $\lfloor n/k \rfloor = \text{value}$ exactly for $\text{lo} \leq k \leq \text{hi}$,
+/// in increasing order of `lo` and covering $1 \leq k \leq n$ (empty for `0`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::summatory::divisor_blocks;
+/// let blocks: Vec<(u64, u64, u64)> = divisor_blocks(10).collect();
+/// assert_eq!(blocks, vec![(10, 1, 1), (5, 2, 2), (3, 3, 3), (2, 4, 5), (1, 6, 10)]);
+/// ```
+pub fn divisor_blocks(n: u64) -> impl Iterator<Item = (u64, u64, u64)> {
+    let mut next: Option<u64> = Some(1);
+    std::iter::from_fn(move || {
+        let lo: u64 = next.filter(|lo| *lo <= n)?;
+        let value: u64 = n / lo;
+        let hi: u64 = n / value;
+        next = hi.checked_add(1);
+        Some((value, lo, hi))
+    })
+}
+
+/// Calculates the Möbius function $\mu(n)$ for every $n$ up to a limit.
+///
+/// $\mu(n)$ is $0$ if a square divides $n$, and otherwise $(-1)^k$ where $k$ is the number of prime factors of $n$,
+/// so $\mu(p) = -1$ and $\mu(p^k) = 0$ for $k \geq 2$ determine it through [`multiplicative_sieve_with`].
+///
+/// [A008683](https://oeis.org/A008683)
+///
+/// # Arguments
+///
+/// * `limit` - The (inclusive) upper bound of the numbers.
+///
+/// # Returns
+///
+/// Vector of length `limit + 1` where index `n` holds $\mu(n)$ (and index `0` holds `0`).
+pub(crate) fn mobius_sieve(limit: usize) -> Vec<i8> {
+    multiplicative_sieve_with(limit, |_, k| if k == 1 { -1 } else { 0 })
+}
+
+/// Chooses how far the sublinear summatory functions sieve directly, which is about $n^{2/3}$.
+///
+/// # Arguments
+///
+/// * `n` - The argument of the summatory function.
+///
+/// # Returns
+///
+/// The sieve limit, between `1` and `n` (when `n` is positive).
+fn summatory_cutoff(n: u64) -> u64 {
+    let cutoff: u64 = (n as f64).cbrt().powi(2) as u64;
+    cutoff.clamp(1, n.max(1))
+}
+
+/// Calculates the Mertens function $M(n) = \sum_{k=1}^{n} \mu(k)$ by summing a Möbius sieve.
+///
+/// This takes $O(n)$ time and memory, and is mostly useful for checking [`mertens_fast`].
+///
+/// [A002321](https://oeis.org/A002321)
+///
+/// # Arguments
+///
+/// * `n` - The (inclusive) upper bound of the sum.
+///
+/// # Returns
+///
+/// $M(n)$ (`0` for `0`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::summatory::mertens_sieve;
+/// assert_eq!(mertens_sieve(10), -1); // 1 - 1 - 1 + 0 - 1 + 1 - 1 + 0 + 0 + 1
+/// assert_eq!(mertens_sieve(10_000), -23);
+/// ```
+pub fn mertens_sieve(n: u64) -> i64 {
+    mobius_sieve(n as usize).iter().map(|mu| *mu as i64).sum()
+}
+
+/// Calculates the Mertens function $M(n) = \sum_{k=1}^{n} \mu(k)$ in about $O(n^{2/3})$ time.
+///
+/// Summing $\mu$ over all divisors gives $\sum_{k=1}^{n} M(\lfloor n/k \rfloor) = 1$, so
+/// $$M(n) = 1 - \sum_{k=2}^{n} M\left(\left\lfloor \frac{n}{k} \right\rfloor\right).$$
+/// The values up to about $n^{2/3}$ come from a Möbius sieve, and the larger values $M(\lfloor n/k \rfloor)$ are
+/// filled in from the smallest up, with each sum taken over [`divisor_blocks`].
+///
+/// # Arguments
+///
+/// * `n` - The (inclusive) upper bound of the sum.
+///
+/// # Returns
+///
+/// $M(n)$ (`0` for `0`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::summatory::mertens_fast;
+/// assert_eq!(mertens_fast(1_000_000), 212);
+/// assert_eq!(mertens_fast(1_000_000_000), -222);
+/// ```
+pub fn mertens_fast(n: u64) -> i64 {
+    if n == 0 {
+        return 0;
+    }
+    let cutoff: u64 = summatory_cutoff(n);
+    let mut small: Vec<i64> = Vec::with_capacity(cutoff as usize + 1);
+    let mut total: i64 = 0;
+    for mu in mobius_sieve(cutoff as usize) {
+        total += mu as i64;
+        small.push(total);
+    }
+    // large[k] holds M(n / k) for every k with n / k > cutoff
+    let count: usize = (n / (cutoff + 1)) as usize;
+    let mut large: Vec<i64> = vec![0; count + 1];
+    let mut k: usize = count;
+    while k >= 1 {
+        let v: u64 = n / k as u64;
+        let mut value: i64 = 1;
+        for (quotient, lo, hi) in divisor_blocks(v).skip(1) {
+            let m: i64 = if quotient <= cutoff {
+                small[quotient as usize]
+            } else {
+                large[k * lo as usize]
+            };
+            value -= (hi - lo + 1) as i64 * m;
+        }
+        large[k] = value;
+        k -= 1;
+    }
+    if n <= cutoff {
+        small[n as usize]
+    } else {
+        large[1]
+    }
+}
+
+/// Calculates the totient summatory function $\Phi(n) = \sum_{k=1}^{n} \varphi(k)$ by summing a totient sieve.
+///
+/// This takes $O(n)$ time and memory, and is mostly useful for checking [`totient_summatory`].
+///
+/// [A002088](https://oeis.org/A002088)
+///
+/// # Arguments
+///
+/// * `n` - The (inclusive) upper bound of the sum.
+///
+/// # Returns
+///
+/// $\Phi(n)$ (`0` for `0`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::summatory::totient_summatory_sieve;
+/// assert_eq!(totient_summatory_sieve(10), 32);
+/// assert_eq!(totient_summatory_sieve(10_000), 30_397_486);
+/// ```
+pub fn totient_summatory_sieve(n: u64) -> u128 {
+    totient_sieve(n as usize + 1)
+        .iter()
+        .map(|phi| *phi as u128)
+        .sum()
+}
+
+/// Calculates the totient summatory function $\Phi(n) = \sum_{k=1}^{n} \varphi(k)$ in about $O(n^{2/3})$ time.
+///
+/// Every pair $1 \leq a \leq b \leq n$ has $\gcd(a, b) = d$ for exactly one $d$, and there are
+/// $\Phi(\lfloor n/d \rfloor)$ such pairs, so
+/// $$\Phi(n) = \frac{n(n + 1)}{2} - \sum_{k=2}^{n} \Phi\left(\left\lfloor \frac{n}{k} \right\rfloor\right).$$
+/// As in [`mertens_fast`], the values up to about $n^{2/3}$ come from a sieve and the larger ones are filled in
+/// from the smallest up over [`divisor_blocks`].
+///
+/// # Arguments
+///
+/// * `n` - The (inclusive) upper bound of the sum.
+///
+/// # Returns
+///
+/// $\Phi(n)$ (`0` for `0`).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::summatory::totient_summatory;
+/// assert_eq!(totient_summatory(10_000), 30_397_486);
+/// assert_eq!(totient_summatory(1_000_000_000), 303_963_551_173_008_414);
+/// ```
+pub fn totient_summatory(n: u64) -> u128 {
+    if n == 0 {
+        return 0;
+    }
+    let cutoff: u64 = summatory_cutoff(n);
+    // the prefix sums stay below 2^64 for any cutoff that fits in memory
+    let mut small: Vec<u64> = totient_sieve(cutoff as usize + 1);
+    let mut i: usize = 1;
+    while i < small.len() {
+        small[i] += small[i - 1];
+        i += 1;
+    }
+    // large[k] holds Phi(n / k) for every k with n / k > cutoff
+    let count: usize = (n / (cutoff + 1)) as usize;
+    let mut large: Vec<u128> = vec![0; count + 1];
+    let mut k: usize = count;
+    while k >= 1 {
+        let v: u128 = (n / k as u64) as u128;
+        let mut value: u128 = v * (v + 1) / 2;
+        for (quotient, lo, hi) in divisor_blocks(v as u64).skip(1) {
+            let phi: u128 = if quotient <= cutoff {
+                small[quotient as usize] as u128
+            } else {
+                large[k * lo as usize]
+            };
+            value -= (hi - lo + 1) as u128 * phi;
+        }
+        large[k] = value;
+        k -= 1;
+    }
+    if n <= cutoff {
+        small[n as usize] as u128
+    } else {
+        large[1]
+    }
+}
+
+/// Counts the squarefree numbers below a limit.
+///
+/// Every $k$ is $a^2 b$ with $b$ squarefree for exactly one $a$, and $\sum_{d \mid a} \mu(d)$ is $1$ for $a = 1$
+/// and $0$ otherwise, so summing over the squares $d^2$ dividing each $k$ gives
+/// $$Q(m) = \sum_{d = 1}^{\lfloor \sqrt{m} \rfloor} \mu(d) \left\lfloor \frac{m}{d^2} \right\rfloor$$
+/// for the count $Q(m)$ up to $m$. Only a Möbius sieve up to $\sqrt{m}$ is needed, which takes $O(\sqrt{m})$ time.
+///
+/// [A013928](https://oeis.org/A013928)
+///
+/// # Arguments
+///
+/// * `n` - The (exclusive) upper bound of the numbers.
+///
+/// # Returns
+///
+/// The number of squarefree $k$ with $1 \leq k < n$.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::summatory::count_squarefree_below;
+/// assert_eq!(count_squarefree_below(11), 7); // 1, 2, 3, 5, 6, 7, 10
+/// assert_eq!(count_squarefree_below(100_000_000), 60_792_694);
+/// ```
+pub fn count_squarefree_below(n: u64) -> u64 {
+    if n <= 1 {
+        return 0;
+    }
+    let m: u64 = n - 1;
+    let root: u64 = m.isqrt();
+    let mut count: i64 = 0;
+    for (d, mu) in mobius_sieve(root as usize).iter().enumerate().skip(1) {
+        let d: u64 = d as u64;
+        count += *mu as i64 * (m / (d * d)) as i64;
+    }
+    count as u64
+}
+
+/// Finds the squarefree numbers below a limit by sieving out the multiples of every square.
+///
+/// This takes $O(n)$ time and memory, so it suits moderate limits; [`count_squarefree_below`] counts them for much
+/// larger ones.
+///
+/// [A005117](https://oeis.org/A005117)
+///
+/// # Arguments
+///
+/// * `limit` - The (exclusive) upper bound of the numbers.
+///
+/// # Returns
+///
+/// Iterator over the squarefree $k$ with $1 \leq k < \text{limit}$, in increasing order.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::summatory::squarefree_numbers_below;
+/// let squarefree: Vec<u64> = squarefree_numbers_below(16).collect();
+/// assert_eq!(squarefree, vec![1, 2, 3, 5, 6, 7, 10, 11, 13, 14, 15]);
+/// ```
+pub fn squarefree_numbers_below(limit: u64) -> impl Iterator<Item = u64> {
+    let size: usize = limit as usize;
+    let mut squareful: Vec<bool> = vec![false; size];
+    let mut d: usize = 2;
+    while d * d < size {
+        // if p^2 divides d, the multiples of d^2 were already crossed out as multiples of p^2
+        if !squareful[d] {
+            let mut k: usize = d * d;
+            while k < size {
+                squareful[k] = true;
+                k += d * d;
+            }
+        }
+        d += 1;
+    }
+    (1..limit).filter(move |k| !squareful[*k as usize])
+}
@@ -0,0 +1,149 @@
+//! **For education only.** These keys are far too small to protect anything, the primes come from whatever
+//! generator is passed in, and there is no padding, so never use them for real encryption.
+
+use crate::modular::{gcd, mod_inverse, mod_pow};
+use crate::primes::{is_probable_prime, random_prime};
+use num::BigInt;
+
+/// A textbook RSA keypair with a modulus that fits in a `u64`.
+///
+/// **For education only**, see the [module documentation](self).
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::toy_rsa::RsaKeypair;
+///
+/// let keypair: RsaKeypair = RsaKeypair::from_primes(61, 53, 17).unwrap();
+/// assert_eq!(keypair.n, 3233);
+/// assert_eq!(keypair.d, 413);
+/// let c: u64 = keypair.encrypt(65);
+/// assert_eq!(keypair.decrypt(c), 65);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RsaKeypair {
+    /// The modulus $n = pq$.
+    pub n: u64,
+    /// The public exponent.
+    pub e: u64,
+    /// The private exponent, the inverse of $e$ modulo $\lambda(n)$.
+    pub d: u64,
+    /// The first prime factor of the modulus.
+    pub p: u64,
+    /// The second prime factor of the modulus.
+    pub q: u64,
+}
+
+impl RsaKeypair {
+    /// Builds a keypair from two primes and a public exponent.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - The first prime.
+    /// * `q` - The second prime.
+    /// * `e` - The public exponent.
+    ///
+    /// # Returns
+    ///
+    /// `Some(keypair)` with $d = e^{-1} \bmod \lambda(pq)$, or `None` if `p` and `q` are equal or not both prime,
+    /// if $pq$ does not fit in a `u64`, or if `e` is not coprime to $\lambda(pq)$.
+    pub fn from_primes(p: u64, q: u64, e: u64) -> Option<RsaKeypair> {
+        if p == q || !is_probable_prime(&BigInt::from(p)) || !is_probable_prime(&BigInt::from(q)) {
+            return None;
+        }
+        let n: u64 = p.checked_mul(q)?;
+        let d: u64 = mod_inverse(e, lambda(p, q))?;
+        Some(RsaKeypair { n, e, d, p, q })
+    }
+
+    /// Encrypts a message with the public key, $c = m^e \bmod n$.
+    ///
+    /// # Arguments
+    ///
+    /// * `m` - The message, which should be less than $n$.
+    ///
+    /// # Returns
+    ///
+    /// The ciphertext.
+    pub fn encrypt(&self, m: u64) -> u64 {
+        mod_pow(m, self.e, self.n)
+    }
+
+    /// Decrypts a ciphertext with the private key, $m = c^d \bmod n$.
+    ///
+    /// # Arguments
+    ///
+    /// * `c` - The ciphertext.
+    ///
+    /// # Returns
+    ///
+    /// The message.
+    pub fn decrypt(&self, c: u64) -> u64 {
+        mod_pow(c, self.d, self.n)
+    }
+}
+
+/// Generates a random textbook RSA keypair.
+///
+/// **For education only**, see the [module documentation](self).
+///
+/// Two distinct random primes of `bits / 2` bits each are multiplied for the modulus, so $n$ has `bits` or
+/// `bits - 1` bits. The public exponent is $65537$ when it is coprime to and smaller than $\lambda(n)$, and
+/// otherwise the smallest odd number from $3$ that is coprime to $\lambda(n)$.
+///
+/// # Arguments
+///
+/// * `bits` - The size of the modulus in bits, an even number from 8 to 64.
+/// * `rng` - The source of randomness.
+///
+/// # Returns
+///
+/// The keypair.
+///
+/// # Panics
+///
+/// Panics if `bits` is odd or not between 8 and 64.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::toy_rsa::{generate_rsa_keypair, RsaKeypair};
+///
+/// let keypair: RsaKeypair = generate_rsa_keypair(32, &mut rand::rngs::mock::StepRng::new(1, 0x9E3779B97F4A7C15));
+/// assert_eq!(keypair.decrypt(keypair.encrypt(42)), 42);
+/// ```
+pub fn generate_rsa_keypair<R: rand::Rng + ?Sized>(bits: u32, rng: &mut R) -> RsaKeypair {
+    if !(8..=64).contains(&bits) || bits % 2 == 1 {
+        panic!("bits must be an even number between 8 and 64");
+    }
+    let p: u64 = random_prime(bits / 2, rng);
+    let mut q: u64 = random_prime(bits / 2, rng);
+    while q == p {
+        q = random_prime(bits / 2, rng);
+    }
+    let lambda: u64 = lambda(p, q);
+    let mut e: u64 = 65537;
+    if e >= lambda || gcd(e, lambda) != 1 {
+        e = 3;
+        while gcd(e, lambda) != 1 {
+            e += 2;
+        }
+    }
+    RsaKeypair::from_primes(p, q, e).unwrap()
+}
+
+/// Calculates the Carmichael function of a product of two distinct primes, $\lambda(pq) = \operatorname{lcm}(p - 1, q - 1)$.
+///
+/// Going through the primes avoids factorizing the modulus.
+///
+/// # Arguments
+///
+/// * `p` - The first prime.
+/// * `q` - The second prime.
+///
+/// # Returns
+///
+/// $\lambda(pq)$.
+fn lambda(p: u64, q: u64) -> u64 {
+    (p - 1) / gcd(p - 1, q - 1) * (q - 1)
+}
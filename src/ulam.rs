@@ -0,0 +1,229 @@
+use crate::primes::{is_probable_prime, prime_flags};
+use num::BigInt;
+
+/// Calculates the position of a number on the Ulam spiral.
+///
+/// The spiral starts with $1$ at the origin and winds counterclockwise, with $2$ at $(1, 0)$, $3$ at $(1, 1)$
+/// and $4$ at $(0, 1)$. Ring $k$ (the square with corners $(\pm k, \pm k)$) holds the numbers from
+/// $(2k - 1)^2 + 1$ to $(2k + 1)^2$, with the last one in the bottom right corner.
+///
+/// # Arguments
+///
+/// * `n` - The number to place (must not be `0`).
+///
+/// # Returns
+///
+/// The coordinates `(x, y)` of `n`, with $y$ increasing upwards.
+///
+/// # Panics
+///
+/// Panics if `n` is `0`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::ulam::ulam_coordinates;
+/// assert_eq!(ulam_coordinates(1), (0, 0));
+/// assert_eq!(ulam_coordinates(5), (-1, 1));
+/// assert_eq!(ulam_coordinates(9), (1, -1));
+/// assert_eq!(ulam_coordinates(10), (2, -1));
+/// ```
+pub fn ulam_coordinates(n: u64) -> (i64, i64) {
+    if n == 0 {
+        panic!("the Ulam spiral starts at 1");
+    }
+    if n == 1 {
+        return (0, 0);
+    }
+    let ring: u64 = (n - 1).isqrt().div_ceil(2);
+    let offset: i64 = (n - (2 * ring - 1) * (2 * ring - 1) - 1) as i64;
+    let k: i64 = ring as i64;
+    let position: i64 = offset % (2 * k);
+    match offset / (2 * k) {
+        0 => (k, -k + 1 + position),
+        1 => (k - 1 - position, k),
+        2 => (-k, k - 1 - position),
+        _ => (-k + 1 + position, -k),
+    }
+}
+
+/// Calculates the number at a position on the Ulam spiral (the inverse of [`ulam_coordinates`]).
+///
+/// # Arguments
+///
+/// * `x` - The horizontal coordinate.
+/// * `y` - The vertical coordinate, increasing upwards.
+///
+/// # Returns
+///
+/// The number at `(x, y)`.
+///
+/// # Panics
+///
+/// Panics if the number does not fit in a `u64`.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::ulam::ulam_value;
+/// assert_eq!(ulam_value(0, 0), 1);
+/// assert_eq!(ulam_value(-1, 1), 5);
+/// assert_eq!(ulam_value(2, 2), 13);
+/// ```
+pub fn ulam_value(x: i64, y: i64) -> u64 {
+    let k: u64 = x.unsigned_abs().max(y.unsigned_abs());
+    if k == 0 {
+        return 1;
+    }
+    let (x, y, k): (i128, i128, i128) = (x as i128, y as i128, k as i128);
+    let (side, position): (i128, i128) = if x == k && y > -k {
+        (0, y + k - 1)
+    } else if y == k {
+        (1, k - 1 - x)
+    } else if x == -k {
+        (2, k - 1 - y)
+    } else {
+        (3, x + k - 1)
+    };
+    let value: i128 = (2 * k - 1) * (2 * k - 1) + 1 + side * 2 * k + position;
+    match u64::try_from(value) {
+        Ok(value) => value,
+        Err(_) => panic!("the value does not fit in a u64"),
+    }
+}
+
+/// Builds the grid of which cells of the Ulam spiral are prime, out to a given radius.
+///
+/// The diagonal lines of primes that show up in this grid are what made the spiral famous.
+///
+/// # Arguments
+///
+/// * `radius` - The largest $|x|$ and $|y|$ included.
+///
+/// # Returns
+///
+/// A $(2r + 1) \times (2r + 1)$ grid where `grid[row][column]` tells whether the number at
+/// $(x, y) = (\text{column} - r, r - \text{row})$ is prime, so the first row is the top of the spiral.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::ulam::ulam_prime_grid;
+/// // 5 4 3
+/// // 6 1 2
+/// // 7 8 9
+/// assert_eq!(
+///     ulam_prime_grid(1),
+///     vec![vec![true, false, true], vec![false, false, true], vec![true, false, false]]
+/// );
+/// ```
+pub fn ulam_prime_grid(radius: u32) -> Vec<Vec<bool>> {
+    let r: i64 = radius as i64;
+    let side: u64 = 2 * radius as u64 + 1;
+    let flags: Vec<bool> = prime_flags((side * side) as usize);
+    let mut grid: Vec<Vec<bool>> = vec![];
+    let mut y: i64 = r;
+    while y >= -r {
+        let mut row: Vec<bool> = vec![];
+        let mut x: i64 = -r;
+        while x <= r {
+            row.push(flags[ulam_value(x, y) as usize]);
+            x += 1;
+        }
+        grid.push(row);
+        y -= 1;
+    }
+    grid
+}
+
+/// Calculates the fraction of the numbers on the diagonals of the Ulam spiral that are prime.
+///
+/// The square of odd side length $s = 2k + 1$ has $2s - 1$ numbers on its diagonals: $1$ and the four
+/// corners $(2j + 1)^2 - 2ji$ for $i = 0, 1, 2, 3$ of every ring $j \le k$.
+///
+/// # Arguments
+///
+/// * `side_length` - The side length of the square (odd).
+///
+/// # Returns
+///
+/// The number of primes on the diagonals divided by the $2s - 1$ numbers there.
+///
+/// # Panics
+///
+/// Panics if `side_length` is even.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::ulam::diagonal_prime_ratio;
+/// assert_eq!(diagonal_prime_ratio(7), 8.0 / 13.0);
+/// ```
+pub fn diagonal_prime_ratio(side_length: u64) -> f64 {
+    if side_length.is_multiple_of(2) {
+        panic!("the side length must be odd");
+    }
+    let mut primes: u64 = 0;
+    let mut j: u64 = 1;
+    while 2 * j < side_length {
+        primes += diagonal_primes_in_ring(j);
+        j += 1;
+    }
+    primes as f64 / (2 * side_length - 1) as f64
+}
+
+/// Finds the smallest side length at which the fraction of primes on the diagonals of the Ulam spiral
+/// drops below a ratio (see [`diagonal_prime_ratio`]).
+///
+/// The rings are added one at a time, so the primes of every corner are only checked once.
+///
+/// # Arguments
+///
+/// * `ratio` - The ratio to drop below, between $0$ and $1$.
+///
+/// # Returns
+///
+/// The smallest side length $s \geq 3$ whose diagonal prime ratio is less than `ratio`.
+///
+/// # Panics
+///
+/// Panics if `ratio` is not positive, since the ratio never reaches $0$.
+///
+/// # Examples
+///
+/// ```
+/// use bens_number_theory::ulam::side_length_below_diagonal_ratio;
+/// assert_eq!(side_length_below_diagonal_ratio(0.5), 11);
+/// ```
+pub fn side_length_below_diagonal_ratio(ratio: f64) -> u64 {
+    if ratio.is_nan() || ratio <= 0.0 {
+        panic!("the ratio must be positive");
+    }
+    let mut primes: u64 = 0;
+    let mut j: u64 = 1;
+    loop {
+        primes += diagonal_primes_in_ring(j);
+        let side_length: u64 = 2 * j + 1;
+        if (primes as f64) < ratio * (2 * side_length - 1) as f64 {
+            return side_length;
+        }
+        j += 1;
+    }
+}
+
+/// Counts the primes among the four corners of ring `j` of the Ulam spiral.
+///
+/// # Arguments
+///
+/// * `j` - The ring, at least `1`.
+///
+/// # Returns
+///
+/// The number of the corners $(2j + 1)^2 - 2ji$ for $i = 1, 2, 3$ that are prime (the bottom right corner
+/// $(2j + 1)^2$ is a square).
+fn diagonal_primes_in_ring(j: u64) -> u64 {
+    let square: u64 = (2 * j + 1) * (2 * j + 1);
+    (1..4)
+        .filter(|i| is_probable_prime(&BigInt::from(square - 2 * j * i)))
+        .count() as u64
+}
@@ -0,0 +1,152 @@
+#[cfg(test)]
+mod untouchable_tests {
+    use bens_number_theory::aliquot::{aliquot_preimages, untouchable_numbers_below};
+    use bens_number_theory::primes::generate_primes;
+
+    #[test]
+    fn aliquot_preimages_test() {
+        assert_eq!(aliquot_preimages(0, 100), vec![1]);
+        assert_eq!(aliquot_preimages(28, 1000), vec![28]);
+        assert_eq!(aliquot_preimages(2, 10000), vec![]);
+        for m in aliquot_preimages(16, 300) {
+            assert_eq!((1..m).filter(|d| m % d == 0).sum::<u64>(), 16);
+        }
+    }
+
+    #[test]
+    fn aliquot_preimages_of_one_test() {
+        let primes: Vec<u64> = generate_primes(1000_u64);
+        assert_eq!(aliquot_preimages(1, 1000), primes);
+    }
+
+    #[test]
+    fn untouchable_test() {
+        let untouchable: Vec<u64> = untouchable_numbers_below(500);
+        assert_eq!(untouchable[..6], [2, 5, 52, 88, 96, 120]);
+        for n in &untouchable {
+            assert!(aliquot_preimages(*n, (n - 1) * (n - 1)).is_empty());
+        }
+        // 5 is conjectured to be the only odd untouchable number
+        let odd: Vec<u64> = untouchable
+            .iter()
+            .filter(|n| *n % 2 == 1)
+            .copied()
+            .collect();
+        assert_eq!(odd, vec![5]);
+        assert_eq!(untouchable_numbers_below(2), vec![]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn untouchable_bound_overflow_test() {
+        // (2^32)^2 does not fit in a u64
+        untouchable_numbers_below((1 << 32) + 2);
+    }
+}
+
+#[cfg(test)]
+mod sociable_tests {
+    use bens_number_theory::aliquot::sociable_cycles_below;
+
+    #[test]
+    fn sociable_cycles_test() {
+        let cycles: Vec<Vec<u64>> = sociable_cycles_below(15000, 28);
+        assert_eq!(cycles.len(), 2);
+        assert_eq!(cycles[0], vec![12496, 14288, 15472, 14536, 14264]);
+        assert_eq!(cycles[1].len(), 28);
+        assert_eq!(cycles[1][0], 14316);
+        assert_eq!(*cycles[1].iter().max().unwrap(), 629072);
+    }
+
+    #[test]
+    fn sociable_cycles_exclude_perfect_and_amicable_test() {
+        // 6, 28, 496, 8128 are perfect and (220, 284), (1184, 1210), ... are amicable
+        assert_eq!(sociable_cycles_below(12000, 28), Vec::<Vec<u64>>::new());
+        // the 28-cycle is not found when only looking for shorter cycles
+        assert_eq!(sociable_cycles_below(15000, 27).len(), 1);
+    }
+
+    #[test]
+    fn sociable_cycles_members_test() {
+        for cycle in sociable_cycles_below(15000, 28) {
+            for (i, m) in cycle.iter().enumerate() {
+                let next: u64 = cycle[(i + 1) % cycle.len()];
+                assert_eq!((1..*m).filter(|d| m % d == 0).sum::<u64>(), next);
+                assert!(*m >= cycle[0]);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod aliquot_sieve_tests {
+    use bens_number_theory::aliquot::{
+        abundant_numbers_below, aliquot_sieve, deficient_numbers_below,
+        non_sum_of_two_abundants_below,
+    };
+    use bens_number_theory::divisor_functions::divisor_sum;
+    use std::collections::HashSet;
+
+    #[test]
+    fn sieve_test() {
+        let sums: Vec<u64> = aliquot_sieve(5000);
+        assert_eq!(sums.len(), 5001);
+        assert_eq!(sums[0], 0);
+        for n in 1..=5000_u64 {
+            assert_eq!(sums[n as usize], divisor_sum(n) - n, "{n}");
+        }
+        assert_eq!(aliquot_sieve(0), vec![0]);
+        assert_eq!(aliquot_sieve(1), vec![0, 0]);
+    }
+
+    #[test]
+    fn abundant_test() {
+        let abundant: Vec<u64> = abundant_numbers_below(10_000);
+        assert_eq!(abundant[0], 12);
+        assert_eq!(abundant.iter().find(|n| *n % 2 == 1), Some(&945));
+        // the abundant numbers below 10000 and the deficient ones, with the perfect 6, 28, 496 and 8128
+        let deficient: Vec<u64> = deficient_numbers_below(10_000);
+        assert_eq!(abundant.len() + deficient.len() + 4, 9999);
+        assert_eq!(abundant.len(), 2487);
+        let all: HashSet<u64> = abundant.iter().chain(&deficient).copied().collect();
+        let missing: Vec<u64> = (1..10_000).filter(|n| !all.contains(n)).collect();
+        assert_eq!(missing, vec![6, 28, 496, 8128]);
+        assert_eq!(abundant_numbers_below(12), vec![]);
+        assert_eq!(abundant_numbers_below(13), vec![12]);
+        assert_eq!(deficient_numbers_below(1), vec![]);
+        assert_eq!(deficient_numbers_below(2), vec![1]);
+    }
+
+    #[test]
+    fn non_sum_test() {
+        let numbers: Vec<u64> = non_sum_of_two_abundants_below(28_124);
+        assert_eq!(numbers.iter().sum::<u64>(), 4_179_871);
+        assert_eq!(numbers.len(), 1456);
+        assert_eq!(numbers[..3], [1, 2, 3]);
+        assert_eq!(*numbers.last().unwrap(), 20161);
+        // 24 = 12 + 12 is the first sum
+        assert_eq!(numbers[22..24], [23, 25]);
+        assert_eq!(non_sum_of_two_abundants_below(30_000), numbers);
+    }
+
+    #[test]
+    fn brute_force_test() {
+        let limit: u64 = 2000;
+        let abundant: Vec<u64> = (1..limit).filter(|n| divisor_sum(*n) > 2 * n).collect();
+        assert_eq!(abundant_numbers_below(limit), abundant);
+        let expected: Vec<u64> = (1..limit)
+            .filter(|n| {
+                !abundant
+                    .iter()
+                    .any(|a| a < n && abundant.binary_search(&(n - a)).is_ok())
+            })
+            .collect();
+        assert_eq!(non_sum_of_two_abundants_below(limit), expected);
+        assert_eq!(non_sum_of_two_abundants_below(0), vec![]);
+        assert_eq!(non_sum_of_two_abundants_below(1), vec![]);
+        assert_eq!(
+            non_sum_of_two_abundants_below(25),
+            (1..24).collect::<Vec<u64>>()
+        );
+    }
+}
@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod agreement_tests {
+    use bens_number_theory::consistency::{check_agreement, check_agreement_with, Disagreement};
+    use bens_number_theory::factorization::least_prime_factor;
+
+    #[test]
+    fn sweep_test() {
+        assert_eq!(check_agreement(0..10_000_000), Ok(()));
+    }
+
+    #[test]
+    fn large_ranges_test() {
+        // the Lucas n - 1 test stops voting at 2^40
+        let start: u64 = (1 << 40) - 2000;
+        assert_eq!(check_agreement(start..start + 4000), Ok(()));
+        assert_eq!(
+            check_agreement(1_000_000_000_000..1_000_000_005_000),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn empty_and_small_ranges_test() {
+        assert_eq!(check_agreement(0..0), Ok(()));
+        assert_eq!(check_agreement(5..5), Ok(()));
+        assert_eq!(check_agreement(0..1), Ok(()));
+        assert_eq!(check_agreement(1..3), Ok(()));
+        assert_eq!(check_agreement(70_000..70_001), Ok(()));
+    }
+
+    #[test]
+    fn broken_checker_test() {
+        // calls 7919^2 prime, the kind of mistake a float square root makes
+        let broken = |n: u64| n == 7919 * 7919 || least_prime_factor(n) == Some(n);
+        assert_eq!(
+            check_agreement_with(62_000_000..63_000_000, &[("broken", &broken)]),
+            Err(Disagreement {
+                n: 7919 * 7919,
+                votes: vec![
+                    ("sieve", false),
+                    ("trial division", false),
+                    ("miller-rabin", false),
+                    ("baillie-psw", false),
+                    ("lucas n - 1", false),
+                    ("broken", true),
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn first_disagreement_test() {
+        let never = |_: u64| false;
+        let always = |_: u64| true;
+        let first: Disagreement = check_agreement_with(0..100, &[("never", &never)]).unwrap_err();
+        assert_eq!(first.n, 2);
+        let first: Disagreement =
+            check_agreement_with(10..100, &[("always", &always)]).unwrap_err();
+        assert_eq!(first.n, 10);
+        assert_eq!(first.votes.last(), Some(&("always", true)));
+        assert_eq!(
+            first.to_string(),
+            "the primality checks disagree on 10: sieve says composite, trial division says composite, \
+             miller-rabin says composite, baillie-psw says composite, lucas n - 1 says composite, \
+             always says prime"
+        );
+    }
+}
@@ -0,0 +1,199 @@
+#![cfg(feature = "diagnostics")]
+
+#[cfg(test)]
+mod diagnostics_tests {
+    use bens_number_theory::diagnostics::{
+        clear_sink, set_sink, DiagnosticsSink, Event, FactorMethod,
+    };
+    use bens_number_theory::factorization::{factorize, trial_divide};
+    use bens_number_theory::primes::{check_prime, generate_primes_with_progress, primes_in_range};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    struct Collector(RefCell<Vec<Event>>);
+
+    impl DiagnosticsSink for Collector {
+        fn event(&self, ev: Event) {
+            self.0.borrow_mut().push(ev);
+        }
+    }
+
+    /// Runs a function with a fresh collecting sink registered, and returns the events it emitted.
+    fn collect(f: impl FnOnce()) -> Vec<Event> {
+        let collector: Rc<Collector> = Rc::new(Collector(RefCell::new(vec![])));
+        set_sink(collector.clone());
+        f();
+        clear_sink();
+        collector.0.take()
+    }
+
+    fn trial(n: u64, factor: u64, exponent: u32) -> Event {
+        Event::FactorFound {
+            n,
+            factor,
+            exponent,
+            method: FactorMethod::TrialDivision,
+        }
+    }
+
+    #[test]
+    fn factorize_test() {
+        assert_eq!(
+            collect(|| {
+                factorize(360);
+            }),
+            vec![
+                trial(360, 2, 3),
+                trial(360, 3, 2),
+                Event::FactorFound {
+                    n: 360,
+                    factor: 5,
+                    exponent: 1,
+                    method: FactorMethod::Cofactor
+                }
+            ]
+        );
+        assert_eq!(
+            collect(|| {
+                factorize(97);
+                factorize(1024);
+                factorize(1);
+            }),
+            vec![
+                Event::FactorFound {
+                    n: 97,
+                    factor: 97,
+                    exponent: 1,
+                    method: FactorMethod::Cofactor
+                },
+                trial(1024, 2, 10)
+            ]
+        );
+        // a cofactor that is not known to be prime is not reported
+        assert_eq!(
+            collect(|| {
+                trial_divide(16 * 3 * 101, 10);
+            }),
+            vec![trial(4848, 2, 4), trial(4848, 3, 1)]
+        );
+    }
+
+    #[test]
+    fn witness_test() {
+        let events: Vec<Event> = collect(|| {
+            // a strong pseudoprime to the bases 2, 3, 5 and 7
+            assert!(!check_prime(3_215_031_751));
+            // 1000003 * 1000033 has no small factor, and the base 2 catches it
+            assert!(!check_prime(1_000_036_000_099));
+            assert!(check_prime(1_000_000_007));
+            // caught by trial division and by the table before any round
+            assert!(!check_prime(561));
+            assert!(!check_prime(10_403));
+        });
+        assert_eq!(
+            events,
+            vec![
+                Event::WitnessFailed {
+                    n: 3_215_031_751,
+                    base: 11
+                },
+                Event::WitnessFailed {
+                    n: 1_000_036_000_099,
+                    base: 2
+                }
+            ]
+        );
+        // above 2^64 the Baillie–PSW test starts with a round to the base 2
+        let n: u128 = 1_000_000_000_039 * 1_000_000_000_061;
+        assert_eq!(
+            collect(|| assert!(!check_prime(n))),
+            vec![Event::WitnessFailed { n, base: 2 }]
+        );
+    }
+
+    #[test]
+    fn sieve_segment_test() {
+        let segments = |events: Vec<Event>| -> Vec<(u64, u64, u64)> {
+            events
+                .into_iter()
+                .filter_map(|event| match event {
+                    Event::SieveSegmentDone { lo, hi, count } => Some((lo, hi, count)),
+                    _ => None,
+                })
+                .collect()
+        };
+        assert_eq!(
+            segments(collect(|| {
+                generate_primes_with_progress(200_000, None, None).unwrap();
+            })),
+            vec![
+                (2, 65_538, 6543),
+                (65_538, 131_074, 5708),
+                (131_074, 196_610, 5453),
+                (196_610, 200_000, 280)
+            ]
+        );
+        assert_eq!(
+            segments(collect(|| {
+                primes_in_range(0, 600_000);
+            })),
+            vec![
+                (0, 262_144, 23000),
+                (262_144, 524_288, 20390),
+                (524_288, 600_000, 5708)
+            ]
+        );
+    }
+
+    #[test]
+    fn sink_registration_test() {
+        // nothing is collected without a sink, or after it is cleared
+        assert!(clear_sink().is_none());
+        factorize(12);
+        let events: Vec<Event> = collect(|| {});
+        assert_eq!(events, vec![]);
+        let first: Rc<Collector> = Rc::new(Collector(RefCell::new(vec![])));
+        let second: Rc<Collector> = Rc::new(Collector(RefCell::new(vec![])));
+        assert!(set_sink(first.clone()).is_none());
+        factorize(4);
+        assert!(set_sink(second.clone()).is_some());
+        factorize(9);
+        assert!(clear_sink().is_some());
+        factorize(25);
+        assert_eq!(first.0.borrow()[..], [trial(4, 2, 2)]);
+        assert_eq!(second.0.borrow()[..], [trial(9, 3, 2)]);
+    }
+
+    #[test]
+    fn thread_local_test() {
+        let events: Vec<Event> = collect(|| {
+            std::thread::spawn(|| factorize(8)).join().unwrap();
+            factorize(27);
+        });
+        assert_eq!(events, vec![trial(27, 3, 3)]);
+    }
+
+    /// A sink that factors the factors it is told about, calling back into the crate.
+    struct Reentrant(RefCell<Vec<Event>>);
+
+    impl DiagnosticsSink for Reentrant {
+        fn event(&self, ev: Event) {
+            if let Event::FactorFound { factor, .. } = ev {
+                if self.0.borrow().len() < 10 {
+                    self.0.borrow_mut().push(ev);
+                    factorize(factor);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reentrant_sink_test() {
+        let sink: Rc<Reentrant> = Rc::new(Reentrant(RefCell::new(vec![])));
+        set_sink(sink.clone());
+        factorize(6);
+        clear_sink();
+        assert_eq!(sink.0.borrow()[0], trial(6, 2, 1));
+        assert!(sink.0.borrow().len() >= 2);
+    }
+}
@@ -0,0 +1,964 @@
+#[cfg(test)]
+mod digit_utility_tests {
+    use bens_number_theory::digits::{digit_sum, digits};
+
+    #[test]
+    fn digits_test() {
+        assert_eq!(digits(9876543210, 10), vec![9, 8, 7, 6, 5, 4, 3, 2, 1, 0]);
+        assert_eq!(digits(255, 16), vec![15, 15]);
+        assert_eq!(digits(0, 2), vec![0]);
+    }
+
+    #[test]
+    fn digit_sum_test() {
+        assert_eq!(digit_sum(0, 10), 0);
+        assert_eq!(digit_sum(999, 10), 27);
+        assert_eq!(digit_sum(255, 2), 8);
+    }
+
+    #[test]
+    #[should_panic]
+    fn digit_sum_base_one() {
+        digit_sum(10, 1);
+    }
+}
+
+#[cfg(test)]
+mod harshad_tests {
+    use bens_number_theory::digits::{
+        harshad_streak, harshad_streak_base_10, is_harshad, is_harshad_base_10, is_strong_harshad,
+    };
+
+    #[test]
+    fn harshad_small_numbers_test() {
+        for n in 1..=10 {
+            assert!(is_harshad_base_10(n));
+        }
+        assert!(!is_harshad_base_10(11));
+        assert!(!is_harshad_base_10(0));
+    }
+
+    #[test]
+    fn harshad_other_bases_test() {
+        // 12 = 1100 has digit sum 2
+        assert!(is_harshad(12, 2));
+        assert!(!is_harshad(7, 2)); // 111
+        assert!(is_harshad(15, 16)); // a single digit
+    }
+
+    #[test]
+    fn harshad_streak_test() {
+        assert_eq!(harshad_streak_base_10(1), 10);
+        assert_eq!(harshad_streak_base_10(11), 0);
+        assert_eq!(harshad_streak(510, 10), 4);
+    }
+
+    #[test]
+    fn first_harshad_streaks_test() {
+        // A060159: smallest start of exactly k consecutive Harshad numbers
+        let expected: [(u32, u64); 4] = [(2, 20), (3, 110), (4, 510), (5, 131052)];
+        for (length, start) in expected {
+            let mut n: u64 = 1;
+            while harshad_streak_base_10(n) != length || is_harshad_base_10(n - 1) {
+                n += 1;
+            }
+            assert_eq!(n, start);
+        }
+    }
+
+    #[test]
+    fn strong_harshad_test() {
+        assert!(is_strong_harshad(201)); // 201 / 3 = 67
+        assert!(is_strong_harshad(18)); // 18 / 9 = 2
+        assert!(is_strong_harshad(21)); // 21 / 3 = 7
+        assert!(!is_strong_harshad(1)); // 1 / 1 = 1
+        assert!(!is_strong_harshad(24)); // 24 / 6 = 4
+        assert!(!is_strong_harshad(11));
+    }
+}
+
+#[cfg(test)]
+mod happy_tests {
+    use bens_number_theory::digits::{
+        happy_numbers_below, is_happy, is_happy_in_base, is_happy_prime,
+    };
+
+    #[test]
+    fn happy_numbers_test() {
+        for n in [1, 7, 10, 13, 19, 23] {
+            assert!(is_happy(n));
+        }
+        for n in [0, 2, 3, 4] {
+            assert!(!is_happy(n));
+        }
+    }
+
+    #[test]
+    fn happy_primes_test() {
+        let happy_primes: Vec<u64> = (1..500).filter(|n| is_happy_prime(*n)).collect();
+        assert_eq!(
+            happy_primes,
+            vec![
+                7, 13, 19, 23, 31, 79, 97, 103, 109, 139, 167, 193, 239, 263, 293, 313, 331, 367,
+                379, 383, 397, 409, 487
+            ]
+        );
+    }
+
+    #[test]
+    fn happy_numbers_below_million_test() {
+        // every unhappy base 10 number falls into the cycle 4 -> 16 -> 37 -> 58 -> 89 -> 145 -> 42 -> 20
+        let unhappy_cycle: [u64; 8] = [4, 16, 37, 58, 89, 145, 42, 20];
+        let happy: Vec<u64> = happy_numbers_below(1_000_000);
+        let mut count: usize = 0;
+        for n in 1..1_000_000_u64 {
+            let mut m: u64 = n;
+            let mut steps: u32 = 0;
+            while m != 1 && !unhappy_cycle.contains(&m) {
+                m = m
+                    .to_string()
+                    .chars()
+                    .map(|c| (c.to_digit(10).unwrap() as u64).pow(2))
+                    .sum();
+                steps += 1;
+                // a number below 10^6 has a digit square sum of at most 486
+                assert!(steps < 20);
+            }
+            assert_eq!(is_happy(n), m == 1);
+            if m == 1 {
+                count += 1;
+            }
+        }
+        assert_eq!(happy.len(), count);
+        assert_eq!(happy.len(), 143070);
+    }
+
+    #[test]
+    fn happy_bases_test() {
+        // 2 and 4 are happy bases, meaning every positive number is happy in them
+        for n in 1..1000 {
+            assert!(is_happy_in_base(n, 2));
+            assert!(is_happy_in_base(n, 4));
+        }
+        let base_3: Vec<u64> = (1..40).filter(|n| is_happy_in_base(*n, 3)).collect();
+        assert_eq!(base_3, vec![1, 3, 9, 13, 17, 23, 25, 27, 31, 35, 37, 39]);
+    }
+}
+
+#[cfg(test)]
+mod kaprekar_tests {
+    use bens_number_theory::digits::{is_kaprekar, kaprekar_routine};
+
+    #[test]
+    fn kaprekar_numbers_test() {
+        for n in [1, 9, 45, 55, 99, 297] {
+            assert!(is_kaprekar(n));
+        }
+        let kaprekar: Vec<u64> = (0..10000).filter(|n| is_kaprekar(*n)).collect();
+        assert_eq!(
+            kaprekar,
+            vec![
+                1, 9, 45, 55, 99, 297, 703, 999, 2223, 2728, 4879, 4950, 5050, 5292, 7272, 7777,
+                9999
+            ]
+        );
+    }
+
+    #[test]
+    fn kaprekar_large_test() {
+        // 10^10 - 1 squared does not fit in a u64
+        assert!(is_kaprekar(9_999_999_999));
+        // from 10^19 the square has 39 digits, and the next power of 10 does not fit in a u128
+        assert!(is_kaprekar(9_999_999_999_999_999_999));
+        assert!(!is_kaprekar(10_000_000_000_000_000_000));
+        assert!(!is_kaprekar(u64::MAX - 1));
+        assert!(!is_kaprekar(u64::MAX));
+    }
+
+    #[test]
+    fn kaprekar_routine_test() {
+        let trajectory: Vec<u32> = kaprekar_routine(3524);
+        assert_eq!(trajectory.len() - 1, 3);
+        assert_eq!(*trajectory.last().unwrap(), 6174);
+        assert_eq!(kaprekar_routine(0), vec![0]);
+        assert_eq!(kaprekar_routine(2222), vec![2222, 0]);
+    }
+
+    #[test]
+    fn kaprekar_routine_all_four_digits_test() {
+        for n in 1000..=9999_u32 {
+            let trajectory: Vec<u32> = kaprekar_routine(n);
+            if n % 1111 == 0 {
+                assert_eq!(trajectory, vec![n, 0]);
+            } else {
+                assert_eq!(*trajectory.last().unwrap(), 6174);
+                assert!(trajectory.len() - 1 <= 7);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn kaprekar_routine_five_digits() {
+        kaprekar_routine(12345);
+    }
+}
+
+#[cfg(test)]
+mod narcissistic_tests {
+    use bens_number_theory::digits::{
+        is_narcissistic, is_narcissistic_base_10, narcissistic_numbers,
+        narcissistic_numbers_base_10,
+    };
+
+    #[test]
+    fn narcissistic_detection_test() {
+        for n in [153, 370, 371, 407, 1634, 8208, 9474] {
+            assert!(is_narcissistic_base_10(n));
+        }
+        assert!(!is_narcissistic_base_10(100));
+        assert!(!is_narcissistic_base_10(9475));
+        assert!(is_narcissistic(4929273885928088826, 10));
+    }
+
+    #[test]
+    fn narcissistic_base_10_list_test() {
+        let list: Vec<u64> = narcissistic_numbers_base_10();
+        // 50 of the 88 base 10 narcissistic numbers fit in a u64
+        assert_eq!(list.len(), 50);
+        assert_eq!(list[..9], [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(*list.last().unwrap(), 4929273885928088826);
+        for n in &list {
+            assert!(is_narcissistic_base_10(*n));
+        }
+        let brute_force: Vec<u64> = (1..1_000_000)
+            .filter(|n| is_narcissistic_base_10(*n))
+            .collect();
+        assert_eq!(
+            list.iter()
+                .filter(|n| **n < 1_000_000)
+                .copied()
+                .collect::<Vec<u64>>(),
+            brute_force
+        );
+    }
+
+    #[test]
+    fn narcissistic_other_bases_test() {
+        assert_eq!(narcissistic_numbers(2), vec![1]);
+        assert_eq!(narcissistic_numbers(3), vec![1, 2, 5, 8, 17]);
+        assert_eq!(
+            narcissistic_numbers(4),
+            vec![1, 2, 3, 28, 29, 35, 43, 55, 62, 83, 243]
+        );
+    }
+}
+
+#[cfg(test)]
+mod repunit_tests {
+    use bens_number_theory::digits::{is_repunit, repunit, repunit_prime_exponents};
+    use bens_number_theory::primes::is_mersenne_prime;
+
+    #[test]
+    fn repunit_test() {
+        assert_eq!(repunit(1, 10), Some(1));
+        assert_eq!(repunit(19, 10), Some(1111111111111111111));
+        assert_eq!(
+            repunit(39, 10),
+            Some(111111111111111111111111111111111111111)
+        );
+        assert_eq!(repunit(40, 10), None);
+        assert_eq!(repunit(128, 2), Some(u128::MAX));
+        assert_eq!(repunit(129, 2), None);
+    }
+
+    #[test]
+    fn is_repunit_test() {
+        for k in 1..=39 {
+            assert!(is_repunit(repunit(k, 10).unwrap(), 10));
+        }
+        assert!(!is_repunit(0, 10));
+        assert!(!is_repunit(1011, 10));
+        assert!(is_repunit(u128::MAX, 2));
+    }
+
+    #[test]
+    fn repunit_primes_test() {
+        assert_eq!(repunit_prime_exponents(10, 50), vec![2, 19, 23]);
+        assert_eq!(repunit_prime_exponents(3, 20), vec![3, 7, 13]);
+    }
+
+    #[test]
+    fn base_2_repunits_are_mersenne_test() {
+        let exponents: Vec<u32> = repunit_prime_exponents(2, 20);
+        for k in 2..=20 {
+            let mersenne: u128 = repunit(k, 2).unwrap();
+            assert_eq!(mersenne, 2_u128.pow(k) - 1);
+            assert_eq!(exponents.contains(&k), is_mersenne_prime(mersenne));
+        }
+    }
+}
+
+#[cfg(test)]
+mod decimal_period_tests {
+    use bens_number_theory::digits::{decimal_period, period_in_base, repeating_decimal};
+
+    #[test]
+    fn decimal_period_test() {
+        assert_eq!(decimal_period(1), 0);
+        assert_eq!(decimal_period(3), 1);
+        assert_eq!(decimal_period(7), 6);
+        assert_eq!(decimal_period(983), 982);
+        assert_eq!(decimal_period(1000), 0);
+        assert_eq!(decimal_period(2 * 5 * 7), 6);
+    }
+
+    #[test]
+    fn longest_period_below_1000_test() {
+        let longest: u64 = (1..1000).max_by_key(|d| decimal_period(*d)).unwrap();
+        assert_eq!(longest, 983);
+    }
+
+    #[test]
+    fn repeating_decimal_test() {
+        assert_eq!(
+            repeating_decimal(1, 7),
+            ("0.".to_string(), "142857".to_string())
+        );
+        assert_eq!(
+            repeating_decimal(22, 7),
+            ("3.".to_string(), "142857".to_string())
+        );
+        assert_eq!(
+            repeating_decimal(1, 12),
+            ("0.08".to_string(), "3".to_string())
+        );
+        assert_eq!(repeating_decimal(0, 5), ("0".to_string(), "".to_string()));
+        for den in [2, 4, 5, 8, 16, 20, 25, 80, 1024] {
+            assert_eq!(repeating_decimal(1, den).1, "");
+        }
+        let (prefix, repetend): (String, String) = repeating_decimal(1, 983);
+        assert_eq!(prefix, "0.");
+        assert_eq!(repetend.len(), 982);
+        assert!(repetend.starts_with("0010172939979654"));
+    }
+
+    #[test]
+    fn period_matches_long_division_test() {
+        // the period is the distance between the first two repeats of a remainder
+        for base in [2, 3, 10, 12] {
+            for n in 1..300_u64 {
+                let mut seen: Vec<Option<u64>> = vec![None; n as usize];
+                let mut remainder: u64 = 1 % n;
+                let mut step: u64 = 0;
+                while remainder != 0 && seen[remainder as usize].is_none() {
+                    seen[remainder as usize] = Some(step);
+                    remainder = remainder * base % n;
+                    step += 1;
+                }
+                let expected: u64 = if remainder == 0 {
+                    0
+                } else {
+                    step - seen[remainder as usize].unwrap()
+                };
+                assert_eq!(period_in_base(n, base as u32), expected);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn repeating_decimal_zero_denominator() {
+        repeating_decimal(1, 0);
+    }
+}
+
+#[cfg(test)]
+mod smith_tests {
+    use bens_number_theory::digits::{is_smith, smith_numbers_below};
+    use bens_number_theory::primes::generate_primes;
+
+    #[test]
+    fn smith_numbers_test() {
+        assert_eq!(smith_numbers_below(130), vec![4, 22, 27, 58, 85, 94, 121]);
+        assert!(is_smith(666));
+        assert!(is_smith(1776));
+        assert!(is_smith(4937775)); // the original example, from Smith's phone number
+        assert!(!is_smith(0));
+        assert!(!is_smith(1));
+    }
+
+    #[test]
+    fn smith_count_test() {
+        assert_eq!(smith_numbers_below(10000).len(), 376);
+    }
+
+    #[test]
+    fn primes_are_not_smith_test() {
+        for p in generate_primes(10000_u64) {
+            assert!(!is_smith(p));
+        }
+    }
+}
+
+#[cfg(test)]
+mod prime_permutation_tests {
+    use bens_number_theory::digits::{
+        arithmetic_prime_permutation_triples, digits, prime_permutation_families,
+    };
+    use bens_number_theory::primes::generate_primes;
+    use std::collections::HashSet;
+
+    fn sorted_digits(n: u64) -> Vec<u32> {
+        let mut sorted: Vec<u32> = digits(n, 10);
+        sorted.sort();
+        sorted
+    }
+
+    #[test]
+    fn four_digit_triples_test() {
+        assert_eq!(
+            arithmetic_prime_permutation_triples(4),
+            vec![[1487, 4817, 8147], [2969, 6299, 9629]]
+        );
+    }
+
+    #[test]
+    fn families_test() {
+        for d in 1..=5_u32 {
+            let families: Vec<Vec<u64>> = prime_permutation_families(d);
+            let mut members: Vec<u64> = families.concat();
+            members.sort();
+            let expected: Vec<u64> = generate_primes(10_u64.pow(d))
+                .into_iter()
+                .filter(|p| *p >= 10_u64.pow(d - 1) && *p < 10_u64.pow(d))
+                .collect();
+            // every prime with d digits is in exactly one family
+            assert_eq!(members, expected);
+            for family in &families {
+                assert!(family.windows(2).all(|pair| pair[0] < pair[1]));
+                assert!(family
+                    .iter()
+                    .all(|p| sorted_digits(*p) == sorted_digits(family[0])));
+            }
+            // families are kept apart only when they are not permutations
+            assert!(families.windows(2).all(|pair| pair[0][0] < pair[1][0]));
+            let signatures: HashSet<Vec<u32>> = families
+                .iter()
+                .map(|family| sorted_digits(family[0]))
+                .collect();
+            assert_eq!(signatures.len(), families.len());
+        }
+    }
+
+    #[test]
+    fn triples_test() {
+        let primes: HashSet<u64> = generate_primes(1_000_000_u64).into_iter().collect();
+        for d in 1..=6_u32 {
+            let triples: Vec<[u64; 3]> = arithmetic_prime_permutation_triples(d);
+            assert!(triples.windows(2).all(|pair| pair[0] < pair[1]));
+            for [a, b, c] in triples {
+                assert!(a < b && b - a == c - b);
+                assert!(primes.contains(&a) && primes.contains(&b) && primes.contains(&c));
+                assert_eq!(sorted_digits(a), sorted_digits(b));
+                assert_eq!(sorted_digits(b), sorted_digits(c));
+            }
+        }
+        assert!(arithmetic_prime_permutation_triples(1).is_empty());
+        assert!(arithmetic_prime_permutation_triples(3).is_empty());
+        assert_eq!(arithmetic_prime_permutation_triples(5).len(), 53);
+    }
+
+    #[test]
+    fn small_families_test() {
+        assert_eq!(
+            prime_permutation_families(1),
+            vec![vec![2], vec![3], vec![5], vec![7]]
+        );
+        assert!(prime_permutation_families(3).contains(&vec![113, 131, 311]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_digits_test() {
+        prime_permutation_families(0);
+    }
+}
+
+#[cfg(test)]
+mod digit_replacement_tests {
+    use bens_number_theory::digits::{
+        digit_replacement_family, digits, smallest_prime_with_family_size,
+    };
+    use bens_number_theory::factorization::least_prime_factor;
+
+    #[test]
+    fn family_test() {
+        assert_eq!(
+            digit_replacement_family(13, &[0]),
+            vec![13, 23, 43, 53, 73, 83]
+        );
+        assert_eq!(
+            digit_replacement_family(121313, &[0, 2, 4]),
+            vec![121313, 222323, 323333, 424343, 525353, 626363, 828383, 929393]
+        );
+        // the last digit of 10 can be 1, 3, 7 or 9
+        assert_eq!(digit_replacement_family(10, &[1]), vec![11, 13, 17, 19]);
+        // replacing the only digit gives the one-digit primes
+        assert_eq!(digit_replacement_family(7, &[0]), vec![2, 3, 5, 7]);
+        // repeated positions are the same as one
+        assert_eq!(
+            digit_replacement_family(13, &[0, 0]),
+            digit_replacement_family(13, &[0])
+        );
+    }
+
+    #[test]
+    fn members_test() {
+        for p in [13_u64, 56003, 121313, 4_294_967_291] {
+            let length: usize = digits(p, 10).len();
+            for position in 0..length as u8 {
+                let family: Vec<u64> = digit_replacement_family(p, &[position]);
+                assert!(family.windows(2).all(|pair| pair[0] < pair[1]));
+                for member in family {
+                    assert_eq!(least_prime_factor(member), Some(member));
+                    let member_digits: Vec<u32> = digits(member, 10);
+                    assert_eq!(member_digits.len(), length);
+                    // only the replaced digit differs
+                    let p_digits: Vec<u32> = digits(p, 10);
+                    for i in 0..length {
+                        if i != position as usize {
+                            assert_eq!(member_digits[i], p_digits[i]);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn smallest_test() {
+        assert_eq!(smallest_prime_with_family_size(1), 11); // 11, 22, ..., 99
+        assert_eq!(smallest_prime_with_family_size(6), 13);
+        assert_eq!(smallest_prime_with_family_size(7), 56003);
+        assert_eq!(smallest_prime_with_family_size(8), 121313);
+    }
+
+    #[test]
+    #[should_panic]
+    fn position_out_of_range_test() {
+        digit_replacement_family(13, &[2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_positions_test() {
+        digit_replacement_family(13, &[]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_large_target_test() {
+        smallest_prime_with_family_size(11);
+    }
+}
+
+#[cfg(test)]
+mod concat_prime_tests {
+    use bens_number_theory::digits::{prime_pair_concat_compatible, smallest_concat_prime_clique};
+    use bens_number_theory::factorization::least_prime_factor;
+    use bens_number_theory::primes::primes_in_range;
+
+    fn is_prime_by_string(a: u64, b: u64) -> bool {
+        let n: u64 = format!("{a}{b}").parse().unwrap();
+        least_prime_factor(n) == Some(n)
+    }
+
+    #[test]
+    fn compatible_test() {
+        assert!(prime_pair_concat_compatible(3, 7));
+        assert!(prime_pair_concat_compatible(109, 673));
+        assert!(!prime_pair_concat_compatible(2, 3)); // 32 is even
+        assert!(!prime_pair_concat_compatible(3, 5)); // 35 = 5 * 7
+                                                      // zeros in the second number count as digits
+        assert_eq!(
+            prime_pair_concat_compatible(3, 109),
+            is_prime_by_string(3, 109) && is_prime_by_string(109, 3)
+        );
+    }
+
+    #[test]
+    fn matches_string_concatenation_test() {
+        let primes: Vec<u64> = primes_in_range(0, 300);
+        for a in &primes {
+            for b in &primes {
+                let expected: bool = is_prime_by_string(*a, *b) && is_prime_by_string(*b, *a);
+                assert_eq!(prime_pair_concat_compatible(*a, *b), expected);
+                // symmetric by construction
+                assert_eq!(
+                    prime_pair_concat_compatible(*a, *b),
+                    prime_pair_concat_compatible(*b, *a)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn large_concatenation_test() {
+        // the concatenations are past u64::MAX
+        let p: u64 = 18_446_744_073_709_551_557;
+        assert!(!prime_pair_concat_compatible(p, 2));
+        assert!(!prime_pair_concat_compatible(p, p));
+    }
+
+    #[test]
+    fn four_clique_test() {
+        let clique: Vec<u64> = smallest_concat_prime_clique(4, 1000).unwrap();
+        assert_eq!(clique, vec![3, 7, 109, 673]);
+        assert_eq!(clique.iter().sum::<u64>(), 792);
+        for a in &clique {
+            for b in &clique {
+                assert!(a == b || prime_pair_concat_compatible(*a, *b));
+            }
+        }
+    }
+
+    #[test]
+    fn five_clique_test() {
+        assert_eq!(smallest_concat_prime_clique(5, 1000), None);
+        let clique: Vec<u64> = smallest_concat_prime_clique(5, 10_000).unwrap();
+        assert_eq!(clique, vec![13, 5197, 5701, 6733, 8389]);
+        assert_eq!(clique.iter().sum::<u64>(), 26033);
+    }
+
+    #[test]
+    fn small_sizes_test() {
+        assert_eq!(smallest_concat_prime_clique(0, 100), Some(vec![]));
+        assert_eq!(smallest_concat_prime_clique(1, 100), Some(vec![2]));
+        assert_eq!(smallest_concat_prime_clique(1, 2), None);
+        assert_eq!(smallest_concat_prime_clique(3, 100), Some(vec![3, 37, 67]));
+    }
+}
+
+#[cfg(test)]
+mod truncatable_prime_tests {
+    use bens_number_theory::digits::{
+        left_truncatable_primes_in_base, right_truncatable_primes_in_base,
+    };
+    use bens_number_theory::primes::check_prime;
+    use std::collections::HashSet;
+
+    /// Checks that every member stays prime down to one digit under a truncation, and that there are no repeats.
+    fn verify(primes: &[u128], base: u32, truncate: impl Fn(u128, u128) -> u128) {
+        let base: u128 = base as u128;
+        let distinct: HashSet<u128> = primes.iter().copied().collect();
+        assert_eq!(distinct.len(), primes.len());
+        assert!(primes.windows(2).all(|pair| pair[0] < pair[1]));
+        for p in primes {
+            let mut m: u128 = *p;
+            let mut power: u128 = 1;
+            while power <= m / base {
+                power *= base;
+            }
+            loop {
+                assert!(check_prime(m), "{p} in base {base}");
+                if m < base {
+                    break;
+                }
+                m = truncate(m, power);
+                power /= base;
+            }
+        }
+    }
+
+    #[test]
+    fn right_base_10_test() {
+        let primes: Vec<u128> = right_truncatable_primes_in_base(10);
+        assert_eq!(primes.len(), 83);
+        assert_eq!(primes[..12], [2, 3, 5, 7, 23, 29, 31, 37, 53, 59, 71, 73]);
+        assert_eq!(*primes.last().unwrap(), 73_939_133);
+        verify(&primes, 10, |m, _| m / 10);
+    }
+
+    #[test]
+    fn left_base_10_test() {
+        let primes: Vec<u128> = left_truncatable_primes_in_base(10);
+        assert_eq!(primes.len(), 4260);
+        assert_eq!(primes[..12], [2, 3, 5, 7, 13, 17, 23, 37, 43, 47, 53, 67]);
+        assert_eq!(*primes.last().unwrap(), 357_686_312_646_216_567_629_137);
+        verify(&primes, 10, |m, power| m % power);
+        // no zero digits
+        for p in &primes {
+            let mut m: u128 = *p;
+            while m > 0 {
+                assert_ne!(m % 10, 0, "{p}");
+                m /= 10;
+            }
+        }
+    }
+
+    #[test]
+    fn counts_by_base_test() {
+        let right: Vec<usize> = (2..=13)
+            .map(|base| right_truncatable_primes_in_base(base).len())
+            .collect();
+        assert_eq!(right, vec![0, 4, 7, 14, 36, 19, 68, 68, 83, 89, 179, 176]);
+        let left: Vec<usize> = (2..=11)
+            .map(|base| left_truncatable_primes_in_base(base).len())
+            .collect();
+        assert_eq!(left, vec![0, 3, 16, 15, 454, 22, 446, 108, 4260, 75]);
+    }
+
+    #[test]
+    fn other_bases_test() {
+        let mut base: u32 = 2;
+        while base <= 16 {
+            let b: u128 = base as u128;
+            verify(&right_truncatable_primes_in_base(base), base, |m, _| m / b);
+            if base <= 11 {
+                verify(&left_truncatable_primes_in_base(base), base, |m, power| {
+                    m % power
+                });
+            }
+            base += 1;
+        }
+        assert_eq!(
+            *right_truncatable_primes_in_base(16).last().unwrap(),
+            16_778_492_037_124_607
+        );
+    }
+
+    #[test]
+    fn base_2_test() {
+        // neither binary digit is prime, so there is nothing to extend
+        assert_eq!(right_truncatable_primes_in_base(2), vec![]);
+        assert_eq!(left_truncatable_primes_in_base(2), vec![]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn base_1_test() {
+        right_truncatable_primes_in_base(1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn base_37_test() {
+        left_truncatable_primes_in_base(37);
+    }
+}
+
+#[cfg(test)]
+mod minimal_prime_tests {
+    use bens_number_theory::digits::{is_digit_subsequence, minimal_primes};
+    use bens_number_theory::primes::primes_in_range;
+
+    #[test]
+    fn base_10_test() {
+        assert_eq!(
+            minimal_primes(10, 8),
+            vec![
+                2, 3, 5, 7, 11, 19, 41, 61, 89, 409, 449, 499, 881, 991, 6469, 6949, 9001, 9049,
+                9649, 9949, 60649, 666649, 946669, 60000049, 66000049, 66600049
+            ]
+        );
+        assert_eq!(minimal_primes(10, 7), minimal_primes(10, 8)[..23]);
+        assert_eq!(minimal_primes(10, 1), vec![2, 3, 5, 7]);
+        assert_eq!(minimal_primes(10, 0), vec![]);
+    }
+
+    #[test]
+    fn small_bases_test() {
+        // 10 and 11
+        assert_eq!(minimal_primes(2, 64), vec![2, 3]);
+        // 2, 10 and 111
+        assert_eq!(minimal_primes(3, 40), vec![2, 3, 13]);
+        // 2, 3 and 11
+        assert_eq!(minimal_primes(4, 32), vec![2, 3, 5]);
+    }
+
+    #[test]
+    fn subsequence_test() {
+        assert!(is_digit_subsequence(66600049, 66600049, 10));
+        assert!(is_digit_subsequence(449, 40409, 10));
+        assert!(is_digit_subsequence(7, 1070, 10));
+        assert!(!is_digit_subsequence(449, 4094, 10));
+        assert!(!is_digit_subsequence(11, 10, 10));
+        assert!(!is_digit_subsequence(123, 12, 10));
+        // 0b101 in 0b1001
+        assert!(is_digit_subsequence(5, 9, 2));
+        // 0x1f in 0x1aaf
+        assert!(is_digit_subsequence(0x1f, 0x1aaf, 16));
+        assert!(!is_digit_subsequence(0xf1, 0x1aaf, 16));
+    }
+
+    #[test]
+    fn minimality_test() {
+        let set: Vec<u64> = minimal_primes(10, 8);
+        // no member contains another
+        for p in &set {
+            for q in &set {
+                assert!(p == q || !is_digit_subsequence(*p, *q, 10), "{p} {q}");
+            }
+        }
+        // every other prime with up to 4 digits contains a member
+        for p in primes_in_range(2, 10_000) {
+            assert!(set.iter().any(|m| is_digit_subsequence(*m, p, 10)), "{p}");
+        }
+    }
+
+    #[test]
+    fn bases_agree_with_brute_force_test() {
+        // the minimal primes below base^4, straight from the definition
+        let mut base: u32 = 2;
+        while base <= 12 {
+            let limit: u64 = (base as u64).pow(4);
+            let mut expected: Vec<u64> = vec![];
+            for p in primes_in_range(2, limit) {
+                if !expected.iter().any(|m| is_digit_subsequence(*m, p, base)) {
+                    expected.push(p);
+                }
+            }
+            assert_eq!(minimal_primes(base, 4), expected, "{base}");
+            base += 1;
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn base_1_test() {
+        minimal_primes(1, 5);
+    }
+}
+
+#[cfg(test)]
+mod digit_power_sum_tests {
+    use bens_number_theory::digits::{
+        digit_power_sum_trajectory, digits, fixed_points_and_cycles, is_happy_in_base, Trajectory,
+    };
+
+    fn power_sum(n: u64, power: u32, base: u32) -> u64 {
+        if n == 0 {
+            return 0;
+        }
+        digits(n, base).iter().map(|d| (*d as u64).pow(power)).sum()
+    }
+
+    fn fixed_points(power: u32, base: u32) -> Vec<u64> {
+        fixed_points_and_cycles(power, base)
+            .into_iter()
+            .filter(|cycle| cycle.len() == 1)
+            .map(|cycle| cycle[0])
+            .collect()
+    }
+
+    #[test]
+    fn fifth_power_test() {
+        assert_eq!(
+            fixed_points(5, 10),
+            vec![0, 1, 4150, 4151, 54748, 92727, 93084, 194979]
+        );
+        for n in [4150, 4151, 54748, 92727, 93084, 194979] {
+            assert_eq!(power_sum(n, 5, 10), n);
+        }
+    }
+
+    #[test]
+    fn cube_cycles_test() {
+        assert_eq!(
+            fixed_points_and_cycles(3, 10),
+            vec![
+                vec![0],
+                vec![1],
+                vec![55, 250, 133],
+                vec![136, 244],
+                vec![153],
+                vec![160, 217, 352],
+                vec![370],
+                vec![371],
+                vec![407],
+                vec![919, 1459],
+            ]
+        );
+    }
+
+    #[test]
+    fn cycles_are_cycles_test() {
+        for (power, base) in [(2, 3), (2, 10), (3, 7), (4, 10), (5, 10), (3, 16), (6, 4)] {
+            for cycle in fixed_points_and_cycles(power, base) {
+                for (i, value) in cycle.iter().enumerate() {
+                    assert!(cycle[0] <= *value);
+                    assert_eq!(
+                        power_sum(*value, power, base),
+                        cycle[(i + 1) % cycle.len()],
+                        "{power} {base}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn trajectories_enter_listed_cycles_test() {
+        for (power, base) in [(2, 10), (3, 10), (4, 10), (5, 10), (3, 5)] {
+            let cycles: Vec<Vec<u64>> = fixed_points_and_cycles(power, base);
+            let mut n: u64 = 0;
+            while n < 200_000 {
+                let trajectory: Trajectory = digit_power_sum_trajectory(n, power, base);
+                assert_eq!(trajectory.path[0], n);
+                let mut i: usize = 0;
+                while i + 1 < trajectory.path.len() {
+                    assert_eq!(
+                        power_sum(trajectory.path[i], power, base),
+                        trajectory.path[i + 1]
+                    );
+                    i += 1;
+                }
+                let last: u64 = *trajectory.path.last().unwrap();
+                assert_eq!(
+                    power_sum(last, power, base),
+                    trajectory.path[trajectory.cycle_start]
+                );
+                let mut cycle: Vec<u64> = trajectory.cycle().to_vec();
+                let smallest: usize = (0..cycle.len()).min_by_key(|i| cycle[*i]).unwrap();
+                cycle.rotate_left(smallest);
+                assert!(cycles.contains(&cycle), "{n} {power} {base}");
+                n += 7;
+            }
+        }
+    }
+
+    #[test]
+    fn happy_agreement_test() {
+        for base in [3, 5, 10] {
+            for n in 1..2000 {
+                let trajectory: Trajectory = digit_power_sum_trajectory(n, 2, base);
+                assert_eq!(trajectory.cycle() == [1], is_happy_in_base(n, base));
+            }
+        }
+    }
+
+    #[test]
+    fn large_start_test() {
+        let trajectory: Trajectory = digit_power_sum_trajectory(u64::MAX, 4, 10);
+        assert_eq!(trajectory.path[1], power_sum(u64::MAX, 4, 10));
+        // the cycles of fourth powers are 0, 1, 1634, 8208, 9474 and two longer ones
+        let smallest: u64 = *trajectory.cycle().iter().min().unwrap();
+        assert!(fixed_points_and_cycles(4, 10)
+            .iter()
+            .any(|cycle| cycle[0] == smallest && cycle.len() == trajectory.cycle().len()));
+        assert_eq!(
+            digit_power_sum_trajectory(0, 7, 10),
+            Trajectory {
+                path: vec![0],
+                cycle_start: 0
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn overflow_test() {
+        digit_power_sum_trajectory(9, 21, 10);
+    }
+}
@@ -0,0 +1,236 @@
+#[cfg(test)]
+mod dirichlet_tests {
+    use bens_number_theory::dirichlet::{
+        dirichlet_convolve, identity, mobius_array, one, totient_array, unit,
+    };
+
+    fn random_array(len: usize, seed: u64) -> Vec<i64> {
+        let mut x: u64 = seed;
+        let mut values: Vec<i64> = vec![0];
+        while values.len() < len {
+            x = x
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            values.push((x >> 54) as i64 - 512);
+        }
+        values
+    }
+
+    #[test]
+    fn mobius_identity_test() {
+        // mu * 1 = epsilon
+        let limit: usize = 100_000;
+        assert_eq!(
+            dirichlet_convolve(&mobius_array(limit), &one(limit)),
+            unit(limit)
+        );
+    }
+
+    #[test]
+    fn totient_identity_test() {
+        // phi * 1 = id
+        let limit: usize = 100_000;
+        assert_eq!(
+            dirichlet_convolve(&totient_array(limit), &one(limit)),
+            identity(limit)
+        );
+    }
+
+    #[test]
+    fn divisor_functions_test() {
+        // 1 * 1 = tau and id * 1 = sigma
+        let tau: Vec<i64> = dirichlet_convolve(&one(1000), &one(1000));
+        let sigma: Vec<i64> = dirichlet_convolve(&identity(1000), &one(1000));
+        for n in 1..=1000_i64 {
+            let divisors: Vec<i64> = (1..=n).filter(|d| n % d == 0).collect();
+            assert_eq!(tau[n as usize], divisors.len() as i64);
+            assert_eq!(sigma[n as usize], divisors.iter().sum::<i64>());
+        }
+    }
+
+    #[test]
+    fn commutative_test() {
+        for seed in 0..10_u64 {
+            let f: Vec<i64> = random_array(2000, seed);
+            let g: Vec<i64> = random_array(2000, seed + 100);
+            assert_eq!(dirichlet_convolve(&f, &g), dirichlet_convolve(&g, &f));
+        }
+    }
+
+    #[test]
+    fn associative_test() {
+        for seed in 0..10_u64 {
+            let f: Vec<i64> = random_array(2000, seed);
+            let g: Vec<i64> = random_array(2000, seed + 100);
+            let h: Vec<i64> = random_array(2000, seed + 200);
+            assert_eq!(
+                dirichlet_convolve(&dirichlet_convolve(&f, &g), &h),
+                dirichlet_convolve(&f, &dirichlet_convolve(&g, &h))
+            );
+        }
+    }
+
+    #[test]
+    fn unit_is_identity_element_test() {
+        let f: Vec<i64> = random_array(500, 7);
+        assert_eq!(dirichlet_convolve(&f, &unit(499)), f);
+        assert_eq!(dirichlet_convolve(&unit(499), &f), f);
+    }
+
+    #[test]
+    fn length_test() {
+        assert_eq!(dirichlet_convolve(&one(10), &one(4)).len(), 5);
+        assert_eq!(dirichlet_convolve(&[], &one(4)), vec![]);
+        assert_eq!(dirichlet_convolve(&[0], &[0]), vec![0]);
+        assert_eq!(unit(0), vec![0]);
+        assert_eq!(one(0), vec![0]);
+        assert_eq!(identity(0), vec![0]);
+        assert_eq!(mobius_array(0), vec![0]);
+        assert_eq!(totient_array(0), vec![0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn nonzero_index_zero_test() {
+        dirichlet_convolve(&[1, 1, 1], &one(2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn overflow_test() {
+        let big: Vec<i64> = vec![0, i64::MAX, i64::MAX];
+        dirichlet_convolve(&big, &big);
+    }
+}
+
+#[cfg(test)]
+mod multiplicative_sieve_tests {
+    use bens_number_theory::dirichlet::{
+        liouville_array, mobius_array, multiplicative_sieve, totient_array,
+    };
+    use bens_number_theory::divisor_functions::{
+        divisor_count, divisor_sum, sigma_sieve, tau_sieve,
+    };
+    use bens_number_theory::factorization::factorize;
+    use bens_number_theory::modular::euler_totient;
+    use std::cell::RefCell;
+
+    // the divisor counts, divisor sums, totients and Möbius values up to a limit, from loops over multiples
+    fn multiples_loop_reference(limit: usize) -> [Vec<i64>; 4] {
+        let mut tau: Vec<i64> = vec![0; limit + 1];
+        let mut sigma: Vec<i64> = vec![0; limit + 1];
+        let mut phi: Vec<i64> = (0..=limit as i64).collect();
+        let mut mu: Vec<i64> = vec![1; limit + 1];
+        mu[0] = 0;
+        let mut d: usize = 1;
+        while d <= limit {
+            let mut multiple: usize = d;
+            while multiple <= limit {
+                tau[multiple] += 1;
+                sigma[multiple] += d as i64;
+                multiple += d;
+            }
+            // d is prime exactly when no smaller prime has touched its totient
+            if d > 1 && phi[d] == d as i64 {
+                let mut multiple: usize = d;
+                while multiple <= limit {
+                    phi[multiple] -= phi[multiple] / d as i64;
+                    mu[multiple] = if multiple.is_multiple_of(d * d) {
+                        0
+                    } else {
+                        -mu[multiple]
+                    };
+                    multiple += d;
+                }
+            }
+            d += 1;
+        }
+        [tau, sigma, phi, mu]
+    }
+
+    #[test]
+    fn dedicated_sieves_test() {
+        let limit: usize = 20_000;
+        let [tau, sigma, phi, mu]: [Vec<i64>; 4] = multiples_loop_reference(limit);
+        assert_eq!(multiplicative_sieve(limit, |_, k| k as i64 + 1), tau);
+        assert_eq!(
+            multiplicative_sieve(limit, |p, k| (0..=k).map(|i| p.pow(i) as i64).sum()),
+            sigma
+        );
+        assert_eq!(
+            multiplicative_sieve(limit, |p, k| ((p - 1) * p.pow(k - 1)) as i64),
+            phi
+        );
+        assert_eq!(
+            multiplicative_sieve(limit, |_, k| if k == 1 { -1 } else { 0 }),
+            mu
+        );
+        // and the crate's sieves agree with the same reference
+        let tau_values: Vec<i64> = tau_sieve(limit).into_iter().map(i64::from).collect();
+        let sigma_values: Vec<i64> = sigma_sieve(limit).into_iter().map(|s| s as i64).collect();
+        assert_eq!(tau_values, tau);
+        assert_eq!(sigma_values, sigma);
+        assert_eq!(totient_array(limit), phi);
+        assert_eq!(mobius_array(limit), mu);
+    }
+
+    #[test]
+    fn brute_force_test() {
+        let limit: usize = 5000;
+        let tau: Vec<u32> = tau_sieve(limit);
+        let sigma: Vec<u64> = sigma_sieve(limit);
+        let phi: Vec<i64> = totient_array(limit);
+        let mu: Vec<i64> = mobius_array(limit);
+        let lambda: Vec<i64> = liouville_array(limit);
+        let custom: Vec<i64> = multiplicative_sieve(limit, |_, k| k as i64 + 2);
+        for n in 1..=limit as u64 {
+            let factors: Vec<(u64, u32)> = factorize(n);
+            let big_omega: u32 = factors.iter().map(|(_, e)| e).sum();
+            let squarefree: bool = factors.iter().all(|(_, e)| *e == 1);
+            let i: usize = n as usize;
+            assert_eq!(tau[i] as u64, divisor_count(n));
+            assert_eq!(sigma[i], divisor_sum(n));
+            assert_eq!(phi[i], euler_totient(n) as i64);
+            let expected_mu: i64 = match (squarefree, factors.len() % 2) {
+                (false, _) => 0,
+                (true, 0) => 1,
+                (true, _) => -1,
+            };
+            assert_eq!(mu[i], expected_mu);
+            assert_eq!(lambda[i], if big_omega.is_multiple_of(2) { 1 } else { -1 });
+            let expected: i64 = factors.iter().map(|(_, e)| *e as i64 + 2).product();
+            assert_eq!(custom[i], expected);
+        }
+    }
+
+    #[test]
+    fn called_on_prime_powers_test() {
+        let limit: usize = 10_000;
+        let calls: RefCell<Vec<(u64, u32)>> = RefCell::new(vec![]);
+        multiplicative_sieve(limit, |p, k| {
+            calls.borrow_mut().push((p, k));
+            1
+        });
+        let mut calls: Vec<(u64, u32)> = calls.into_inner();
+        calls.sort();
+        let mut expected: Vec<(u64, u32)> = vec![];
+        for n in 2..=limit as u64 {
+            let factors: Vec<(u64, u32)> = factorize(n);
+            if factors.len() == 1 {
+                expected.push(factors[0]);
+            }
+        }
+        expected.sort();
+        assert_eq!(calls, expected);
+    }
+
+    #[test]
+    fn small_limits_test() {
+        assert_eq!(multiplicative_sieve(0, |_, _| 5), vec![0]);
+        assert_eq!(multiplicative_sieve(1, |_, _| 5), vec![0, 1]);
+        assert_eq!(multiplicative_sieve(4, |_, _| 5), vec![0, 1, 5, 5, 5]);
+        assert_eq!(tau_sieve(0), vec![0]);
+        assert_eq!(sigma_sieve(1), vec![0, 1]);
+        assert_eq!(liouville_array(0), vec![0]);
+    }
+}
@@ -0,0 +1,474 @@
+#[cfg(test)]
+mod divisor_count_and_sum_tests {
+    use bens_number_theory::divisor_functions::{divisor_count, divisor_sum};
+
+    #[test]
+    fn divisor_count_test() {
+        assert_eq!(divisor_count(0), 0);
+        assert_eq!(divisor_count(1), 1);
+        assert_eq!(divisor_count(720720), 240);
+        for n in 1..2000 {
+            assert_eq!(
+                divisor_count(n),
+                (1..=n).filter(|d| n.is_multiple_of(*d)).count() as u64
+            );
+        }
+    }
+
+    #[test]
+    fn divisor_sum_test() {
+        assert_eq!(divisor_sum(0), 0);
+        assert_eq!(divisor_sum(1), 1);
+        assert_eq!(divisor_sum(28), 56);
+        for n in 1..2000 {
+            assert_eq!(
+                divisor_sum(n),
+                (1..=n).filter(|d| n.is_multiple_of(*d)).sum::<u64>()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod highly_composite_tests {
+    use bens_number_theory::divisor_functions::{
+        divisor_count, divisor_sum, highly_abundant_numbers_below, highly_composite_numbers_below,
+        is_highly_composite,
+    };
+
+    #[test]
+    fn highly_composite_start_test() {
+        assert_eq!(highly_composite_numbers_below(0), vec![]);
+        assert_eq!(
+            highly_composite_numbers_below(721),
+            vec![1, 2, 4, 6, 12, 24, 36, 48, 60, 120, 180, 240, 360, 720]
+        );
+    }
+
+    #[test]
+    fn highly_composite_below_million_test() {
+        // brute force records of a divisor count sieve
+        let limit: usize = 1_000_000;
+        let mut counts: Vec<u32> = vec![0; limit];
+        for d in 1..limit {
+            let mut multiple: usize = d;
+            while multiple < limit {
+                counts[multiple] += 1;
+                multiple += d;
+            }
+        }
+        let mut expected: Vec<u64> = vec![];
+        let mut best: u32 = 0;
+        for (n, count) in counts.iter().enumerate() {
+            if *count > best {
+                best = *count;
+                expected.push(n as u64);
+            }
+        }
+        let found: Vec<u64> = highly_composite_numbers_below(limit as u64);
+        assert_eq!(found, expected);
+        assert_eq!(found.len(), 38);
+        assert_eq!(*found.last().unwrap(), 720720);
+    }
+
+    #[test]
+    fn highly_composite_records_test() {
+        // every value is a strict record compared to the previous one
+        let found: Vec<u64> = highly_composite_numbers_below(u64::MAX);
+        for pair in found.windows(2) {
+            assert!(divisor_count(pair[1]) > divisor_count(pair[0]));
+        }
+        assert!(is_highly_composite(*found.last().unwrap()));
+        assert!(!is_highly_composite(u64::MAX));
+        assert!(!is_highly_composite(0));
+    }
+
+    #[test]
+    fn highly_abundant_test() {
+        let found: Vec<u64> = highly_abundant_numbers_below(10000);
+        let mut expected: Vec<u64> = vec![];
+        let mut best: u64 = 0;
+        for n in 1..10000 {
+            if divisor_sum(n) > best {
+                best = divisor_sum(n);
+                expected.push(n);
+            }
+        }
+        assert_eq!(found, expected);
+        assert_eq!(found[..12], [1, 2, 3, 4, 6, 8, 10, 12, 16, 18, 20, 24]);
+    }
+}
+
+#[cfg(test)]
+mod practical_tests {
+    use bens_number_theory::divisor_functions::{is_practical, practical_numbers_below};
+
+    /// Checks practicality directly by finding every sum of distinct divisors.
+    fn is_practical_brute_force(n: u64) -> bool {
+        let mut reachable: Vec<bool> = vec![false; n as usize + 1];
+        reachable[0] = true;
+        for d in (1..=n).filter(|d| n.is_multiple_of(*d)) {
+            for s in (d..=n).rev() {
+                if reachable[(s - d) as usize] {
+                    reachable[s as usize] = true;
+                }
+            }
+        }
+        reachable.iter().all(|r| *r)
+    }
+
+    #[test]
+    fn practical_numbers_test() {
+        for n in [1, 2, 4, 6, 8, 12, 16, 18, 20] {
+            assert!(is_practical(n));
+        }
+        assert!(!is_practical(0));
+        assert!(!is_practical(10));
+        assert!(!is_practical(3));
+    }
+
+    #[test]
+    fn practical_brute_force_test() {
+        for n in 1..500 {
+            assert_eq!(is_practical(n), is_practical_brute_force(n));
+        }
+    }
+
+    #[test]
+    fn practical_count_test() {
+        assert_eq!(practical_numbers_below(10000).len(), 1455);
+        // every power of 2 and every factorial is practical
+        assert!(is_practical(1 << 63));
+        assert!(is_practical(2432902008176640000)); // 20!
+    }
+}
+
+#[cfg(test)]
+mod smallest_with_divisor_count_tests {
+    use bens_number_theory::divisor_functions::{
+        divisor_count, smallest_with_divisor_count, smallest_with_n_divisors,
+    };
+
+    /// Counts the divisors of a number whose prime factors are all below 320.
+    fn smooth_divisor_count(mut n: u128) -> u64 {
+        let mut count: u64 = 1;
+        for p in 2..320_u128 {
+            let mut e: u64 = 0;
+            while n.is_multiple_of(p) {
+                n /= p;
+                e += 1;
+            }
+            count *= e + 1;
+        }
+        assert_eq!(n, 1);
+        count
+    }
+
+    #[test]
+    fn smallest_with_divisor_count_test() {
+        let first: Vec<u64> = (1..=16).map(smallest_with_divisor_count).collect();
+        assert_eq!(
+            first,
+            vec![1, 2, 4, 6, 16, 12, 64, 24, 36, 48, 1024, 60, 4096, 192, 144, 120]
+        );
+        assert_eq!(smallest_with_divisor_count(48), 2520);
+        assert_eq!(smallest_with_divisor_count(1024), 294053760);
+        assert_eq!(smallest_with_divisor_count(1000), 810810000);
+        assert_eq!(smallest_with_divisor_count(4096), 128501493120);
+    }
+
+    #[test]
+    fn smallest_with_divisor_count_matches_search_test() {
+        let counts: Vec<u64> = (0..200000).map(divisor_count).collect();
+        for k in 1..=100_u64 {
+            // a prime k needs 2^(k - 1), which does not fit for the primes above 64
+            if k > 64 && (2..k).all(|d| !k.is_multiple_of(d)) {
+                continue;
+            }
+            let n: u64 = smallest_with_divisor_count(k);
+            assert_eq!(divisor_count(n), k);
+            if n < 200000 {
+                assert_eq!(counts.iter().position(|c| *c == k), Some(n as usize));
+            }
+        }
+    }
+
+    #[test]
+    fn smallest_with_n_divisors_test() {
+        assert_eq!(smallest_with_n_divisors(0), None);
+        assert_eq!(smallest_with_n_divisors(1), Some(1));
+        assert_eq!(smallest_with_n_divisors(4), Some(6));
+        assert_eq!(smallest_with_n_divisors(6), Some(12));
+        assert_eq!(smallest_with_n_divisors(16), Some(120));
+        assert_eq!(smallest_with_n_divisors(64), Some(7560));
+        assert_eq!(smallest_with_n_divisors(100), Some(45360));
+        // 2^66 no longer overflows
+        assert_eq!(smallest_with_n_divisors(67), Some(1 << 66));
+        assert_eq!(smallest_with_n_divisors(127), Some(1 << 126));
+        assert_eq!(smallest_with_n_divisors(131), None);
+        assert_eq!(smallest_with_n_divisors(1_000_000_007), None);
+        // a prime only has itself to try
+        assert_eq!(smallest_with_n_divisors(999_999_999_989), None);
+        assert_eq!(smallest_with_n_divisors(1 << 63), None);
+        for k in 1..=64_u64 {
+            assert_eq!(
+                smallest_with_n_divisors(k),
+                Some(smallest_with_divisor_count(k) as u128)
+            );
+        }
+    }
+
+    #[test]
+    fn smallest_with_n_divisors_count_test() {
+        for k in 1..=2000_u64 {
+            if let Some(n) = smallest_with_n_divisors(k) {
+                assert_eq!(smooth_divisor_count(n), k);
+            }
+        }
+        assert_eq!(
+            smooth_divisor_count(smallest_with_n_divisors(1 << 20).unwrap()),
+            1 << 20
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn smallest_with_divisor_count_overflow() {
+        smallest_with_divisor_count(67); // 2^66
+    }
+}
+
+#[cfg(test)]
+mod sigma_inverse_tests {
+    use bens_number_theory::divisor_functions::{divisor_sum, sigma_inverse};
+
+    #[test]
+    fn sigma_inverse_test() {
+        assert_eq!(sigma_inverse(12, 1000), vec![6, 11]);
+        assert_eq!(sigma_inverse(1, 1000), vec![1]);
+        assert_eq!(sigma_inverse(24, 1000), vec![14, 15, 23]);
+        assert_eq!(sigma_inverse(24, 14), vec![14]);
+        assert_eq!(sigma_inverse(0, 1000), vec![]);
+        for m in 1..500_u64 {
+            for n in sigma_inverse(m, u64::MAX) {
+                assert_eq!(divisor_sum(n), m);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod batch_sieve_tests {
+    use bens_number_theory::divisor_functions::{
+        divisor_count, divisor_sum, sigma_sieve, tau_sieve,
+    };
+
+    #[test]
+    fn prefix_agreement_test() {
+        let sums: Vec<u64> = sigma_sieve(10_000);
+        let counts: Vec<u32> = tau_sieve(10_000);
+        assert_eq!(sums.len(), 10_001);
+        assert_eq!(counts.len(), 10_001);
+        for n in 0..=10_000_u64 {
+            assert_eq!(sums[n as usize], divisor_sum(n), "{}", n);
+            assert_eq!(counts[n as usize] as u64, divisor_count(n), "{}", n);
+        }
+        // a shorter sieve is a prefix of a longer one
+        assert_eq!(sigma_sieve(100)[..], sums[..101]);
+        assert_eq!(tau_sieve(100)[..], counts[..101]);
+    }
+
+    #[test]
+    fn highly_composite_test() {
+        let sums: Vec<u64> = sigma_sieve(720_720);
+        let counts: Vec<u32> = tau_sieve(720_720);
+        assert_eq!(sums[720_720], 3_249_792);
+        assert_eq!(counts[720_720], 240);
+    }
+
+    #[test]
+    fn memory_test() {
+        let sums: Vec<u64> = sigma_sieve(1000);
+        let counts: Vec<u32> = tau_sieve(1000);
+        assert_eq!(sums.capacity(), 1001);
+        assert_eq!(counts.capacity(), 1001);
+        assert_eq!(std::mem::size_of_val(&sums[..]), 1001 * 8);
+        assert_eq!(std::mem::size_of_val(&counts[..]), 1001 * 4);
+    }
+}
+
+#[cfg(test)]
+mod unitary_divisor_tests {
+    use bens_number_theory::divisor_functions::{
+        divisor_count, divisor_pairs, unitary_divisor_sum, unitary_divisors,
+    };
+    use bens_number_theory::modular::gcd;
+
+    #[test]
+    fn divisor_pairs_test() {
+        assert_eq!(
+            divisor_pairs(36),
+            vec![(1, 36), (2, 18), (3, 12), (4, 9), (6, 6)]
+        );
+        assert_eq!(divisor_pairs(0), vec![]);
+        assert_eq!(divisor_pairs(1), vec![(1, 1)]);
+        for n in 1..3000_u64 {
+            let expected: Vec<(u64, u64)> = (1..=n)
+                .filter(|d| n % d == 0 && *d <= n / d)
+                .map(|d| (d, n / d))
+                .collect();
+            assert_eq!(divisor_pairs(n), expected);
+            assert_eq!(divisor_pairs(n).len() as u64, divisor_count(n).div_ceil(2));
+        }
+        let last: (u64, u64) = *divisor_pairs(u64::MAX).last().unwrap();
+        assert_eq!(last.0 * last.1, u64::MAX);
+    }
+
+    #[test]
+    fn unitary_divisors_test() {
+        assert_eq!(unitary_divisors(72), vec![1, 8, 9, 72]);
+        assert_eq!(unitary_divisors(0), vec![]);
+        assert_eq!(unitary_divisors(1), vec![1]);
+        for n in 1..3000_u64 {
+            let expected: Vec<u64> = (1..=n)
+                .filter(|d| n % d == 0 && gcd(*d, n / d) == 1)
+                .collect();
+            assert_eq!(unitary_divisors(n), expected);
+            assert_eq!(unitary_divisor_sum(n), expected.iter().sum::<u64>());
+        }
+    }
+
+    #[test]
+    fn unitary_divisor_sum_test() {
+        assert_eq!(unitary_divisor_sum(72), 90);
+        assert_eq!(unitary_divisor_sum(0), 0);
+        assert_eq!(unitary_divisor_sum(1 << 63), (1 << 63) + 1);
+        // sigma* is multiplicative
+        for m in 1..100_u64 {
+            for n in 1..100_u64 {
+                if gcd(m, n) == 1 {
+                    assert_eq!(
+                        unitary_divisor_sum(m * n),
+                        unitary_divisor_sum(m) * unitary_divisor_sum(n)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn unitary_divisor_sum_overflow_test() {
+        unitary_divisor_sum(u64::MAX - 1);
+    }
+}
+
+#[cfg(test)]
+mod factorization_count_tests {
+    use bens_number_theory::divisor_functions::{ordered_factorizations, unordered_factorizations};
+
+    fn ordered_brute_force(n: u64) -> u64 {
+        if n == 1 {
+            return 1;
+        }
+        (2..=n)
+            .filter(|d| n.is_multiple_of(*d))
+            .map(|d| ordered_brute_force(n / d))
+            .sum()
+    }
+
+    // factorizations of n whose factors are all at least `smallest`, listed in nondecreasing order
+    fn unordered_brute_force(n: u64, smallest: u64) -> u64 {
+        if n == 1 {
+            return 1;
+        }
+        (smallest..=n)
+            .filter(|d| n.is_multiple_of(*d))
+            .map(|d| unordered_brute_force(n / d, d))
+            .sum()
+    }
+
+    fn partition_numbers(count: usize) -> Vec<u64> {
+        let mut p: Vec<u64> = vec![0; count];
+        p[0] = 1;
+        for part in 1..count {
+            for total in part..count {
+                p[total] += p[total - part];
+            }
+        }
+        p
+    }
+
+    #[test]
+    fn small_test() {
+        assert_eq!(ordered_factorizations(12), 8);
+        assert_eq!(unordered_factorizations(12), 4);
+        assert_eq!(ordered_factorizations(1), 1);
+        assert_eq!(unordered_factorizations(1), 1);
+        let ordered: Vec<u64> = (1..=16).map(ordered_factorizations).collect();
+        assert_eq!(
+            ordered,
+            vec![1, 1, 1, 2, 1, 3, 1, 4, 2, 3, 1, 8, 1, 3, 3, 8]
+        );
+        let unordered: Vec<u64> = (1..=16).map(unordered_factorizations).collect();
+        assert_eq!(
+            unordered,
+            vec![1, 1, 1, 2, 1, 2, 1, 3, 2, 2, 1, 4, 1, 2, 2, 5]
+        );
+    }
+
+    #[test]
+    fn brute_force_test() {
+        let mut n: u64 = 1;
+        while n <= 200 {
+            assert_eq!(ordered_factorizations(n), ordered_brute_force(n), "{n}");
+            assert_eq!(
+                unordered_factorizations(n),
+                unordered_brute_force(n, 2),
+                "{n}"
+            );
+            n += 1;
+        }
+    }
+
+    #[test]
+    fn prime_power_test() {
+        // compositions and partitions of the exponent
+        let partitions: Vec<u64> = partition_numbers(64);
+        for p in [2_u64, 3, 5, 7, 1_000_003] {
+            let mut k: u32 = 1;
+            while (p as u128).pow(k) <= u64::MAX as u128 {
+                let n: u64 = p.pow(k);
+                assert_eq!(ordered_factorizations(n), 1 << (k - 1), "{p}^{k}");
+                assert_eq!(
+                    unordered_factorizations(n),
+                    partitions[k as usize],
+                    "{p}^{k}"
+                );
+                k += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn squarefree_test() {
+        // for a product of k distinct primes these are the ordered Bell (Fubini) numbers and the Bell numbers
+        let fubini: [u64; 9] = [1, 1, 3, 13, 75, 541, 4683, 47293, 545835];
+        let bell: [u64; 9] = [1, 1, 2, 5, 15, 52, 203, 877, 4140];
+        let primes: [u64; 8] = [2, 3, 5, 7, 11, 13, 17, 19];
+        let mut n: u64 = 1;
+        for k in 0..9 {
+            assert_eq!(ordered_factorizations(n), fubini[k]);
+            assert_eq!(unordered_factorizations(n), bell[k]);
+            if k < 8 {
+                n *= primes[k];
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_test() {
+        ordered_factorizations(0);
+    }
+}
@@ -0,0 +1,134 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Tracks the bytes allocated by the current thread, so that tests running in parallel do not disturb each other.
+struct CountingAllocator;
+
+thread_local! {
+    static CURRENT: Cell<usize> = const { Cell::new(0) };
+    static PEAK: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = CURRENT.try_with(|current| {
+            current.set(current.get() + layout.size());
+            let _ = PEAK.try_with(|peak| peak.set(peak.get().max(current.get())));
+        });
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let _ =
+            CURRENT.try_with(|current| current.set(current.get().saturating_sub(layout.size())));
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Measures the largest number of bytes held at once while running `f`, beyond what was held before.
+fn peak_allocation<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let start: usize = CURRENT.with(|current| current.get());
+    PEAK.with(|peak| peak.set(start));
+    let result: T = f();
+    (result, PEAK.with(|peak| peak.get()) - start)
+}
+
+#[cfg(test)]
+mod prime_count_bound_tests {
+    use bens_number_theory::estimate::prime_count_upper_bound;
+    use bens_number_theory::primes::prime_pi;
+
+    #[test]
+    fn bound_test() {
+        assert_eq!(prime_count_upper_bound(0), 0);
+        assert_eq!(prime_count_upper_bound(1), 0);
+        for x in 2..100_000 {
+            assert!(prime_count_upper_bound(x) >= prime_pi(x), "{}", x);
+        }
+        let mut x: u64 = 100_000;
+        while x <= 100_000_000_000 {
+            let count: u64 = prime_pi(x);
+            let bound: u64 = prime_count_upper_bound(x);
+            assert!(bound >= count, "{}", x);
+            // the bound is never more than about 30% too large
+            assert!(bound < count + count / 3, "{}", x);
+            x = x * 7 / 3;
+        }
+    }
+}
+
+#[cfg(test)]
+mod sieve_memory_tests {
+    use super::peak_allocation;
+    use bens_number_theory::estimate::{sieve_memory_bytes, SieveRepr};
+    use bens_number_theory::primes::{generate_primes, prime_bitset};
+
+    #[test]
+    fn bitset_allocation_test() {
+        for limit in [1000_u64, 65_535, 1_000_000, 10_000_000] {
+            let (bits, peak): (Vec<u64>, usize) = peak_allocation(|| prime_bitset(limit));
+            let estimate: u64 = sieve_memory_bytes(limit, SieveRepr::Bitset);
+            assert_eq!(bits.len() * 8, estimate as usize);
+            assert!(peak as u64 >= estimate);
+            assert!(
+                (peak as u64) < estimate + estimate / 50,
+                "{} {}",
+                peak,
+                estimate
+            );
+        }
+    }
+
+    #[test]
+    fn prime_list_allocation_test() {
+        let limit: u64 = 100_000;
+        let (primes, peak): (Vec<u64>, usize) = peak_allocation(|| generate_primes(limit));
+        let estimate: u64 = sieve_memory_bytes(limit, SieveRepr::PrimeList);
+        // the output is allocated once and never grows
+        assert_eq!(primes.capacity() as u64 * 8, estimate);
+        assert!(primes.len() as u64 * 8 <= estimate);
+        assert!(peak as u64 >= estimate);
+    }
+
+    #[test]
+    fn representation_test() {
+        assert_eq!(sieve_memory_bytes(0, SieveRepr::Bytes), 1);
+        assert_eq!(sieve_memory_bytes(63, SieveRepr::Bitset), 8);
+        assert_eq!(sieve_memory_bytes(64, SieveRepr::Bitset), 16);
+        assert_eq!(sieve_memory_bytes(127, SieveRepr::OddBitset), 8);
+        assert_eq!(sieve_memory_bytes(u64::MAX, SieveRepr::Bytes), u64::MAX);
+        for limit in [10_u64, 1000, 1 << 40] {
+            let bytes: u64 = sieve_memory_bytes(limit, SieveRepr::Bytes);
+            let bits: u64 = sieve_memory_bytes(limit, SieveRepr::Bitset);
+            let odd: u64 = sieve_memory_bytes(limit, SieveRepr::OddBitset);
+            assert!(odd <= bits && bits <= bytes);
+        }
+    }
+}
+
+#[cfg(test)]
+mod prime_bitset_tests {
+    use bens_number_theory::primes::{is_probable_prime, prime_bitset};
+    use num::BigInt;
+
+    #[test]
+    fn bitset_test() {
+        for limit in [0_u64, 1, 2, 63, 64, 65, 1000] {
+            let bits: Vec<u64> = prime_bitset(limit);
+            assert_eq!(bits.len() as u64, (limit + 1).div_ceil(64));
+            for i in 0..bits.len() as u64 * 64 {
+                let set: bool = bits[(i / 64) as usize] >> (i % 64) & 1 == 1;
+                assert_eq!(
+                    set,
+                    i <= limit && is_probable_prime(&BigInt::from(i)),
+                    "{} {}",
+                    limit,
+                    i
+                );
+            }
+        }
+    }
+}
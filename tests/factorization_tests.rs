@@ -0,0 +1,658 @@
+#[cfg(test)]
+mod factorize_tests {
+    use bens_number_theory::factorization::factorize;
+
+    #[test]
+    fn factorize_test() {
+        assert_eq!(factorize(0), vec![]);
+        assert_eq!(factorize(1), vec![]);
+        assert_eq!(factorize(2), vec![(2, 1)]);
+        assert_eq!(factorize(1024), vec![(2, 10)]);
+        assert_eq!(
+            factorize(720720),
+            vec![(2, 4), (3, 2), (5, 1), (7, 1), (11, 1), (13, 1)]
+        );
+        assert_eq!(
+            factorize(600851475143),
+            vec![(71, 1), (839, 1), (1471, 1), (6857, 1)]
+        );
+    }
+
+    #[test]
+    fn factorize_reconstructs_test() {
+        for n in 1..10000_u64 {
+            let mut product: u64 = 1;
+            for (p, e) in factorize(n) {
+                product *= p.pow(e);
+            }
+            assert_eq!(product, n);
+        }
+    }
+
+    #[test]
+    fn factorize_large_prime_test() {
+        assert_eq!(factorize(4294967291), vec![(4294967291, 1)]); // largest u32 prime
+        assert_eq!(factorize(4294967291 * 3), vec![(3, 1), (4294967291, 1)]);
+    }
+}
+
+#[cfg(test)]
+mod perfect_power_tests {
+    use bens_number_theory::factorization::{as_perfect_power, factorize};
+
+    /// Calculates the gcd of the exponents in the factorization, which is the largest perfect power exponent.
+    fn exponent_gcd(n: u64) -> u32 {
+        let mut g: u32 = 0;
+        for (_, e) in factorize(n) {
+            let (mut a, mut b): (u32, u32) = (g, e);
+            while b != 0 {
+                (a, b) = (b, a % b);
+            }
+            g = a;
+        }
+        g
+    }
+
+    #[test]
+    fn perfect_power_test() {
+        assert_eq!(as_perfect_power(0), None);
+        assert_eq!(as_perfect_power(1), None);
+        assert_eq!(as_perfect_power(1 << 63), Some((2, 63)));
+        assert_eq!(as_perfect_power(3_u64.pow(40)), Some((3, 40)));
+        assert_eq!(
+            as_perfect_power(4294967295 * 4294967295),
+            Some((4294967295, 2))
+        );
+        assert_eq!(as_perfect_power(u64::MAX), None);
+        assert_eq!(as_perfect_power(10_u64.pow(18)), Some((10, 18)));
+    }
+
+    #[test]
+    fn perfect_power_matches_factorization_test() {
+        for n in 2..100000_u64 {
+            let k: u32 = exponent_gcd(n);
+            match as_perfect_power(n) {
+                Some((b, e)) => {
+                    assert_eq!(e, k);
+                    assert_eq!(b.pow(e), n);
+                }
+                None => assert_eq!(k, 1),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod semiprime_tests {
+    use bens_number_theory::factorization::{factorize, is_semiprime};
+
+    #[test]
+    fn semiprime_matches_factorization_test() {
+        for n in 0..20000_u64 {
+            let omega: u32 = factorize(n).iter().map(|(_, e)| e).sum();
+            assert_eq!(is_semiprime(n), omega == 2);
+        }
+    }
+
+    #[test]
+    fn semiprime_large_test() {
+        assert!(is_semiprime(4294967291 * 4294967279)); // two large u32 primes
+        assert!(is_semiprime(2 * 9223372036854775783)); // twice the largest prime below 2^63
+        assert!(!is_semiprime(18446744073709551557)); // largest u64 prime
+        assert!(!is_semiprime(3 * 5 * 1099511627791));
+    }
+}
+
+#[cfg(test)]
+mod arithmetic_derivative_tests {
+    use bens_number_theory::factorization::{
+        arithmetic_derivative, arithmetic_derivative_sequence, arithmetic_derivative_signed,
+    };
+    use bens_number_theory::primes::generate_primes;
+
+    #[test]
+    fn arithmetic_derivative_test() {
+        assert_eq!(arithmetic_derivative(0), 0);
+        assert_eq!(arithmetic_derivative(1), 0);
+        assert_eq!(arithmetic_derivative(6), 5);
+        assert_eq!(arithmetic_derivative(9), 6);
+        assert_eq!(arithmetic_derivative(27), 27);
+        assert_eq!(arithmetic_derivative(3125), 3125); // 5^5
+        let first: Vec<u64> = (0..16).map(arithmetic_derivative).collect();
+        assert_eq!(
+            first,
+            vec![0, 0, 1, 1, 4, 1, 5, 1, 12, 6, 7, 1, 16, 1, 9, 8]
+        );
+        for p in generate_primes(5000_u64) {
+            assert_eq!(arithmetic_derivative(p), 1);
+        }
+    }
+
+    #[test]
+    fn leibniz_rule_test() {
+        let mut state: u64 = 0x2545F4914F6CDD1D;
+        let mut next_random = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..2000 {
+            let a: u64 = next_random() % 100000;
+            let b: u64 = next_random() % 100000;
+            assert_eq!(
+                arithmetic_derivative(a * b),
+                arithmetic_derivative(a) * b + a * arithmetic_derivative(b)
+            );
+        }
+    }
+
+    #[test]
+    fn arithmetic_derivative_signed_test() {
+        for n in -500..=500_i64 {
+            let expected: i64 = arithmetic_derivative(n.unsigned_abs()) as i64;
+            assert_eq!(arithmetic_derivative_signed(n), expected * n.signum());
+        }
+        assert_eq!(arithmetic_derivative_signed(-27), -27);
+    }
+
+    #[test]
+    #[should_panic]
+    fn arithmetic_derivative_overflow() {
+        arithmetic_derivative(1 << 62);
+    }
+
+    #[test]
+    fn arithmetic_derivative_sequence_test() {
+        let mut exploding = arithmetic_derivative_sequence(12);
+        let trajectory: Vec<u64> = exploding.by_ref().collect();
+        assert_eq!(
+            trajectory[..12],
+            [12, 16, 32, 80, 176, 368, 752, 1520, 3424, 8592, 20096, 70464]
+        );
+        assert_eq!(trajectory.len(), 30);
+        assert!(exploding.overflowed());
+        assert!(!exploding.cycled());
+
+        let mut to_zero = arithmetic_derivative_sequence(10);
+        assert_eq!(to_zero.by_ref().collect::<Vec<u64>>(), vec![10, 7, 1, 0]);
+        assert!(to_zero.cycled());
+
+        let mut fixed = arithmetic_derivative_sequence(823543); // 7^7
+        assert_eq!(fixed.by_ref().count(), 1);
+        assert!(fixed.cycled());
+        assert!(!fixed.overflowed());
+    }
+}
+
+#[cfg(test)]
+mod ruth_aaron_tests {
+    use bens_number_theory::factorization::{
+        is_ruth_aaron_pair, is_ruth_aaron_pair_distinct, ruth_aaron_pairs_below,
+        ruth_aaron_pairs_below_distinct,
+    };
+
+    #[test]
+    fn ruth_aaron_pair_test() {
+        assert!(is_ruth_aaron_pair(714));
+        assert!(is_ruth_aaron_pair_distinct(714));
+        assert!(is_ruth_aaron_pair(5));
+        assert!(!is_ruth_aaron_pair(1));
+        assert!(!is_ruth_aaron_pair_distinct(0));
+    }
+
+    #[test]
+    fn ruth_aaron_pairs_with_multiplicity_test() {
+        let pairs: Vec<u64> = ruth_aaron_pairs_below(10000);
+        assert_eq!(
+            pairs,
+            vec![
+                5, 8, 15, 77, 125, 714, 948, 1330, 1520, 1862, 2491, 3248, 4185, 4191, 5405, 5560,
+                5959, 6867, 8280, 8463
+            ]
+        );
+        let checked: Vec<u64> = (0..10000).filter(|n| is_ruth_aaron_pair(*n)).collect();
+        assert_eq!(pairs, checked);
+    }
+
+    #[test]
+    fn ruth_aaron_pairs_distinct_test() {
+        let pairs: Vec<u64> = ruth_aaron_pairs_below_distinct(10000);
+        assert_eq!(pairs.len(), 19);
+        assert_eq!(
+            pairs[..15],
+            [5, 24, 49, 77, 104, 153, 369, 492, 714, 1682, 2107, 2299, 2600, 2783, 5405]
+        );
+        let checked: Vec<u64> = (0..10000)
+            .filter(|n| is_ruth_aaron_pair_distinct(*n))
+            .collect();
+        assert_eq!(pairs, checked);
+    }
+}
+
+#[cfg(test)]
+mod home_prime_tests {
+    use bens_number_theory::factorization::{home_prime, HomePrimeResult};
+    use bens_number_theory::primes::generate_primes;
+
+    #[test]
+    fn home_prime_test() {
+        assert_eq!(home_prime(10, 100), HomePrimeResult::Prime(773, 4));
+        assert_eq!(home_prime(4, 100), HomePrimeResult::Prime(211, 2)); // 4 -> 22 -> 211
+        assert_eq!(home_prime(9, 100), HomePrimeResult::Prime(311, 2)); // 9 -> 33 -> 311
+        let first: Vec<HomePrimeResult> = (2..=7).map(|n| home_prime(n, 100)).collect();
+        assert_eq!(
+            first,
+            vec![
+                HomePrimeResult::Prime(2, 0),
+                HomePrimeResult::Prime(3, 0),
+                HomePrimeResult::Prime(211, 2),
+                HomePrimeResult::Prime(5, 0),
+                HomePrimeResult::Prime(23, 1),
+                HomePrimeResult::Prime(7, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn home_prime_of_8_test() {
+        assert_eq!(
+            home_prime(8, 100),
+            HomePrimeResult::Prime(3331113965338635107, 13)
+        );
+        assert_eq!(home_prime(8, 12), HomePrimeResult::ExceededSteps);
+    }
+
+    #[test]
+    fn home_prime_of_prime_test() {
+        for p in generate_primes(1000_u64) {
+            assert_eq!(home_prime(p, 0), HomePrimeResult::Prime(p, 0));
+        }
+    }
+
+    #[test]
+    fn home_prime_overflow_test() {
+        // 2^63 concatenates to a 63 digit number of twos
+        assert_eq!(
+            home_prime(1 << 63, u32::MAX),
+            HomePrimeResult::ExceededWidth(0)
+        );
+        // the home prime of 49 is not known
+        assert_eq!(home_prime(49, 10), HomePrimeResult::ExceededSteps);
+        assert_eq!(home_prime(49, u32::MAX), HomePrimeResult::ExceededWidth(18));
+    }
+
+    #[test]
+    #[should_panic]
+    fn home_prime_of_one() {
+        home_prime(1, 10);
+    }
+}
+
+#[cfg(test)]
+mod prime_signature_tests {
+    use bens_number_theory::factorization::{
+        prime_signature, same_signature, smallest_with_signature,
+    };
+
+    #[test]
+    fn prime_signature_test() {
+        assert_eq!(prime_signature(0), vec![]);
+        assert_eq!(prime_signature(1), vec![]);
+        assert_eq!(prime_signature(97), vec![1]);
+        assert_eq!(prime_signature(360), vec![3, 2, 1]);
+        assert_eq!(prime_signature(2 * 3 * 3 * 5 * 5 * 5 * 7), vec![3, 2, 1, 1]);
+    }
+
+    #[test]
+    fn same_signature_test() {
+        assert!(same_signature(6, 35));
+        assert!(same_signature(1, 0));
+        assert!(!same_signature(8, 12));
+    }
+
+    #[test]
+    fn smallest_with_signature_test() {
+        for n in 1..5000_u64 {
+            let smallest: u64 = smallest_with_signature(&prime_signature(n));
+            assert!(smallest <= n);
+            assert!(same_signature(smallest, n));
+        }
+        assert_eq!(smallest_with_signature(&[0, 1, 0]), 2);
+        assert_eq!(smallest_with_signature(&[63]), 1 << 63);
+    }
+
+    #[test]
+    #[should_panic]
+    fn smallest_with_signature_overflow() {
+        smallest_with_signature(&[64]);
+    }
+}
+
+#[cfg(test)]
+mod factorization_struct_tests {
+    use bens_number_theory::divisor_functions::{divisor_count, divisor_sum};
+    use bens_number_theory::factorization::{factorize, Factorization, FactorizationStyle};
+    use num::BigInt;
+
+    #[test]
+    fn formatting_test() {
+        let one: Factorization = Factorization::of(1);
+        assert_eq!(one.to_string(), "1");
+        assert_eq!(one.format(FactorizationStyle::Unicode), "1");
+        assert_eq!(one.to_latex(), "1");
+
+        let prime: Factorization = Factorization::of(97);
+        assert_eq!(prime.to_string(), "97");
+        assert_eq!(prime.to_latex(), "97");
+
+        let large: Factorization = Factorization::of(2_u64.pow(12) * 3_u64.pow(10) * 7 * 101);
+        assert_eq!(large.to_string(), "2^12 · 3^10 · 7 · 101");
+        assert_eq!(
+            large.format(FactorizationStyle::Ascii),
+            "2^12 * 3^10 * 7 * 101"
+        );
+        assert_eq!(
+            large.format(FactorizationStyle::Unicode),
+            "2¹² · 3¹⁰ · 7 · 101"
+        );
+        assert_eq!(large.to_latex(), "2^{12} \\cdot 3^{10} \\cdot 7 \\cdot 101");
+    }
+
+    #[test]
+    fn multiplication_round_trip_test() {
+        for a in 1..300_u64 {
+            for b in (1..300_u64).step_by(7) {
+                let product: Factorization = Factorization::of(a) * Factorization::of(b);
+                assert_eq!(product.checked_value(), Some(a * b));
+                assert_eq!(product, Factorization::of(a * b));
+            }
+        }
+    }
+
+    #[test]
+    fn factored_form_beyond_u64_test() {
+        let big: Factorization = Factorization::of(u64::MAX) * Factorization::of(u64::MAX);
+        assert_eq!(big.checked_value(), None);
+        let value: BigInt = BigInt::from(u64::MAX) * BigInt::from(u64::MAX);
+        // 2^64 - 1 = 3 * 5 * 17 * 257 * 641 * 65537 * 6700417, so its square has 3^7 divisors
+        assert_eq!(big.divisor_count(), BigInt::from(2187));
+        let mut product: BigInt = BigInt::from(1);
+        let mut sum: BigInt = BigInt::from(1);
+        for (p, e) in big.factors() {
+            assert_eq!(*e, 2);
+            product *= BigInt::from(*p).pow(2);
+            sum *= 1 + p + p * p;
+        }
+        assert_eq!(product, value);
+        assert_eq!(big.divisor_sum(), sum);
+    }
+
+    #[test]
+    fn divisor_functions_agree_test() {
+        for n in 1..2000_u64 {
+            let f: Factorization = Factorization::of(n);
+            assert_eq!(f.divisor_count(), BigInt::from(divisor_count(n)));
+            assert_eq!(f.divisor_sum(), BigInt::from(divisor_sum(n)));
+        }
+    }
+
+    #[test]
+    fn from_pairs_test() {
+        let f: Factorization = Factorization::from(vec![(5, 1), (2, 2), (5, 2), (3, 0)]);
+        assert_eq!(f.factors(), [(2, 2), (5, 3)]);
+        assert_eq!(Factorization::from(factorize(720)), Factorization::of(720));
+    }
+
+    #[test]
+    #[should_panic]
+    fn factorization_of_zero() {
+        Factorization::of(0);
+    }
+}
+
+#[cfg(test)]
+mod blum_integer_tests {
+    use bens_number_theory::factorization::is_blum_integer;
+
+    #[test]
+    fn blum_integer_test() {
+        for n in [21, 33, 57, 69, 77, 93, 129, 133, 141, 161, 177] {
+            assert!(is_blum_integer(n));
+        }
+        for n in [0, 1, 3, 9, 15, 35, 49, 63, 105] {
+            assert!(!is_blum_integer(n));
+        }
+        let count: usize = (0..1000).filter(|n| is_blum_integer(*n)).count();
+        assert_eq!(count, 66);
+    }
+}
+
+#[cfg(test)]
+mod prime_factor_accessor_tests {
+    use bens_number_theory::factorization::{factorize, greatest_prime_factor, least_prime_factor};
+
+    #[test]
+    fn least_prime_factor_test() {
+        assert_eq!(least_prime_factor(0), None);
+        assert_eq!(least_prime_factor(1), None);
+        assert_eq!(least_prime_factor(2), Some(2));
+        assert_eq!(
+            least_prime_factor(2_u64.pow(61) - 1),
+            Some(2_u64.pow(61) - 1)
+        );
+        assert_eq!(least_prime_factor(1_000_003 * 1_000_033), Some(1_000_003));
+        assert_eq!(least_prime_factor(1021 * 1031), Some(1021));
+        assert_eq!(least_prime_factor(1031 * 1033), Some(1031));
+    }
+
+    #[test]
+    fn even_numbers_test() {
+        for n in [4_u64, 6, 1 << 40, u64::MAX - 1, 2 * 1_000_000_007] {
+            assert_eq!(least_prime_factor(n), Some(2));
+        }
+    }
+
+    #[test]
+    fn greatest_prime_factor_test() {
+        assert_eq!(greatest_prime_factor(1), None);
+        assert_eq!(greatest_prime_factor(2), Some(2));
+        assert_eq!(greatest_prime_factor(600_851_475_143), Some(6857));
+        assert_eq!(greatest_prime_factor(1 << 63), Some(2));
+        assert_eq!(greatest_prime_factor(u64::MAX), Some(6_700_417));
+        assert_eq!(
+            greatest_prime_factor(2_u64.pow(61) - 1),
+            Some(2_u64.pow(61) - 1)
+        );
+        assert_eq!(greatest_prime_factor(9 * 2_u64.pow(59)), Some(3));
+    }
+
+    #[test]
+    fn agreement_with_factorize_test() {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..10_000 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let n: u64 = state % 100_000_000;
+            let factors: Vec<(u64, u32)> = factorize(n);
+            assert_eq!(
+                least_prime_factor(n),
+                factors.first().map(|(p, _)| *p),
+                "{}",
+                n
+            );
+            assert_eq!(
+                greatest_prime_factor(n),
+                factors.last().map(|(p, _)| *p),
+                "{}",
+                n
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod square_part_tests {
+    use bens_number_theory::factorization::{factorize, simplify_sqrt, square_part_decomposition};
+
+    fn check(n: u64) {
+        let (s, q): (u64, u64) = square_part_decomposition(n);
+        assert_eq!(s as u128 * s as u128 * q as u128, n as u128, "{n}");
+        if n < 1 << 48 {
+            assert!(factorize(q).iter().all(|(_, e)| *e == 1), "{n}");
+        } else {
+            // factorizing q by trial division would be too slow, but q must decompose as itself
+            assert_eq!(square_part_decomposition(q), (1, q), "{n}");
+        }
+    }
+
+    #[test]
+    fn known_values_test() {
+        assert_eq!(square_part_decomposition(72), (6, 2));
+        assert_eq!(square_part_decomposition(50), (5, 2));
+        assert_eq!(square_part_decomposition(0), (0, 1));
+        assert_eq!(square_part_decomposition(1), (1, 1));
+        assert_eq!(simplify_sqrt(72), (6, 2));
+        assert_eq!(simplify_sqrt(50), (5, 2));
+    }
+
+    #[test]
+    fn primes_and_squares_test() {
+        for p in [
+            2_u64,
+            3,
+            97,
+            65_521,
+            1_000_000_007,
+            18_446_744_073_709_551_557,
+        ] {
+            assert_eq!(square_part_decomposition(p), (1, p));
+        }
+        for r in [1_u64, 2, 12, 65_521, 4_294_967_291, u32::MAX as u64] {
+            assert_eq!(square_part_decomposition(r * r), (r, 1));
+        }
+        // large prime factors left over after trial division
+        assert_eq!(
+            square_part_decomposition(3 * 2_147_483_647 * 2_147_483_647),
+            (2_147_483_647, 3)
+        );
+        assert_eq!(
+            square_part_decomposition(4_294_967_291 * 4_294_967_279),
+            (1, 4_294_967_291 * 4_294_967_279)
+        );
+    }
+
+    #[test]
+    fn brute_force_test() {
+        for n in 1..5000_u64 {
+            let mut s: u64 = 1;
+            let mut q: u64 = 1;
+            for (p, e) in factorize(n) {
+                s *= p.pow(e / 2);
+                q *= p.pow(e % 2);
+            }
+            assert_eq!(square_part_decomposition(n), (s, q));
+        }
+    }
+
+    #[test]
+    fn random_samples_test() {
+        let mut x: u64 = 1;
+        for _ in 0..200 {
+            x = x
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            check(x >> 20);
+            check(x);
+        }
+    }
+}
+
+#[cfg(test)]
+mod trial_divide_tests {
+    use bens_number_theory::factorization::{factorize, trial_divide, TrialDivision};
+
+    fn product(division: &TrialDivision) -> u128 {
+        division
+            .factors
+            .iter()
+            .map(|(p, e)| (*p as u128).pow(*e))
+            .product::<u128>()
+            * division.cofactor as u128
+    }
+
+    #[test]
+    fn partial_test() {
+        let n: u64 = 16 * 3 * 101;
+        assert_eq!(
+            trial_divide(n, 10),
+            TrialDivision {
+                factors: vec![(2, 4), (3, 1)],
+                cofactor: 101
+            }
+        );
+        assert_eq!(trial_divide(n, 2).factors, vec![(2, 4)]);
+        assert_eq!(trial_divide(n, 2).cofactor, 303);
+        // the cofactor can be composite
+        assert_eq!(trial_divide(101 * 103 * 7, 50).cofactor, 101 * 103);
+    }
+
+    #[test]
+    fn reconstruction_test() {
+        let mut x: u64 = 7;
+        let mut i: u32 = 0;
+        while i < 2000 {
+            x = x
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            let n: u64 = x >> (28 + i % 36);
+            let limit: u64 = (x >> 40) % 2000;
+            let division: TrialDivision = trial_divide(n, limit);
+            assert_eq!(product(&division), n as u128);
+            // every factor found is at most the limit, except a last prime left once the rest is finished
+            for (p, _) in &division.factors {
+                assert!(*p <= limit || division.cofactor == 1);
+            }
+            assert!(division
+                .factors
+                .windows(2)
+                .all(|pair| pair[0].0 < pair[1].0));
+            // no prime up to the limit divides the cofactor
+            let mut d: u64 = 2;
+            while d <= limit.min(division.cofactor) {
+                assert!(division.cofactor == 1 || !division.cofactor.is_multiple_of(d));
+                d += 1;
+            }
+            i += 1;
+        }
+    }
+
+    #[test]
+    fn full_factorization_test() {
+        for n in 2..3000_u64 {
+            let division: TrialDivision = trial_divide(n, n.isqrt() + 1);
+            assert_eq!(division.cofactor, 1);
+            assert_eq!(division.factors, factorize(n));
+        }
+        let n: u64 = 1_000_003 * 999_983;
+        assert_eq!(trial_divide(n, 999_982).cofactor, n);
+        assert_eq!(
+            trial_divide(n, 999_983).factors,
+            vec![(999_983, 1), (1_000_003, 1)]
+        );
+    }
+
+    #[test]
+    fn no_budget_test() {
+        for n in [0_u64, 1, 2, 3, 4, 360, 97, u64::MAX] {
+            for limit in [0, 1] {
+                let division: TrialDivision = trial_divide(n, limit);
+                assert!(division.factors.is_empty());
+                assert_eq!(division.cofactor, n);
+            }
+        }
+        assert_eq!(trial_divide(1, 100).cofactor, 1);
+        assert_eq!(trial_divide(0, 100).cofactor, 0);
+    }
+}
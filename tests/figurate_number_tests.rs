@@ -0,0 +1,207 @@
+#[cfg(test)]
+mod polygonal_tests {
+    use bens_number_theory::figurate_numbers::{is_polygonal, polygonal};
+
+    #[test]
+    fn polygonal_test() {
+        assert_eq!(polygonal(3, 285), 40755);
+        assert_eq!(polygonal(5, 165), 40755);
+        assert_eq!(polygonal(6, 143), 40755);
+        assert_eq!(polygonal(3, 0), 0);
+        assert_eq!(polygonal(7, 1), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn polygonal_overflow() {
+        polygonal(4, u64::MAX);
+    }
+
+    #[test]
+    #[should_panic]
+    fn polygonal_two_sides() {
+        polygonal(2, 10);
+    }
+
+    #[test]
+    fn is_polygonal_round_trip_test() {
+        for s in 3..=12 {
+            let mut expected: u64 = 0;
+            for x in 0..2000 {
+                let index: Option<u64> = is_polygonal(s, x);
+                if polygonal(s, expected) == x {
+                    assert_eq!(index, Some(expected));
+                    expected += 1;
+                } else {
+                    assert_eq!(index, None);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn is_polygonal_large_test() {
+        // near 10^18 floating point square roots are no longer exact
+        for n in [999_999_999_u64, 1_000_000_000, 1_414_213_562] {
+            for s in [3, 4, 5, 6] {
+                let x: u64 = polygonal(s, n);
+                assert_eq!(is_polygonal(s, x), Some(n));
+                assert_eq!(is_polygonal(s, x - 1), None);
+                assert_eq!(is_polygonal(s, x + 1), None);
+            }
+        }
+        assert_eq!(is_polygonal(4, 4294967295 * 4294967295), Some(4294967295));
+        assert_eq!(is_polygonal(4, u64::MAX), None);
+    }
+}
+
+#[cfg(test)]
+mod triple_figurate_tests {
+    use bens_number_theory::figurate_numbers::{
+        is_hexagonal, is_pentagonal, is_square, is_triangular, polygonal,
+    };
+
+    #[test]
+    fn predicates_test() {
+        assert!(is_triangular(40755) && is_pentagonal(40755) && is_hexagonal(40755));
+        assert!(is_square(0) && is_square(1) && is_square(49));
+        assert!(!is_square(50));
+    }
+
+    #[test]
+    fn next_triple_figurate_test() {
+        // every hexagonal number is triangular, so only the pentagonal check is needed
+        let mut n: u64 = 144;
+        while !is_pentagonal(polygonal(6, n)) {
+            n += 1;
+        }
+        let x: u64 = polygonal(6, n);
+        assert_eq!(x, 1533776805);
+        assert!(is_triangular(x) && is_hexagonal(x));
+    }
+}
+
+#[cfg(test)]
+mod sum_of_tests {
+    use bens_number_theory::factorization::least_prime_factor;
+    use bens_number_theory::figurate_numbers::{
+        is_cube, is_square, is_sum_of, is_sum_of_scaled, is_triangular, SummandKind,
+    };
+
+    const KINDS: [SummandKind; 4] = [
+        SummandKind::Prime,
+        SummandKind::Square,
+        SummandKind::Triangular,
+        SummandKind::Cube,
+    ];
+
+    fn is_kind(kind: SummandKind, x: u64) -> bool {
+        match kind {
+            SummandKind::Prime => least_prime_factor(x) == Some(x),
+            SummandKind::Square => is_square(x),
+            SummandKind::Triangular => is_triangular(x),
+            SummandKind::Cube => is_cube(x),
+        }
+    }
+
+    #[test]
+    fn goldbach_other_conjecture_test() {
+        let mut counterexamples: Vec<u64> = vec![];
+        let mut n: u64 = 9;
+        while n < 10_000 {
+            if least_prime_factor(n) != Some(n)
+                && is_sum_of_scaled(n, SummandKind::Prime, SummandKind::Square, 2).is_none()
+            {
+                counterexamples.push(n);
+            }
+            n += 2;
+        }
+        assert_eq!(counterexamples, vec![5777, 5993]);
+    }
+
+    #[test]
+    fn goldbach_test() {
+        let mut n: u64 = 4;
+        while n <= 100_000 {
+            let (a, b): (u64, u64) = is_sum_of(n, SummandKind::Prime, SummandKind::Prime).unwrap();
+            assert_eq!(a + b, n);
+            assert!(is_kind(SummandKind::Prime, a) && is_kind(SummandKind::Prime, b));
+            n += 2;
+        }
+    }
+
+    #[test]
+    fn witnesses_verify_test() {
+        for kind_a in KINDS {
+            for kind_b in KINDS {
+                for scale in 1..=3_u64 {
+                    for n in 0..300_u64 {
+                        match is_sum_of_scaled(n, kind_a, kind_b, scale) {
+                            Some((a, b)) => {
+                                assert_eq!(a + scale * b, n);
+                                assert!(is_kind(kind_a, a) && is_kind(kind_b, b));
+                            }
+                            None => {
+                                // no pair exists, checked by enumerating both sides
+                                let mut b: u64 = 0;
+                                while scale * b <= n {
+                                    assert!(
+                                        !(is_kind(kind_b, b) && is_kind(kind_a, n - scale * b))
+                                    );
+                                    b += 1;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sum_of_two_squares_test() {
+        // n is a sum of two squares exactly when every prime 3 mod 4 divides it an even number of times
+        assert_eq!(
+            is_sum_of(0, SummandKind::Square, SummandKind::Square),
+            Some((0, 0))
+        );
+        assert_eq!(
+            is_sum_of(1_000_000_009, SummandKind::Square, SummandKind::Square),
+            Some((31_400 * 31_400, 3_747 * 3_747))
+        );
+        assert_eq!(
+            is_sum_of(3 * 3 * 7, SummandKind::Square, SummandKind::Square),
+            None
+        );
+        assert_eq!(
+            is_sum_of(
+                4_000_000_000_000_000_000,
+                SummandKind::Square,
+                SummandKind::Square
+            ),
+            Some((4_000_000_000_000_000_000, 0))
+        );
+    }
+
+    #[test]
+    fn large_test() {
+        // 1729 = 12^3 + 1^3 = 10^3 + 9^3
+        assert_eq!(
+            is_sum_of(1729, SummandKind::Cube, SummandKind::Cube),
+            Some((1728, 1))
+        );
+        let n: u64 = u64::MAX - 1;
+        let (a, b): (u64, u64) = is_sum_of(n, SummandKind::Prime, SummandKind::Prime).unwrap();
+        assert_eq!(a.checked_add(b), Some(n));
+        assert_eq!(
+            is_sum_of(u64::MAX, SummandKind::Cube, SummandKind::Triangular),
+            None
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_scale_test() {
+        is_sum_of_scaled(10, SummandKind::Prime, SummandKind::Prime, 0);
+    }
+}
@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod gaussian_int_tests {
+    use bens_number_theory::gaussian::GaussianInt;
+
+    #[test]
+    fn arithmetic_test() {
+        let z: GaussianInt = GaussianInt::new(3, 2);
+        let w: GaussianInt = GaussianInt::new(1, -4);
+        assert_eq!(z * w, GaussianInt::new(11, -10));
+        assert_eq!((z * w).norm(), z.norm() * w.norm());
+        assert_eq!(z.conjugate(), GaussianInt::new(3, -2));
+        assert!(GaussianInt::new(0, -1).is_unit());
+        assert!(!GaussianInt::new(1, 1).is_unit());
+    }
+
+    #[test]
+    fn canonical_test() {
+        let z: GaussianInt = GaussianInt::new(4, 7);
+        let units: [GaussianInt; 4] = [
+            GaussianInt::new(1, 0),
+            GaussianInt::new(0, 1),
+            GaussianInt::new(-1, 0),
+            GaussianInt::new(0, -1),
+        ];
+        for u in units {
+            assert_eq!((z * u).canonical(), z);
+        }
+        assert_eq!(GaussianInt::new(0, 0).canonical(), GaussianInt::new(0, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn multiplication_overflow() {
+        let _ = GaussianInt::new(i64::MAX, 1) * GaussianInt::new(2, 0);
+    }
+}
+
+#[cfg(test)]
+mod gaussian_prime_tests {
+    use bens_number_theory::gaussian::{sum_of_two_squares_prime, GaussianInt};
+    use bens_number_theory::primes::generate_primes;
+
+    #[test]
+    fn gaussian_primes_test() {
+        for (re, im) in [(1, 1), (3, 0), (2, 1), (0, 7), (-2, 1), (4, 1), (11, 0)] {
+            assert!(GaussianInt::new(re, im).is_gaussian_prime());
+        }
+        for (re, im) in [
+            (0, 0),
+            (1, 0),
+            (0, -1),
+            (2, 0),
+            (5, 0),
+            (13, 0),
+            (3, 3),
+            (2, 2),
+        ] {
+            assert!(!GaussianInt::new(re, im).is_gaussian_prime());
+        }
+    }
+
+    #[test]
+    fn sum_of_two_squares_test() {
+        for p in generate_primes(10000_u64) {
+            if p % 4 == 1 {
+                let (a, b): (u64, u64) = sum_of_two_squares_prime(p);
+                assert_eq!(a * a + b * b, p);
+                assert!(a > b);
+            }
+        }
+    }
+
+    #[test]
+    fn factorization_examples_test() {
+        assert_eq!(GaussianInt::new(1, 0).factorize(), vec![]);
+        assert_eq!(GaussianInt::new(0, 0).factorize(), vec![]);
+        assert_eq!(
+            GaussianInt::new(3, 0).factorize(),
+            vec![(GaussianInt::new(3, 0), 1)]
+        );
+        assert_eq!(
+            GaussianInt::new(2, 0).factorize(),
+            vec![(GaussianInt::new(1, 1), 2)]
+        );
+        // (1 + i)^2 = 2i, so 2 = -i(1 + i)^2
+        let one_plus_i: GaussianInt = GaussianInt::new(1, 1);
+        assert_eq!(
+            GaussianInt::new(0, -1) * one_plus_i * one_plus_i,
+            GaussianInt::new(2, 0)
+        );
+        assert_eq!(
+            GaussianInt::new(5, 0).factorize(),
+            vec![(GaussianInt::new(1, 2), 1), (GaussianInt::new(2, 1), 1)]
+        );
+        assert_eq!(
+            GaussianInt::new(2, 1) * GaussianInt::new(2, -1),
+            GaussianInt::new(5, 0)
+        );
+        // 25 + 0i has both factors of 5 twice, while (2 + i)^2 = 3 + 4i only has one of them
+        assert_eq!(
+            GaussianInt::new(3, 4).factorize(),
+            vec![(GaussianInt::new(2, 1), 2)]
+        );
+    }
+
+    #[test]
+    fn factorization_reconstructs_test() {
+        for re in -40..=40_i64 {
+            for im in -40..=40_i64 {
+                let z: GaussianInt = GaussianInt::new(re, im);
+                if z.norm() == 0 {
+                    continue;
+                }
+                let mut product: GaussianInt = GaussianInt::new(1, 0);
+                for (pi, e) in z.factorize() {
+                    assert!(pi.is_gaussian_prime());
+                    assert_eq!(pi.canonical(), pi);
+                    for _ in 0..e {
+                        product = product * pi;
+                    }
+                }
+                assert_eq!(product.canonical(), z.canonical());
+            }
+        }
+    }
+}
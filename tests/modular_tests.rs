@@ -0,0 +1,1466 @@
+#[cfg(test)]
+mod gcd_tests {
+    use bens_number_theory::modular::gcd;
+
+    #[test]
+    fn gcd_test() {
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(gcd(5, 0), 5);
+        assert_eq!(gcd(48, 180), 12);
+        assert_eq!(gcd(u64::MAX, u64::MAX - 1), 1);
+        assert_eq!(gcd(1 << 40, 1 << 20), 1 << 20);
+    }
+
+    #[test]
+    fn gcd_divides_test() {
+        for a in 1..200_u64 {
+            for b in 1..200_u64 {
+                let g: u64 = gcd(a, b);
+                assert_eq!(a % g, 0);
+                assert_eq!(b % g, 0);
+                assert_eq!(gcd(a / g, b / g), 1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod order_tests {
+    use bens_number_theory::modular::{gcd, mod_pow, multiplicative_order};
+
+    #[test]
+    fn mod_pow_test() {
+        assert_eq!(mod_pow(2, 64, u64::MAX), 1);
+        assert_eq!(mod_pow(u64::MAX - 1, 2, u64::MAX), 1);
+        assert_eq!(mod_pow(0, 0, 13), 1);
+        for a in 0..20_u64 {
+            for e in 0..10_u32 {
+                assert_eq!(mod_pow(a, e as u64, 1009), a.pow(e) % 1009);
+            }
+        }
+    }
+
+    #[test]
+    fn multiplicative_order_test() {
+        for n in 1..300_u64 {
+            for a in 0..n {
+                let mut expected: Option<u64> = None;
+                if gcd(a, n) == 1 {
+                    let mut k: u64 = 1;
+                    while mod_pow(a, k, n) != 1 % n {
+                        k += 1;
+                    }
+                    expected = Some(k);
+                }
+                assert_eq!(multiplicative_order(a, n), expected);
+            }
+        }
+        assert_eq!(multiplicative_order(5, 0), None);
+    }
+}
+
+#[cfg(test)]
+mod totient_tests {
+    use bens_number_theory::modular::{
+        carmichael_lambda, euler_totient, gcd, mod_inverse, multiplicative_order,
+    };
+
+    #[test]
+    fn euler_totient_test() {
+        assert_eq!(euler_totient(0), 0);
+        for n in 1..1000_u64 {
+            let coprime: u64 = (1..=n).filter(|k| gcd(*k, n) == 1).count() as u64;
+            assert_eq!(euler_totient(n), coprime);
+        }
+    }
+
+    #[test]
+    fn carmichael_lambda_test() {
+        let first: Vec<u64> = (1..=16).map(carmichael_lambda).collect();
+        assert_eq!(
+            first,
+            vec![1, 1, 2, 2, 4, 2, 6, 2, 6, 4, 10, 2, 12, 6, 4, 4]
+        );
+        // lambda(n) is the largest multiplicative order modulo n
+        for n in 2..400_u64 {
+            let largest: u64 = (1..n)
+                .filter_map(|a| multiplicative_order(a, n))
+                .max()
+                .unwrap();
+            assert_eq!(carmichael_lambda(n), largest);
+        }
+    }
+
+    #[test]
+    fn mod_inverse_test() {
+        assert_eq!(mod_inverse(5, 0), None);
+        assert_eq!(mod_inverse(5, 1), Some(0));
+        assert_eq!(mod_inverse(u64::MAX - 1, u64::MAX), Some(u64::MAX - 1));
+        for m in 2..200_u64 {
+            for a in 0..m {
+                match mod_inverse(a, m) {
+                    Some(x) => {
+                        assert!(x < m);
+                        assert_eq!(a * x % m, 1);
+                    }
+                    None => assert_ne!(gcd(a, m), 1),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod totient_inverse_tests {
+    use bens_number_theory::modular::{euler_totient, has_totient_preimage, totient_inverse};
+
+    #[test]
+    fn totient_inverse_test() {
+        assert_eq!(totient_inverse(8, 1000), vec![15, 16, 20, 24, 30]);
+        assert_eq!(totient_inverse(8, 20), vec![15, 16, 20]);
+        assert_eq!(totient_inverse(14, 10_000_000), vec![]);
+        assert_eq!(totient_inverse(1, 100), vec![1, 2]);
+        assert_eq!(totient_inverse(0, 100), vec![]);
+        for m in 1..100_u64 {
+            for n in totient_inverse(m, u64::MAX) {
+                assert_eq!(euler_totient(n), m);
+            }
+        }
+    }
+
+    #[test]
+    fn totient_inverse_24_test() {
+        assert_eq!(
+            totient_inverse(24, u64::MAX),
+            vec![35, 39, 45, 52, 56, 70, 72, 78, 84, 90]
+        );
+    }
+
+    #[test]
+    fn has_totient_preimage_test() {
+        let even_nontotients: Vec<u64> = (1..100)
+            .filter(|m| m % 2 == 0 && !has_totient_preimage(*m))
+            .collect();
+        assert_eq!(
+            even_nontotients,
+            vec![14, 26, 34, 38, 50, 62, 68, 74, 76, 86, 90, 94, 98]
+        );
+        for m in 1..200_u64 {
+            assert_eq!(
+                has_totient_preimage(m),
+                !totient_inverse(m, u64::MAX).is_empty()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod jacobi_tests {
+    use bens_number_theory::modular::{jacobi_symbol, mod_pow};
+
+    #[test]
+    fn euler_criterion_test() {
+        // for an odd prime p, (a/p) = a^((p - 1)/2) (mod p)
+        for p in [3_u64, 5, 7, 11, 13, 97, 101, 65537] {
+            for a in 0..200 {
+                let expected: i8 = match mod_pow(a, (p - 1) / 2, p) {
+                    0 => 0,
+                    1 => 1,
+                    _ => -1,
+                };
+                assert_eq!(jacobi_symbol(a, p), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn multiplicative_in_bottom_test() {
+        // (a/mn) = (a/m)(a/n)
+        for m in (1..60_u64).step_by(2) {
+            for n in (1..60_u64).step_by(2) {
+                for a in 0..30 {
+                    assert_eq!(
+                        jacobi_symbol(a, m * n),
+                        jacobi_symbol(a, m) * jacobi_symbol(a, n)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn edge_cases_test() {
+        assert_eq!(jacobi_symbol(0, 1), 1);
+        assert_eq!(jacobi_symbol(5, 1), 1);
+        assert_eq!(jacobi_symbol(u64::MAX, u64::MAX), 0);
+        assert_eq!(jacobi_symbol(2, u64::MAX), 1); // u64::MAX = 7 (mod 8)
+    }
+
+    #[test]
+    #[should_panic]
+    fn even_bottom_test() {
+        jacobi_symbol(3, 10);
+    }
+}
+
+#[cfg(test)]
+mod polynomial_root_tests {
+    use bens_number_theory::modular::{
+        chinese_remainder, hensel_lift, roots_mod_m, roots_mod_prime_power,
+    };
+
+    fn eval(f: &[i64], x: u64, m: u64) -> u64 {
+        let mut value: i128 = 0;
+        for coefficient in f.iter().rev() {
+            value = (value * x as i128 + *coefficient as i128).rem_euclid(m as i128);
+        }
+        value as u64
+    }
+
+    fn brute_force(f: &[i64], m: u64) -> Vec<u64> {
+        (0..m).filter(|x| eval(f, *x, m) == 0).collect()
+    }
+
+    #[test]
+    fn chinese_remainder_test() {
+        assert_eq!(chinese_remainder(&[]), Some((0, 1)));
+        assert_eq!(chinese_remainder(&[(5, 7)]), Some((5, 7)));
+        assert_eq!(chinese_remainder(&[(9, 7)]), Some((2, 7)));
+        assert_eq!(
+            chinese_remainder(&[(2, 3), (3, 5), (2, 7)]),
+            Some((23, 105))
+        );
+        assert_eq!(chinese_remainder(&[(3, 6), (3, 6)]), Some((3, 6)));
+        assert_eq!(chinese_remainder(&[(0, 4), (1, 6)]), None);
+        assert_eq!(
+            chinese_remainder(&[(1, 1 << 40), (2, (1 << 40) - 1), (3, 1 << 30)]),
+            None
+        );
+        assert_eq!(chinese_remainder(&[(0, u64::MAX), (0, u64::MAX - 1)]), None);
+        for a in 0..12_u64 {
+            for b in 0..18_u64 {
+                let expected: Option<u64> = (0..36).find(|x| x % 12 == a && x % 18 == b);
+                assert_eq!(
+                    chinese_remainder(&[(a, 12), (b, 18)]),
+                    expected.map(|x| (x, 36))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn square_roots_test() {
+        for a in 0..50_i64 {
+            let f: [i64; 3] = [-a, 0, 1];
+            assert_eq!(roots_mod_prime_power(&f, 3, 5), brute_force(&f, 243));
+            assert_eq!(roots_mod_prime_power(&f, 2, 10), brute_force(&f, 1024));
+        }
+    }
+
+    #[test]
+    fn singular_roots_test() {
+        // x^2 = 0 modulo p^2 is solved by every multiple of p
+        for p in [2_u64, 3, 5, 7, 11] {
+            assert_eq!(
+                roots_mod_prime_power(&[0, 0, 1], p, 2),
+                (0..p).map(|t| t * p).collect::<Vec<u64>>()
+            );
+        }
+        let f: [i64; 4] = [0, 0, 0, 1];
+        assert_eq!(roots_mod_prime_power(&f, 2, 7), brute_force(&f, 128));
+        // (x - 1)^2 (x + 2) has a double root
+        let g: [i64; 4] = [2, -3, 0, 1];
+        assert_eq!(roots_mod_prime_power(&g, 3, 6), brute_force(&g, 729));
+        assert_eq!(roots_mod_prime_power(&g, 5, 3), brute_force(&g, 125));
+    }
+
+    #[test]
+    fn hensel_lift_test() {
+        let f: [i64; 3] = [-2, 0, 1];
+        let r: u64 = hensel_lift(&f, 3, 7, 10).unwrap();
+        let modulus: u64 = 7_u64.pow(10);
+        assert_eq!(eval(&f, r, modulus), 0);
+        assert_eq!(r % 7, 3);
+        assert_eq!(hensel_lift(&f, 3, 7, 1), Some(3));
+        assert_eq!(hensel_lift(&f, 2, 7, 3), None); // not a root
+        assert_eq!(hensel_lift(&f, 3, 7, 0), None);
+        assert_eq!(hensel_lift(&f, 3, 7, 40), None); // 7^40 overflows
+        assert_eq!(hensel_lift(&[0, 0, 1], 0, 5, 3), None); // singular
+                                                            // x^3 + x + 1 = 0 modulo 3^20 from the root 1
+        let g: [i64; 4] = [1, 1, 0, 1];
+        let r: u64 = hensel_lift(&g, 1, 3, 20).unwrap();
+        assert_eq!(eval(&g, r, 3_u64.pow(20)), 0);
+    }
+
+    #[test]
+    fn roots_mod_m_test() {
+        let polynomials: [&[i64]; 5] = [
+            &[-1, 0, 1],
+            &[1, 1, 1],
+            &[0, 0, 1],
+            &[-8, 0, 0, 1],
+            &[6, -5, 1],
+        ];
+        for f in polynomials {
+            for m in (1..200).chain([720, 1001, 1024, 2310, 4096, 9240, 9999, 10_000]) {
+                assert_eq!(roots_mod_m(f, m), brute_force(f, m), "{:?} {}", f, m);
+            }
+        }
+        assert_eq!(roots_mod_m(&[5], 1), vec![0]);
+        assert_eq!(roots_mod_m(&[5], 7), vec![]);
+    }
+}
+
+#[cfg(test)]
+mod power_residue_tests {
+    use bens_number_theory::modular::{
+        discrete_log, is_kth_power_residue, kth_power_residue_solve, kth_roots_mod, mod_pow,
+        primitive_root, tonelli_shanks,
+    };
+
+    const PRIMES: [u64; 10] = [2, 3, 5, 7, 11, 13, 31, 97, 101, 257];
+
+    #[test]
+    fn primitive_root_test() {
+        // A001918
+        let roots: Vec<u64> = [3_u64, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41]
+            .iter()
+            .map(|p| primitive_root(*p).unwrap())
+            .collect();
+        assert_eq!(roots, vec![2, 2, 3, 2, 2, 3, 2, 5, 2, 3, 2, 6]);
+        assert_eq!(primitive_root(1_000_000_007), Some(5));
+        assert_eq!(primitive_root(1), None);
+        assert_eq!(primitive_root(91), None);
+    }
+
+    #[test]
+    fn discrete_log_test() {
+        for p in PRIMES {
+            let g: u64 = primitive_root(p).unwrap();
+            for y in 0..p - 1 {
+                assert_eq!(discrete_log(g, mod_pow(g, y, p), p), Some(y));
+            }
+        }
+        let p: u64 = 1_000_000_007;
+        assert_eq!(
+            discrete_log(5, mod_pow(5, 987_654_321, p), p),
+            Some(987_654_321)
+        );
+        assert_eq!(discrete_log(2, 0, 7), None);
+        assert_eq!(discrete_log(6, 1, 9), None);
+    }
+
+    #[test]
+    fn cube_roots_test() {
+        assert_eq!(kth_power_residue_solve(8, 3, 13), vec![2, 5, 6]);
+        assert_eq!(kth_power_residue_solve(0, 5, 13), vec![0]);
+        assert_eq!(kth_power_residue_solve(1, 1, 13), vec![1]);
+    }
+
+    #[test]
+    fn all_roots_verified_test() {
+        for p in PRIMES {
+            for k in 1..20 {
+                for a in 0..p {
+                    let expected: Vec<u64> = (0..p).filter(|x| mod_pow(*x, k, p) == a).collect();
+                    assert_eq!(
+                        kth_power_residue_solve(a, k, p),
+                        expected,
+                        "{} {} {}",
+                        a,
+                        k,
+                        p
+                    );
+                    assert_eq!(is_kth_power_residue(a, k, p), !expected.is_empty());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn large_prime_test() {
+        let p: u64 = 1_000_000_007; // p - 1 = 2 * 500000003
+        let roots: Vec<u64> = kth_power_residue_solve(4, 2, p);
+        assert_eq!(roots, vec![2, p - 2]);
+        for x in kth_power_residue_solve(123_456, 3, p) {
+            assert_eq!(mod_pow(x, 3, p), 123_456);
+        }
+        assert!(kth_power_residue_solve(5, 2, p).is_empty()); // 5 is a primitive root
+    }
+
+    #[test]
+    fn tonelli_shanks_test() {
+        for p in [
+            3_u64,
+            5,
+            7,
+            13,
+            17,
+            41,
+            97,
+            257,
+            65537,
+            998_244_353,
+            1_000_000_007,
+        ] {
+            for a in (0..200).chain([p - 1, p - 2]) {
+                let roots: Vec<u64> = kth_power_residue_solve(a, 2, p);
+                match tonelli_shanks(a, p) {
+                    Some(r) => {
+                        assert_eq!(mod_pow(r, 2, p), a % p);
+                        assert_eq!(roots[0], r.min(p - r) % p);
+                        assert!(roots.contains(&r));
+                    }
+                    None => assert!(roots.is_empty()),
+                }
+            }
+        }
+        assert_eq!(tonelli_shanks(1, 2), Some(1));
+    }
+
+    #[test]
+    fn general_modulus_test() {
+        for m in (1..300_u64).chain([512, 625, 729, 1000, 2310, 3600]) {
+            for k in 1..7 {
+                for a in [0, 1, 2, 3, 4, 8, 9, 16, 27 % m] {
+                    let expected: Vec<u64> =
+                        (0..m).filter(|x| mod_pow(*x, k, m) == a % m).collect();
+                    assert_eq!(kth_roots_mod(a, k, m), expected, "{} {} {}", a, k, m);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn composite_modulus_test() {
+        kth_power_residue_solve(4, 2, 15);
+    }
+}
+
+#[cfg(test)]
+mod order_mod_tests {
+    use bens_number_theory::modular::{
+        euler_totient, gcd, is_primitive_root, multiplicative_order,
+        multiplicative_order_prime_power, order_mod, order_profile,
+    };
+
+    fn brute_order(a: u64, m: u64) -> Option<u64> {
+        if gcd(a % m, m) != 1 {
+            return None;
+        }
+        let mut power: u64 = a % m;
+        let mut k: u64 = 1;
+        while power != 1 % m {
+            power = power * a % m;
+            k += 1;
+        }
+        Some(k)
+    }
+
+    #[test]
+    fn powers_of_three_test() {
+        // 2 is a primitive root modulo every power of 3
+        for k in 1..=40 {
+            let expected: u64 = 2 * 3_u64.pow(k - 1);
+            assert_eq!(multiplicative_order_prime_power(2, 3, k), Some(expected));
+            assert_eq!(order_mod(2, 3_u64.pow(k)), Some(expected));
+        }
+        // 10 has order 3^(k - 2) modulo 3^k, since 10 = 1 + 3^2
+        for k in 2..=40 {
+            assert_eq!(
+                multiplicative_order_prime_power(10, 3, k),
+                Some(3_u64.pow(k - 2))
+            );
+        }
+    }
+
+    #[test]
+    fn order_mod_test() {
+        for m in 1..1000_u64 {
+            for a in 0..m.min(60) {
+                assert_eq!(order_mod(a, m), brute_order(a, m), "{} {}", a, m);
+                assert_eq!(order_mod(a, m), multiplicative_order(a, m));
+            }
+        }
+        assert_eq!(order_mod(3, 0), None);
+        assert_eq!(order_mod(2, 1_000_000_007), Some(500_000_003));
+    }
+
+    #[test]
+    fn order_profile_test() {
+        for m in 1..2000_u64 {
+            for a in (1..m.min(40)).filter(|a| gcd(*a, m) == 1) {
+                let profile: Vec<(u64, u32, u64)> = order_profile(a, m);
+                let mut product: u64 = 1;
+                let mut lcm: u64 = 1;
+                for (p, e, order) in profile {
+                    product *= p.pow(e);
+                    assert_eq!(Some(order), order_mod(a, p.pow(e)));
+                    lcm = lcm / gcd(lcm, order) * order;
+                }
+                assert_eq!(product, m);
+                assert_eq!(Some(lcm), order_mod(a, m), "{} {}", a, m);
+            }
+        }
+    }
+
+    #[test]
+    fn primitive_root_test() {
+        for m in 1..500_u64 {
+            let phi: u64 = euler_totient(m);
+            let mut count: u64 = 0;
+            for a in 0..m {
+                let expected: bool = brute_order(a, m) == Some(phi);
+                assert_eq!(is_primitive_root(a, m), expected, "{} {}", a, m);
+                count += expected as u64;
+            }
+            // when primitive roots exist there are phi(phi(m)) of them
+            assert!(count == 0 || count == euler_totient(phi));
+        }
+        assert!(!is_primitive_root(1, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn composite_prime_power_test() {
+        multiplicative_order_prime_power(2, 9, 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn non_coprime_profile_test() {
+        order_profile(6, 9);
+    }
+}
+
+#[cfg(test)]
+mod legendre_tests {
+    use bens_number_theory::modular::{legendre_symbol, mod_pow, sqrt_mod_prime, tonelli_shanks};
+    use bens_number_theory::primes::Prime;
+
+    #[test]
+    fn euler_criterion_test() {
+        for p in (3..500_u64).filter_map(Prime::new) {
+            for a in 0..2 * *p {
+                let power: u64 = mod_pow(a, (*p - 1) / 2, *p);
+                let expected: i8 = if power == *p - 1 { -1 } else { power as i8 };
+                assert_eq!(legendre_symbol(a, p), expected, "{} {}", a, p);
+            }
+        }
+        let two: Prime = Prime::new(2).unwrap();
+        assert_eq!(legendre_symbol(4, two), 0);
+        assert_eq!(legendre_symbol(7, two), 1);
+    }
+
+    #[test]
+    fn sqrt_mod_prime_test() {
+        for p in (2..500_u64).filter_map(Prime::new) {
+            for a in 0..*p {
+                assert_eq!(sqrt_mod_prime(a, p), tonelli_shanks(a, *p));
+                assert_eq!(sqrt_mod_prime(a, p).is_some(), legendre_symbol(a, p) >= 0);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod euler_criterion_tests {
+    use bens_number_theory::modular::{
+        euler_criterion, legendre_symbol, smallest_quadratic_nonresidue,
+    };
+    use bens_number_theory::primes::Prime;
+
+    #[test]
+    fn legendre_agreement_test() {
+        // a small linear congruential generator keeps the inputs reproducible
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = || -> u64 {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            state >> 1
+        };
+        let mut checked: u32 = 0;
+        while checked < 2000 {
+            let candidate: u64 = next();
+            if let Some(p) = Prime::new(candidate % 1_000_000_000_000 + 2) {
+                let a: u64 = next();
+                assert_eq!(euler_criterion(a, *p), legendre_symbol(a, p));
+                assert_eq!(euler_criterion(a % 1000 * *p, *p), 0);
+                checked += 1;
+            }
+        }
+        assert_eq!(euler_criterion(5, 2), 1);
+        assert_eq!(euler_criterion(4, 2), 0);
+    }
+
+    #[test]
+    fn smallest_nonresidue_test() {
+        assert_eq!(smallest_quadratic_nonresidue(3), 2);
+        assert_eq!(smallest_quadratic_nonresidue(5), 2);
+        assert_eq!(smallest_quadratic_nonresidue(7), 3);
+        assert_eq!(smallest_quadratic_nonresidue(41), 3);
+        assert_eq!(smallest_quadratic_nonresidue(1_000_000_007), 5);
+        let primes: Vec<u64> = (3..100_000).filter(|n| Prime::new(*n).is_some()).collect();
+        let mut total: u64 = 0;
+        for p in &primes {
+            let q: u64 = smallest_quadratic_nonresidue(*p);
+            assert_eq!(euler_criterion(q, *p), -1);
+            assert!((2..q).all(|a| euler_criterion(a, *p) == 1));
+            total += q;
+        }
+        let average: f64 = total as f64 / primes.len() as f64;
+        assert!(average > 3.0 && average < 4.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn composite_modulus_test() {
+        euler_criterion(2, 15);
+    }
+
+    #[test]
+    #[should_panic]
+    fn even_prime_nonresidue_test() {
+        smallest_quadratic_nonresidue(2);
+    }
+}
+
+#[cfg(test)]
+mod kronecker_tests {
+    use bens_number_theory::modular::{jacobi_symbol, kronecker_symbol};
+
+    #[test]
+    fn jacobi_agreement_test() {
+        for n in (1..300_i64).step_by(2) {
+            for a in -300..300_i64 {
+                let expected: i8 = jacobi_symbol(a.rem_euclid(n) as u64, n as u64);
+                assert_eq!(kronecker_symbol(a, n), expected, "{} {}", a, n);
+            }
+        }
+    }
+
+    #[test]
+    fn two_table_test() {
+        let table: [i8; 8] = [0, 1, 0, -1, 0, -1, 0, 1];
+        for a in -100..100_i64 {
+            assert_eq!(
+                kronecker_symbol(a, 2),
+                table[a.rem_euclid(8) as usize],
+                "{}",
+                a
+            );
+        }
+    }
+
+    #[test]
+    fn special_values_test() {
+        assert_eq!(kronecker_symbol(1, 0), 1);
+        assert_eq!(kronecker_symbol(-1, 0), 1);
+        assert_eq!(kronecker_symbol(2, 0), 0);
+        assert_eq!(kronecker_symbol(0, 1), 1);
+        assert_eq!(kronecker_symbol(0, -1), 1);
+        assert_eq!(kronecker_symbol(-5, -1), -1);
+        assert_eq!(kronecker_symbol(5, -1), 1);
+        assert_eq!(kronecker_symbol(3, i64::MIN), -1); // (3|2)^63, and 3 is positive
+        assert_eq!(kronecker_symbol(-3, i64::MIN), 1); // (-3|-1)(-3|2)^63 = (-1)(-1)
+        assert_eq!(kronecker_symbol(i64::MIN, 3), kronecker_symbol(1, 3));
+    }
+
+    #[test]
+    fn multiplicativity_test() {
+        // a small linear congruential generator keeps the inputs reproducible
+        let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut next = || -> i64 {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            ((state >> 33) as i64 % 20_001) - 10_000
+        };
+        for _ in 0..20_000 {
+            let (a, b, n, m): (i64, i64, i64, i64) = (next(), next(), next(), next());
+            assert_eq!(
+                kronecker_symbol(a * b, n),
+                kronecker_symbol(a, n) * kronecker_symbol(b, n),
+                "{} {} {}",
+                a,
+                b,
+                n
+            );
+            if n == 0 || m == 0 {
+                // (a|0) is not multiplicative with the other factors when a = -1
+                continue;
+            }
+            assert_eq!(
+                kronecker_symbol(a, n * m),
+                kronecker_symbol(a, n) * kronecker_symbol(a, m),
+                "{} {} {}",
+                a,
+                n,
+                m
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod coprime_tests {
+    use bens_number_theory::modular::{
+        are_coprime, coprimes_below, count_coprime_in_range, euler_totient, gcd,
+    };
+
+    #[test]
+    fn are_coprime_test() {
+        for a in 0..200_u64 {
+            for b in 0..200_u64 {
+                assert_eq!(are_coprime(a, b), gcd(a, b) == 1);
+            }
+        }
+    }
+
+    #[test]
+    fn coprimes_below_test() {
+        for n in 2..=1000_u64 {
+            let expected: Vec<u64> = (1..n).filter(|k| gcd(*k, n) == 1).collect();
+            let coprimes: Vec<u64> = coprimes_below(n).collect();
+            assert_eq!(coprimes.len() as u64, euler_totient(n));
+            assert_eq!(coprimes, expected, "{}", n);
+        }
+        assert_eq!(coprimes_below(0).count(), 0);
+        assert_eq!(coprimes_below(1).count(), 0);
+    }
+
+    #[test]
+    fn small_range_test() {
+        for n in 0..60_u64 {
+            for lo in 0..70_u64 {
+                for hi in lo.saturating_sub(3)..80 {
+                    let expected: u64 = (lo..hi).filter(|k| gcd(*k, n) == 1).count() as u64;
+                    assert_eq!(
+                        count_coprime_in_range(lo, hi, n),
+                        expected,
+                        "{} {} {}",
+                        lo,
+                        hi,
+                        n
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn large_range_test() {
+        // 47# has 15 distinct prime factors, the most a u64 can have
+        let primorials: [u64; 3] = [6_469_693_230, 200_560_490_130, 614_889_782_588_491_410];
+        for n in primorials {
+            assert_eq!(count_coprime_in_range(0, n, n), euler_totient(n));
+            assert_eq!(count_coprime_in_range(n, 2 * n, n), euler_totient(n));
+            for lo in [1_u64, 999_999_000_000, n - 500] {
+                let expected: u64 = (lo..lo + 1000).filter(|k| gcd(*k, n) == 1).count() as u64;
+                assert_eq!(count_coprime_in_range(lo, lo + 1000, n), expected);
+            }
+        }
+        let n: u64 = 1_000_000_007 * 3 * 3;
+        let lo: u64 = 999_999_999_000;
+        let expected: u64 = (lo..lo + 5000).filter(|k| gcd(*k, n) == 1).count() as u64;
+        assert_eq!(count_coprime_in_range(lo, lo + 5000, n), expected);
+        assert_eq!(count_coprime_in_range(0, u64::MAX, 2), (1 << 63) - 1);
+    }
+}
+
+#[cfg(test)]
+mod jordan_totient_tests {
+    use bens_number_theory::modular::{dedekind_psi, euler_totient, gcd, jordan_totient};
+
+    #[test]
+    fn first_values_test() {
+        let j2: Vec<u128> = (1..=10).map(|n| jordan_totient(n, 2)).collect();
+        assert_eq!(j2, vec![1, 3, 8, 12, 24, 24, 48, 48, 72, 72]);
+        let psi: Vec<u64> = (1..=10).map(dedekind_psi).collect();
+        assert_eq!(psi, vec![1, 3, 4, 6, 6, 12, 8, 12, 12, 18]);
+        assert_eq!(jordan_totient(0, 3), 0);
+        assert_eq!(jordan_totient(1, 0), 1);
+        assert_eq!(jordan_totient(12, 0), 0);
+        assert_eq!(dedekind_psi(0), 0);
+    }
+
+    #[test]
+    fn euler_totient_agreement_test() {
+        let mut x: u64 = 1;
+        for _ in 0..10_000 {
+            x = x
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            let n: u64 = x >> 24;
+            assert_eq!(jordan_totient(n, 1), euler_totient(n) as u128);
+        }
+    }
+
+    #[test]
+    fn brute_force_test() {
+        // J_2(n) counts the pairs (a, b) with gcd(a, b, n) = 1
+        for n in 1..60_u64 {
+            let pairs: u128 = (1..=n)
+                .flat_map(|a| (1..=n).map(move |b| gcd(gcd(a, b), n)))
+                .filter(|g| *g == 1)
+                .count() as u128;
+            assert_eq!(jordan_totient(n, 2), pairs);
+        }
+    }
+
+    #[test]
+    fn multiplicativity_test() {
+        for m in 1..80_u64 {
+            for n in 1..80_u64 {
+                if gcd(m, n) != 1 {
+                    continue;
+                }
+                for k in 0..5 {
+                    assert_eq!(
+                        jordan_totient(m * n, k),
+                        jordan_totient(m, k) * jordan_totient(n, k)
+                    );
+                }
+                assert_eq!(dedekind_psi(m * n), dedekind_psi(m) * dedekind_psi(n));
+            }
+        }
+    }
+
+    #[test]
+    fn large_values_test() {
+        assert_eq!(jordan_totient(u64::MAX, 1), euler_totient(u64::MAX) as u128);
+        // 2^63 has psi = 3 * 2^62
+        assert_eq!(dedekind_psi(1 << 63), 3 << 62);
+        assert_eq!(jordan_totient(1 << 63, 2), 3 << 124);
+    }
+
+    #[test]
+    #[should_panic]
+    fn jordan_overflow_test() {
+        jordan_totient(3, 81);
+    }
+
+    #[test]
+    #[should_panic]
+    fn psi_overflow_test() {
+        // psi(n) > n for every n > 1, and here it exceeds 2^64
+        dedekind_psi(u64::MAX - 1);
+    }
+}
+
+#[cfg(test)]
+mod binomial_mod_tests {
+    use bens_number_theory::modular::{binomial_mod, binomial_mod_prime_power, mod_inverse};
+    use num::{BigInt, ToPrimitive};
+
+    fn exact_binomial(n: u64, k: u64) -> BigInt {
+        let mut value: BigInt = BigInt::from(1);
+        let mut i: u64 = 0;
+        while i < k {
+            value = value * BigInt::from(n - i) / BigInt::from(i + 1);
+            i += 1;
+        }
+        value
+    }
+
+    // n! with the factors of p removed, and the exponent of p, by multiplying every number up to n
+    fn slow_factorial(n: u64, p: u64, modulus: u64) -> (u64, u64) {
+        let mut value: u128 = 1;
+        let mut exponent: u64 = 0;
+        let mut i: u64 = 2;
+        while i <= n {
+            let mut m: u64 = i;
+            while m.is_multiple_of(p) {
+                m /= p;
+                exponent += 1;
+            }
+            value = value * m as u128 % modulus as u128;
+            i += 1;
+        }
+        (value as u64, exponent)
+    }
+
+    fn slow_binomial_mod_prime_power(n: u64, k: u64, p: u64, e: u32) -> u64 {
+        let modulus: u64 = p.pow(e);
+        let (a, x): (u64, u64) = slow_factorial(n, p, modulus);
+        let (b, y): (u64, u64) = slow_factorial(k, p, modulus);
+        let (c, z): (u64, u64) = slow_factorial(n - k, p, modulus);
+        let v: u64 = x - y - z;
+        if v >= e as u64 {
+            return 0;
+        }
+        let inverse: u64 =
+            mod_inverse((b as u128 * c as u128 % modulus as u128) as u64, modulus).unwrap();
+        (a as u128 * inverse as u128 % modulus as u128 * p.pow(v as u32) as u128 % modulus as u128)
+            as u64
+    }
+
+    #[test]
+    fn exact_binomial_test() {
+        for m in [
+            1_u64,
+            2,
+            8,
+            9,
+            10,
+            12,
+            27,
+            64,
+            100,
+            243,
+            360,
+            1 << 20,
+            1_000_000_007,
+            3_u64.pow(40),
+        ] {
+            for n in (0..120_u64).step_by(7) {
+                for k in 0..=n {
+                    let expected: u64 = (exact_binomial(n, k) % BigInt::from(m)).to_u64().unwrap();
+                    assert_eq!(binomial_mod(n, k, m), expected, "C({n}, {k}) mod {m}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn prime_power_test() {
+        for (p, e) in [
+            (2_u64, 1_u32),
+            (2, 5),
+            (2, 63),
+            (3, 4),
+            (5, 3),
+            (7, 2),
+            (13, 1),
+            (101, 2),
+        ] {
+            for n in [0_u64, 1, 50, 99, 100, 257] {
+                for k in 0..=n {
+                    let expected: BigInt = exact_binomial(n, k) % BigInt::from(p).pow(e);
+                    assert_eq!(
+                        binomial_mod_prime_power(n, k, p, e),
+                        expected.to_u64().unwrap()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn slow_path_test() {
+        // 2^30 3^20 with the same parameters shrunk to where every factor can be multiplied out
+        let (n, k): (u64, u64) = (1_000_000, 31_623);
+        let m: u64 = (1 << 30) * 3_u64.pow(20);
+        let two: u64 = slow_binomial_mod_prime_power(n, k, 2, 30);
+        let three: u64 = slow_binomial_mod_prime_power(n, k, 3, 20);
+        assert_eq!(binomial_mod_prime_power(n, k, 2, 30), two);
+        assert_eq!(binomial_mod_prime_power(n, k, 3, 20), three);
+        let value: u64 = binomial_mod(n, k, m);
+        assert_eq!(value % (1 << 30), two);
+        assert_eq!(value % 3_u64.pow(20), three);
+        assert_eq!(
+            binomial_mod_prime_power(400_000, 123_456, 5, 9),
+            slow_binomial_mod_prime_power(400_000, 123_456, 5, 9)
+        );
+    }
+
+    #[test]
+    fn huge_parameters_test() {
+        let (n, k): (u64, u64) = (1_000_000_000_000_000_000, 1_000_000_000);
+        let m: u64 = (1 << 30) * 3_u64.pow(20);
+        let value: u64 = binomial_mod(n, k, m);
+        assert_ne!(value, 0);
+        // Pascal's rule and symmetry
+        let left: u64 = binomial_mod(n - 1, k - 1, m);
+        let right: u64 = binomial_mod(n - 1, k, m);
+        assert_eq!((left as u128 + right as u128) % m as u128, value as u128);
+        assert_eq!(binomial_mod(n, n - k, m), value);
+        // the powers of 2 and 5 in C(10^18, 10^9) are at least 9
+        assert_eq!(binomial_mod(n, k, 1_000_000_000), 0);
+    }
+
+    #[test]
+    fn k_larger_than_n_test() {
+        assert_eq!(binomial_mod(5, 6, 1000), 0);
+        assert_eq!(binomial_mod_prime_power(0, 1, 2, 10), 0);
+        assert_eq!(binomial_mod(0, 0, 1000), 1);
+        assert_eq!(binomial_mod(7, 3, 1), 0);
+        assert_eq!(binomial_mod_prime_power(7, 3, 5, 0), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_modulus_test() {
+        binomial_mod(5, 2, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn prime_power_overflow_test() {
+        binomial_mod_prime_power(5, 2, 2, 64);
+    }
+}
+
+#[cfg(test)]
+mod mulmod_tests {
+    use bens_number_theory::modular::{addmod, mod_pow, mulmod, submod};
+    use num::{BigInt, ToPrimitive};
+
+    fn boundary_values() -> Vec<u128> {
+        let mut values: Vec<u128> = vec![];
+        for center in [0, 1 << 63, 1 << 64, 1 << 126, 1 << 127, u128::MAX] {
+            for offset in 0..3_u128 {
+                values.push(center.wrapping_sub(offset));
+                values.push(center.wrapping_add(offset));
+            }
+        }
+        values.sort();
+        values.dedup();
+        values
+    }
+
+    fn random_values(count: usize, seed: u64) -> Vec<u128> {
+        let mut x: u64 = seed;
+        let mut next = || {
+            x = x
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            x
+        };
+        (0..count)
+            .map(|i| {
+                let value: u128 = (next() as u128) << 64 | next() as u128;
+                // spread the sizes from 64 to 128 bits
+                value >> (i % 65)
+            })
+            .collect()
+    }
+
+    fn reference(value: BigInt, m: u128) -> u128 {
+        (((value % BigInt::from(m)) + BigInt::from(m)) % BigInt::from(m))
+            .to_u128()
+            .unwrap()
+    }
+
+    fn check(a: u128, b: u128, m: u128) {
+        let (x, y): (BigInt, BigInt) = (BigInt::from(a), BigInt::from(b));
+        assert_eq!(mulmod(a, b, m), reference(&x * &y, m), "{a} * {b} mod {m}");
+        assert_eq!(addmod(a, b, m), reference(&x + &y, m), "{a} + {b} mod {m}");
+        assert_eq!(submod(a, b, m), reference(&x - &y, m), "{a} - {b} mod {m}");
+    }
+
+    #[test]
+    fn boundary_test() {
+        let values: Vec<u128> = boundary_values();
+        for a in &values {
+            for b in &values {
+                for m in &values {
+                    if *m > 0 {
+                        check(*a, *b, *m);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn top_range_test() {
+        // operands from 2^127 up with moduli close to u128::MAX
+        let values: Vec<u128> = random_values(60, 1)
+            .into_iter()
+            .map(|x| x | 1 << 127)
+            .collect();
+        for a in &values {
+            for b in &values {
+                for m in [u128::MAX, u128::MAX - 1, u128::MAX - 158, *a, *b] {
+                    check(*a, *b, m);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn random_test() {
+        let values: Vec<u128> = random_values(3000, 2);
+        for window in values.windows(3) {
+            check(window[0], window[1], window[2].max(1));
+        }
+    }
+
+    #[test]
+    fn mod_pow_test() {
+        // mod_pow squares through mulmod, so moduli up to u64::MAX work
+        let m: u64 = u64::MAX;
+        let base: u64 = u64::MAX - 1;
+        assert_eq!(
+            mod_pow(base, 3, m),
+            (BigInt::from(base).pow(3) % BigInt::from(m))
+                .to_u64()
+                .unwrap()
+        );
+        assert_eq!(mod_pow(2, 64, u64::MAX), 1);
+        assert_eq!(
+            mod_pow(3, 18_446_744_073_709_551_556, 18_446_744_073_709_551_557),
+            1
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_modulus_test() {
+        mulmod(3, 4, 0);
+    }
+}
+
+#[cfg(test)]
+mod gcd_variant_tests {
+    use bens_number_theory::modular::{gcd, gcd_batch, gcd_binary, gcd_lehmer};
+    use std::time::{Duration, Instant};
+
+    fn euclid(a: u128, b: u128) -> u128 {
+        let (mut a, mut b): (u128, u128) = (a, b);
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        a
+    }
+
+    fn next(state: &mut u128) -> u128 {
+        *state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        *state
+    }
+
+    fn fibonacci_pairs() -> Vec<(u128, u128)> {
+        let mut pairs: Vec<(u128, u128)> = vec![];
+        let (mut f, mut g): (u128, u128) = (0, 1);
+        while let Some(h) = f.checked_add(g) {
+            pairs.push((h, g));
+            (f, g) = (g, h);
+        }
+        pairs
+    }
+
+    #[test]
+    fn random_test() {
+        let mut state: u128 = 183;
+        let mut i: u32 = 0;
+        while i < 20_000 {
+            let shift: u32 = (next(&mut state) >> 121) as u32;
+            let a: u128 = next(&mut state) >> shift;
+            let b: u128 = next(&mut state) >> (next(&mut state) >> 121);
+            let common: u128 = next(&mut state) >> 100;
+            let (a, b): (u128, u128) = (a / (common + 1) * common, b / (common + 1) * common);
+            assert_eq!(gcd_lehmer(a, b), euclid(a, b), "{a} {b}");
+            assert_eq!(gcd_lehmer(b, a), euclid(a, b), "{b} {a}");
+            let (x, y): (u64, u64) = (a as u64 >> (shift / 3), b as u64);
+            let expected: u64 = euclid(x as u128, y as u128) as u64;
+            assert_eq!(gcd_binary(x, y), expected);
+            assert_eq!(gcd(x, y), expected);
+            i += 1;
+        }
+    }
+
+    #[test]
+    fn edge_test() {
+        for (a, b) in [
+            (0, 0),
+            (0, 5),
+            (5, 0),
+            (1, 1),
+            (u64::MAX, 0),
+            (u64::MAX, u64::MAX),
+        ] {
+            assert_eq!(gcd_binary(a, b) as u128, euclid(a as u128, b as u128));
+        }
+        let wide: [u128; 7] = [
+            0,
+            1,
+            u64::MAX as u128,
+            1 << 64,
+            u128::MAX,
+            u128::MAX - 1,
+            (1 << 127) - 1,
+        ];
+        for a in wide {
+            for b in wide {
+                assert_eq!(gcd_lehmer(a, b), euclid(a, b), "{a} {b}");
+            }
+        }
+        assert_eq!(gcd_lehmer(1 << 127, 1 << 100), 1 << 100);
+        assert_eq!(gcd_lehmer(u128::MAX, u128::MAX), u128::MAX);
+    }
+
+    #[test]
+    fn fibonacci_test() {
+        // consecutive Fibonacci numbers take the most Euclidean steps for their size
+        let pairs: Vec<(u128, u128)> = fibonacci_pairs();
+        assert_eq!(pairs.len(), 185);
+        for (f, g) in &pairs {
+            assert_eq!(gcd_lehmer(*f, *g), 1);
+            if let Some(triple) = f.checked_mul(3) {
+                assert_eq!(gcd_lehmer(triple, *g * 3), 3);
+            }
+            if *f <= u64::MAX as u128 {
+                assert_eq!(gcd_binary(*f as u64, *g as u64), 1);
+            }
+        }
+        let (f, g): (u128, u128) = pairs[pairs.len() - 1];
+        assert_eq!(gcd_lehmer(f, f - g), euclid(f, f - g));
+    }
+
+    #[test]
+    fn batch_test() {
+        let mut state: u128 = 1830;
+        let pairs: Vec<(u64, u64)> = (0..1000)
+            .map(|_| {
+                let a: u64 = (next(&mut state) >> 64) as u64;
+                (a >> (a % 64), (next(&mut state) >> 80) as u64 * 6)
+            })
+            .collect();
+        let expected: Vec<u64> = pairs.iter().map(|(a, b)| gcd(*a, *b)).collect();
+        assert_eq!(gcd_batch(&pairs), expected);
+        assert!(gcd_batch(&[]).is_empty());
+    }
+
+    #[test]
+    #[cfg_attr(
+        debug_assertions,
+        ignore = "timing only means something in release mode"
+    )]
+    fn fibonacci_speed_test() {
+        let pairs: Vec<(u128, u128)> = fibonacci_pairs();
+        let (f, g): (u128, u128) = pairs[pairs.len() - 1];
+        let start: Instant = Instant::now();
+        let mut total: u128 = 0;
+        let mut i: u128 = 0;
+        while i < 100_000 {
+            total += gcd_lehmer(std::hint::black_box(f), std::hint::black_box(g));
+            i += 1;
+        }
+        assert_eq!(total, 100_000);
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+}
+
+#[cfg(test)]
+mod covering_system_tests {
+    use bens_number_theory::modular::{find_uncovered, is_covering_system};
+
+    fn covered(x: u64, congruences: &[(u64, u64)]) -> bool {
+        congruences.iter().any(|(a, m)| x % m == a % m)
+    }
+
+    fn lcm(congruences: &[(u64, u64)]) -> u64 {
+        congruences.iter().fold(1, |l, (_, m)| {
+            let (mut a, mut b): (u64, u64) = (l, *m);
+            while b != 0 {
+                (a, b) = (b, a % b);
+            }
+            l / a * m
+        })
+    }
+
+    fn brute_force(congruences: &[(u64, u64)]) -> Option<u64> {
+        (0..lcm(congruences)).find(|x| !covered(*x, congruences))
+    }
+
+    #[test]
+    fn classic_cover_test() {
+        let cover: [(u64, u64); 5] = [(0, 2), (0, 3), (1, 4), (5, 6), (7, 12)];
+        assert!(is_covering_system(&cover));
+        assert_eq!(find_uncovered(&cover), None);
+        // without any one congruence the rest leave a gap
+        for i in 0..cover.len() {
+            let mut rest: Vec<(u64, u64)> = cover.to_vec();
+            rest.remove(i);
+            let witness: u64 = find_uncovered(&rest).unwrap();
+            assert!(!covered(witness, &rest), "{i}");
+            assert!(covered(witness, &cover[i..=i]), "{i}");
+            assert_eq!(Some(witness), brute_force(&rest), "{i}");
+            assert!(!is_covering_system(&rest));
+        }
+    }
+
+    #[test]
+    fn sierpinski_cover_test() {
+        // the covering behind the Sierpinski number 78557 uses these moduli
+        let cover: [(u64, u64); 6] = [(0, 2), (0, 3), (1, 4), (3, 8), (7, 12), (23, 24)];
+        assert!(is_covering_system(&cover));
+        assert_eq!(find_uncovered(&cover[..5]), Some(23));
+    }
+
+    #[test]
+    fn small_systems_test() {
+        assert_eq!(find_uncovered(&[]), Some(0));
+        assert!(!is_covering_system(&[]));
+        assert!(is_covering_system(&[(5, 1)]));
+        assert!(is_covering_system(&[(0, 2), (1, 2)]));
+        assert!(is_covering_system(&[(0, 3), (4, 3), (8, 3)]));
+        assert_eq!(find_uncovered(&[(0, 3), (4, 3)]), Some(2));
+        // repeated and redundant congruences
+        assert_eq!(find_uncovered(&[(1, 2), (1, 2), (3, 4)]), Some(0));
+        assert_eq!(find_uncovered(&[(0, 2), (0, 4), (1, 4)]), Some(3));
+    }
+
+    #[test]
+    fn brute_force_test() {
+        let mut state: u64 = 7;
+        let mut next = move || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            state >> 33
+        };
+        let moduli: [u64; 14] = [2, 3, 4, 5, 6, 8, 9, 10, 12, 15, 16, 18, 20, 24];
+        let mut covers: u32 = 0;
+        for _ in 0..3000 {
+            let count: u64 = next() % 9 + 1;
+            let congruences: Vec<(u64, u64)> = (0..count)
+                .map(|_| {
+                    let m: u64 = moduli[(next() % moduli.len() as u64) as usize];
+                    (next() % (3 * m), m)
+                })
+                .collect();
+            let expected: Option<u64> = brute_force(&congruences);
+            assert_eq!(find_uncovered(&congruences), expected, "{congruences:?}");
+            if expected.is_none() {
+                covers += 1;
+            }
+        }
+        assert!(covers > 10);
+    }
+
+    #[test]
+    fn large_moduli_test() {
+        // the least common multiple is far too big to walk through
+        let mut congruences: Vec<(u64, u64)> = vec![(0, 2), (1, 2)];
+        let mut p: u64 = 1_000_003;
+        for q in [1_000_033, 1_000_037, 1_000_039] {
+            congruences.push((7, p * q));
+            p = q;
+        }
+        assert!(is_covering_system(&congruences));
+        congruences.remove(1);
+        assert_eq!(find_uncovered(&congruences), Some(1));
+        assert_eq!(
+            find_uncovered(&[(0, 1_000_003), (1, 1_000_033), (2, 999_983)]),
+            Some(3)
+        );
+        // the classic cover with every modulus multiplied by a large prime covers only the multiples of it
+        let scaled: Vec<(u64, u64)> = [(0, 2), (0, 3), (1, 4), (5, 6), (7, 12)]
+            .iter()
+            .map(|(a, m)| (a * 1_000_003, m * 1_000_003))
+            .collect();
+        assert_eq!(find_uncovered(&scaled), Some(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_modulus_test() {
+        find_uncovered(&[(0, 2), (1, 0)]);
+    }
+}
+
+#[cfg(test)]
+mod fermat_quotient_tests {
+    use bens_number_theory::modular::{fermat_quotient, is_wieferich_to_base, wall_sun_sun_check};
+    use bens_number_theory::primes::generate_primes;
+    use num::BigUint;
+
+    #[test]
+    fn definition_test() {
+        for p in generate_primes(600_u64) {
+            let mut a: u64 = 1;
+            while a < 40 {
+                if !a.is_multiple_of(p) {
+                    let power: BigUint = BigUint::from(a).pow(p as u32 - 1);
+                    let expected: BigUint = (power - 1_u32) / p % p;
+                    assert_eq!(BigUint::from(fermat_quotient(a, p)), expected, "{a} {p}");
+                }
+                a += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn wieferich_pairs_test() {
+        for (p, a) in [
+            (1093, 2),
+            (3511, 2),
+            (11, 3),
+            (1_006_003, 3),
+            (487, 10),
+            (5, 7),
+        ] {
+            assert_eq!(fermat_quotient(a, p), 0, "{p} {a}");
+            assert!(is_wieferich_to_base(p, a), "{p} {a}");
+        }
+        let primes: Vec<u64> = generate_primes(100_000_u64);
+        let expected: [(u64, Vec<u64>); 5] = [
+            (2, vec![1093, 3511]),
+            (3, vec![11]),
+            (5, vec![2, 20771, 40487]),
+            (7, vec![5]),
+            (10, vec![3, 487]),
+        ];
+        for (a, wieferich) in expected {
+            let found: Vec<u64> = primes
+                .iter()
+                .copied()
+                .filter(|p| is_wieferich_to_base(*p, a))
+                .collect();
+            assert_eq!(found, wieferich, "{a}");
+        }
+        // a prime dividing the base is never a Wieferich prime to it
+        assert!(!is_wieferich_to_base(7, 14));
+    }
+
+    #[test]
+    fn large_prime_test() {
+        // p^2 no longer fits in a u64
+        assert_eq!(fermat_quotient(2, 4_294_967_311), 1_244_670_419);
+        assert_eq!(
+            fermat_quotient(2, 18_446_744_073_709_551_557),
+            15_030_232_386_713_887_116
+        );
+        assert_eq!(
+            fermat_quotient(3, 18_446_744_073_709_551_557),
+            8_151_936_860_970_011_893
+        );
+        assert!(!wall_sun_sun_check(18_446_744_073_709_551_557));
+    }
+
+    #[test]
+    fn wall_sun_sun_test() {
+        let found: Vec<u64> = generate_primes(1_000_000_u64)
+            .into_iter()
+            .filter(|p| wall_sun_sun_check(*p))
+            .collect();
+        assert_eq!(found, vec![]);
+        assert!(!wall_sun_sun_check(2)); // F_3 = 2
+        assert!(!wall_sun_sun_check(3)); // F_4 = 3
+        assert!(!wall_sun_sun_check(5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn divisible_base_test() {
+        fermat_quotient(14, 7);
+    }
+
+    #[test]
+    #[should_panic]
+    fn composite_fermat_test() {
+        fermat_quotient(2, 15);
+    }
+
+    #[test]
+    #[should_panic]
+    fn composite_wall_sun_sun_test() {
+        wall_sun_sun_check(21);
+    }
+}
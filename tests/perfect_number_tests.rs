@@ -18,3 +18,202 @@ mod general_tests {
         assert!(!is_perfect_number(8130));
     }
 }
+
+#[cfg(test)]
+mod semiperfect_tests {
+    use bens_number_theory::divisor_functions::divisor_sum;
+    use bens_number_theory::perfect_numbers::{
+        divisors, is_semiperfect, is_weird, weird_numbers_below,
+    };
+
+    #[test]
+    fn small_divisors_test() {
+        assert_eq!(divisors(1), vec![1]);
+        assert_eq!(divisors(2), vec![1, 2]);
+        assert_eq!(divisors(4), vec![1, 2, 4]);
+        assert_eq!(divisors(36), vec![1, 2, 3, 4, 6, 9, 12, 18, 36]);
+    }
+
+    #[test]
+    fn semiperfect_test() {
+        assert!(is_semiperfect(12));
+        assert!(is_semiperfect(6));
+        assert!(is_semiperfect(20));
+        assert!(!is_semiperfect(0));
+        assert!(!is_semiperfect(1));
+        assert!(!is_semiperfect(7));
+    }
+
+    #[test]
+    fn large_semiperfect_test() {
+        // deficient and perfect numbers need no bitset, however large
+        assert!(!is_semiperfect(999_999_999_989));
+        assert!(is_semiperfect(2_305_843_008_139_952_128)); // 2^30 (2^31 - 1)
+        assert!(!is_weird(2_305_843_008_139_952_128));
+        // 2n does not fit in a u64
+        assert!(!is_semiperfect(1 << 63));
+        assert!(!is_weird(1 << 63));
+    }
+
+    #[test]
+    #[should_panic]
+    fn semiperfect_too_large_test() {
+        // abundant, so it would take a bitset of 10^12 bits
+        is_semiperfect(1_000_000_000_000);
+    }
+
+    #[test]
+    fn abundant_below_70_semiperfect_test() {
+        for n in 1..70 {
+            if divisor_sum(n) > 2 * n {
+                assert!(is_semiperfect(n));
+            }
+        }
+    }
+
+    #[test]
+    fn weird_test() {
+        assert!(is_weird(70));
+        assert!(is_weird(836));
+        assert!(!is_weird(12));
+        assert_eq!(
+            weird_numbers_below(10000),
+            vec![70, 836, 4030, 5830, 7192, 7912, 9272]
+        );
+    }
+}
+
+#[cfg(test)]
+mod multiperfect_tests {
+    use bens_number_theory::perfect_numbers::{
+        generate_even_perfect_numbers, is_multiperfect, multiperfect_numbers_below,
+    };
+    use bens_number_theory::primes::generate_primes;
+
+    #[test]
+    fn multiperfect_test() {
+        assert_eq!(is_multiperfect(120), Some(3));
+        assert_eq!(is_multiperfect(672), Some(3));
+        assert_eq!(is_multiperfect(30240), Some(4));
+        assert_eq!(is_multiperfect(0), None);
+        assert_eq!(is_multiperfect(1), Some(1));
+        assert_eq!(is_multiperfect(12), None);
+    }
+
+    #[test]
+    fn multiperfect_perfect_and_prime_test() {
+        for n in generate_even_perfect_numbers(10) {
+            assert_eq!(is_multiperfect(n), Some(2));
+        }
+        for p in generate_primes(1000_u64) {
+            assert_eq!(is_multiperfect(p), None);
+        }
+    }
+
+    #[test]
+    fn multiperfect_numbers_below_test() {
+        assert_eq!(
+            multiperfect_numbers_below(40000),
+            vec![
+                (1, 1),
+                (6, 2),
+                (28, 2),
+                (120, 3),
+                (496, 2),
+                (672, 3),
+                (8128, 2),
+                (30240, 4),
+                (32760, 4)
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+mod abundancy_tests {
+    use bens_number_theory::perfect_numbers::{
+        abundancy_index, friendly_pair, solitary_certificate,
+    };
+
+    #[test]
+    fn abundancy_index_test() {
+        assert_eq!(abundancy_index(1), (1, 1));
+        assert_eq!(abundancy_index(6), (2, 1));
+        assert_eq!(abundancy_index(12), (7, 3));
+        assert_eq!(abundancy_index(120), (3, 1));
+        assert_eq!(abundancy_index(7), (8, 7));
+    }
+
+    #[test]
+    #[should_panic]
+    fn abundancy_index_zero() {
+        abundancy_index(0);
+    }
+
+    #[test]
+    fn friendly_pair_test() {
+        assert!(friendly_pair(6, 28));
+        assert!(friendly_pair(30, 140));
+        assert!(friendly_pair(80, 200)); // both 93/40
+        assert!(!friendly_pair(10, 20));
+    }
+
+    #[test]
+    fn solitary_certificate_test() {
+        // every prime power p^k satisfies it, since sigma(p^k) = 1 + p + ... + p^k
+        for n in [2, 3, 4, 5, 8, 9, 25, 27, 49] {
+            assert!(solitary_certificate(n));
+        }
+        // 10 is believed to be solitary, but gcd(10, 18) = 2 so the certificate can't show it
+        assert!(!solitary_certificate(10));
+        assert!(!solitary_certificate(6));
+        assert!(!solitary_certificate(0));
+    }
+}
+
+#[cfg(test)]
+mod perfect_totient_tests {
+    use bens_number_theory::perfect_numbers::{is_perfect_totient, perfect_totient_numbers_below};
+
+    #[test]
+    fn first_perfect_totients_test() {
+        let first: Vec<u64> = vec![3, 9, 15, 27, 39, 81, 111, 183, 243, 255];
+        assert_eq!(perfect_totient_numbers_below(256), first);
+        for n in 0..256 {
+            assert_eq!(is_perfect_totient(n), first.contains(&n));
+        }
+    }
+
+    #[test]
+    fn powers_of_three_test() {
+        let found: Vec<u64> = perfect_totient_numbers_below(1_000_000);
+        let mut power: u64 = 3;
+        while power < 1_000_000 {
+            assert!(is_perfect_totient(power));
+            assert!(found.contains(&power));
+            power *= 3;
+        }
+    }
+
+    #[test]
+    fn count_below_ten_thousand_test() {
+        // A082897 has 22 terms below 10^4
+        let found: Vec<u64> = perfect_totient_numbers_below(10_000);
+        assert_eq!(found.len(), 22);
+        assert_eq!(found[21], 8751);
+        for n in found {
+            assert!(is_perfect_totient(n));
+        }
+    }
+}
+
+#[cfg(test)]
+mod unitary_perfect_tests {
+    use bens_number_theory::perfect_numbers::is_unitary_perfect;
+
+    #[test]
+    fn is_unitary_perfect_test() {
+        let found: Vec<u64> = (0..100_000).filter(|n| is_unitary_perfect(*n)).collect();
+        assert_eq!(found, vec![6, 60, 90, 87_360]);
+    }
+}
@@ -1,4 +1,5 @@
 #[cfg(test)]
+#[allow(deprecated)]
 mod is_prime_tests {
     use bens_number_theory::primes::{generate_primes, is_prime, is_prime_lazy, is_prime_list};
 
@@ -102,6 +103,7 @@ mod is_prime_tests {
 
 #[cfg(test)]
 mod generate_primes_tests {
+    use bens_number_theory::estimate::prime_count_upper_bound;
     use bens_number_theory::primes::generate_primes;
 
     #[test]
@@ -170,6 +172,14 @@ mod generate_primes_tests {
         // Test case for generating primes with a large limit
         assert_eq!(generate_primes(100000).len(), 9592); // There are 9592 primes up to 100000
     }
+
+    #[test]
+    fn generate_primes_capacity_test() {
+        // the output is allocated once, with room for the bound on the number of primes
+        let primes: Vec<u64> = generate_primes(100_000_u64);
+        assert_eq!(primes.len(), 9592);
+        assert_eq!(primes.capacity() as u64, prime_count_upper_bound(100_000));
+    }
 }
 
 #[cfg(test)]
@@ -325,3 +335,2805 @@ mod is_mersenne_prime_tests {
         assert!(!is_mersenne_prime((2_u128.pow(30)) - 1));
     }
 }
+
+#[cfg(test)]
+mod is_probable_prime_tests {
+    use bens_number_theory::primes::{generate_primes, is_probable_prime};
+    use num::BigInt;
+    use std::str::FromStr;
+
+    #[test]
+    fn probable_prime_small_test() {
+        let primes: Vec<i32> = generate_primes(10000);
+        for n in -10..10000 {
+            assert_eq!(is_probable_prime(&BigInt::from(n)), primes.contains(&n));
+        }
+    }
+
+    #[test]
+    fn probable_prime_pseudoprimes_test() {
+        // strong pseudoprimes to the first few prime bases
+        assert!(!is_probable_prime(&BigInt::from(2047_u64)));
+        assert!(!is_probable_prime(&BigInt::from(3215031751_u64)));
+        assert!(!is_probable_prime(&BigInt::from(3825123056546413051_u64)));
+        assert!(!is_probable_prime(
+            &BigInt::from_str("318665857834031151167461").unwrap()
+        ));
+    }
+
+    #[test]
+    fn probable_prime_large_test() {
+        assert!(is_probable_prime(&BigInt::from(18446744073709551557_u64))); // largest u64 prime
+        assert!(is_probable_prime(
+            &((BigInt::from(1) << 521) - BigInt::from(1))
+        ));
+        assert!(!is_probable_prime(
+            &((BigInt::from(1) << 523) - BigInt::from(1))
+        ));
+    }
+}
+
+#[cfg(test)]
+#[allow(deprecated)]
+mod chebyshev_tests {
+    use bens_number_theory::factorization::as_perfect_power;
+    use bens_number_theory::primes::{
+        chebyshev_psi, chebyshev_theta, is_prime_lazy, von_mangoldt, von_mangoldt_exact,
+    };
+
+    #[test]
+    fn von_mangoldt_exact_test() {
+        for n in 2..20000_u64 {
+            let expected: Option<(u64, u32)> = if is_prime_lazy(n) {
+                Some((n, 1))
+            } else {
+                match as_perfect_power(n) {
+                    // the base of the largest power is a prime exactly when `n` is a prime power
+                    Some((b, k)) if is_prime_lazy(b) => Some((b, k)),
+                    _ => None,
+                }
+            };
+            assert_eq!(von_mangoldt_exact(n), expected);
+        }
+        assert_eq!(von_mangoldt_exact(0), None);
+        assert_eq!(von_mangoldt_exact(1 << 63), Some((2, 63)));
+    }
+
+    #[test]
+    fn von_mangoldt_test() {
+        assert_eq!(von_mangoldt(1), 0.0);
+        assert_eq!(von_mangoldt(6), 0.0);
+        assert_eq!(von_mangoldt(49), 7_f64.ln());
+        assert_eq!(von_mangoldt(1024), 2_f64.ln());
+    }
+
+    #[test]
+    fn chebyshev_small_values_test() {
+        assert_eq!(chebyshev_theta(0), 0.0);
+        assert_eq!(chebyshev_theta(1), 0.0);
+        assert_eq!(chebyshev_psi(1), 0.0);
+        assert!((chebyshev_theta(2) - 2_f64.ln()).abs() < 1e-12);
+        assert!((chebyshev_psi(100) - 94.045).abs() < 0.001);
+        assert!((chebyshev_theta(100) - 83.728).abs() < 0.001);
+    }
+
+    #[test]
+    fn psi_is_sum_of_von_mangoldt_test() {
+        let mut sum: f64 = 0.0;
+        for n in 1..=3000_u64 {
+            sum += von_mangoldt(n);
+            if n % 100 == 0 {
+                assert!((chebyshev_psi(n) - sum).abs() < 1e-8);
+            }
+        }
+    }
+
+    #[test]
+    fn theta_ratio_trend_test() {
+        // theta(x) / x tends to 1 by the prime number theorem
+        let mut previous_error: f64 = f64::INFINITY;
+        for x in [100, 10_000, 1_000_000, 10_000_000] {
+            let error: f64 = (chebyshev_theta(x) / x as f64 - 1.0).abs();
+            assert!(error < previous_error);
+            previous_error = error;
+        }
+        assert!(previous_error < 0.001);
+        assert!(chebyshev_theta(1_000_000) < chebyshev_psi(1_000_000));
+    }
+}
+
+#[cfg(test)]
+mod ramanujan_prime_tests {
+    use bens_number_theory::primes::{generate_primes, is_ramanujan_prime, ramanujan_primes};
+
+    #[test]
+    fn first_ramanujan_primes_test() {
+        assert_eq!(
+            ramanujan_primes(10),
+            vec![2, 11, 17, 29, 41, 47, 59, 67, 71, 97]
+        );
+        assert_eq!(ramanujan_primes(0), vec![]);
+        assert_eq!(ramanujan_primes(1), vec![2]);
+    }
+
+    #[test]
+    fn ramanujan_primes_definition_test() {
+        let ramanujan: Vec<u64> = ramanujan_primes(1000);
+        let primes: Vec<u64> = generate_primes(30000_u64);
+        let pi = |x: u64| primes.iter().filter(|p| **p <= x).count();
+        for (i, r) in ramanujan.iter().enumerate() {
+            let n: usize = i + 1;
+            // R_n lies between the n-th prime and the 3n-th prime
+            assert!(*r >= primes[n - 1]);
+            assert!(*r < primes[3 * n - 1]);
+            if n <= 100 {
+                assert_eq!(pi(*r - 1) - pi((*r - 1) / 2), n - 1);
+                for x in *r..*r + 200 {
+                    assert!(pi(x) - pi(x / 2) >= n);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn is_ramanujan_prime_test() {
+        let ramanujan: Vec<u64> = ramanujan_primes(100);
+        for p in 1..=*ramanujan.last().unwrap() {
+            assert_eq!(is_ramanujan_prime(p), ramanujan.contains(&p));
+        }
+    }
+}
+
+#[cfg(test)]
+mod chen_prime_tests {
+    use bens_number_theory::primes::{chen_primes_below, is_chen_prime};
+
+    #[test]
+    fn chen_primes_test() {
+        for p in [2, 3, 5, 7, 11, 13] {
+            assert!(is_chen_prime(p));
+        }
+        for n in [0, 1, 4, 9, 43, 61] {
+            assert!(!is_chen_prime(n));
+        }
+        assert!(!is_chen_prime(u64::MAX));
+        assert!(!is_chen_prime(u64::MAX - 1));
+    }
+
+    #[test]
+    fn chen_primes_below_test() {
+        let chen: Vec<u64> = chen_primes_below(100000);
+        assert_eq!(chen.len(), 4234);
+        assert_eq!(
+            chen[..20],
+            [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 47, 53, 59, 67, 71, 83, 89]
+        );
+        let below_1000: Vec<u64> = (0..1000).filter(|p| is_chen_prime(*p)).collect();
+        assert_eq!(below_1000, chen_primes_below(1000));
+    }
+}
+
+#[cfg(test)]
+mod proth_prime_tests {
+    use bens_number_theory::modular::mod_pow;
+    use bens_number_theory::primes::{
+        is_probable_prime, is_proth_prime, proth_primes_below, ProthResult,
+    };
+    use num::BigInt;
+
+    #[test]
+    fn small_proth_primes_test() {
+        for p in [3, 5, 13, 17, 41, 97, 113, 193] {
+            match is_proth_prime(p) {
+                ProthResult::Prime(a) => assert_eq!(mod_pow(a, (p - 1) / 2, p), p - 1),
+                other => panic!("{} gave {:?}", p, other),
+            }
+        }
+        assert_eq!(
+            proth_primes_below(200),
+            vec![3, 5, 13, 17, 41, 97, 113, 193]
+        );
+    }
+
+    #[test]
+    fn fermat_number_test() {
+        // F_5 = 2^32 + 1 = 641 * 6700417 is a Proth number with k = 1
+        assert_eq!(is_proth_prime(2_u64.pow(32) + 1), ProthResult::Composite);
+        assert_eq!(is_proth_prime(65537), ProthResult::Prime(3));
+        assert_eq!(is_proth_prime(9), ProthResult::Composite); // 1 * 2^3 + 1
+    }
+
+    #[test]
+    fn shape_detection_test() {
+        // 7 = 3 * 2 + 1 and 11 = 5 * 2 + 1 have k >= 2^m
+        assert_eq!(is_proth_prime(7), ProthResult::NotProthForm);
+        assert_eq!(is_proth_prime(11), ProthResult::NotProthForm);
+        assert_eq!(is_proth_prime(23), ProthResult::NotProthForm); // 11 * 2 + 1
+        assert_eq!(is_proth_prime(25), ProthResult::Composite); // 3 * 2^3 + 1
+        assert_eq!(is_proth_prime(0), ProthResult::NotProthForm);
+        assert_eq!(is_proth_prime(1), ProthResult::NotProthForm);
+        assert_eq!(is_proth_prime(2), ProthResult::NotProthForm);
+        assert_eq!(is_proth_prime(16), ProthResult::NotProthForm);
+    }
+
+    #[test]
+    fn proth_primes_below_brute_force_test() {
+        let mut expected: Vec<u64> = vec![];
+        for n in (3..20_000_u64).step_by(2) {
+            let m: u32 = (n - 1).trailing_zeros();
+            if (n - 1) >> m < 1 << m && is_probable_prime(&BigInt::from(n)) {
+                expected.push(n);
+            }
+        }
+        assert_eq!(proth_primes_below(20_000), expected);
+        assert_eq!(proth_primes_below(3), vec![]);
+        assert_eq!(proth_primes_below(4), vec![3]);
+    }
+
+    #[test]
+    fn large_proth_prime_test() {
+        for (k, m) in [(27, 59), (5, 55), (7, 50)] {
+            let n: u64 = k * 2_u64.pow(m) + 1;
+            match is_proth_prime(n) {
+                ProthResult::Prime(a) => assert_eq!(mod_pow(a, (n - 1) / 2, n), n - 1),
+                other => panic!("{} gave {:?}", n, other),
+            }
+        }
+        assert_eq!(
+            is_proth_prime(5 * 2_u64.pow(60) + 1),
+            ProthResult::Composite
+        );
+        assert_eq!(
+            is_proth_prime(3 * 2_u64.pow(62) + 1),
+            ProthResult::Composite
+        );
+    }
+}
+
+#[cfg(test)]
+mod cullen_woodall_tests {
+    use bens_number_theory::primes::{
+        cullen_number, is_cullen_prime, is_woodall_prime, woodall_number,
+    };
+
+    #[test]
+    fn cullen_number_test() {
+        assert_eq!(cullen_number(0), Some(1));
+        assert_eq!(cullen_number(1), Some(3));
+        assert_eq!(cullen_number(2), Some(9));
+        assert_eq!(cullen_number(4), Some(65));
+        assert_eq!(cullen_number(121), Some(121 * 2_u128.pow(121) + 1));
+        assert_eq!(cullen_number(122), None);
+        assert_eq!(cullen_number(141), None);
+    }
+
+    #[test]
+    fn woodall_number_test() {
+        assert_eq!(woodall_number(1), Some(1));
+        assert_eq!(woodall_number(2), Some(7));
+        assert_eq!(woodall_number(4), Some(63));
+        assert_eq!(woodall_number(0), None);
+        assert_eq!(woodall_number(122), None);
+    }
+
+    #[test]
+    fn cullen_prime_test() {
+        // the next Cullen prime is at n = 141, which is past the u128 range
+        let primes: Vec<u32> = (0..200)
+            .filter(|n| cullen_number(*n).is_some_and(is_cullen_prime))
+            .collect();
+        assert_eq!(primes, vec![1]);
+    }
+
+    #[test]
+    fn woodall_prime_test() {
+        let primes: Vec<u32> = (0..200)
+            .filter(|n| woodall_number(*n).is_some_and(is_woodall_prime))
+            .collect();
+        // the next one, n = 123, is past the u128 range
+        assert_eq!(primes, vec![2, 3, 6, 30, 75, 81, 115]);
+    }
+
+    #[test]
+    fn near_miss_test() {
+        // primes next to the Cullen and Woodall numbers are not of the right shape
+        assert!(!is_cullen_prime(7));
+        assert!(!is_cullen_prime(23));
+        assert!(!is_woodall_prime(3));
+        assert!(!is_woodall_prime(5));
+        assert!(!is_woodall_prime(381)); // 3 * 127
+        assert!(!is_woodall_prime(woodall_number(30).unwrap() + 2));
+        assert!(!is_woodall_prime(woodall_number(81).unwrap() - 2));
+        assert!(!is_cullen_prime(0));
+        assert!(!is_woodall_prime(0));
+        assert!(!is_woodall_prime(u128::MAX));
+        assert!(!is_cullen_prime(u128::MAX));
+    }
+}
+
+#[cfg(test)]
+mod pocklington_tests {
+    use bens_number_theory::primes::{
+        is_probable_prime, prove_prime_n_minus_1, verify_certificate, PrimalityCertificate,
+    };
+    use num::BigInt;
+
+    #[test]
+    fn small_primes_test() {
+        for n in 0..5000_u64 {
+            let certificate: Option<PrimalityCertificate> = prove_prime_n_minus_1(n);
+            if n > 2 && is_probable_prime(&BigInt::from(n)) {
+                assert!(verify_certificate(&certificate.unwrap()), "{}", n);
+            } else {
+                assert_eq!(certificate, None, "{}", n);
+            }
+        }
+    }
+
+    #[test]
+    fn large_primes_test() {
+        for n in [
+            1_000_000_007_u64,
+            998_244_353,
+            2_305_843_009_213_693_951, // 2^61 - 1
+            1_000_000_000_000_000_003,
+            999_999_999_999_999_989,
+            1_000_000_000_000_037,
+            4_611_686_018_427_387_847,
+            18_446_744_073_709_551_557, // the largest u64 prime
+        ] {
+            let certificate: PrimalityCertificate = prove_prime_n_minus_1(n).unwrap();
+            assert!(verify_certificate(&certificate), "{}", n);
+            assert_eq!(certificate.factored * certificate.cofactor, n - 1);
+        }
+    }
+
+    #[test]
+    fn composites_test() {
+        for n in [
+            561_u64,       // Carmichael
+            3_215_031_751, // strong pseudoprime to bases 2, 3, 5, 7
+            1_000_000_007 * 998_244_353,
+            u64::MAX,
+            4,
+        ] {
+            assert_eq!(prove_prime_n_minus_1(n), None);
+        }
+    }
+
+    #[test]
+    fn tampered_certificate_test() {
+        let certificate: PrimalityCertificate = prove_prime_n_minus_1(1_000_000_007).unwrap();
+
+        let mut wrong_n: PrimalityCertificate = certificate.clone();
+        wrong_n.n += 2;
+        assert!(!verify_certificate(&wrong_n));
+
+        // 1 is never a witness, since 1^((n - 1)/q) - 1 = 0
+        let mut bad_witness: PrimalityCertificate = certificate.clone();
+        bad_witness.witnesses[0].1 = 1;
+        assert!(!verify_certificate(&bad_witness));
+
+        let mut missing_prime: PrimalityCertificate = certificate.clone();
+        missing_prime.witnesses.pop();
+        assert!(!verify_certificate(&missing_prime));
+
+        // claiming all of n - 1 is factored when it is not
+        let mut too_small: PrimalityCertificate = certificate.clone();
+        too_small.factored = 2;
+        too_small.cofactor = (certificate.n - 1) / 2;
+        too_small.witnesses = vec![(2, 5)];
+        assert!(!verify_certificate(&too_small));
+    }
+}
+
+#[cfg(test)]
+mod pratt_tests {
+    use bens_number_theory::primes::{
+        is_probable_prime, pratt_certificate, verify_pratt, PrattCert,
+    };
+    use num::BigInt;
+
+    #[test]
+    fn small_primes_test() {
+        for p in 0..10_000_u64 {
+            match pratt_certificate(p) {
+                Some(certificate) => {
+                    assert!(is_probable_prime(&BigInt::from(p)));
+                    assert_eq!(certificate.prime(), p);
+                    assert!(verify_pratt(&certificate), "{}", p);
+                }
+                None => assert!(!is_probable_prime(&BigInt::from(p))),
+            }
+        }
+    }
+
+    #[test]
+    fn large_primes_test() {
+        for p in [
+            999_999_999_999_989_u64,
+            1_000_000_000_000_037,
+            1_000_000_000_000_091,
+            2_305_843_009_213_693_951, // 2^61 - 1, where p - 1 is smooth
+        ] {
+            let certificate: PrattCert = pratt_certificate(p).unwrap();
+            assert!(verify_pratt(&certificate), "{}", p);
+        }
+    }
+
+    #[test]
+    fn composites_test() {
+        for n in [0_u64, 1, 4, 561, 1105, 3_215_031_751, 999_999_999_999_999] {
+            assert_eq!(pratt_certificate(n), None);
+        }
+    }
+
+    #[test]
+    fn non_primitive_root_test() {
+        let mut certificate: PrattCert = pratt_certificate(1_000_003).unwrap();
+        if let PrattCert::Node { root, .. } = &mut certificate {
+            // squares are never primitive roots
+            *root = 4;
+        }
+        assert!(!verify_pratt(&certificate));
+    }
+
+    #[test]
+    fn tampered_tree_test() {
+        // 15 = 2 * 7 + 1 is not prime, but claims a certificate shaped like one
+        let fake: PrattCert = PrattCert::Node {
+            p: 15,
+            root: 2,
+            factors: vec![(PrattCert::Base(2), 1), (pratt_certificate(7).unwrap(), 1)],
+        };
+        assert!(!verify_pratt(&fake));
+        assert!(!verify_pratt(&PrattCert::Base(5)));
+        // p - 1 = 12 = 2^2 * 3, but the exponent of 2 is missing
+        let incomplete: PrattCert = PrattCert::Node {
+            p: 13,
+            root: 2,
+            factors: vec![(PrattCert::Base(2), 1), (PrattCert::Base(3), 1)],
+        };
+        assert!(!verify_pratt(&incomplete));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_derive_test() {
+        fn assert_serde<T: serde::Serialize + serde::de::DeserializeOwned>(_: &T) {}
+        assert_serde(&pratt_certificate(1_000_000_007).unwrap());
+    }
+}
+
+#[cfg(test)]
+mod lucas_prp_tests {
+    use bens_number_theory::primes::{
+        is_frobenius_prp, is_lucas_prp, is_probable_prime, is_strong_lucas_prp,
+    };
+    use num::BigInt;
+
+    fn sieve(limit: usize) -> Vec<bool> {
+        let mut flags: Vec<bool> = vec![true; limit];
+        flags[0] = false;
+        flags[1] = false;
+        let mut p: usize = 2;
+        while p * p < limit {
+            if flags[p] {
+                for multiple in (p * p..limit).step_by(p) {
+                    flags[multiple] = false;
+                }
+            }
+            p += 1;
+        }
+        flags
+    }
+
+    #[test]
+    fn agreement_with_sieve_test() {
+        let flags: Vec<bool> = sieve(1_000_000);
+        let mut strong_pseudoprimes: Vec<u64> = vec![];
+        let mut frobenius_pseudoprimes: Vec<u64> = vec![];
+        for (n, prime) in flags.iter().enumerate() {
+            let n: u64 = n as u64;
+            let strong: bool = is_strong_lucas_prp(n);
+            let frobenius: bool = is_frobenius_prp(n);
+            if *prime {
+                assert!(strong && frobenius && is_lucas_prp(n), "{}", n);
+            } else {
+                if strong {
+                    strong_pseudoprimes.push(n);
+                }
+                if frobenius {
+                    frobenius_pseudoprimes.push(n);
+                }
+            }
+        }
+        // A217255 has 58 terms below 10^6
+        assert_eq!(strong_pseudoprimes.len(), 58);
+        assert_eq!(strong_pseudoprimes[..4], [5459, 5777, 10877, 16109]);
+        assert_eq!(
+            frobenius_pseudoprimes,
+            vec![
+                5777, 10877, 75077, 100127, 113573, 161027, 162133, 231703, 430127, 635627, 851927
+            ]
+        );
+    }
+
+    #[test]
+    fn lucas_pseudoprimes_test() {
+        // Lucas pseudoprimes that the strong test catches
+        for n in [323, 377, 1159, 1829, 3827] {
+            assert!(is_lucas_prp(n));
+            assert!(!is_strong_lucas_prp(n));
+        }
+        // strong Lucas pseudoprimes, 5459 = 53 * 103 and 5777 = 53 * 109
+        for n in [5459, 5777] {
+            assert!(is_lucas_prp(n));
+            assert!(is_strong_lucas_prp(n));
+            assert!(!is_probable_prime(&BigInt::from(n)));
+        }
+        assert!(!is_frobenius_prp(5459));
+        assert!(is_frobenius_prp(5777));
+    }
+
+    #[test]
+    fn base_two_strong_pseudoprimes_test() {
+        // strong pseudoprimes to base 2 (A001262)
+        for n in [
+            2047, 3277, 4033, 4681, 8321, 15841, 29341, 42799, 49141, 52633,
+        ] {
+            assert!(!is_strong_lucas_prp(n));
+            assert!(!is_frobenius_prp(n));
+        }
+    }
+
+    #[test]
+    fn squares_and_large_values_test() {
+        for root in [3_u64, 5, 7, 101, 65521, 4_294_967_291] {
+            assert!(!is_strong_lucas_prp(root * root));
+            assert!(!is_frobenius_prp(root * root));
+            assert!(!is_lucas_prp(root * root));
+        }
+        for n in [
+            18_446_744_073_709_551_557_u64, // the largest u64 prime
+            2_305_843_009_213_693_951,
+            1_000_000_000_000_000_003,
+        ] {
+            assert!(is_strong_lucas_prp(n));
+            assert!(is_frobenius_prp(n));
+        }
+        assert!(!is_strong_lucas_prp(u64::MAX));
+        assert!(!is_strong_lucas_prp(18_446_744_073_709_551_555));
+    }
+}
+
+#[cfg(test)]
+mod arithmetic_progression_tests {
+    use bens_number_theory::primes::{
+        count_primes_in_residue_class, longest_ap_of_primes_below, primes_in_ap,
+    };
+
+    #[test]
+    fn primes_in_ap_test() {
+        assert_eq!(primes_in_ap(5, 6, 30), vec![5, 11, 17, 23, 29]);
+        assert_eq!(primes_in_ap(2, 1, 20), vec![2, 3, 5, 7, 11, 13, 17, 19]);
+        assert_eq!(primes_in_ap(100, 7, 100), vec![]);
+    }
+
+    #[test]
+    fn common_factor_test() {
+        // every term shares the factor gcd(first, step), so only first itself can be prime
+        assert_eq!(primes_in_ap(2, 4, 1000), vec![2]);
+        assert_eq!(primes_in_ap(7, 14, 1000), vec![7]);
+        assert_eq!(primes_in_ap(6, 9, 1000), vec![]);
+        assert_eq!(primes_in_ap(15, 5, 1000), vec![]);
+    }
+
+    #[test]
+    fn residue_class_count_test() {
+        assert_eq!(count_primes_in_residue_class(4, 1, 1_000_000), 39175);
+        assert_eq!(count_primes_in_residue_class(4, 3, 1_000_000), 39322);
+        assert_eq!(count_primes_in_residue_class(4, 2, 1_000_000), 1);
+        assert_eq!(count_primes_in_residue_class(4, 7, 1_000_000), 39322);
+        assert_eq!(count_primes_in_residue_class(1, 0, 1_000_000), 78498);
+        assert_eq!(count_primes_in_residue_class(3, 0, 0), 0);
+    }
+
+    #[test]
+    fn residue_classes_agree_with_ap_test() {
+        for modulus in 1..12_u64 {
+            for residue in 0..modulus {
+                assert_eq!(
+                    count_primes_in_residue_class(modulus, residue, 5000),
+                    primes_in_ap(residue, modulus, 5000).len() as u64
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn prime_progressions_test() {
+        assert_eq!(longest_ap_of_primes_below(10, 2), Some(vec![2, 3]));
+        assert_eq!(longest_ap_of_primes_below(10, 3), Some(vec![3, 5, 7]));
+        assert_eq!(longest_ap_of_primes_below(30, 4), Some(vec![5, 11, 17, 23]));
+        assert_eq!(
+            longest_ap_of_primes_below(30, 5),
+            Some(vec![5, 11, 17, 23, 29])
+        );
+        assert_eq!(longest_ap_of_primes_below(29, 5), None);
+        assert_eq!(
+            longest_ap_of_primes_below(1000, 6),
+            Some(vec![7, 37, 67, 97, 127, 157])
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn short_progression_test() {
+        longest_ap_of_primes_below(100, 1);
+    }
+}
+
+#[cfg(test)]
+mod quadratic_polynomial_tests {
+    use bens_number_theory::primes::{best_quadratic_coefficients, consecutive_prime_run};
+
+    #[test]
+    fn known_polynomials_test() {
+        assert_eq!(consecutive_prime_run(1, 41), 40);
+        assert_eq!(consecutive_prime_run(-79, 1601), 80);
+        assert_eq!(consecutive_prime_run(-61, 971), 71);
+        assert_eq!(consecutive_prime_run(0, 2), 2); // 2, 3, 6
+        assert_eq!(consecutive_prime_run(0, 1), 0);
+    }
+
+    #[test]
+    fn negative_values_test() {
+        // -2 is not prime even though 2 is
+        assert_eq!(consecutive_prime_run(0, -2), 0);
+        // 3, -1, ... stops at the negative value
+        assert_eq!(consecutive_prime_run(-5, 3), 1);
+        assert_eq!(consecutive_prime_run(i64::MIN, i64::MIN), 0);
+    }
+
+    #[test]
+    fn coefficient_search_test() {
+        // Project Euler 27
+        let (a, b, run): (i64, i64, u32) = best_quadratic_coefficients(-999..=999, -1000..=1000);
+        assert_eq!((a, b, run), (-61, 971, 71));
+        assert_eq!(a * b, -59231);
+    }
+
+    #[test]
+    fn empty_range_test() {
+        #[allow(clippy::reversed_empty_ranges)]
+        let empty: std::ops::RangeInclusive<i64> = 1..=0;
+        assert_eq!(best_quadratic_coefficients(empty, 0..=10), (0, 0, 0));
+    }
+}
+
+#[cfg(test)]
+mod prime_sum_tests {
+    use bens_number_theory::primes::{
+        sum_primes_below, sum_primes_below_lucy, sum_primes_below_sieve,
+    };
+
+    #[test]
+    fn small_sums_test() {
+        assert_eq!(sum_primes_below(0), 0);
+        assert_eq!(sum_primes_below(2), 0);
+        assert_eq!(sum_primes_below(3), 2);
+        assert_eq!(sum_primes_below(10), 17);
+        assert_eq!(sum_primes_below(11), 17);
+        assert_eq!(sum_primes_below(12), 28);
+        assert_eq!(sum_primes_below(2_000_000), 142_913_828_922);
+    }
+
+    #[test]
+    fn backends_agree_test() {
+        for limit in 0..2000 {
+            assert_eq!(
+                sum_primes_below_lucy(limit),
+                sum_primes_below_sieve(limit),
+                "{}",
+                limit
+            );
+        }
+        for limit in [65_535, 65_536, 65_537, 1_000_000, 9_999_991, 10_000_000] {
+            assert_eq!(
+                sum_primes_below_lucy(limit),
+                sum_primes_below_sieve(limit),
+                "{}",
+                limit
+            );
+        }
+    }
+
+    #[test]
+    fn hundred_million_test() {
+        assert_eq!(sum_primes_below_sieve(100_000_000), 279_209_790_387_276);
+        assert_eq!(sum_primes_below_lucy(100_000_000), 279_209_790_387_276);
+    }
+
+    #[test]
+    fn ten_billion_test() {
+        assert_eq!(sum_primes_below(10_000_000_000), 2_220_822_432_581_729_238);
+    }
+}
+
+#[cfg(test)]
+mod twin_prime_tests {
+    use bens_number_theory::primes::{
+        brun_constant_partial, count_twin_primes_below, is_probable_prime, twin_primes_below,
+    };
+    use num::BigInt;
+
+    #[test]
+    fn twin_pairs_test() {
+        assert_eq!(twin_primes_below(7), vec![(3, 5)]);
+        assert_eq!(twin_primes_below(8), vec![(3, 5), (5, 7)]);
+        assert_eq!(twin_primes_below(2), vec![]);
+        for (p, q) in twin_primes_below(100_000) {
+            assert_eq!(q, p + 2);
+            assert!(is_probable_prime(&BigInt::from(p)));
+            assert!(is_probable_prime(&BigInt::from(q)));
+        }
+    }
+
+    #[test]
+    fn count_matches_listing_test() {
+        for limit in (0..3000).chain([65_535, 65_536, 65_537, 65_539, 131_072, 200_000]) {
+            assert_eq!(
+                count_twin_primes_below(limit),
+                twin_primes_below(limit).len() as u64,
+                "{}",
+                limit
+            );
+        }
+    }
+
+    #[test]
+    fn known_counts_test() {
+        assert_eq!(count_twin_primes_below(1_000_000), 8169);
+        assert_eq!(count_twin_primes_below(10_000_000), 58980);
+    }
+
+    #[test]
+    fn brun_partial_sums_test() {
+        let mut last: f64 = 0.0;
+        for limit in [10, 100, 1000, 10_000, 100_000, 1_000_000, 10_000_000] {
+            let sum: f64 = brun_constant_partial(limit);
+            assert!(sum > last);
+            assert!(sum < 1.95);
+            last = sum;
+        }
+        // the partial sum up to 10^7 is 1.738357...
+        assert!((last - 1.738_357).abs() < 1e-5);
+        assert_eq!(brun_constant_partial(5), 0.0);
+    }
+}
+
+#[cfg(test)]
+mod prime_power_tests {
+    use bens_number_theory::factorization::factorize;
+    use bens_number_theory::primes::{is_prime_power, prime_powers_below};
+
+    #[test]
+    fn is_prime_power_test() {
+        assert_eq!(is_prime_power(0), None);
+        assert_eq!(is_prime_power(1), None);
+        assert_eq!(is_prime_power(2), Some((2, 1)));
+        assert_eq!(is_prime_power(6), None);
+        assert_eq!(is_prime_power(8192), Some((2, 13)));
+        assert_eq!(is_prime_power(1 << 63), Some((2, 63)));
+        assert_eq!(is_prime_power(3_u64.pow(40)), Some((3, 40)));
+        assert_eq!(
+            is_prime_power(4_294_967_291_u64.pow(2)),
+            Some((4_294_967_291, 2))
+        );
+        assert_eq!(is_prime_power(4_294_967_295_u64.pow(2)), None);
+        assert_eq!(is_prime_power(u64::MAX), None);
+    }
+
+    #[test]
+    fn agreement_with_factorize_test() {
+        for n in 0..20_000_u64 {
+            let factors: Vec<(u64, u32)> = factorize(n);
+            let expected: Option<(u64, u32)> = if factors.len() == 1 {
+                Some(factors[0])
+            } else {
+                None
+            };
+            assert_eq!(is_prime_power(n), expected, "{}", n);
+        }
+    }
+
+    #[test]
+    fn prime_powers_below_100_test() {
+        let powers: Vec<u64> = prime_powers_below(100).collect();
+        assert_eq!(powers.len(), 35);
+        assert_eq!(
+            powers,
+            vec![
+                2, 3, 4, 5, 7, 8, 9, 11, 13, 16, 17, 19, 23, 25, 27, 29, 31, 32, 37, 41, 43, 47,
+                49, 53, 59, 61, 64, 67, 71, 73, 79, 81, 83, 89, 97
+            ]
+        );
+    }
+
+    #[test]
+    fn prime_powers_below_agree_test() {
+        let powers: Vec<u64> = prime_powers_below(100_000).collect();
+        let expected: Vec<u64> = (0..100_000)
+            .filter(|n| is_prime_power(*n).is_some())
+            .collect();
+        assert_eq!(powers, expected);
+        assert_eq!(prime_powers_below(0).count(), 0);
+        assert_eq!(prime_powers_below(3).collect::<Vec<u64>>(), vec![2]);
+    }
+
+    #[test]
+    fn prime_powers_below_large_limit_test() {
+        // only the primes up to 2^22 are sieved before the first item
+        let powers: Vec<u64> = prime_powers_below(1 << 44).take(12).collect();
+        assert_eq!(powers, vec![2, 3, 4, 5, 7, 8, 9, 11, 13, 16, 17, 19]);
+        // the end of the last block, 2^20 itself being left out
+        let last: Vec<u64> = prime_powers_below(1 << 20)
+            .skip_while(|n| *n < 1_048_549)
+            .collect();
+        assert_eq!(last, vec![1_048_549, 1_048_559, 1_048_571, 1_048_573]);
+    }
+}
+
+#[cfg(test)]
+mod progress_tests {
+    use bens_number_theory::primes::{
+        generate_primes, generate_primes_with_progress, is_probable_prime, lucas_lehmer,
+        lucas_lehmer_with_progress, Cancelled,
+    };
+    use num::BigInt;
+    use std::cell::RefCell;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    fn check_monotone(calls: &[(u64, u64)], total: u64) {
+        assert!(!calls.is_empty());
+        for window in calls.windows(2) {
+            assert!(window[0].0 < window[1].0);
+        }
+        assert!(calls.iter().all(|(_, t)| *t == total));
+        assert_eq!(*calls.last().unwrap(), (total, total));
+    }
+
+    #[test]
+    fn generate_primes_test() {
+        let primes: Vec<u64> = generate_primes_with_progress(10_000, None, None).unwrap();
+        assert_eq!(primes, generate_primes(10_000_u64));
+        for limit in 0..100_u64 {
+            let expected: Vec<u64> = (0..limit)
+                .filter(|n| is_probable_prime(&BigInt::from(*n)))
+                .collect();
+            assert_eq!(
+                generate_primes_with_progress(limit, None, None),
+                Ok(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn generate_primes_progress_test() {
+        let calls: RefCell<Vec<(u64, u64)>> = RefCell::new(vec![]);
+        let report = |done: u64, total: u64| calls.borrow_mut().push((done, total));
+        let primes: Vec<u64> =
+            generate_primes_with_progress(10_000_000, Some(&report), None).unwrap();
+        assert_eq!(primes.len(), 664_579);
+        let calls: Vec<(u64, u64)> = calls.into_inner();
+        check_monotone(&calls, 10_000_000);
+        // one report per block of 2^16 numbers
+        assert_eq!(calls.len(), 153);
+    }
+
+    #[test]
+    fn generate_primes_cancel_test() {
+        let cancel: AtomicBool = AtomicBool::new(false);
+        let calls: RefCell<u64> = RefCell::new(0);
+        let report = |_: u64, _: u64| {
+            *calls.borrow_mut() += 1;
+            cancel.store(true, Ordering::Relaxed);
+        };
+        assert_eq!(
+            generate_primes_with_progress(1_000_000_000_000, Some(&report), Some(&cancel)),
+            Err(Cancelled)
+        );
+        assert_eq!(*calls.borrow(), 1);
+        assert_eq!(
+            generate_primes_with_progress(10, None, Some(&cancel)),
+            Err(Cancelled)
+        );
+    }
+
+    #[test]
+    fn lucas_lehmer_test() {
+        let exponents: Vec<u32> = (1..700).filter(|p| lucas_lehmer(*p)).collect();
+        assert_eq!(
+            exponents,
+            vec![2, 3, 5, 7, 13, 17, 19, 31, 61, 89, 107, 127, 521, 607]
+        );
+    }
+
+    #[test]
+    fn lucas_lehmer_progress_test() {
+        let calls: RefCell<Vec<(u64, u64)>> = RefCell::new(vec![]);
+        let report = |done: u64, total: u64| calls.borrow_mut().push((done, total));
+        assert_eq!(
+            lucas_lehmer_with_progress(4423, Some(&report), None),
+            Ok(true)
+        );
+        let calls: Vec<(u64, u64)> = calls.into_inner();
+        check_monotone(&calls, 4421);
+        assert!(calls.len() <= 1025);
+    }
+
+    #[test]
+    fn lucas_lehmer_cancel_test() {
+        let cancel: AtomicBool = AtomicBool::new(false);
+        let calls: RefCell<u64> = RefCell::new(0);
+        let report = |_: u64, _: u64| {
+            *calls.borrow_mut() += 1;
+            cancel.store(true, Ordering::Relaxed);
+        };
+        assert_eq!(
+            lucas_lehmer_with_progress(86_243, Some(&report), Some(&cancel)),
+            Err(Cancelled)
+        );
+        assert_eq!(*calls.borrow(), 1);
+        assert_eq!(Cancelled.to_string(), "the computation was cancelled");
+    }
+}
+
+#[cfg(test)]
+mod prime_type_tests {
+    use bens_number_theory::primes::{is_probable_prime, lucas_lehmer_prime, prime_pi, Prime};
+    use num::BigInt;
+
+    #[test]
+    fn construction_test() {
+        for n in 0..5000_u64 {
+            let expected: bool = is_probable_prime(&BigInt::from(n));
+            assert_eq!(Prime::new(n).is_some(), expected, "{}", n);
+        }
+        assert_eq!(Prime::new(561), None);
+        assert_eq!(Prime::new(3_215_031_751), None);
+        assert_eq!(Prime::new(u64::MAX), None);
+        assert_eq!(
+            Prime::new(18_446_744_073_709_551_557).map(u64::from),
+            Some(18_446_744_073_709_551_557)
+        );
+        assert_eq!(*Prime::new_unchecked(15), 15);
+    }
+
+    #[test]
+    fn conversion_test() {
+        let p: Prime = Prime::new(101).unwrap();
+        assert_eq!(p.to_string(), "101");
+        assert_eq!(*p % 10, 1);
+        let n: u64 = p.into();
+        assert_eq!(n, 101);
+        assert!(Prime::new(7).unwrap() < p);
+    }
+
+    #[test]
+    fn next_and_index_test() {
+        let mut p: Prime = Prime::new(2).unwrap();
+        let mut index: u64 = 1;
+        while *p < 10_000 {
+            assert_eq!(p.index(), Some(index));
+            let next: Prime = p.next().unwrap();
+            assert!((*p + 1..*next).all(|n| Prime::new(n).is_none()));
+            p = next;
+            index += 1;
+        }
+        assert_eq!(Prime::new(18_446_744_073_709_551_557).unwrap().next(), None);
+        assert_eq!(Prime::new(1_000_000_007).unwrap().index(), Some(50_847_535));
+        assert_eq!(
+            Prime::new(18_446_744_073_709_551_557).unwrap().index(),
+            None
+        );
+        assert_eq!(Prime::new(1_099_511_627_791).unwrap().index(), None); // the first prime above 2^40
+    }
+
+    #[test]
+    fn prime_pi_test() {
+        let mut count: u64 = 0;
+        for x in 0..20_000_u64 {
+            if is_probable_prime(&BigInt::from(x)) {
+                count += 1;
+            }
+            assert_eq!(prime_pi(x), count, "{}", x);
+        }
+        assert_eq!(prime_pi(10_000_000_000), 455_052_511);
+    }
+
+    #[test]
+    fn lucas_lehmer_prime_test() {
+        assert!(lucas_lehmer_prime(Prime::new(2).unwrap()));
+        assert!(lucas_lehmer_prime(Prime::new(607).unwrap()));
+        assert!(!lucas_lehmer_prime(Prime::new(23).unwrap()));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_test() {
+        use serde::de::value::{Error, U64Deserializer};
+        use serde::de::IntoDeserializer;
+        use serde::Deserialize;
+
+        let prime: U64Deserializer<Error> = 13_u64.into_deserializer();
+        assert_eq!(Prime::deserialize(prime), Ok(Prime::new(13).unwrap()));
+        let composite: U64Deserializer<Error> = 15_u64.into_deserializer();
+        let error: Error = Prime::deserialize(composite).unwrap_err();
+        assert_eq!(error.to_string(), "15 is not prime");
+    }
+}
+
+#[cfg(test)]
+mod checkpoint_tests {
+    use bens_number_theory::primes::{
+        prime_pi, sum_primes_below, SegmentResult, SegmentedSieveRun,
+    };
+    use std::io::ErrorKind;
+
+    fn finish(run: &mut SegmentedSieveRun) {
+        while run.run_next_segment() != SegmentResult::Finished {}
+    }
+
+    fn save(run: &SegmentedSieveRun) -> Vec<u8> {
+        let mut bytes: Vec<u8> = vec![];
+        run.checkpoint(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn uninterrupted_test() {
+        for (limit, segment_size) in [(0, 10), (2, 10), (3, 10), (1000, 7), (1_000_000, 1 << 16)] {
+            let mut run: SegmentedSieveRun = SegmentedSieveRun::new(limit, segment_size);
+            finish(&mut run);
+            assert!(run.is_finished());
+            assert_eq!(run.count(), prime_pi(limit.saturating_sub(1)));
+            assert_eq!(run.sum(), sum_primes_below(limit));
+        }
+    }
+
+    #[test]
+    fn segment_result_test() {
+        let mut run: SegmentedSieveRun = SegmentedSieveRun::new(30, 10);
+        assert_eq!(
+            run.run_next_segment(),
+            SegmentResult::Sieved {
+                index: 0,
+                low: 2,
+                high: 12,
+                count: 5,
+                sum: 28
+            }
+        );
+        assert_eq!(
+            run.run_next_segment(),
+            SegmentResult::Sieved {
+                index: 1,
+                low: 12,
+                high: 22,
+                count: 3,
+                sum: 49
+            }
+        );
+        assert_eq!(
+            run.run_next_segment(),
+            SegmentResult::Sieved {
+                index: 2,
+                low: 22,
+                high: 30,
+                count: 2,
+                sum: 52
+            }
+        );
+        assert_eq!(run.run_next_segment(), SegmentResult::Finished);
+        assert_eq!(run.segments_done(), 3);
+    }
+
+    #[test]
+    fn resume_test() {
+        let limit: u64 = 10_000_000;
+        let segment_size: u64 = 100_000;
+        let mut uninterrupted: SegmentedSieveRun = SegmentedSieveRun::new(limit, segment_size);
+        finish(&mut uninterrupted);
+        let expected: Vec<u8> = save(&uninterrupted);
+        for k in [0, 1, 17, 99, 100] {
+            let mut first: SegmentedSieveRun = SegmentedSieveRun::new(limit, segment_size);
+            for _ in 0..k {
+                first.run_next_segment();
+            }
+            let saved: Vec<u8> = save(&first);
+            drop(first);
+            let mut resumed: SegmentedSieveRun = SegmentedSieveRun::new(limit, segment_size);
+            resumed.resume(saved.as_slice()).unwrap();
+            assert_eq!(resumed.segments_done(), k);
+            finish(&mut resumed);
+            assert_eq!(save(&resumed), expected);
+            assert_eq!(resumed.count(), 664_579);
+            assert_eq!(resumed.sum(), 3_203_324_994_356);
+        }
+    }
+
+    #[test]
+    fn rejected_checkpoint_test() {
+        let mut run: SegmentedSieveRun = SegmentedSieveRun::new(100_000, 1000);
+        run.run_next_segment();
+        let saved: Vec<u8> = save(&run);
+
+        let mut other_limit: SegmentedSieveRun = SegmentedSieveRun::new(200_000, 1000);
+        let error = other_limit.resume(saved.as_slice()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert_eq!(other_limit.segments_done(), 0);
+
+        let mut other_size: SegmentedSieveRun = SegmentedSieveRun::new(100_000, 999);
+        assert!(other_size.resume(saved.as_slice()).is_err());
+
+        let mut wrong_version: Vec<u8> = saved.clone();
+        wrong_version[8] = 2;
+        let mut fresh: SegmentedSieveRun = SegmentedSieveRun::new(100_000, 1000);
+        let error = fresh.resume(wrong_version.as_slice()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+        let mut wrong_magic: Vec<u8> = saved.clone();
+        wrong_magic[0] = b'X';
+        assert!(fresh.resume(wrong_magic.as_slice()).is_err());
+
+        let truncated: &[u8] = &saved[..saved.len() - 1];
+        assert_eq!(
+            fresh.resume(truncated).unwrap_err().kind(),
+            ErrorKind::UnexpectedEof
+        );
+        assert_eq!(fresh.segments_done(), 0);
+
+        fresh.resume(saved.as_slice()).unwrap();
+        assert_eq!(fresh.segments_done(), 1);
+    }
+}
+
+#[cfg(test)]
+mod selfridge_tests {
+    use bens_number_theory::modular::kronecker_symbol;
+    use bens_number_theory::primes::selfridge_parameters;
+
+    #[test]
+    fn known_parameters_test() {
+        for (n, d) in [
+            (5, -7),
+            (7, 5),
+            (11, 13),
+            (19, -7),
+            (323, 5),
+            (377, 5),
+            (1159, 13),
+            (5459, -7),
+            (5777, 5),
+            (16_109, 13),
+            (18_971, -11),
+            (3_215_031_751, -11),
+            (18_446_744_073_709_551_557, 5),
+        ] {
+            assert_eq!(selfridge_parameters(n), Some((d, 1, (1 - d) / 4)), "{}", n);
+        }
+    }
+
+    #[test]
+    fn kronecker_condition_test() {
+        for n in (3..100_000_u64).step_by(2) {
+            let root: u64 = n.isqrt();
+            match selfridge_parameters(n) {
+                Some((d, p, q)) => {
+                    assert_ne!(root * root, n);
+                    assert_eq!(kronecker_symbol(d, n as i64), -1, "{}", n);
+                    assert_eq!(p, 1);
+                    assert_eq!(d, p * p - 4 * q);
+                    // every earlier candidate failed
+                    let mut earlier: i64 = 5;
+                    while earlier != d {
+                        assert_ne!(kronecker_symbol(earlier, n as i64), -1);
+                        earlier = if earlier > 0 {
+                            -earlier - 2
+                        } else {
+                            -earlier + 2
+                        };
+                    }
+                }
+                None => assert_eq!(root * root, n, "{}", n),
+            }
+        }
+    }
+
+    #[test]
+    fn perfect_square_test() {
+        assert_eq!(selfridge_parameters(0), None);
+        assert_eq!(selfridge_parameters(1), None);
+        for root in (3..1_000_u64).step_by(2).chain([4_294_967_295]) {
+            assert_eq!(selfridge_parameters(root * root), None);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(deprecated)]
+mod batch_primality_tests {
+    use bens_number_theory::primes::{are_prime, is_prime, is_probable_prime};
+    use num::BigInt;
+
+    #[test]
+    fn empty_test() {
+        assert_eq!(are_prime(&[]), Vec::<bool>::new());
+    }
+
+    #[test]
+    fn order_test() {
+        let values: Vec<u64> = vec![
+            97,
+            4,
+            1 << 61,
+            2,
+            (1 << 61) - 1,
+            0,
+            1,
+            3,
+            16_777_259,
+            16_777_216,
+        ];
+        assert_eq!(
+            are_prime(&values),
+            vec![true, false, false, true, true, false, false, true, true, false]
+        );
+        let reversed: Vec<u64> = values.iter().rev().copied().collect();
+        let mut expected: Vec<bool> = are_prime(&values);
+        expected.reverse();
+        assert_eq!(are_prime(&reversed), expected);
+    }
+
+    #[test]
+    fn mixed_batch_test() {
+        // a small linear congruential generator keeps the inputs reproducible
+        let mut state: u64 = 0x853c_49e6_748f_ea9b;
+        let mut next = || -> u64 {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            state
+        };
+        let mut values: Vec<u64> = vec![];
+        for i in 0..30_000 {
+            let value: u64 = match i % 4 {
+                0 => next() % 1000,
+                1 => next() % (1 << 24),
+                2 => next() % 1_000_000_000_000,
+                _ => next() | 1,
+            };
+            values.push(value);
+        }
+        let answers: Vec<bool> = are_prime(&values);
+        assert_eq!(answers.len(), values.len());
+        for (value, answer) in values.iter().zip(&answers) {
+            assert_eq!(
+                *answer,
+                is_probable_prime(&BigInt::from(*value)),
+                "{}",
+                value
+            );
+        }
+        for value in values.iter().filter(|v| **v < 100_000) {
+            assert_eq!(are_prime(&[*value])[0], is_prime(*value));
+        }
+        assert!(answers.iter().filter(|a| **a).count() > 1000);
+    }
+
+    #[test]
+    fn pseudoprime_test() {
+        // strong pseudoprimes to several of the bases, and Carmichael numbers above the sieve
+        let composites: [u64; 5] = [
+            3_215_031_751,
+            3_825_123_056_546_413_051,
+            1_000_000_007 * 998_244_353,
+            41_041 * 1_000_003,
+            2_152_302_898_747,
+        ];
+        for n in composites {
+            assert_eq!(are_prime(&[n]), vec![is_probable_prime(&BigInt::from(n))]);
+        }
+        assert_eq!(are_prime(&[3_825_123_056_546_413_051]), vec![false]);
+        assert_eq!(are_prime(&[18_446_744_073_709_551_557]), vec![true]);
+    }
+}
+
+#[cfg(test)]
+mod nearest_prime_tests {
+    use bens_number_theory::primes::{is_probable_prime, nearest_prime, prime_distance};
+    use num::BigInt;
+
+    fn is_prime(n: u64) -> bool {
+        is_probable_prime(&BigInt::from(n))
+    }
+
+    #[test]
+    fn small_values_test() {
+        assert_eq!(nearest_prime(0), 2);
+        assert_eq!(nearest_prime(1), 2);
+        assert_eq!(nearest_prime(2), 2);
+        assert_eq!(nearest_prime(4), 3);
+        assert_eq!(nearest_prime(10), 11);
+        assert_eq!(nearest_prime(15), 13);
+        assert_eq!(nearest_prime(26), 23); // 23 and 29 are both 3 away
+        assert_eq!(nearest_prime(28), 29);
+    }
+
+    #[test]
+    fn brute_force_test() {
+        for n in 0..5000_u64 {
+            let below: Option<u64> = (2..=n).rev().find(|m| is_prime(*m));
+            let above: u64 = (n..).find(|m| is_prime(*m)).unwrap();
+            let expected: u64 = match below {
+                Some(below) if n - below <= above - n => below,
+                _ => above,
+            };
+            assert_eq!(nearest_prime(n), expected, "{}", n);
+            assert_eq!(prime_distance(n), expected.abs_diff(n));
+            assert_eq!(prime_distance(n) == 0, is_prime(n));
+        }
+    }
+
+    #[test]
+    fn large_values_test() {
+        let n: u64 = 1 << 63;
+        let above: u64 = (n..).find(|m| is_prime(*m)).unwrap();
+        let below: u64 = (0..n).rev().find(|m| is_prime(*m)).unwrap();
+        assert_eq!(above, n + 29);
+        assert_eq!(below, n - 25);
+        assert_eq!(nearest_prime(n), below);
+        assert_eq!(prime_distance(n), 25);
+        assert_eq!(nearest_prime(u64::MAX), u64::MAX - 58);
+        assert_eq!(prime_distance(u64::MAX), 58);
+        assert_eq!(nearest_prime(u64::MAX - 58), u64::MAX - 58);
+    }
+}
+
+#[cfg(test)]
+mod hash_prime_tests {
+    use bens_number_theory::primes::{
+        good_hash_prime, hash_prime_ladder, is_probable_prime, next_prime_capacity, Prime,
+    };
+    use num::BigInt;
+
+    #[test]
+    fn ladder_test() {
+        let ladder: &[u64] = hash_prime_ladder();
+        assert_eq!(ladder.len(), 62);
+        assert_eq!(ladder[0], 5);
+        assert_eq!(*ladder.last().unwrap(), 14_480_561_146_010_017_169);
+        for p in ladder {
+            assert!(is_probable_prime(&BigInt::from(*p)), "{}", p);
+        }
+        for pair in ladder.windows(2) {
+            let ratio: f64 = pair[1] as f64 / pair[0] as f64;
+            assert!((1.8..=2.2).contains(&ratio), "{} {}", pair[0], pair[1]);
+        }
+        assert!(std::ptr::eq(ladder, hash_prime_ladder()));
+    }
+
+    #[test]
+    fn good_hash_prime_test() {
+        let ladder: &[u64] = hash_prime_ladder();
+        for at_least in (0..100_000).chain(ladder.iter().flat_map(|p| [*p - 1, *p, *p + 1])) {
+            let p: u64 = good_hash_prime(at_least);
+            assert!(p >= at_least);
+            if at_least <= *ladder.last().unwrap() {
+                let index: usize = ladder.iter().position(|q| *q == p).unwrap();
+                assert!(index == 0 || ladder[index - 1] < at_least);
+            }
+        }
+    }
+
+    #[test]
+    fn above_ladder_test() {
+        let top: u64 = *hash_prime_ladder().last().unwrap();
+        let expected: u64 = *Prime::new(top).unwrap().next().unwrap();
+        assert_eq!(good_hash_prime(top + 1), expected);
+        assert_eq!(good_hash_prime(u64::MAX - 58), u64::MAX - 58);
+        assert_eq!(next_prime_capacity(top), u64::MAX - 58);
+    }
+
+    #[test]
+    fn growth_test() {
+        let mut capacity: u64 = next_prime_capacity(0);
+        assert_eq!(capacity, 5);
+        for expected in &hash_prime_ladder()[1..] {
+            capacity = next_prime_capacity(capacity);
+            assert_eq!(capacity, *expected);
+        }
+        assert_eq!(next_prime_capacity(1000), 1597);
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_large_test() {
+        good_hash_prime(u64::MAX - 57);
+    }
+}
+
+#[cfg(test)]
+#[allow(deprecated)]
+mod strong_liar_tests {
+    use bens_number_theory::primes::{is_prime, smallest_strong_liar, strong_prp_bases};
+
+    fn is_strong_liar(n: u64, a: u64) -> bool {
+        let mut d: u64 = n - 1;
+        let mut s: u32 = 0;
+        while d.is_multiple_of(2) {
+            d /= 2;
+            s += 1;
+        }
+        let mut x: u64 = 1;
+        let mut power: u64 = a % n;
+        let mut e: u64 = d;
+        while e > 0 {
+            if e % 2 == 1 {
+                x = x * power % n;
+            }
+            power = power * power % n;
+            e /= 2;
+        }
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        for _ in 1..s {
+            x = x * x % n;
+            if x == n - 1 {
+                return true;
+            }
+        }
+        false
+    }
+
+    #[test]
+    fn known_pseudoprimes_test() {
+        assert!(strong_prp_bases(2047, [2]).contains(&2));
+        assert_eq!(strong_prp_bases(1_373_653, [2, 3]), vec![2, 3]);
+        assert_eq!(strong_prp_bases(1_373_653, [5]), vec![]);
+        // 3215031751 is the smallest strong pseudoprime to 2, 3, 5 and 7
+        assert_eq!(
+            strong_prp_bases(3_215_031_751, [2, 3, 5, 7, 11]),
+            vec![2, 3, 5, 7]
+        );
+        assert_eq!(smallest_strong_liar(2047), Some(2));
+        assert_eq!(smallest_strong_liar(1_373_653), Some(2));
+    }
+
+    #[test]
+    fn primes_pass_every_base_test() {
+        for p in [3_u64, 5, 101, 7919, 1_000_000_007] {
+            let bases: Vec<u64> = (1..60).filter(|a| a % p != 0).collect();
+            assert_eq!(strong_prp_bases(p, 0..60), bases);
+            assert_eq!(smallest_strong_liar(p), None);
+        }
+        assert_eq!(strong_prp_bases(u64::MAX - 58, [2, 3, u64::MAX]).len(), 3);
+    }
+
+    #[test]
+    fn brute_force_test() {
+        for n in (9..2000_u64).step_by(2) {
+            let liars: Vec<u64> = (1..n).filter(|a| is_strong_liar(n, *a)).collect();
+            assert_eq!(strong_prp_bases(n, 1..n), liars);
+        }
+        for n in (9..10_000_u64).step_by(2) {
+            if is_prime(n) {
+                assert_eq!(smallest_strong_liar(n), None);
+            } else {
+                let expected: Option<u64> = (2..n).find(|a| is_strong_liar(n, *a));
+                assert_eq!(smallest_strong_liar(n), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn small_and_even_test() {
+        assert_eq!(smallest_strong_liar(0), None);
+        assert_eq!(smallest_strong_liar(4), None);
+        assert_eq!(smallest_strong_liar(10), None);
+        assert_eq!(smallest_strong_liar(9), Some(8));
+    }
+
+    #[test]
+    #[should_panic]
+    fn even_modulus_test() {
+        strong_prp_bases(10, [3]);
+    }
+}
+
+#[cfg(test)]
+mod sieve_segment_tests {
+    use bens_number_theory::primes::{
+        generate_primes, is_probable_prime, primes_in_range, SieveSegment,
+    };
+    use num::BigInt;
+
+    #[test]
+    fn primes_in_range_test() {
+        let primes: Vec<u64> = generate_primes(100_000_u64);
+        assert_eq!(primes_in_range(0, 100_000), primes);
+        for (lo, hi) in [
+            (0, 2),
+            (0, 3),
+            (1, 3),
+            (2, 3),
+            (50, 50),
+            (60, 40),
+            (99_990, 100_000),
+        ] {
+            let expected: Vec<u64> = primes
+                .iter()
+                .copied()
+                .filter(|p| lo <= *p && *p < hi)
+                .collect();
+            assert_eq!(primes_in_range(lo, hi), expected);
+        }
+        let lo: u64 = 1 << 40;
+        let expected: Vec<u64> = (lo..lo + 2000)
+            .filter(|n| is_probable_prime(&BigInt::from(*n)))
+            .collect();
+        assert_eq!(primes_in_range(lo, lo + 2000), expected);
+    }
+
+    #[test]
+    fn full_sieve_test() {
+        for (lo, hi) in [
+            (0_u64, 1000_u64),
+            (1, 2),
+            (0, 1),
+            (10_000, 10_500),
+            (777, 1000),
+        ] {
+            let mut segment: SieveSegment = SieveSegment::new(lo, hi);
+            for p in primes_in_range(2, (hi as f64).sqrt() as u64 + 1) {
+                segment.mark_multiples(p);
+            }
+            let unmarked: Vec<u64> = segment.unmarked().collect();
+            assert_eq!(unmarked, primes_in_range(lo, hi));
+            assert_eq!(segment.count_unmarked(), unmarked.len() as u64);
+        }
+    }
+
+    #[test]
+    fn subset_sieve_test() {
+        // sieve by the primes that are 1 modulo 4
+        let sieving: Vec<u64> = primes_in_range(2, 1000)
+            .into_iter()
+            .filter(|p| p % 4 == 1)
+            .collect();
+        let mut segment: SieveSegment = SieveSegment::new(0, 5000);
+        for p in &sieving {
+            segment.mark_multiples(*p);
+        }
+        let expected: Vec<u64> = (2..5000_u64)
+            .filter(|n| sieving.iter().all(|p| n % p != 0 || n == p))
+            .collect();
+        assert_eq!(segment.unmarked().collect::<Vec<u64>>(), expected);
+        // 0 and 1 start marked, and nothing else does
+        let segment: SieveSegment = SieveSegment::new(0, 130);
+        assert_eq!(
+            segment.unmarked().collect::<Vec<u64>>(),
+            (2..130).collect::<Vec<u64>>()
+        );
+        assert_eq!(segment.count_unmarked(), 128);
+    }
+
+    #[test]
+    fn chained_segments_test() {
+        let mut all: Vec<u64> = vec![];
+        let mut total: u64 = 0;
+        let mut lo: u64 = 0;
+        for width in [1_u64, 63, 64, 65, 127, 1000, 3, 4096] {
+            let hi: u64 = lo + width;
+            let mut segment: SieveSegment = SieveSegment::new(lo, hi);
+            for p in [
+                2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79,
+            ] {
+                segment.mark_multiples(p);
+            }
+            total += segment.count_unmarked();
+            all.extend(segment.unmarked());
+            lo = hi;
+        }
+        assert_eq!(all, primes_in_range(0, lo));
+        assert_eq!(total, all.len() as u64);
+    }
+
+    #[test]
+    fn edge_cases_test() {
+        let empty: SieveSegment = SieveSegment::new(10, 5);
+        assert_eq!(empty.count_unmarked(), 0);
+        assert_eq!(empty.unmarked().next(), None);
+        let mut top: SieveSegment = SieveSegment::new(u64::MAX - 10, u64::MAX);
+        top.mark_multiples(u64::MAX / 3);
+        top.mark_multiples(2);
+        let expected: Vec<u64> = (u64::MAX - 10..u64::MAX).filter(|n| n % 2 == 1).collect();
+        assert_eq!(top.unmarked().collect::<Vec<u64>>(), expected);
+    }
+
+    #[test]
+    #[should_panic]
+    fn mark_multiples_of_one_test() {
+        SieveSegment::new(0, 10).mark_multiples(1);
+    }
+}
+
+#[cfg(test)]
+mod witness_set_tests {
+    use bens_number_theory::primes::{minimal_witness_set, strong_prp_bases};
+
+    #[test]
+    fn small_domains_test() {
+        assert_eq!(minimal_witness_set(0, &[2]), vec![]);
+        assert_eq!(minimal_witness_set(9, &[]), vec![]);
+        assert_eq!(minimal_witness_set(10, &[3, 2]), vec![3]);
+        assert_eq!(minimal_witness_set(2047, &[2, 3]), vec![2]);
+        assert_eq!(minimal_witness_set(2048, &[2, 3]), vec![2, 3]);
+    }
+
+    #[test]
+    fn u16_test() {
+        assert_eq!(minimal_witness_set(1 << 16, &[2, 3, 5, 7]), vec![2, 3]);
+        // once 2 is chosen, 3 and 7 both catch every survivor, and ties go to the base listed first
+        assert_eq!(minimal_witness_set(1 << 16, &[7, 3, 2]), vec![2, 7]);
+    }
+
+    #[test]
+    fn u32_bases_test() {
+        // 2, 7 and 61 cover every u32; below 400000 two of them already do
+        assert_eq!(minimal_witness_set(300_000, &[2, 7, 61]), vec![2, 7]);
+        assert_eq!(minimal_witness_set(400_000, &[2, 7, 61]), vec![2, 61]);
+        // 314821 is the smallest strong pseudoprime to both 2 and 7
+        assert_eq!(strong_prp_bases(314_821, [2, 7, 61]), vec![2, 7]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn insufficient_candidates_test() {
+        minimal_witness_set(3000, &[2]);
+    }
+}
+
+#[cfg(test)]
+mod consecutive_prime_sum_tests {
+    use bens_number_theory::primes::{
+        consecutive_prime_sum_representations, generate_primes, longest_consecutive_prime_sum_below,
+    };
+
+    #[test]
+    fn longest_test() {
+        assert_eq!(longest_consecutive_prime_sum_below(100), (41, 6));
+        assert_eq!(longest_consecutive_prime_sum_below(1000), (953, 21));
+        assert_eq!(
+            longest_consecutive_prime_sum_below(1_000_000),
+            (997651, 543)
+        );
+        assert_eq!(longest_consecutive_prime_sum_below(3), (2, 1));
+        assert_eq!(longest_consecutive_prime_sum_below(6), (5, 2));
+    }
+
+    #[test]
+    fn representations_of_41_test() {
+        let primes: Vec<u64> = generate_primes(100_u64);
+        let windows: Vec<(usize, usize)> = consecutive_prime_sum_representations(41);
+        assert!(windows.contains(&(0, 6)));
+        assert_eq!(primes[0..6], [2, 3, 5, 7, 11, 13]);
+        assert_eq!(windows, vec![(0, 6), (4, 7), (12, 13)]);
+    }
+
+    #[test]
+    fn windows_re_sum_test() {
+        let primes: Vec<u64> = generate_primes(3000_u64);
+        for n in 0..2000_u64 {
+            let windows: Vec<(usize, usize)> = consecutive_prime_sum_representations(n);
+            assert!(windows.windows(2).all(|pair| pair[0].0 < pair[1].0));
+            for (i, j) in &windows {
+                assert!(i < j);
+                assert_eq!(primes[*i..*j].iter().sum::<u64>(), n);
+            }
+            // brute force over every window
+            let mut count: usize = 0;
+            for i in 0..primes.len() {
+                let mut sum: u64 = 0;
+                for p in &primes[i..] {
+                    sum += p;
+                    if sum >= n {
+                        break;
+                    }
+                }
+                if sum == n && n > 0 {
+                    count += 1;
+                }
+            }
+            assert_eq!(windows.len(), count);
+        }
+    }
+
+    #[test]
+    fn longest_is_a_representation_test() {
+        for limit in [10_u64, 100, 500, 5000] {
+            let (p, terms): (u64, usize) = longest_consecutive_prime_sum_below(limit);
+            assert!(p < limit);
+            let windows: Vec<(usize, usize)> = consecutive_prime_sum_representations(p);
+            assert!(windows.iter().any(|(i, j)| j - i == terms));
+            // no prime below the limit has a longer window
+            for q in generate_primes(limit) {
+                if q < limit {
+                    for (i, j) in consecutive_prime_sum_representations(q) {
+                        assert!(j - i <= terms);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_primes_test() {
+        longest_consecutive_prime_sum_below(2);
+    }
+}
+
+#[cfg(test)]
+mod mr_witness_tests {
+    use bens_number_theory::factorization::least_prime_factor;
+    use bens_number_theory::primes::{
+        is_strong_lucas_prp, mr_witnesses_for, primes_in_range, strong_prp_bases,
+    };
+    use std::collections::HashSet;
+
+    // the smallest strong pseudoprimes to the first 1, 2, 3, 4, 5, 6, 7 and 9 primes
+    const THRESHOLDS: [u64; 8] = [
+        2_047,
+        1_373_653,
+        25_326_001,
+        3_215_031_751,
+        2_152_302_898_747,
+        3_474_749_660_383,
+        341_550_071_728_321,
+        3_825_123_056_546_413_051,
+    ];
+
+    fn passes(n: u64, bases: &[u64]) -> bool {
+        strong_prp_bases(n, bases.iter().copied()).len() == bases.len()
+    }
+
+    #[test]
+    fn threshold_pseudoprime_test() {
+        for threshold in THRESHOLDS {
+            let bases: &[u64] = mr_witnesses_for(threshold);
+            let next: &[u64] = mr_witnesses_for(threshold + 1);
+            assert!(least_prime_factor(threshold) < Some(threshold));
+            assert!(passes(threshold, bases));
+            assert!(!passes(threshold, next));
+            assert!(next.len() > bases.len());
+        }
+    }
+
+    #[test]
+    fn sieve_window_test() {
+        const WINDOW: u64 = 20_000;
+        for threshold in THRESHOLDS {
+            let lo: u64 = threshold - WINDOW.min(threshold - 39);
+            let hi: u64 = threshold + WINDOW;
+            let sieved: HashSet<u64> = if threshold < 1 << 42 {
+                primes_in_range(lo, hi).into_iter().collect()
+            } else {
+                HashSet::new()
+            };
+            let mut n: u64 = lo | 1;
+            while n < hi {
+                let prime: bool = if threshold < 1 << 42 {
+                    sieved.contains(&n)
+                } else {
+                    passes(n, &[2]) && is_strong_lucas_prp(n)
+                };
+                let bases: &[u64] = if n < threshold {
+                    mr_witnesses_for(threshold)
+                } else {
+                    mr_witnesses_for(n + 1)
+                };
+                assert_eq!(passes(n, bases), prime, "{n}");
+                n += 2;
+            }
+        }
+    }
+
+    #[test]
+    fn monotonic_test() {
+        let mut previous: &[u64] = mr_witnesses_for(0);
+        let mut bound: u64 = 1;
+        while bound < u64::MAX / 3 {
+            let bases: &[u64] = mr_witnesses_for(bound);
+            assert!(bases.starts_with(previous));
+            previous = bases;
+            bound = bound * 3 + 1;
+        }
+        assert_eq!(mr_witnesses_for(0), &[2]);
+        assert_eq!(mr_witnesses_for(2047), &[2]);
+        assert_eq!(mr_witnesses_for(2048), &[2, 3]);
+    }
+
+    #[test]
+    fn fallback_test() {
+        let full: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+        assert_eq!(mr_witnesses_for(3_825_123_056_546_413_051).len(), 9);
+        assert_eq!(mr_witnesses_for(3_825_123_056_546_413_052), &full);
+        assert_eq!(mr_witnesses_for(1 << 63), &full);
+        assert_eq!(mr_witnesses_for(u64::MAX), &full);
+    }
+}
+
+#[cfg(test)]
+mod gap_tests {
+    use bens_number_theory::primes::{
+        gap_histogram, gap_stats, prime_pi, primes_in_range, GapStats,
+    };
+    use std::collections::BTreeMap;
+
+    // the maximal prime gaps and the primes they start at
+    const MAXIMAL_GAPS: [(u64, u64); 21] = [
+        (1, 2),
+        (2, 3),
+        (4, 7),
+        (6, 23),
+        (8, 89),
+        (14, 113),
+        (18, 523),
+        (20, 887),
+        (22, 1129),
+        (34, 1327),
+        (36, 9551),
+        (44, 15683),
+        (52, 19609),
+        (72, 31397),
+        (86, 155921),
+        (96, 360653),
+        (112, 370261),
+        (114, 492113),
+        (118, 1349533),
+        (132, 1357201),
+        (148, 2010733),
+    ];
+
+    #[test]
+    fn small_histogram_test() {
+        let histogram: BTreeMap<u64, u64> = gap_histogram(2, 100);
+        // 2 3 5 7 11 13 17 19 23 29 31 37 41 43 47 53 59 61 67 71 73 79 83 89 97
+        let expected: BTreeMap<u64, u64> = BTreeMap::from([(1, 1), (2, 8), (4, 7), (6, 7), (8, 1)]);
+        assert_eq!(histogram, expected);
+        assert_eq!(gap_histogram(0, 101), expected);
+        assert_eq!(gap_histogram(3, 100).get(&1), None);
+    }
+
+    #[test]
+    fn maximal_gap_test() {
+        assert_eq!(gap_stats(0, 1_000_000).unwrap().max, 114);
+        assert_eq!(gap_stats(0, 2_010_733 + 148 + 1).unwrap().max, 148);
+        let mut previous: u64 = 0;
+        for (gap, start) in MAXIMAL_GAPS {
+            // the record only shows up once the prime ending it is in the range
+            assert_eq!(
+                gap_stats(0, start + gap).map_or(0, |stats| stats.max),
+                previous
+            );
+            assert_eq!(gap_stats(0, start + gap + 1).unwrap().max, gap);
+            assert_eq!(gap_stats(start, start + gap + 1).unwrap().max, gap);
+            previous = gap;
+        }
+    }
+
+    #[test]
+    fn count_test() {
+        let ranges: [(u64, u64); 6] = [
+            (0, 1_000_000),
+            (1_000, 2_000),
+            (999_983, 1_000_004),
+            (4_000_000_000, 4_000_300_000),
+            (0, 4),
+            (1 << 40, (1 << 40) + 100_000),
+        ];
+        for (lo, hi) in ranges {
+            let primes: Vec<u64> = primes_in_range(lo, hi);
+            let stats: GapStats = gap_stats(lo, hi).unwrap();
+            assert_eq!(stats.count, primes.len() as u64 - 1);
+            assert_eq!(gap_histogram(lo, hi).values().sum::<u64>(), stats.count);
+            let span: u64 = primes[primes.len() - 1] - primes[0];
+            assert_eq!(stats.mean, span as f64 / stats.count as f64);
+            let gaps: Vec<u64> = primes.windows(2).map(|pair| pair[1] - pair[0]).collect();
+            assert_eq!(stats.min, *gaps.iter().min().unwrap());
+            assert_eq!(stats.max, *gaps.iter().max().unwrap());
+        }
+        assert_eq!(
+            gap_stats(0, 1_000_000).unwrap().count,
+            prime_pi(999_999) - prime_pi(0) - 1
+        );
+        assert_eq!(
+            gap_stats(1000, 10_000).unwrap().count,
+            prime_pi(9_999) - prime_pi(999) - 1
+        );
+    }
+
+    #[test]
+    fn few_primes_test() {
+        assert!(gap_histogram(0, 0).is_empty());
+        assert!(gap_histogram(0, 3).is_empty());
+        assert!(gap_histogram(90, 97).is_empty());
+        assert!(gap_histogram(90, 98).is_empty());
+        assert_eq!(gap_histogram(90, 102), BTreeMap::from([(4, 1)]));
+        assert!(gap_histogram(492_114, 492_227).is_empty());
+        assert_eq!(gap_stats(100, 50), None);
+        assert_eq!(
+            gap_stats(2, 4),
+            Some(GapStats {
+                min: 1,
+                max: 1,
+                mean: 1.0,
+                count: 1
+            })
+        );
+    }
+}
+
+#[cfg(test)]
+mod prime_tuple_tests {
+    use bens_number_theory::primes::{first_occurrence, is_admissible_tuple, prime_bitset};
+
+    fn admissible_brute_force(offsets: &[u64]) -> bool {
+        // trying every prime below 50 covers the tuples of up to 15 offsets used here
+        [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47]
+            .iter()
+            .all(|q| (0..*q).any(|r| offsets.iter().all(|o| o % q != r)))
+    }
+
+    #[test]
+    fn admissibility_test() {
+        assert!(!is_admissible_tuple(&[0, 2, 4]));
+        assert!(is_admissible_tuple(&[0, 2, 6]));
+        assert!(is_admissible_tuple(&[0, 4, 6]));
+        assert!(is_admissible_tuple(&[0, 2, 6, 8, 12, 18, 20, 26]));
+        assert!(!is_admissible_tuple(&[0, 1]));
+        assert!(!is_admissible_tuple(&[0, 2, 6, 8, 12, 14]));
+        assert!(is_admissible_tuple(&[]));
+        assert!(is_admissible_tuple(&[5]));
+        // every subset of {0, ..., 14} with distinct residues checked against the definition
+        let mut mask: u32 = 1;
+        while mask < 1 << 15 {
+            let offsets: Vec<u64> = (0..15).filter(|i| mask >> i & 1 == 1).collect();
+            assert_eq!(
+                is_admissible_tuple(&offsets),
+                admissible_brute_force(&offsets),
+                "{offsets:?}"
+            );
+            mask += 1;
+        }
+    }
+
+    #[test]
+    fn known_occurrence_test() {
+        assert_eq!(first_occurrence(&[0, 2], 100), Some(3));
+        assert_eq!(first_occurrence(&[0, 2, 6], 100), Some(5));
+        assert_eq!(first_occurrence(&[0, 4, 6], 100), Some(7));
+        assert_eq!(first_occurrence(&[0, 2, 6, 8], 100), Some(5));
+        assert_eq!(first_occurrence(&[0, 2, 6, 8, 12], 100), Some(5));
+        assert_eq!(first_occurrence(&[0, 4, 6, 10, 12], 100), Some(7));
+        assert_eq!(
+            first_occurrence(&[0, 2, 6, 8, 12, 18, 20, 26], 100),
+            Some(11)
+        );
+        // the next prime quadruplet after 11, 13, 17, 19
+        assert_eq!(first_occurrence(&[90, 92, 96, 98], 100), Some(11));
+        assert_eq!(first_occurrence(&[0, 2, 6, 8], 6), Some(5));
+        assert_eq!(first_occurrence(&[0, 2, 6, 8], 5), None);
+        assert_eq!(first_occurrence(&[0], 10), Some(2));
+        assert_eq!(first_occurrence(&[0], 0), None);
+        assert_eq!(
+            first_occurrence(&[0, 2, 6, 8, 12, 18, 20, 26, 30, 32], 100),
+            Some(11)
+        );
+        // two prime quadruplets 30 apart, first found several sieve blocks in
+        let pair: [u64; 8] = [0, 2, 6, 8, 30, 32, 36, 38];
+        assert_eq!(first_occurrence(&pair, 2_000_000), Some(1_006_301));
+        assert_eq!(first_occurrence(&pair, 1_006_301), None);
+    }
+
+    #[test]
+    fn inadmissible_test() {
+        // an inadmissible tuple can only be all prime when one of its numbers is the prime covering it
+        assert_eq!(first_occurrence(&[0, 2, 4], 1_000_000), Some(3));
+        assert_eq!(first_occurrence(&[0, 1], 1_000_000), Some(2));
+        assert_eq!(first_occurrence(&[0, 2, 4, 6], 1_000_000), None);
+        assert_eq!(first_occurrence(&[0, 2, 4, 8, 14], 1_000_000), Some(3));
+        assert_eq!(first_occurrence(&[0, 2, 4, 6, 8], 1_000_000), None);
+    }
+
+    #[test]
+    fn brute_force_test() {
+        let bits: Vec<u64> = prime_bitset(20_100);
+        let prime = |n: u64| bits[(n / 64) as usize] >> (n % 64) & 1 == 1;
+        let mut state: u64 = 181;
+        let mut tuple: u32 = 0;
+        while tuple < 200 {
+            let mut offsets: Vec<u64> = vec![];
+            let mut offset: u64 = 0;
+            while offsets.len() < 4 {
+                state = state
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                offset += state >> 61;
+                offsets.push(offset);
+            }
+            let expected: Option<u64> = (0..20_000).find(|n| offsets.iter().all(|o| prime(n + o)));
+            assert_eq!(first_occurrence(&offsets, 20_000), expected, "{offsets:?}");
+            tuple += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod check_prime_tests {
+    use bens_number_theory::primes::{
+        check_prime, check_prime_with, is_probable_prime, prime_bitset, PrimalityStrategy,
+    };
+    use num::BigInt;
+
+    const STRATEGIES: [PrimalityStrategy; 3] = [
+        PrimalityStrategy::Table,
+        PrimalityStrategy::MillerRabin,
+        PrimalityStrategy::BailliePsw,
+    ];
+
+    // every strategy that supports n gives the same answer as check_prime
+    fn agree(n: u128) -> bool {
+        let expected: bool = check_prime(n);
+        STRATEGIES
+            .iter()
+            .filter(|strategy| strategy.supports(n))
+            .all(|strategy| check_prime_with(n, *strategy) == expected)
+    }
+
+    #[test]
+    fn strategy_selection_test() {
+        assert_eq!(PrimalityStrategy::for_number(0), PrimalityStrategy::Table);
+        assert_eq!(
+            PrimalityStrategy::for_number((1 << 16) - 1),
+            PrimalityStrategy::Table
+        );
+        assert_eq!(
+            PrimalityStrategy::for_number(1 << 16),
+            PrimalityStrategy::MillerRabin
+        );
+        assert_eq!(
+            PrimalityStrategy::for_number(u64::MAX as u128),
+            PrimalityStrategy::MillerRabin
+        );
+        assert_eq!(
+            PrimalityStrategy::for_number(1 << 64),
+            PrimalityStrategy::BailliePsw
+        );
+        assert_eq!(
+            PrimalityStrategy::for_number(u128::MAX),
+            PrimalityStrategy::BailliePsw
+        );
+        for n in [
+            0,
+            1,
+            (1 << 16) - 1,
+            1 << 16,
+            u64::MAX as u128,
+            1 << 64,
+            u128::MAX,
+        ] {
+            assert!(PrimalityStrategy::for_number(n).supports(n));
+            assert!(PrimalityStrategy::BailliePsw.supports(n));
+        }
+        assert!(!PrimalityStrategy::Table.supports(1 << 16));
+        assert!(!PrimalityStrategy::MillerRabin.supports(1 << 64));
+    }
+
+    #[test]
+    fn table_boundary_test() {
+        let bits: Vec<u64> = prime_bitset(1 << 17);
+        let mut n: u128 = 0;
+        while n < 1 << 17 {
+            let sieved: bool = bits[(n / 64) as usize] >> (n % 64) & 1 == 1;
+            assert_eq!(check_prime(n), sieved, "{n}");
+            assert!(agree(n), "{n}");
+            n += 1;
+        }
+        assert!(!check_prime(65_535));
+        assert!(!check_prime(65_536));
+        assert!(check_prime(65_537));
+        assert!(check_prime(65_521));
+    }
+
+    #[test]
+    fn u64_boundary_test() {
+        let top: u128 = 1 << 64;
+        let mut n: u128 = top - 2000;
+        while n < top + 2000 {
+            let expected: bool = is_probable_prime(&BigInt::from(n));
+            assert_eq!(check_prime(n), expected, "{n}");
+            assert!(agree(n), "{n}");
+            n += 1;
+        }
+        assert!(check_prime(top - 59));
+        assert!(!check_prime(top - 1));
+        assert!(!check_prime(top + 1));
+        assert!(check_prime(top + 13));
+    }
+
+    #[test]
+    fn large_test() {
+        assert!(check_prime((1 << 61) - 1));
+        assert!(check_prime((1 << 89) - 1));
+        assert!(check_prime((1 << 107) - 1));
+        assert!(check_prime((1 << 127) - 1));
+        assert!(check_prime(u128::MAX - 158)); // the largest prime below 2^128
+        assert!(!check_prime(u128::MAX));
+        assert!(!check_prime(u128::MAX - 156));
+        // a Carmichael number (6k + 1)(12k + 1)(18k + 1)
+        assert!(!check_prime(1_296_198_694_153_288_947_529));
+        // products of two large primes and a square
+        assert!(!check_prime(((1 << 64) - 59) * ((1 << 61) - 1)));
+        assert!(!check_prime(((1 << 61) - 1) * ((1 << 61) - 1)));
+        assert!(!check_prime(((1 << 64) + 13) * 65_537));
+        let mut state: u128 = 182;
+        let mut i: u32 = 0;
+        while i < 300 {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            let n: u128 = state | 1 << 100 | 1;
+            assert_eq!(check_prime(n), is_probable_prime(&BigInt::from(n)), "{n}");
+            i += 1;
+        }
+    }
+
+    #[test]
+    fn pseudoprime_test() {
+        // strong pseudoprimes to base 2 and strong Lucas pseudoprimes
+        for n in [2047, 3277, 4033, 5459, 5777, 10_877, 3_215_031_751] {
+            for strategy in STRATEGIES {
+                if strategy.supports(n) {
+                    assert!(!check_prime_with(n, strategy), "{n} {strategy:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn table_out_of_range_test() {
+        check_prime_with(1 << 16, PrimalityStrategy::Table);
+    }
+
+    #[test]
+    #[should_panic]
+    fn miller_rabin_out_of_range_test() {
+        check_prime_with(1 << 64, PrimalityStrategy::MillerRabin);
+    }
+}
+
+#[cfg(test)]
+mod residue_class_tests {
+    use bens_number_theory::modular::gcd;
+    use bens_number_theory::primes::{class_counts_below, primes_in_class, primes_in_range};
+
+    #[test]
+    fn mod_four_test() {
+        let counts: Vec<u64> = class_counts_below(4, 1_000_000);
+        assert_eq!(counts, vec![0, 39175, 1, 39322]);
+        assert_eq!(
+            primes_in_class(4, 1).take_while(|p| *p < 1_000_000).count(),
+            39175
+        );
+        assert_eq!(
+            primes_in_class(4, 3).take_while(|p| *p < 1_000_000).count(),
+            39322
+        );
+        // 3 (mod 4) stays ahead here, Chebyshev's bias
+        let mut lead: u32 = 0;
+        for limit in [100, 1000, 10_000, 100_000] {
+            let counts: Vec<u64> = class_counts_below(4, limit);
+            if counts[3] > counts[1] {
+                lead += 1;
+            }
+        }
+        assert_eq!(lead, 4);
+    }
+
+    #[test]
+    fn iterator_test() {
+        let limit: u64 = 300_000;
+        let primes: Vec<u64> = primes_in_range(0, limit);
+        for modulus in [1_u64, 2, 3, 4, 7, 10, 30, 97, 210, 1000, 65_537, 100_003] {
+            let counts: Vec<u64> = class_counts_below(modulus, limit);
+            assert_eq!(counts.iter().sum::<u64>(), primes.len() as u64);
+            for residue in [0, 1, 2, 3, 5, 6, 9, 11, 29, 96, 101, 209, 999, 65_536] {
+                let residue: u64 = residue % modulus;
+                let expected: Vec<u64> = primes
+                    .iter()
+                    .copied()
+                    .filter(|p| p % modulus == residue)
+                    .collect();
+                let found: Vec<u64> = primes_in_class(modulus, residue)
+                    .take_while(|p| *p < limit)
+                    .collect();
+                assert_eq!(found, expected, "{residue} mod {modulus}");
+                assert_eq!(counts[residue as usize], expected.len() as u64);
+            }
+        }
+    }
+
+    #[test]
+    fn non_coprime_test() {
+        assert_eq!(primes_in_class(4, 2).collect::<Vec<u64>>(), vec![2]);
+        assert_eq!(primes_in_class(6, 3).collect::<Vec<u64>>(), vec![3]);
+        assert_eq!(primes_in_class(9, 3).collect::<Vec<u64>>(), vec![3]);
+        assert_eq!(primes_in_class(5, 0).collect::<Vec<u64>>(), vec![5]);
+        assert_eq!(primes_in_class(5, 10).collect::<Vec<u64>>(), vec![5]);
+        assert_eq!(primes_in_class(6, 0).count(), 0);
+        assert_eq!(primes_in_class(15, 10).count(), 0);
+        assert_eq!(primes_in_class(12, 8).count(), 0);
+        for modulus in 2..60 {
+            let counts: Vec<u64> = class_counts_below(modulus, 10_000);
+            for residue in 0..modulus {
+                if gcd(residue, modulus) > 1 {
+                    let found: Vec<u64> = primes_in_class(modulus, residue).collect();
+                    assert!(found.len() <= 1);
+                    assert_eq!(found.len() as u64, counts[residue as usize]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn large_modulus_test() {
+        // most blocks hold no term of the class at all
+        let found: Vec<u64> = primes_in_class(1 << 20, 1).take(3).collect();
+        let expected: Vec<u64> = primes_in_range(0, 1 << 26)
+            .into_iter()
+            .filter(|p| p % (1 << 20) == 1)
+            .take(3)
+            .collect();
+        assert_eq!(found, expected);
+        assert_eq!(
+            primes_in_class(u64::MAX, 5).take(1).collect::<Vec<u64>>(),
+            vec![5]
+        );
+        assert_eq!(class_counts_below(7, 2), vec![0; 7]);
+        assert_eq!(class_counts_below(1, 100), vec![25]);
+    }
+}
+
+#[cfg(test)]
+mod classify_mersenne_tests {
+    use bens_number_theory::primes::{classify_mersenne, is_mersenne_prime, MersenneClass};
+
+    #[test]
+    fn example_test() {
+        assert_eq!(
+            classify_mersenne((1 << 11) - 1),
+            MersenneClass::CompositeMersenne {
+                p: 11,
+                known_factor: Some(23)
+            }
+        );
+        assert_eq!(
+            classify_mersenne((1 << 13) - 1),
+            MersenneClass::MersennePrime { p: 13 }
+        );
+        assert_eq!(classify_mersenne(12), MersenneClass::NotMersenneForm);
+        assert_eq!(
+            classify_mersenne((1 << 4) - 1),
+            MersenneClass::ExponentComposite { p: 4 }
+        );
+        assert!(!is_mersenne_prime(15));
+        assert!(!is_mersenne_prime(2047));
+        // prime, and 11 + 1 has a prime bit length, but 11 is not 2^p - 1
+        assert!(!is_mersenne_prime(11));
+        assert!(!is_mersenne_prime(-1));
+    }
+
+    #[test]
+    fn exponent_test() {
+        let primes: [u32; 12] = [2, 3, 5, 7, 13, 17, 19, 31, 61, 89, 107, 127];
+        let mut p: u32 = 2;
+        while p <= 128 {
+            let m: u128 = u128::MAX >> (128 - p);
+            let class: MersenneClass = classify_mersenne(m);
+            if primes.contains(&p) {
+                assert_eq!(class, MersenneClass::MersennePrime { p });
+                assert!(is_mersenne_prime(m));
+            } else {
+                assert!(!is_mersenne_prime(m));
+                if matches!(p, 4 | 6 | 8 | 9 | 10 | 12 | 33 | 64 | 100 | 128) {
+                    assert_eq!(class, MersenneClass::ExponentComposite { p });
+                }
+            }
+            p += 1;
+        }
+    }
+
+    #[test]
+    fn known_factor_test() {
+        let factors: [(u32, Option<u128>); 10] = [
+            (11, Some(23)),
+            (23, Some(47)),
+            (29, Some(233)),
+            (37, Some(223)),
+            (41, Some(13_367)),
+            (43, Some(431)),
+            (59, Some(179_951)),
+            (71, Some(228_479)),
+            // the smallest factors, 193707721 and 2550183799, are beyond the search
+            (67, None),
+            (103, None),
+        ];
+        for (p, known_factor) in factors {
+            let m: u128 = u128::MAX >> (128 - p);
+            assert_eq!(
+                classify_mersenne(m),
+                MersenneClass::CompositeMersenne { p, known_factor }
+            );
+            if let Some(q) = known_factor {
+                assert_eq!(m % q, 0);
+                assert_eq!((q - 1) % (2 * p as u128), 0);
+            }
+        }
+    }
+
+    #[test]
+    fn form_test() {
+        let mut m: u128 = 0;
+        while m < 1 << 16 {
+            let form: bool = m >= 3 && (m + 1).is_power_of_two();
+            assert_eq!(
+                classify_mersenne(m) != MersenneClass::NotMersenneForm,
+                form,
+                "{m}"
+            );
+            m += 1;
+        }
+        assert_eq!(classify_mersenne(1), MersenneClass::NotMersenneForm);
+        assert_eq!(classify_mersenne(3), MersenneClass::MersennePrime { p: 2 });
+        assert_eq!(classify_mersenne(1 << 100), MersenneClass::NotMersenneForm);
+        assert_eq!(
+            classify_mersenne(u128::MAX),
+            MersenneClass::ExponentComposite { p: 128 }
+        );
+    }
+}
+
+#[cfg(test)]
+mod riesel_tests {
+    use bens_number_theory::primes::{check_prime, is_riesel_prime, lucas_lehmer};
+
+    #[test]
+    fn small_test() {
+        // every odd k < 2^n, which covers both ways of choosing the starting value
+        let mut n: u32 = 2;
+        while n <= 14 {
+            let mut k: u64 = 1;
+            while k < (1 << n).min(3000) {
+                let value: u128 = ((k as u128) << n) - 1;
+                assert_eq!(is_riesel_prime(k, n), check_prime(value), "{k} * 2^{n} - 1");
+                k += 2;
+            }
+            n += 1;
+        }
+        assert!(is_riesel_prime(3, 2));
+        assert!(is_riesel_prime(5, 4));
+    }
+
+    #[test]
+    fn wide_test() {
+        let mut state: u64 = 188;
+        let mut i: u32 = 0;
+        while i < 2000 {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            let k: u64 = (state >> 40) | 1;
+            let n: u32 = 25 + (state % 80) as u32;
+            let value: u128 = ((k as u128) << n) - 1;
+            assert_eq!(is_riesel_prime(k, n), check_prime(value), "{k} * 2^{n} - 1");
+            i += 1;
+        }
+    }
+
+    #[test]
+    fn known_exponents_test() {
+        // A002235 and A002254
+        let three: Vec<u32> = (2..=500).filter(|n| is_riesel_prime(3, *n)).collect();
+        assert_eq!(
+            three,
+            vec![
+                2, 3, 4, 6, 7, 11, 18, 34, 38, 43, 55, 64, 76, 94, 103, 143, 206, 216, 306, 324,
+                391, 458, 470
+            ]
+        );
+        let fifteen: Vec<u32> = (4..=300).filter(|n| is_riesel_prime(15, *n)).collect();
+        assert_eq!(
+            fifteen,
+            vec![
+                4, 5, 10, 14, 17, 31, 41, 73, 80, 82, 116, 125, 145, 157, 172, 202, 224, 266, 289,
+                293
+            ]
+        );
+    }
+
+    #[test]
+    fn riesel_number_test() {
+        // 509203 * 2^n - 1 always has a factor from the covering set {3, 5, 7, 13, 17, 241}
+        let mut n: u32 = 19;
+        while n <= 200 {
+            assert!(!is_riesel_prime(509_203, n), "{n}");
+            n += 1;
+        }
+    }
+
+    #[test]
+    fn mersenne_test() {
+        let mut n: u32 = 2;
+        while n <= 700 {
+            assert_eq!(is_riesel_prime(1, n), lucas_lehmer(n), "{n}");
+            n += 1;
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn even_k_test() {
+        is_riesel_prime(4, 10);
+    }
+
+    #[test]
+    #[should_panic]
+    fn large_k_test() {
+        is_riesel_prime(17, 4);
+    }
+}
+
+#[cfg(test)]
+mod fortunate_tests {
+    use bens_number_theory::primes::{
+        all_fortunate_prime_below, check_prime, fortunate_number, fortunate_numbers, primorial,
+    };
+
+    #[test]
+    fn primorial_test() {
+        assert_eq!(primorial(0), Some(1));
+        assert_eq!(primorial(1), Some(2));
+        assert_eq!(primorial(6), Some(30_030));
+        assert_eq!(primorial(10), Some(6_469_693_230));
+        // 2 * 3 * ... * 101 is the last primorial below 2^128
+        assert_eq!(primorial(26), Some(primorial(25).unwrap() * 101));
+        assert_eq!(primorial(27), None);
+        assert_eq!(primorial(100), None);
+    }
+
+    #[test]
+    fn sequence_test() {
+        assert_eq!(
+            fortunate_numbers(10),
+            vec![3, 5, 7, 13, 23, 17, 19, 23, 37, 61]
+        );
+        assert_eq!(
+            fortunate_numbers(26)[10..],
+            [67, 61, 71, 47, 107, 59, 61, 109, 89, 103, 79, 151, 197, 101, 103, 233]
+        );
+        assert_eq!(fortunate_numbers(0), vec![]);
+    }
+
+    #[test]
+    fn definition_test() {
+        let mut k: usize = 1;
+        while k <= 26 {
+            let product: u128 = primorial(k).unwrap();
+            let m: u64 = fortunate_number(k);
+            assert!(m > 1, "{k}");
+            assert!(check_prime(product + m as u128), "{k}");
+            let mut smaller: u64 = 2;
+            while smaller < m {
+                assert!(!check_prime(product + smaller as u128), "{k} {smaller}");
+                smaller += 1;
+            }
+            k += 1;
+        }
+    }
+
+    #[test]
+    fn conjecture_test() {
+        assert!(all_fortunate_prime_below(26));
+        assert!(all_fortunate_prime_below(0));
+        for m in fortunate_numbers(26) {
+            assert!(check_prime(m as u128), "{m}");
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_test() {
+        fortunate_number(0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn overflow_test() {
+        fortunate_number(27);
+    }
+}
+
+#[cfg(test)]
+mod sierpinski_screen_tests {
+    use bens_number_theory::primes::{
+        check_prime, is_probable_prime, screen_riesel, screen_sierpinski, ScreenResult,
+        SierpinskiScreen,
+    };
+    use num::BigInt;
+
+    fn first_prime_exponent(k: u64, plus: bool, n_max: u32) -> Option<u32> {
+        (1..=n_max).find(|n| {
+            let shifted: BigInt = BigInt::from(k) << *n;
+            is_probable_prime(&if plus { shifted + 1 } else { shifted - 1 })
+        })
+    }
+
+    #[test]
+    fn sierpinski_number_test() {
+        let screen: SierpinskiScreen = screen_sierpinski(78_557, 2000);
+        assert_eq!(screen.result, ScreenResult::NoPrimeUpTo(2000));
+        assert_eq!(screen.covering_set(), Some(vec![3, 5, 7, 13, 19, 37, 73]));
+        // the covering classes, with 3 | 78557 * 2^n + 1 for every even n and so on
+        let covering: Vec<(u64, u64, u64)> = screen
+            .classes
+            .iter()
+            .filter(|class| [3, 5, 7, 13, 19, 37, 73].contains(&class.prime))
+            .map(|class| (class.prime, class.residue, class.modulus))
+            .collect();
+        assert_eq!(
+            covering,
+            vec![
+                (3, 0, 2),
+                (5, 1, 4),
+                (7, 1, 3),
+                (13, 11, 12),
+                (19, 15, 18),
+                (37, 27, 36),
+                (73, 3, 9)
+            ]
+        );
+        // 271129 is another Sierpinski number, with the covering set {3, 5, 7, 13, 17, 241}
+        let screen: SierpinskiScreen = screen_sierpinski(271_129, 300);
+        assert_eq!(screen.result, ScreenResult::NoPrimeUpTo(300));
+        assert_eq!(screen.covering_set(), Some(vec![3, 5, 7, 13, 17, 241]));
+    }
+
+    #[test]
+    fn riesel_number_test() {
+        let screen: SierpinskiScreen = screen_riesel(509_203, 2000);
+        assert_eq!(screen.result, ScreenResult::NoPrimeUpTo(2000));
+        assert_eq!(screen.covering_set(), Some(vec![3, 5, 7, 13, 17, 241]));
+    }
+
+    #[test]
+    fn immediate_prime_test() {
+        assert_eq!(
+            screen_sierpinski(3, 10).result,
+            ScreenResult::PrimeFound { n: 1 }
+        );
+        assert_eq!(
+            screen_riesel(3, 10).result,
+            ScreenResult::PrimeFound { n: 1 }
+        );
+        assert_eq!(
+            screen_sierpinski(1, 10).result,
+            ScreenResult::PrimeFound { n: 1 }
+        );
+        assert_eq!(
+            screen_riesel(1, 10).result,
+            ScreenResult::PrimeFound { n: 2 }
+        );
+        assert_eq!(screen_sierpinski(3, 0).result, ScreenResult::NoPrimeUpTo(0));
+        // no prime found yet, but no covering either
+        let screen: SierpinskiScreen = screen_sierpinski(47, 500);
+        assert_eq!(screen.result, ScreenResult::NoPrimeUpTo(500));
+        assert_eq!(screen.covering_set(), None);
+    }
+
+    #[test]
+    fn large_exponent_test() {
+        // A046067 and A046069
+        for (k, n) in [(217, 66), (257, 279), (47, 583)] {
+            assert_eq!(
+                screen_sierpinski(k, 1000).result,
+                ScreenResult::PrimeFound { n },
+                "{k}"
+            );
+        }
+        for (k, n) in [(253, 71), (247, 133), (191, 226), (59, 12)] {
+            assert_eq!(
+                screen_riesel(k, 1000).result,
+                ScreenResult::PrimeFound { n },
+                "{k}"
+            );
+        }
+    }
+
+    #[test]
+    fn brute_force_test() {
+        let mut k: u64 = 1;
+        while k < 200 {
+            for plus in [true, false] {
+                let screen: SierpinskiScreen = if plus {
+                    screen_sierpinski(k, 60)
+                } else {
+                    screen_riesel(k, 60)
+                };
+                let expected: ScreenResult = match first_prime_exponent(k, plus, 60) {
+                    Some(n) => ScreenResult::PrimeFound { n },
+                    None => ScreenResult::NoPrimeUpTo(60),
+                };
+                assert_eq!(screen.result, expected, "{k} {plus}");
+            }
+            k += 2;
+        }
+    }
+
+    #[test]
+    fn classes_test() {
+        for k in [1, 5, 15, 91, 78_557, 509_203] {
+            for plus in [true, false] {
+                let screen: SierpinskiScreen = if plus {
+                    screen_sierpinski(k, 0)
+                } else {
+                    screen_riesel(k, 0)
+                };
+                let mut previous: u64 = 2;
+                for class in &screen.classes {
+                    assert!(check_prime(class.prime as u128) && class.prime > previous);
+                    previous = class.prime;
+                    assert!(class.residue < class.modulus);
+                    // p divides exactly the numbers with n in the class
+                    let mut n: u64 = 0;
+                    let mut power: u64 = 1;
+                    while n < 3 * class.modulus {
+                        let value: u64 = if plus {
+                            (k % class.prime * power + 1) % class.prime
+                        } else {
+                            (k % class.prime * power + class.prime - 1) % class.prime
+                        };
+                        assert_eq!(value == 0, n % class.modulus == class.residue, "{k} {n}");
+                        power = power * 2 % class.prime;
+                        n += 1;
+                    }
+                }
+            }
+        }
+        // 7 never divides 2^n + 1, as the powers of 2 modulo 7 are 1, 2 and 4
+        assert!(screen_sierpinski(1, 0)
+            .classes
+            .iter()
+            .all(|class| class.prime != 7));
+        // and no prime dividing k divides k * 2^n + 1
+        assert!(screen_sierpinski(15, 0)
+            .classes
+            .iter()
+            .all(|class| class.prime > 5));
+    }
+
+    #[test]
+    fn small_prime_test() {
+        // 5 = 1 * 2^2 + 1 is prime even though 5 divides 2^n + 1 for every n = 2 (mod 4)
+        let screen: SierpinskiScreen = screen_sierpinski(1, 10);
+        assert!(screen
+            .classes
+            .iter()
+            .any(|class| (class.prime, class.residue) == (5, 2)));
+        assert_eq!(
+            screen_sierpinski(5, 10).result,
+            ScreenResult::PrimeFound { n: 1 }
+        );
+        assert_eq!(
+            screen_riesel(7, 10).result,
+            ScreenResult::PrimeFound { n: 1 }
+        ); // 13
+        assert_eq!(
+            screen_riesel(9, 10).result,
+            ScreenResult::PrimeFound { n: 1 }
+        ); // 17
+    }
+
+    #[test]
+    #[should_panic]
+    fn even_k_test() {
+        screen_sierpinski(10, 10);
+    }
+}
+
+#[cfg(test)]
+mod sync_prime_cache_tests {
+    use bens_number_theory::primes::{check_prime, generate_primes, PrimeSet, SyncPrimeCache};
+
+    #[test]
+    fn prime_set_test() {
+        let set: PrimeSet = PrimeSet::new(10_000);
+        assert_eq!(set.limit(), 10_000);
+        assert_eq!(set.count(), 1229);
+        let primes: Vec<u64> = (0..=10_000).filter(|n| set.contains(*n).unwrap()).collect();
+        assert_eq!(primes, generate_primes(10_000_u64));
+        assert_eq!(set.contains(10_001), None);
+        assert_eq!(PrimeSet::new(0).count(), 0);
+        assert_eq!(PrimeSet::new(2).contains(2), Some(true));
+    }
+
+    #[test]
+    fn single_thread_test() {
+        let cache: SyncPrimeCache = SyncPrimeCache::new(10);
+        let mut n: u64 = 0;
+        while n < 5000 {
+            assert_eq!(cache.is_prime(n), check_prime(n as u128), "{n}");
+            n += 1;
+        }
+        // growing one number at a time still doubles the limit
+        assert!(cache.growth_count() <= 9);
+        let old: PrimeSet = cache.snapshot();
+        assert!(cache.is_prime(1_000_003));
+        assert_eq!(old.contains(1_000_003), None);
+        assert!(cache.snapshot().limit() >= 1_000_003);
+        // above the largest sieve the cache falls back on check_prime
+        let before: usize = cache.growth_count();
+        assert!(cache.is_prime(1_000_000_007));
+        assert!(!cache.is_prime(1_000_000_011));
+        assert_eq!(cache.growth_count(), before);
+    }
+
+    #[test]
+    fn stress_test() {
+        let initial: u64 = 1000;
+        let cache: SyncPrimeCache = SyncPrimeCache::new(initial);
+        std::thread::scope(|scope| {
+            for t in 0..8 {
+                let cache: &SyncPrimeCache = &cache;
+                scope.spawn(move || {
+                    let mut state: u64 = 199 + t;
+                    let mut i: u32 = 0;
+                    while i < 2000 {
+                        state = state
+                            .wrapping_mul(6_364_136_223_846_793_005)
+                            .wrapping_add(1_442_695_040_888_963_407);
+                        // mostly small numbers, some that force growth and some beyond the sieve
+                        let n: u64 = match state % 10 {
+                            0 => (state >> 20) % (1 << 22),
+                            1 => (1 << 28) + (state >> 20) % (1 << 40),
+                            _ => (state >> 20) % 5000,
+                        };
+                        assert_eq!(cache.is_prime(n), check_prime(n as u128), "{n}");
+                        i += 1;
+                    }
+                });
+            }
+        });
+        let limit: u64 = cache.snapshot().limit();
+        assert!(limit < 1 << 23);
+        // every growth at least doubles the limit
+        assert!(cache.growth_count() as u32 <= (limit / initial).ilog2());
+        let set: PrimeSet = PrimeSet::new(limit);
+        assert_eq!(cache.snapshot().count(), set.count());
+    }
+}
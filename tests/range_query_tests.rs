@@ -0,0 +1,135 @@
+#[cfg(test)]
+mod range_query_tests {
+    use bens_number_theory::divisor_functions::{divisor_count, divisor_sum};
+    use bens_number_theory::factorization::factorize;
+    use bens_number_theory::modular::euler_totient;
+    use bens_number_theory::primes::prime_bitset;
+    use bens_number_theory::range_query::numbers_below;
+    use std::cell::Cell;
+
+    fn is_square(n: u64) -> bool {
+        n.isqrt() * n.isqrt() == n
+    }
+
+    #[test]
+    fn sigma_prime_test() {
+        // sigma(n) < 5n below 10^7
+        let bits: Vec<u64> = prime_bitset(50_000_000);
+        let is_prime = |s: u64| bits[(s / 64) as usize] >> (s % 64) & 1 == 1;
+        let found: Vec<u64> = numbers_below(10_000_000).filter_sigma(is_prime).collect();
+        assert_eq!(found.len(), 69);
+        assert_eq!(
+            found[..12],
+            [2, 4, 9, 16, 25, 64, 289, 729, 1681, 2401, 3481, 4096]
+        );
+    }
+
+    #[test]
+    fn totient_square_test() {
+        let query = numbers_below(10_000_000).filter_phi(is_square);
+        assert_eq!(query.count(), 43548);
+        assert_eq!(
+            numbers_below(90).filter_phi(is_square).collect(),
+            vec![1, 2, 5, 8, 10, 12, 17, 32, 34, 37, 40, 48, 57, 60, 63, 74, 76, 85]
+        );
+    }
+
+    #[test]
+    fn brute_force_test() {
+        let limit: u64 = 3000;
+        let sigma: Vec<u64> = numbers_below(limit)
+            .filter_sigma(|s| s.is_multiple_of(3))
+            .collect();
+        let tau: Vec<u64> = numbers_below(limit).filter_tau(|t| t == 6).collect();
+        let phi: Vec<u64> = numbers_below(limit)
+            .filter_phi(|phi| phi.is_multiple_of(8))
+            .collect();
+        let cube_free: Vec<u64> = numbers_below(limit)
+            .filter_factorization(|factors| factors.iter().all(|(_, e)| *e < 3))
+            .collect();
+        let both: Vec<u64> = numbers_below(limit)
+            .filter(|n| n % 4 == 1)
+            .filter_tau(|t| t == 2)
+            .filter_phi(|phi| phi.is_multiple_of(3))
+            .collect();
+        let range = 1..limit;
+        assert_eq!(
+            sigma,
+            range
+                .clone()
+                .filter(|n| divisor_sum(*n).is_multiple_of(3))
+                .collect::<Vec<u64>>()
+        );
+        assert_eq!(
+            tau,
+            range
+                .clone()
+                .filter(|n| divisor_count(*n) == 6)
+                .collect::<Vec<u64>>()
+        );
+        assert_eq!(
+            phi,
+            range
+                .clone()
+                .filter(|n| euler_totient(*n).is_multiple_of(8))
+                .collect::<Vec<u64>>()
+        );
+        assert_eq!(
+            cube_free,
+            range
+                .clone()
+                .filter(|n| factorize(*n).iter().all(|(_, e)| *e < 3))
+                .collect::<Vec<u64>>()
+        );
+        assert_eq!(
+            both,
+            range
+                .filter(|n| n % 4 == 1
+                    && divisor_count(*n) == 2
+                    && euler_totient(*n).is_multiple_of(3))
+                .collect::<Vec<u64>>()
+        );
+    }
+
+    #[test]
+    fn factorization_order_test() {
+        let found: Vec<u64> = numbers_below(1000)
+            .filter_factorization(|factors| {
+                factors.windows(2).all(|pair| pair[0].0 < pair[1].0)
+                    && factors.iter().map(|(p, e)| p.pow(*e)).product::<u64>() > 0
+            })
+            .collect();
+        assert_eq!(found, (1..1000).collect::<Vec<u64>>());
+        assert_eq!(
+            numbers_below(20)
+                .filter_factorization(|factors| factors.is_empty())
+                .collect(),
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn short_circuit_test() {
+        // the second condition only sees the numbers the first one kept
+        let calls: Cell<u64> = Cell::new(0);
+        let found: Vec<u64> = numbers_below(100)
+            .filter(|n| n.is_multiple_of(10))
+            .filter_sigma(|_| {
+                calls.set(calls.get() + 1);
+                true
+            })
+            .collect();
+        assert_eq!(found, vec![10, 20, 30, 40, 50, 60, 70, 80, 90]);
+        assert_eq!(calls.get(), 9);
+    }
+
+    #[test]
+    fn small_limits_test() {
+        assert_eq!(numbers_below(0).collect(), vec![]);
+        assert_eq!(numbers_below(1).collect(), vec![]);
+        assert_eq!(numbers_below(2).filter_sigma(|s| s == 1).collect(), vec![1]);
+        assert_eq!(numbers_below(2).filter_tau(|t| t == 1).count(), 1);
+        assert_eq!(numbers_below(2).filter_phi(|phi| phi == 1).count(), 1);
+        assert_eq!(numbers_below(10).count(), 9);
+    }
+}
@@ -0,0 +1,204 @@
+#[cfg(test)]
+mod rational_tests {
+    use bens_number_theory::rational::{Ratio64, ZeroDenominator};
+    use num::{BigInt, BigRational, Zero};
+    use std::cmp::Ordering;
+
+    /// A linear congruential generator, so the random fractions are reproducible.
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self) -> u64 {
+            self.0 = self
+                .0
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            self.0
+        }
+
+        /// A fraction whose parts have a random number of bits up to `bits`.
+        fn fraction(&mut self, bits: u32) -> Ratio64 {
+            let wide = |lcg: &mut Lcg| ((lcg.next() as u128) << 64) | lcg.next() as u128;
+            let num_bits: u32 = (self.next() % bits as u64) as u32 + 1;
+            let den_bits: u32 = (self.next() % bits as u64) as u32 + 1;
+            let magnitude: u128 = wide(self) >> (128 - num_bits.min(127));
+            let den: u128 = (wide(self) >> (128 - den_bits)).max(1);
+            let num: i128 = if self.next().is_multiple_of(2) {
+                magnitude as i128
+            } else {
+                -(magnitude as i128)
+            };
+            Ratio64::new(num, den).unwrap()
+        }
+    }
+
+    fn big(r: Ratio64) -> BigRational {
+        BigRational::new(BigInt::from(r.numer()), BigInt::from(r.denom()))
+    }
+
+    /// The exact value as a `Ratio64`, if it fits.
+    fn small(r: &BigRational) -> Option<Ratio64> {
+        let num: i128 = r.numer().try_into().ok()?;
+        let den: u128 = r.denom().try_into().ok()?;
+        Some(Ratio64::new(num, den).unwrap())
+    }
+
+    fn is_reduced(r: Ratio64) -> bool {
+        let mut a: u128 = r.numer().unsigned_abs();
+        let mut b: u128 = r.denom();
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        r.denom() > 0 && a == 1 || r.numer() == 0 && r.denom() == 1
+    }
+
+    #[test]
+    fn construction_test() {
+        let r: Ratio64 = Ratio64::new(-6, 4).unwrap();
+        assert_eq!((r.numer(), r.denom()), (-3, 2));
+        assert_eq!(Ratio64::new(0, 17).unwrap().denom(), 1);
+        assert_eq!(Ratio64::new(7, 0), Err(ZeroDenominator));
+        assert_eq!(Ratio64::new(0, 0), Err(ZeroDenominator));
+        assert_eq!(
+            ZeroDenominator.to_string(),
+            "the denominator of a fraction cannot be zero"
+        );
+        // the extremes reduce without overflowing
+        let r: Ratio64 = Ratio64::new(i128::MIN, 1 << 127).unwrap();
+        assert_eq!((r.numer(), r.denom()), (-1, 1));
+        let r: Ratio64 = Ratio64::new(i128::MIN, u128::MAX).unwrap();
+        assert_eq!((r.numer(), r.denom()), (i128::MIN, u128::MAX));
+        let r: Ratio64 = Ratio64::new(i128::MAX, u128::MAX).unwrap();
+        assert!(is_reduced(r));
+        assert_eq!(Ratio64::from_integer(-5), Ratio64::new(-10, 2).unwrap());
+    }
+
+    #[test]
+    fn reduction_idempotence_test() {
+        let mut lcg: Lcg = Lcg(1);
+        for _ in 0..5000 {
+            let r: Ratio64 = lcg.fraction(127);
+            assert!(is_reduced(r), "{r}");
+            assert_eq!(Ratio64::new(r.numer(), r.denom()), Ok(r));
+            // scaling both parts back up reduces to the same fraction
+            let k: u128 = (lcg.next() % 1000 + 1) as u128;
+            if let (Some(num), Some(den)) =
+                (r.numer().checked_mul(k as i128), r.denom().checked_mul(k))
+            {
+                assert_eq!(Ratio64::new(num, den), Ok(r));
+            }
+        }
+    }
+
+    #[test]
+    fn exact_arithmetic_test() {
+        let mut lcg: Lcg = Lcg(2);
+        let mut successes: u32 = 0;
+        for i in 0..5000 {
+            let bits: u32 = if i % 2 == 0 { 40 } else { 127 };
+            let a: Ratio64 = lcg.fraction(bits);
+            let b: Ratio64 = lcg.fraction(bits);
+            let (x, y): (BigRational, BigRational) = (big(a), big(b));
+            let mut expected: Vec<Option<Ratio64>> = vec![small(&(&x * &y)), None];
+            expected[1] = if y.is_zero() { None } else { small(&(&x / &y)) };
+            assert_eq!(a.checked_mul(b), expected[0], "{a} * {b}");
+            assert_eq!(a.checked_div(b), expected[1], "{a} / {b}");
+            for (result, exact) in [(a.checked_add(b), &x + &y), (a.checked_sub(b), &x - &y)] {
+                // a sum can only be refused when it really overflows, or the numerator does before reducing
+                match result {
+                    Some(r) => {
+                        assert_eq!(big(r), exact, "{a} {b}");
+                        assert!(is_reduced(r));
+                        successes += 1;
+                    }
+                    None => assert!(bits == 127, "{a} {b}"),
+                }
+            }
+        }
+        assert!(successes > 5000);
+    }
+
+    #[test]
+    fn add_sub_round_trip_test() {
+        let mut lcg: Lcg = Lcg(3);
+        for i in 0..5000 {
+            let bits: u32 = if i % 2 == 0 { 30 } else { 120 };
+            let a: Ratio64 = lcg.fraction(bits);
+            let b: Ratio64 = lcg.fraction(bits);
+            if let Some(sum) = a.checked_add(b) {
+                if let Some(back) = sum.checked_sub(b) {
+                    assert_eq!(back, a, "{a} {b}");
+                }
+            }
+            if let Some(product) = a.checked_mul(b) {
+                if let Some(back) = product.checked_div(b) {
+                    assert_eq!(back, a, "{a} {b}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn comparison_test() {
+        let mut lcg: Lcg = Lcg(4);
+        for i in 0..5000 {
+            let a: Ratio64 = lcg.fraction(if i % 3 == 0 { 8 } else { 127 });
+            let b: Ratio64 = if i % 5 == 0 {
+                a
+            } else {
+                lcg.fraction(if i % 3 == 0 { 8 } else { 127 })
+            };
+            // a/b < c/d exactly when ad < cb, for positive denominators
+            let cross: Ordering = (BigInt::from(a.numer()) * BigInt::from(b.denom()))
+                .cmp(&(BigInt::from(b.numer()) * BigInt::from(a.denom())));
+            assert_eq!(a.cmp(&b), cross, "{a} {b}");
+            assert_eq!(a == b, cross == Ordering::Equal);
+            assert_eq!(b.cmp(&a), cross.reverse());
+        }
+        let mut sorted: Vec<Ratio64> = [(1, 2), (-1, 3), (2, 3), (0, 1), (-5, 2)]
+            .iter()
+            .map(|(p, q)| Ratio64::new(*p, *q).unwrap())
+            .collect();
+        sorted.sort();
+        let strings: Vec<String> = sorted.iter().map(|r| r.to_string()).collect();
+        assert_eq!(strings, vec!["-5/2", "-1/3", "0/1", "1/2", "2/3"]);
+    }
+
+    #[test]
+    fn overflow_test() {
+        let max: Ratio64 = Ratio64::from_integer(i128::MAX);
+        let min: Ratio64 = Ratio64::from_integer(i128::MIN);
+        let one: Ratio64 = Ratio64::from_integer(1);
+        assert_eq!(max.checked_add(one), None);
+        assert_eq!(min.checked_sub(one), None);
+        assert_eq!(min.checked_neg(), None);
+        assert_eq!(max.checked_neg(), Some(min.checked_add(one).unwrap()));
+        assert_eq!(max.checked_mul(Ratio64::from_integer(2)), None);
+        assert_eq!(min.checked_mul(Ratio64::from_integer(-1)), None);
+        assert_eq!(min.checked_div(Ratio64::from_integer(-1)), None);
+        assert_eq!(
+            min.checked_div(Ratio64::from_integer(2)),
+            Some(Ratio64::from_integer(i128::MIN / 2))
+        );
+        let tiny: Ratio64 = Ratio64::new(1, u128::MAX).unwrap();
+        assert_eq!(tiny.checked_mul(Ratio64::new(1, 2).unwrap()), None);
+        assert_eq!(
+            tiny.checked_mul(Ratio64::from_integer(3)),
+            Ratio64::new(1, u128::MAX / 3).ok()
+        );
+        assert_eq!(one.checked_div(Ratio64::from_integer(0)), None);
+        // 1/3 and 2/3 have a common denominator, so the sum never leaves the range
+        let third: Ratio64 = Ratio64::new(i128::MAX / 3, u128::MAX).unwrap();
+        assert!(third.checked_add(third).is_some());
+    }
+
+    #[test]
+    fn float_test() {
+        assert_eq!(Ratio64::new(1, 4).unwrap().to_f64(), 0.25);
+        assert_eq!(Ratio64::new(-7, 2).unwrap().to_f64(), -3.5);
+        assert_eq!(Ratio64::from_integer(0).to_f64(), 0.0);
+        let third: f64 = Ratio64::new(1, 3).unwrap().to_f64();
+        assert!((third - 1.0 / 3.0).abs() < 1e-16);
+        assert!((Ratio64::new(i128::MAX, u128::MAX).unwrap().to_f64() - 0.5).abs() < 1e-15);
+    }
+}
@@ -0,0 +1,353 @@
+#[cfg(test)]
+mod collatz_tests {
+    use bens_number_theory::sequences::{collatz_length, collatz_sequence, longest_collatz_below};
+
+    #[test]
+    fn collatz_length_test() {
+        assert_eq!(collatz_length(0), 0);
+        assert_eq!(collatz_length(1), 0);
+        assert_eq!(collatz_length(2), 1);
+        assert_eq!(collatz_length(27), 111);
+        assert_eq!(collatz_length(837799), 524);
+    }
+
+    #[test]
+    fn collatz_sequence_terminates_test() {
+        for n in 1..=100_000 {
+            let mut sequence = collatz_sequence(n);
+            let mut count: u32 = 0;
+            let mut last: u64 = 0;
+            for term in sequence.by_ref() {
+                count += 1;
+                last = term;
+            }
+            assert!(!sequence.overflowed());
+            assert_eq!(last, 1);
+            assert_eq!(count, collatz_length(n) + 1);
+        }
+        assert_eq!(collatz_sequence(0).count(), 0);
+    }
+
+    #[test]
+    fn collatz_overflow_test() {
+        // an odd number just above (2^64 - 1) / 3, so 3n + 1 no longer fits in a u64
+        let n: u64 = u64::MAX / 3 + 2;
+        let mut sequence = collatz_sequence(n);
+        assert_eq!(sequence.by_ref().count(), 1);
+        assert!(sequence.overflowed());
+        // the length is still computed, since it is done with u128 values
+        assert!(collatz_length(n) > 0);
+    }
+
+    #[test]
+    fn longest_collatz_below_test() {
+        assert_eq!(longest_collatz_below(0), (0, 0));
+        assert_eq!(longest_collatz_below(2), (1, 0));
+        assert_eq!(longest_collatz_below(1_000_000), (837799, 524));
+    }
+}
+
+#[cfg(test)]
+mod zeckendorf_tests {
+    use bens_number_theory::sequences::{fibonacci_base_digits, from_zeckendorf, zeckendorf};
+
+    #[test]
+    fn zeckendorf_test() {
+        assert_eq!(zeckendorf(100), vec![11, 6, 4]);
+        assert_eq!(zeckendorf(1), vec![2]);
+        assert_eq!(zeckendorf(2), vec![3]);
+        assert_eq!(zeckendorf(4), vec![4, 2]);
+        assert_eq!(from_zeckendorf(&zeckendorf(u128::MAX)), Some(u128::MAX));
+    }
+
+    #[test]
+    fn zeckendorf_round_trip_test() {
+        // a small xorshift generator, so the values are spread over the whole u128 range
+        let mut state: u128 = 0x2545F4914F6CDD1D;
+        for _ in 0..10000 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let n: u128 = state >> (state % 128);
+            let indices: Vec<u32> = zeckendorf(n);
+            for pair in indices.windows(2) {
+                assert!(pair[0] >= pair[1] + 2);
+            }
+            assert_eq!(from_zeckendorf(&indices), Some(n));
+        }
+    }
+
+    #[test]
+    fn from_zeckendorf_rejection_test() {
+        assert_eq!(from_zeckendorf(&[]), Some(0));
+        assert_eq!(from_zeckendorf(&[4, 6, 11]), Some(100));
+        assert_eq!(from_zeckendorf(&[7, 6]), None);
+        assert_eq!(from_zeckendorf(&[6, 6]), None);
+        assert_eq!(from_zeckendorf(&[1]), None);
+        assert_eq!(from_zeckendorf(&[187]), None); // F(187) does not fit in a u128
+        assert_eq!(from_zeckendorf(&[186, 184]), None); // the sum overflows
+    }
+
+    #[test]
+    fn fibonacci_base_digits_test() {
+        assert_eq!(fibonacci_base_digits(0), vec![0]);
+        assert_eq!(fibonacci_base_digits(1), vec![1]);
+        assert_eq!(fibonacci_base_digits(12), vec![1, 0, 1, 0, 1]); // 8 + 3 + 1
+        for n in 1..1000 {
+            let digits: Vec<u8> = fibonacci_base_digits(n);
+            assert_eq!(digits[0], 1);
+            for pair in digits.windows(2) {
+                assert!(pair[0] + pair[1] <= 1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod linear_recurrence_tests {
+    use bens_number_theory::sequences::{
+        fibonacci_mod, fibonacci_sequence, linear_recurrence_mod, lucas_mod, lucas_sequence,
+        pell_mod, tribonacci_mod,
+    };
+    use num::BigInt;
+
+    /// Calculates the terms of a linear recurrence modulo m one at a time.
+    fn iterate(coeffs: &[u64], initial: &[u64], count: usize, m: u64) -> Vec<u64> {
+        let mut terms: Vec<u64> = initial.iter().map(|a| a % m).collect();
+        while terms.len() < count {
+            let mut next: u128 = 0;
+            for (j, c) in coeffs.iter().enumerate() {
+                next += *c as u128 * terms[terms.len() - 1 - j] as u128 % m as u128;
+            }
+            terms.push((next % m as u128) as u64);
+        }
+        terms
+    }
+
+    #[test]
+    fn linear_recurrence_matches_iteration_test() {
+        let mut state: u64 = 0x9E3779B97F4A7C15;
+        let mut next_random = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for order in 2..=5 {
+            let coeffs: Vec<u64> = (0..order).map(|_| next_random()).collect();
+            let initial: Vec<u64> = (0..order).map(|_| next_random()).collect();
+            let m: u64 = next_random() | 1;
+            let terms: Vec<u64> = iterate(&coeffs, &initial, 10001, m);
+            for n in (0..=10000).step_by(37).chain([10000]) {
+                assert_eq!(
+                    linear_recurrence_mod(&coeffs, &initial, n as u64, m),
+                    terms[n]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn named_recurrences_test() {
+        let fibonacci: Vec<BigInt> = fibonacci_sequence(BigInt::from(90));
+        let lucas: Vec<BigInt> = lucas_sequence(BigInt::from(90));
+        for n in 0..90 {
+            assert_eq!(
+                BigInt::from(fibonacci_mod(n, u64::MAX)),
+                fibonacci[n as usize]
+            );
+            assert_eq!(BigInt::from(lucas_mod(n, u64::MAX)), lucas[n as usize]);
+        }
+        assert_eq!(pell_mod(10, 1_000_000), 2378);
+        assert_eq!(tribonacci_mod(37, u64::MAX), 1132436852);
+    }
+
+    #[test]
+    fn fibonacci_huge_index_test() {
+        assert_eq!(
+            fibonacci_mod(1_000_000_000_000_000_000, 1_000_000_007),
+            209783453
+        );
+        assert_eq!(fibonacci_mod(100, 1_000_000_007), 687995182);
+        assert_eq!(fibonacci_mod(12345, 1), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn linear_recurrence_zero_modulus() {
+        linear_recurrence_mod(&[1, 1], &[0, 1], 10, 0);
+    }
+}
+
+#[cfg(test)]
+mod lucky_number_tests {
+    use bens_number_theory::sequences::{is_lucky, lucky_numbers, lucky_primes_below};
+
+    /// Runs the lucky number sieve by deleting elements from a vector.
+    fn naive_lucky_numbers(limit: u64) -> Vec<u64> {
+        let mut survivors: Vec<u64> = (1..limit).step_by(2).collect();
+        let mut position: usize = 1;
+        while position < survivors.len() && survivors[position] as usize <= survivors.len() {
+            let k: usize = survivors[position] as usize;
+            let mut index: usize = 0;
+            survivors.retain(|_| {
+                index += 1;
+                !index.is_multiple_of(k)
+            });
+            position += 1;
+        }
+        survivors
+    }
+
+    #[test]
+    fn lucky_numbers_test() {
+        assert_eq!(lucky_numbers(34), vec![1, 3, 7, 9, 13, 15, 21, 25, 31, 33]);
+        assert_eq!(lucky_numbers(0), vec![]);
+        assert_eq!(lucky_numbers(2), vec![1]);
+        assert_eq!(lucky_numbers(10000).len(), 1118);
+        for limit in 0..600 {
+            assert_eq!(lucky_numbers(limit), naive_lucky_numbers(limit));
+        }
+        assert_eq!(lucky_numbers(100000), naive_lucky_numbers(100000));
+    }
+
+    #[test]
+    fn lucky_numbers_million_test() {
+        let lucky: Vec<u64> = lucky_numbers(1_000_000);
+        assert_eq!(lucky.len(), 71918);
+        assert_eq!(lucky[lucky.len() - 3..], [999973, 999985, 999987]);
+    }
+
+    #[test]
+    fn is_lucky_test() {
+        let lucky: Vec<u64> = lucky_numbers(500);
+        for n in 0..500 {
+            assert_eq!(is_lucky(n), lucky.contains(&n));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn is_lucky_max_test() {
+        is_lucky(u64::MAX);
+    }
+
+    #[test]
+    fn lucky_primes_test() {
+        // 97 is deleted as the 26th survivor when sieving by 13
+        assert_eq!(
+            lucky_primes_below(100),
+            vec![3, 7, 13, 31, 37, 43, 67, 73, 79]
+        );
+        assert_eq!(
+            lucky_primes_below(127),
+            vec![3, 7, 13, 31, 37, 43, 67, 73, 79]
+        );
+        assert_eq!(*lucky_primes_below(128).last().unwrap(), 127);
+    }
+}
+
+#[cfg(test)]
+mod calkin_wilf_tests {
+    use bens_number_theory::modular::gcd;
+    use bens_number_theory::sequences::{calkin_wilf, calkin_wilf_index, fusc};
+    use std::collections::HashSet;
+
+    #[test]
+    fn fusc_test() {
+        let start: Vec<u64> = (0..20).map(fusc).collect();
+        assert_eq!(
+            start,
+            vec![0, 1, 1, 2, 1, 3, 2, 3, 1, 4, 3, 5, 2, 5, 3, 4, 1, 5, 4, 7]
+        );
+        let mut n: u64 = 1;
+        while n < 100_000 {
+            assert_eq!(fusc(2 * n), fusc(n));
+            assert_eq!(fusc(2 * n + 1), fusc(n) + fusc(n + 1));
+            n += 1;
+        }
+        assert_eq!(fusc(1 << 63), 1);
+        assert_eq!(fusc(u64::MAX), 64);
+        // 0b1010...101 reaches the Fibonacci numbers
+        assert_eq!(fusc(0x5555_5555_5555_5555), 10_610_209_857_723); // F(64)
+    }
+
+    #[test]
+    fn sequence_test() {
+        let start: Vec<(u64, u64)> = (1..16).map(calkin_wilf).collect();
+        assert_eq!(
+            start,
+            vec![
+                (1, 1),
+                (1, 2),
+                (2, 1),
+                (1, 3),
+                (3, 2),
+                (2, 3),
+                (3, 1),
+                (1, 4),
+                (4, 3),
+                (3, 5),
+                (5, 2),
+                (2, 5),
+                (5, 3),
+                (3, 4),
+                (4, 1)
+            ]
+        );
+        assert_eq!(calkin_wilf(1 << 63), (1, 64));
+    }
+
+    #[test]
+    fn every_rational_once_test() {
+        // a fraction p/q sits at depth at most p + q - 2, so the first 2^17 - 1 terms contain every p/q with
+        // p + q <= 18
+        let mut seen: HashSet<(u64, u64)> = HashSet::new();
+        let mut n: u64 = 1;
+        while n < 1 << 17 {
+            let (p, q): (u64, u64) = calkin_wilf(n);
+            assert_eq!(gcd(p, q), 1);
+            assert!(seen.insert((p, q)), "{p}/{q}");
+            assert_eq!(calkin_wilf_index(p, q), n);
+            n += 1;
+        }
+        for p in 1..18 {
+            for q in 1..=18 - p {
+                assert_eq!(seen.contains(&(p, q)), gcd(p, q) == 1);
+            }
+        }
+    }
+
+    #[test]
+    fn index_test() {
+        assert_eq!(calkin_wilf_index(1, 1), 1);
+        assert_eq!(calkin_wilf_index(5, 5), 1);
+        assert_eq!(calkin_wilf_index(4, 1), 15);
+        assert_eq!(calkin_wilf_index(1, 63), 1 << 62);
+        assert_eq!(calkin_wilf_index(64, 1), u64::MAX);
+        let mut state: u64 = 184;
+        let mut i: u32 = 0;
+        while i < 10_000 {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            let n: u64 = (state >> (state % 60)) | 1 << 60;
+            let (p, q): (u64, u64) = calkin_wilf(n);
+            assert_eq!(calkin_wilf_index(p, q), n);
+            assert_eq!(calkin_wilf_index(3 * p, 3 * q), n);
+            i += 1;
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_overflow_test() {
+        calkin_wilf_index(65, 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_position_test() {
+        calkin_wilf(0);
+    }
+}
@@ -0,0 +1,168 @@
+#[cfg(test)]
+mod divisor_block_tests {
+    use bens_number_theory::summatory::divisor_blocks;
+
+    #[test]
+    fn block_cover_test() {
+        for n in 1..2000_u64 {
+            let mut expected_lo: u64 = 1;
+            let mut count: u64 = 0;
+            for (value, lo, hi) in divisor_blocks(n) {
+                assert_eq!(lo, expected_lo);
+                assert!(lo <= hi);
+                assert_eq!(n / lo, value);
+                assert_eq!(n / hi, value);
+                if hi < n {
+                    assert!(n / (hi + 1) < value);
+                }
+                expected_lo = hi + 1;
+                count += 1;
+            }
+            assert_eq!(expected_lo, n + 1);
+            assert!(count * count <= 4 * n);
+        }
+        assert_eq!(divisor_blocks(0).count(), 0);
+        assert_eq!(divisor_blocks(1).collect::<Vec<_>>(), vec![(1, 1, 1)]);
+    }
+
+    #[test]
+    fn large_block_test() {
+        let mut blocks = divisor_blocks(u64::MAX);
+        assert_eq!(blocks.next(), Some((u64::MAX, 1, 1)));
+        assert_eq!(blocks.next(), Some((u64::MAX / 2, 2, 2)));
+        assert_eq!(divisor_blocks(1_000_000).count(), 1999);
+    }
+}
+
+#[cfg(test)]
+mod mertens_tests {
+    use bens_number_theory::summatory::{mertens_fast, mertens_sieve};
+
+    #[test]
+    fn known_values_test() {
+        assert_eq!(mertens_sieve(10_000), -23);
+        assert_eq!(mertens_fast(10_000), -23);
+        assert_eq!(mertens_sieve(1_000_000), 212);
+        assert_eq!(mertens_fast(1_000_000), 212);
+        assert_eq!(mertens_fast(0), 0);
+        assert_eq!(mertens_fast(1), 1);
+    }
+
+    #[test]
+    fn sieve_agreement_test() {
+        for n in 0..3000 {
+            assert_eq!(mertens_fast(n), mertens_sieve(n), "{}", n);
+        }
+        for n in (3000..1_000_000).step_by(99_991) {
+            assert_eq!(mertens_fast(n), mertens_sieve(n), "{}", n);
+        }
+    }
+
+    #[test]
+    fn large_value_test() {
+        assert_eq!(mertens_fast(10_000_000_000), -33722);
+    }
+}
+
+#[cfg(test)]
+mod totient_summatory_tests {
+    use bens_number_theory::summatory::{totient_summatory, totient_summatory_sieve};
+
+    #[test]
+    fn known_values_test() {
+        assert_eq!(totient_summatory_sieve(10_000), 30_397_486);
+        assert_eq!(totient_summatory(10_000), 30_397_486);
+        assert_eq!(totient_summatory(1_000_000), 303_963_552_392);
+        assert_eq!(totient_summatory(0), 0);
+        assert_eq!(totient_summatory(1), 1);
+    }
+
+    #[test]
+    fn sieve_agreement_test() {
+        for n in 0..3000 {
+            assert_eq!(totient_summatory(n), totient_summatory_sieve(n), "{}", n);
+        }
+        for n in (3000..1_000_000).step_by(99_991) {
+            assert_eq!(totient_summatory(n), totient_summatory_sieve(n), "{}", n);
+        }
+        assert_eq!(
+            totient_summatory(1_000_000),
+            totient_summatory_sieve(1_000_000)
+        );
+    }
+
+    #[test]
+    fn large_value_test() {
+        assert_eq!(
+            totient_summatory(10_000_000_000),
+            30_396_355_092_886_216_366
+        );
+    }
+}
+
+#[cfg(test)]
+mod squarefree_tests {
+    use bens_number_theory::factorization::factorize;
+    use bens_number_theory::summatory::{count_squarefree_below, squarefree_numbers_below};
+
+    #[test]
+    fn small_counts_test() {
+        assert_eq!(count_squarefree_below(100), 61);
+        assert_eq!(squarefree_numbers_below(100).count(), 61);
+        assert_eq!(count_squarefree_below(0), 0);
+        assert_eq!(count_squarefree_below(1), 0);
+        assert_eq!(count_squarefree_below(2), 1);
+        assert_eq!(count_squarefree_below(5), 3);
+        assert_eq!(squarefree_numbers_below(0).count(), 0);
+        assert_eq!(squarefree_numbers_below(2).collect::<Vec<u64>>(), vec![1]);
+    }
+
+    #[test]
+    fn known_counts_test() {
+        // Q(10^k) for k = 2 to 8
+        let expected: [u64; 7] = [61, 608, 6083, 60794, 607926, 6079291, 60792694];
+        let mut n: u64 = 100;
+        for count in expected {
+            // 10^k is not squarefree, so counting below it or up to it agrees
+            assert_eq!(count_squarefree_below(n), count, "{n}");
+            assert_eq!(count_squarefree_below(n + 1), count, "{n}");
+            n *= 10;
+        }
+        // 10 itself is squarefree
+        assert_eq!(count_squarefree_below(10), 6);
+        assert_eq!(count_squarefree_below(11), 7);
+    }
+
+    #[test]
+    fn sieve_agreement_test() {
+        let squarefree: Vec<u64> = squarefree_numbers_below(1_000_000).collect();
+        // count_squarefree_below(k) is the position of the first squarefree number at least k
+        let mut index: usize = 0;
+        let mut k: u64 = 1;
+        while k <= 1_000_000 {
+            while index < squarefree.len() && squarefree[index] < k {
+                index += 1;
+            }
+            if k < 3000 || k.is_multiple_of(997) || k == 1_000_000 {
+                assert_eq!(count_squarefree_below(k), index as u64, "{k}");
+            }
+            k += 1;
+        }
+        for n in 1..5000 {
+            let expected: bool = factorize(n).iter().all(|(_, e)| *e == 1);
+            assert_eq!(squarefree.binary_search(&n).is_ok(), expected, "{n}");
+        }
+    }
+
+    #[test]
+    fn density_test() {
+        let density: f64 = 6.0 / (std::f64::consts::PI * std::f64::consts::PI);
+        let mut n: u64 = 1000;
+        while n <= 100_000_000_000_000 {
+            let error: f64 = (count_squarefree_below(n) as f64 / n as f64 - density).abs();
+            // the error term is O(sqrt(n)), and in practice much smaller
+            assert!(error < 1.0 / (n as f64).sqrt(), "{n}");
+            n *= 100;
+        }
+    }
+}
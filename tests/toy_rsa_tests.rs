@@ -0,0 +1,107 @@
+#![cfg(feature = "rand")]
+
+/// A xorshift generator, so the tests are reproducible without depending on a particular `rand` generator.
+struct Xorshift(u64);
+
+impl rand::RngCore for Xorshift {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        rand::rngs::mock::StepRng::new(self.next_u64(), self.next_u64()).fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod random_prime_tests {
+    use super::Xorshift;
+    use bens_number_theory::primes::{check_prime, is_probable_prime, random_prime};
+    use num::BigInt;
+
+    #[test]
+    fn random_prime_bits_test() {
+        let mut rng: Xorshift = Xorshift(0x853C49E6748FEA9B);
+        for bits in 2..=20 {
+            for _ in 0..20 {
+                let p: u64 = random_prime(bits, &mut rng);
+                assert!(check_prime(p as u128));
+                assert_eq!(64 - p.leading_zeros(), bits);
+            }
+        }
+        for _ in 0..20 {
+            let p: u64 = random_prime(64, &mut rng);
+            assert!(is_probable_prime(&BigInt::from(p)));
+            assert!(p >= 1 << 63);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn random_prime_one_bit() {
+        random_prime(1, &mut Xorshift(1));
+    }
+}
+
+#[cfg(test)]
+mod toy_rsa_tests {
+    use super::Xorshift;
+    use bens_number_theory::modular::{carmichael_lambda, gcd};
+    use bens_number_theory::toy_rsa::{generate_rsa_keypair, RsaKeypair};
+
+    #[test]
+    fn round_trip_test() {
+        let mut rng: Xorshift = Xorshift(0xDA3E39CB94B95BDB);
+        for bits in [8, 16, 32, 48, 64] {
+            for _ in 0..10 {
+                let keypair: RsaKeypair = generate_rsa_keypair(bits, &mut rng);
+                assert_ne!(keypair.p, keypair.q);
+                assert_eq!(keypair.p * keypair.q, keypair.n);
+                let (p, q): (u64, u64) = (keypair.p, keypair.q);
+                let lambda: u64 = (p - 1) / gcd(p - 1, q - 1) * (q - 1);
+                if bits <= 32 {
+                    assert_eq!(carmichael_lambda(keypair.n), lambda);
+                }
+                assert_eq!(keypair.e as u128 * keypair.d as u128 % lambda as u128, 1);
+                for m in [0, 1, 2, 42, keypair.n / 3, keypair.n - 1] {
+                    assert_eq!(keypair.decrypt(keypair.encrypt(m)), m);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn seeded_keypairs_are_reproducible_test() {
+        let first: RsaKeypair = generate_rsa_keypair(32, &mut Xorshift(7));
+        let second: RsaKeypair = generate_rsa_keypair(32, &mut Xorshift(7));
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn from_primes_test() {
+        assert_eq!(RsaKeypair::from_primes(61, 61, 17), None);
+        assert_eq!(RsaKeypair::from_primes(61, 51, 17), None);
+        assert_eq!(RsaKeypair::from_primes(61, 53, 13), None); // 13 divides lambda = 780
+        let keypair: RsaKeypair = RsaKeypair::from_primes(61, 53, 17).unwrap();
+        assert_eq!(keypair.encrypt(65), 2790);
+        assert_eq!(keypair.decrypt(2790), 65);
+    }
+
+    #[test]
+    #[should_panic]
+    fn generate_rsa_keypair_odd_bits() {
+        generate_rsa_keypair(33, &mut Xorshift(1));
+    }
+}
@@ -0,0 +1,106 @@
+#[cfg(test)]
+mod coordinate_tests {
+    use bens_number_theory::ulam::{ulam_coordinates, ulam_value};
+
+    #[test]
+    fn hand_drawn_spiral_test() {
+        // 17 16 15 14 13
+        // 18  5  4  3 12
+        // 19  6  1  2 11
+        // 20  7  8  9 10
+        // 21 22 23 24 25
+        let spiral: [[u64; 5]; 5] = [
+            [17, 16, 15, 14, 13],
+            [18, 5, 4, 3, 12],
+            [19, 6, 1, 2, 11],
+            [20, 7, 8, 9, 10],
+            [21, 22, 23, 24, 25],
+        ];
+        for (row, values) in spiral.iter().enumerate() {
+            for (column, n) in values.iter().enumerate() {
+                let position: (i64, i64) = (column as i64 - 2, 2 - row as i64);
+                assert_eq!(ulam_coordinates(*n), position, "{}", n);
+                assert_eq!(ulam_value(position.0, position.1), *n);
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_test() {
+        for n in 1..=10_000 {
+            let (x, y): (i64, i64) = ulam_coordinates(n);
+            assert_eq!(ulam_value(x, y), n);
+        }
+        for x in -50..=50 {
+            for y in -50..=50 {
+                assert_eq!(ulam_coordinates(ulam_value(x, y)), (x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn large_values_test() {
+        let n: u64 = u64::MAX;
+        let (x, y): (i64, i64) = ulam_coordinates(n);
+        assert_eq!(ulam_value(x, y), n);
+        assert_eq!(ulam_value(1 << 30, -(1 << 30)), (2_u64.pow(31) + 1).pow(2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_test() {
+        ulam_coordinates(0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_far_test() {
+        ulam_value(i64::MIN, 0);
+    }
+}
+
+#[cfg(test)]
+mod grid_tests {
+    use bens_number_theory::primes::is_probable_prime;
+    use bens_number_theory::ulam::{ulam_prime_grid, ulam_value};
+    use num::BigInt;
+
+    #[test]
+    fn grid_test() {
+        let grid: Vec<Vec<bool>> = ulam_prime_grid(20);
+        assert_eq!(grid.len(), 41);
+        for (row, cells) in grid.iter().enumerate() {
+            assert_eq!(cells.len(), 41);
+            for (column, prime) in cells.iter().enumerate() {
+                let n: u64 = ulam_value(column as i64 - 20, 20 - row as i64);
+                assert_eq!(*prime, is_probable_prime(&BigInt::from(n)));
+            }
+        }
+        assert_eq!(ulam_prime_grid(0), vec![vec![false]]);
+    }
+}
+
+#[cfg(test)]
+mod diagonal_tests {
+    use bens_number_theory::ulam::{diagonal_prime_ratio, side_length_below_diagonal_ratio};
+
+    #[test]
+    fn diagonal_ratio_test() {
+        assert_eq!(diagonal_prime_ratio(1), 0.0);
+        assert_eq!(diagonal_prime_ratio(3), 3.0 / 5.0);
+        assert_eq!(diagonal_prime_ratio(7), 8.0 / 13.0);
+    }
+
+    #[test]
+    fn project_euler_58_test() {
+        assert_eq!(side_length_below_diagonal_ratio(0.1), 26241);
+        assert!(diagonal_prime_ratio(26241) < 0.1);
+        assert!(diagonal_prime_ratio(26239) >= 0.1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn even_side_test() {
+        diagonal_prime_ratio(4);
+    }
+}